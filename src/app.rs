@@ -1,16 +1,19 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use gpui::*;
 use gpui::prelude::*;
 
 use crate::services::{
-    DaemonEvent, DaemonManager, DaemonManagerEvent, DaemonService, DaemonState, ImageIconService,
+    Capability, ConnectionState, DaemonContext, DaemonError, DaemonEvent, DaemonManager, DaemonManagerEvent,
+    DaemonOperation, DaemonService, DaemonState, ImageIconService, Notification, NotificationCenter,
+    SettingsStore, short_id, LOCAL_CONTEXT,
 };
 use crate::theme::{colors, Theme};
 use crate::views::*;
 
 // Define actions using the actions! macro
-actions!(arcbox, [OpenSettings, Quit, ToggleSidebar]);
+actions!(arcbox, [OpenSettings, OpenDaemonLogs, Quit, ToggleSidebar]);
 
 /// Sidebar resize drag state
 #[derive(Clone)]
@@ -58,12 +61,31 @@ pub struct ArcBoxApp {
     current_nav: NavItem,
     sidebar_width: f32,
     sidebar_collapsed: bool,
+    context_menu_open: bool,
     // Lifecycle management
     daemon_manager: Entity<DaemonManager>,
     // Shared services
+    settings_store: Entity<SettingsStore>,
+    /// Daemon service (channel + connection state) of the active context, by name;
+    /// switching away drops it
+    daemon_services: HashMap<String, Entity<DaemonService>>,
+    /// Service for the active context; every view points at it
     daemon_service: Entity<DaemonService>,
+    /// Event forwarding from the active daemon service
+    _daemon_subscriptions: Vec<Subscription>,
     image_icon_service: Entity<ImageIconService>,
+    notifications: Entity<NotificationCenter>,
+    /// Toast for the last daemon start failure, hidden once it runs
+    daemon_failure_toast: Option<usize>,
+    /// Toast for a lost connection, hidden once reconnected
+    connection_toast: Option<usize>,
+    /// Quit requested; the daemon is shutting down before the app exits
+    quitting: bool,
+    /// Re-renders the shutdown countdown while quitting
+    _quit_ticker: Option<Task<()>>,
+    _quit_subscription: Subscription,
     // Views
+    notification_layer: Entity<NotificationLayer>,
     containers_view: Entity<ContainersView>,
     machines_view: Entity<MachinesView>,
     images_view: Entity<ImagesView>,
@@ -82,9 +104,21 @@ impl ArcBoxApp {
         // Create daemon manager first (handles process lifecycle)
         let daemon_manager = cx.new(DaemonManager::new);
 
-        // Create daemon service with gRPC socket path from manager
+        // The local context always targets the gRPC socket of the daemon we manage
+        let settings_store = SettingsStore::global(cx);
         let grpc_socket_path = daemon_manager.read(cx).grpc_socket_path();
-        let daemon_service = cx.new(|cx| DaemonService::with_socket_path(grpc_socket_path, cx));
+        settings_store.update(cx, |store, cx| {
+            store.set_local_socket_path(grpc_socket_path, cx);
+        });
+        let restart_limit = settings_store.read(cx).settings().daemon_restart_limit;
+        daemon_manager.update(cx, |mgr, _cx| {
+            mgr.set_restart_limit(restart_limit);
+        });
+
+        // Create the daemon service for the active context
+        let active_context = settings_store.read(cx).settings().active();
+        let daemon_service = Self::create_daemon_service(active_context.clone(), &daemon_manager, cx);
+        let daemon_services = HashMap::from([(active_context.name, daemon_service.clone())]);
 
         let image_icon_service = cx.new(ImageIconService::new);
 
@@ -97,83 +131,106 @@ impl ArcBoxApp {
             ImagesView::new(daemon_service.clone(), image_icon_service.clone(), cx)
         });
         let volumes_view = cx.new(VolumesView::new);
-        let networks_view = cx.new(NetworksView::new);
+        let networks_view = cx.new(|cx| NetworksView::new(daemon_service.clone(), cx));
+
+        let notifications = NotificationCenter::global(cx);
+        let notification_layer = cx.new(NotificationLayer::new);
+        // Re-render for the unread badge
+        cx.observe(&notifications, |_, _, cx| cx.notify()).detach();
 
-        // Subscribe to daemon manager events - connect when daemon is ready
-        let daemon_service_clone = daemon_service.clone();
+        // Subscribe to daemon manager events - connect the local context when daemon is ready
         cx.subscribe(
             &daemon_manager,
-            move |_this, _, event: &DaemonManagerEvent, cx| {
+            move |this, manager, event: &DaemonManagerEvent, cx| {
+                let local_service = this.daemon_services.get(LOCAL_CONTEXT).cloned();
                 match event {
                     DaemonManagerEvent::StateChanged(DaemonState::Running) => {
                         tracing::info!("Daemon is running, connecting gRPC client...");
-                        daemon_service_clone.update(cx, |svc, cx| {
-                            svc.connect(cx);
-                        });
+                        if let Some(id) = this.daemon_failure_toast.take() {
+                            this.notifications.update(cx, |center, cx| {
+                                center.dismiss(id, cx);
+                                center.push(Notification::success("ArcBox daemon started"), cx);
+                            });
+                        }
+                        if let Some(svc) = local_service {
+                            svc.update(cx, |svc, cx| {
+                                svc.connect(cx);
+                            });
+                        }
                     }
                     DaemonManagerEvent::StateChanged(DaemonState::Starting) => {
                         tracing::info!("Daemon is starting...");
                     }
                     DaemonManagerEvent::StateChanged(DaemonState::Failed(err)) => {
-                        tracing::error!("Daemon failed to start: {}", err);
-                        // TODO: Show error notification to user
+                        tracing::error!("Daemon failed: {}", err);
+                        if let Some(id) = this.daemon_failure_toast.take() {
+                            this.notifications.update(cx, |center, cx| center.dismiss(id, cx));
+                        }
+                        let notification = if manager.read(cx).is_restart_pending() {
+                            Notification::warning("ArcBox daemon crashed")
+                                .message(format!("{}. Restarting…", err))
+                                .action("View Logs", |_window, cx| open_daemon_logs(cx))
+                        } else {
+                            let manager = manager.clone();
+                            Notification::error("ArcBox daemon failed")
+                                .message(err.clone())
+                                .action("Retry", move |_window, cx| {
+                                    manager.update(cx, |mgr, cx| {
+                                        mgr.start(cx);
+                                    });
+                                })
+                                .action("View Logs", |_window, cx| open_daemon_logs(cx))
+                        };
+                        this.daemon_failure_toast = Some(
+                            this.notifications.update(cx, |center, cx| center.push(notification, cx)),
+                        );
+                    }
+                    DaemonManagerEvent::StateChanged(DaemonState::Stopping) => {
+                        tracing::info!("Daemon is stopping...");
+                        // Going away on purpose; don't report it as a lost connection
+                        if let Some(svc) = local_service {
+                            svc.update(cx, |svc, cx| {
+                                svc.disconnect(cx);
+                            });
+                        }
                     }
                     DaemonManagerEvent::StateChanged(DaemonState::Stopped) => {
                         tracing::info!("Daemon stopped");
+                        // Don't keep retrying against a daemon we stopped on purpose
+                        if let Some(svc) = local_service {
+                            svc.update(cx, |svc, cx| {
+                                svc.disconnect(cx);
+                            });
+                        }
                     }
                 }
             },
         )
         .detach();
 
-        // Subscribe to daemon service events and forward to views
-        cx.subscribe(&daemon_service, |this, _, event: &DaemonEvent, cx| {
-            match event {
-                DaemonEvent::ContainersLoaded(response) => {
-                    this.containers_view.update(cx, |view, cx| {
-                        view.on_containers_loaded(response.clone(), cx);
-                    });
-                }
-                DaemonEvent::MachinesLoaded(_response) => {
-                    // TODO: Forward to machines view
-                }
-                DaemonEvent::ImagesLoaded(response) => {
-                    this.images_view.update(cx, |view, cx| {
-                        view.on_images_loaded(response.clone(), cx);
-                    });
-                }
-                DaemonEvent::NetworksLoaded(_response) => {
-                    // TODO: Forward to networks view when it supports data loading
-                }
-                DaemonEvent::NetworkCreated(id) => {
-                    tracing::info!("Network created: {}", id);
-                }
-                DaemonEvent::NetworkRemoved(id) => {
-                    tracing::info!("Network removed: {}", id);
-                }
-                DaemonEvent::ContainerCreated(id) => {
-                    tracing::info!("Container created: {}", id);
-                }
-                DaemonEvent::ContainerStarted(id) => {
-                    tracing::info!("Container started: {}", id);
-                }
-                DaemonEvent::ContainerStopped(id) => {
-                    tracing::info!("Container stopped: {}", id);
-                }
-                DaemonEvent::ContainerRemoved(id) => {
-                    tracing::info!("Container removed: {}", id);
-                }
-                DaemonEvent::OperationFailed(error) => {
-                    tracing::error!("Operation failed: {}", error);
-                    // TODO: Show error notification to user
-                }
-                DaemonEvent::LogsReceived { .. } => {
-                    // Handled by LogViewer components directly via their own subscriptions
-                }
-            }
+        // Follow context changes made from the sidebar or the settings window
+        cx.observe(&settings_store, |this, _, cx| {
+            this.on_settings_changed(cx);
         })
         .detach();
 
+        let daemon_subscriptions = Self::subscribe_daemon(&daemon_service, cx);
+
+        // Quitting through `quit` stops the daemon first. This hook only
+        // catches other exits, where there is no time to wait for it.
+        let quit_subscription = cx.on_app_quit(|this, cx| {
+            let keep_running = this.settings_store.read(cx).settings().keep_running_when_quit;
+            this.daemon_manager.update(cx, |mgr, _cx| {
+                if keep_running {
+                    mgr.detach();
+                } else {
+                    mgr.terminate();
+                }
+            });
+            async {}
+        });
+        cx.set_global(GlobalArcBoxApp(cx.entity().downgrade()));
+
         // Start daemon on app launch
         daemon_manager.update(cx, |mgr, cx| {
             mgr.start(cx);
@@ -183,9 +240,20 @@ impl ArcBoxApp {
             current_nav: NavItem::Containers,
             sidebar_width: SIDEBAR_DEFAULT_WIDTH,
             sidebar_collapsed: false,
+            context_menu_open: false,
             daemon_manager,
+            settings_store,
+            daemon_services,
             daemon_service,
+            _daemon_subscriptions: daemon_subscriptions,
             image_icon_service,
+            notifications,
+            daemon_failure_toast: None,
+            connection_toast: None,
+            quitting: false,
+            _quit_ticker: None,
+            _quit_subscription: quit_subscription,
+            notification_layer,
             containers_view,
             machines_view,
             images_view,
@@ -194,6 +262,529 @@ impl ArcBoxApp {
         }
     }
 
+    /// Create the service for a context and start connecting
+    ///
+    /// The local context only connects once the managed daemon is running;
+    /// the daemon manager subscription takes care of it otherwise.
+    fn create_daemon_service(
+        context: DaemonContext,
+        daemon_manager: &Entity<DaemonManager>,
+        cx: &mut App,
+    ) -> Entity<DaemonService> {
+        let should_connect =
+            !context.is_managed() || *daemon_manager.read(cx).state() == DaemonState::Running;
+        let service = cx.new(|cx| DaemonService::for_context(context, cx));
+        if should_connect {
+            service.update(cx, |svc, cx| {
+                svc.connect(cx);
+            });
+        }
+        service
+    }
+
+    /// Forward events from a daemon service to the views and re-render on state changes
+    fn subscribe_daemon(
+        daemon_service: &Entity<DaemonService>,
+        cx: &mut Context<Self>,
+    ) -> Vec<Subscription> {
+        vec![
+            cx.subscribe(daemon_service, |this, _, event: &DaemonEvent, cx| {
+                this.on_daemon_event(event, cx);
+            }),
+            cx.observe(daemon_service, |_, _, cx| cx.notify()),
+        ]
+    }
+
+    /// Drop services for removed or edited contexts and follow the active context
+    fn on_settings_changed(&mut self, cx: &mut Context<Self>) {
+        let settings = self.settings_store.read(cx).settings().clone();
+
+        self.daemon_manager.update(cx, |mgr, _cx| {
+            mgr.set_restart_limit(settings.daemon_restart_limit);
+        });
+
+        let stale: Vec<String> = self
+            .daemon_services
+            .iter()
+            .filter(|(name, svc)| settings.context(name) != Some(svc.read(cx).context()))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in stale {
+            if let Some(svc) = self.daemon_services.remove(&name) {
+                svc.update(cx, |svc, cx| {
+                    svc.disconnect(cx);
+                });
+            }
+        }
+
+        let active = settings.active();
+        let is_current = self
+            .daemon_services
+            .get(&active.name)
+            .is_some_and(|svc| svc == &self.daemon_service);
+        if !is_current {
+            self.switch_context(active, cx);
+        }
+    }
+
+    /// Re-point every view at the daemon for the given context
+    fn switch_context(&mut self, context: DaemonContext, cx: &mut Context<Self>) {
+        tracing::info!("Switching to context {} ({})", context.name, context.endpoint.display());
+
+        // Only the active context stays around; an inactive one would keep its
+        // runtime, health probe and event stream running for nothing
+        let previous = self.daemon_service.read(cx).context().name.clone();
+        if previous != context.name {
+            if let Some(svc) = self.daemon_services.remove(&previous) {
+                svc.update(cx, |svc, cx| {
+                    svc.disconnect(cx);
+                });
+            }
+        }
+
+        let daemon_service = match self.daemon_services.get(&context.name) {
+            Some(svc) => svc.clone(),
+            None => {
+                let name = context.name.clone();
+                let svc = Self::create_daemon_service(context, &self.daemon_manager, cx);
+                self.daemon_services.insert(name, svc.clone());
+                svc
+            }
+        };
+
+        self._daemon_subscriptions = Self::subscribe_daemon(&daemon_service, cx);
+        self.daemon_service = daemon_service.clone();
+
+        self.containers_view.update(cx, |view, cx| {
+            view.set_daemon_service(daemon_service.clone(), cx);
+        });
+        self.images_view.update(cx, |view, cx| {
+            view.set_daemon_service(daemon_service.clone(), cx);
+        });
+        self.networks_view.update(cx, |view, cx| {
+            view.set_daemon_service(daemon_service.clone(), cx);
+        });
+        self.volumes_view.update(cx, |view, cx| {
+            view.reset(cx);
+        });
+        cx.notify();
+    }
+
+    /// Handle an event from the active daemon service
+    fn on_daemon_event(&mut self, event: &DaemonEvent, cx: &mut Context<Self>) {
+        match event {
+            DaemonEvent::ContainersLoaded(response) => {
+                self.containers_view.update(cx, |view, cx| {
+                    view.on_containers_loaded(response.clone(), cx);
+                });
+            }
+            DaemonEvent::MachinesLoaded(_response) => {
+                // TODO: Forward to machines view
+            }
+            DaemonEvent::ImagesLoaded(response) => {
+                self.images_view.update(cx, |view, cx| {
+                    view.on_images_loaded(response.clone(), cx);
+                });
+            }
+            DaemonEvent::NetworksLoaded(response) => {
+                self.networks_view.update(cx, |view, cx| {
+                    view.on_networks_loaded(response.clone(), cx);
+                });
+            }
+            DaemonEvent::NetworkCreated(id) => {
+                tracing::info!("Network created: {}", id);
+                self.push_notification(Notification::success("Network created"), cx);
+            }
+            DaemonEvent::NetworkRemoved(id) => {
+                tracing::info!("Network removed: {}", id);
+                self.push_notification(Notification::success("Network removed"), cx);
+            }
+            DaemonEvent::ContainerCreated(id) => {
+                tracing::info!("Container created: {}", id);
+                let title = format!("Created {}", self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerStarted(id) => {
+                tracing::info!("Container started: {}", id);
+                let title = format!("Started {}", self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerStopped(id) => {
+                tracing::info!("Container stopped: {}", id);
+                let title = format!("Stopped {}", self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerRemoved(id) => {
+                tracing::info!("Container removed: {}", id);
+                let title = format!("Removed {}", self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerPaused(id) => {
+                tracing::info!("Container paused: {}", id);
+                let title = format!("Paused {}", self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerUnpaused(id) => {
+                tracing::info!("Container unpaused: {}", id);
+                let title = format!("Resumed {}", self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerRestarted(id) => {
+                tracing::info!("Container restarted: {}", id);
+                let title = format!("Restarted {}", self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerKilled { id, signal } => {
+                tracing::info!("Sent {} to container {}", signal, id);
+                let title = format!("Sent {} to {}", signal, self.container_display_name(id, cx));
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::ContainerRenamed { id, name } => {
+                tracing::info!("Container {} renamed to {}", id, name);
+                let title = format!("Renamed {} to {}", self.container_display_name(id, cx), name);
+                self.push_notification(Notification::success(title), cx);
+            }
+            DaemonEvent::OperationFailed(error) => {
+                tracing::error!("{}", error);
+                match error.operation() {
+                    DaemonOperation::ListContainers
+                    | DaemonOperation::CreateContainer
+                    | DaemonOperation::StartContainer
+                    | DaemonOperation::StopContainer
+                    | DaemonOperation::RemoveContainer
+                    | DaemonOperation::PauseContainer
+                    | DaemonOperation::UnpauseContainer
+                    | DaemonOperation::RestartContainer
+                    | DaemonOperation::KillContainer
+                    | DaemonOperation::RenameContainer
+                    | DaemonOperation::InspectContainer
+                    | DaemonOperation::ContainerLogs
+                    | DaemonOperation::Exec
+                    | DaemonOperation::BrowseFiles
+                    | DaemonOperation::DownloadFiles
+                    | DaemonOperation::UploadFiles => {
+                        self.containers_view.update(cx, |view, cx| {
+                            view.on_operation_failed(error, cx);
+                        });
+                    }
+                    DaemonOperation::ListImages | DaemonOperation::PullImage => {
+                        self.images_view.update(cx, |view, cx| {
+                            view.on_operation_failed(error, cx);
+                        });
+                    }
+                    DaemonOperation::ListNetworks
+                    | DaemonOperation::CreateNetwork
+                    | DaemonOperation::RemoveNetwork => {
+                        self.networks_view.update(cx, |view, cx| {
+                            view.on_operation_failed(error, cx);
+                        });
+                    }
+                    DaemonOperation::ListMachines
+                    | DaemonOperation::ContainerStats
+                    | DaemonOperation::Events => {}
+                }
+                self.notify_operation_failed(error, cx);
+            }
+            DaemonEvent::ContainerUpdated(summary) => {
+                self.containers_view.update(cx, |view, cx| {
+                    view.on_container_updated(summary.clone(), cx);
+                });
+            }
+            DaemonEvent::ContainerStateChanged { id, state } => {
+                self.containers_view.update(cx, |view, cx| {
+                    view.on_container_state_changed(id, state, cx);
+                });
+            }
+            DaemonEvent::ContainerDestroyed(id) => {
+                let name = self.containers_view.read(cx).container_name(id);
+                self.containers_view.update(cx, |view, cx| {
+                    view.on_container_destroyed(id, cx);
+                });
+                // The container may have been the last user of its volumes
+                let volumes = name
+                    .map(|name| self.volumes_view.read(cx).volumes_used_by(&name))
+                    .unwrap_or_default();
+                self.daemon_service.update(cx, |svc, cx| {
+                    for name in volumes {
+                        svc.fetch_volume_usage(name, cx);
+                    }
+                });
+            }
+            DaemonEvent::ImageUpdated(summary) => {
+                self.images_view.update(cx, |view, cx| {
+                    view.on_image_updated(summary.clone(), cx);
+                });
+            }
+            DaemonEvent::ImageDeleted(id) => {
+                self.images_view.update(cx, |view, cx| {
+                    view.on_image_deleted(id, cx);
+                });
+            }
+            DaemonEvent::NetworkEventReceived(event) => {
+                self.networks_view.update(cx, |view, cx| {
+                    view.on_network_event(event, cx);
+                });
+            }
+            DaemonEvent::VolumeEventReceived(event) => {
+                self.volumes_view.update(cx, |view, cx| {
+                    view.on_volume_event(event, cx);
+                });
+            }
+            DaemonEvent::VolumeUsageLoaded { name, containers } => {
+                self.volumes_view.update(cx, |view, cx| {
+                    view.on_volume_usage(name, containers.clone(), cx);
+                });
+            }
+            DaemonEvent::ConnectionLost(reason) => {
+                tracing::warn!("Daemon connection lost: {}", reason);
+                let context = self.daemon_service.read(cx).context().name.clone();
+                let notification = Notification::warning(format!("Lost connection to {}", context))
+                    .message(format!("{}. Reconnecting…", reason));
+                self.connection_toast = Some(self.push_notification(notification, cx));
+            }
+            DaemonEvent::Reconnected => {
+                tracing::info!("Daemon connection restored");
+                let context = self.daemon_service.read(cx).context().name.clone();
+                if let Some(id) = self.connection_toast.take() {
+                    self.notifications.update(cx, |center, cx| center.dismiss(id, cx));
+                }
+                let notification = Notification::success(format!("Reconnected to {}", context));
+                self.push_notification(notification, cx);
+            }
+            DaemonEvent::Incompatible(reason) => {
+                let context = self.daemon_service.read(cx).context().clone();
+                if context.is_managed() {
+                    // Reported through the daemon manager's failure toast
+                    let reason = reason.clone();
+                    self.daemon_manager.update(cx, |manager, cx| {
+                        manager.mark_incompatible(reason, cx);
+                    });
+                } else {
+                    let notification =
+                        Notification::error(format!("Incompatible daemon at {}", context.name))
+                            .message(reason.clone());
+                    self.push_notification(notification, cx);
+                }
+            }
+            DaemonEvent::CapabilitiesChanged => {
+                if self.current_nav == NavItem::Machines
+                    && !self.daemon_service.read(cx).supports(Capability::Machines)
+                {
+                    self.current_nav = NavItem::Containers;
+                    cx.notify();
+                }
+            }
+            DaemonEvent::LogsReceived { .. } => {
+                // Handled by LogViewer components directly via their own subscriptions
+            }
+            DaemonEvent::StatsReceived { container_id, stats } => {
+                self.containers_view.update(cx, |view, cx| {
+                    view.on_stats_received(container_id, *stats, cx);
+                });
+            }
+        }
+    }
+
+    /// Push a notification to the shared center
+    fn push_notification(&self, notification: Notification, cx: &mut Context<Self>) -> usize {
+        self.notifications.update(cx, |center, cx| center.push(notification, cx))
+    }
+
+    /// Container name if known, otherwise its short ID
+    fn container_display_name(&self, id: &str, cx: &App) -> String {
+        self.containers_view
+            .read(cx)
+            .container_name(id)
+            .unwrap_or_else(|| short_id(id).to_string())
+    }
+
+    /// Turn a failed operation into a toast with follow-up actions
+    fn notify_operation_failed(&mut self, error: &DaemonError, cx: &mut Context<Self>) {
+        // Unreachable daemon is reported once by the connection-lost toast
+        if matches!(error, DaemonError::Unavailable { .. }) {
+            return;
+        }
+
+        let operation = error.operation();
+        let resource = error.resource().map(str::to_string);
+        let is_container_op = matches!(
+            operation,
+            DaemonOperation::CreateContainer
+                | DaemonOperation::StartContainer
+                | DaemonOperation::StopContainer
+                | DaemonOperation::RemoveContainer
+                | DaemonOperation::PauseContainer
+                | DaemonOperation::UnpauseContainer
+                | DaemonOperation::RestartContainer
+                | DaemonOperation::KillContainer
+                | DaemonOperation::RenameContainer
+                | DaemonOperation::InspectContainer
+                | DaemonOperation::ContainerLogs
+                | DaemonOperation::Exec
+                | DaemonOperation::BrowseFiles
+                | DaemonOperation::DownloadFiles
+                | DaemonOperation::UploadFiles
+        );
+        let target = match &resource {
+            Some(id) if is_container_op => Some(self.container_display_name(id, cx)),
+            Some(id) => Some(short_id(id).to_string()),
+            None => None,
+        };
+        let title = format!("Failed to {}", operation.describe(target.as_deref()));
+
+        let mut notification = match operation {
+            // Background refreshes are less urgent than user actions
+            DaemonOperation::ListMachines
+            | DaemonOperation::ListContainers
+            | DaemonOperation::ListImages
+            | DaemonOperation::ListNetworks
+            | DaemonOperation::Events => Notification::warning(title),
+            _ => Notification::error(title),
+        }
+        .message(capitalize(error.message()));
+
+        let daemon = self.daemon_service.clone();
+        let can_retry = matches!(
+            error,
+            DaemonError::NotConnected { .. } | DaemonError::Timeout { .. } | DaemonError::Internal { .. }
+        );
+        notification = match (operation, resource.clone()) {
+            (DaemonOperation::RemoveContainer, Some(id))
+                if matches!(error, DaemonError::Conflict { .. }) =>
+            {
+                daemon_action(notification, "Force Remove", &daemon, move |svc, cx| {
+                    svc.remove_container(id.clone(), true, cx);
+                })
+            }
+            _ if !can_retry => notification,
+            (DaemonOperation::ListMachines, _) => {
+                daemon_action(notification, "Retry", &daemon, |svc, cx| svc.list_machines(cx))
+            }
+            (DaemonOperation::ListContainers, _) => {
+                daemon_action(notification, "Retry", &daemon, |svc, cx| svc.list_containers(true, cx))
+            }
+            (DaemonOperation::ListImages, _) => {
+                daemon_action(notification, "Retry", &daemon, |svc, cx| svc.list_images(cx))
+            }
+            (DaemonOperation::ListNetworks, _) => {
+                daemon_action(notification, "Retry", &daemon, |svc, cx| svc.list_networks(cx))
+            }
+            (DaemonOperation::StartContainer, Some(id)) => {
+                daemon_action(notification, "Retry", &daemon, move |svc, cx| {
+                    svc.start_container(id.clone(), cx);
+                })
+            }
+            (DaemonOperation::StopContainer, Some(id)) => {
+                daemon_action(notification, "Retry", &daemon, move |svc, cx| {
+                    svc.stop_container(id.clone(), 10, cx);
+                })
+            }
+            (DaemonOperation::RemoveContainer, Some(id)) => {
+                daemon_action(notification, "Retry", &daemon, move |svc, cx| {
+                    svc.remove_container(id.clone(), false, cx);
+                })
+            }
+            (DaemonOperation::PauseContainer, Some(id)) => {
+                daemon_action(notification, "Retry", &daemon, move |svc, cx| {
+                    svc.pause_container(id.clone(), cx);
+                })
+            }
+            (DaemonOperation::UnpauseContainer, Some(id)) => {
+                daemon_action(notification, "Retry", &daemon, move |svc, cx| {
+                    svc.unpause_container(id.clone(), cx);
+                })
+            }
+            (DaemonOperation::RestartContainer, Some(id)) => {
+                daemon_action(notification, "Retry", &daemon, move |svc, cx| {
+                    svc.restart_container(id.clone(), 10, cx);
+                })
+            }
+            (DaemonOperation::RemoveNetwork, Some(id)) => {
+                daemon_action(notification, "Retry", &daemon, move |svc, cx| {
+                    svc.remove_network(id.clone(), cx);
+                })
+            }
+            _ => notification,
+        };
+
+        // A container that failed to start or stop usually says why in its logs
+        if let (
+            DaemonOperation::StartContainer
+            | DaemonOperation::StopContainer
+            | DaemonOperation::RestartContainer,
+            Some(id),
+        ) = (operation, resource)
+        {
+            if !matches!(error, DaemonError::NotFound { .. }) {
+                let app = cx.entity().downgrade();
+                notification = notification.action("View Logs", move |_window, cx| {
+                    app.update(cx, |this, cx| this.show_container_logs(&id, cx)).ok();
+                });
+            }
+        }
+
+        self.push_notification(notification, cx);
+    }
+
+    /// Jump to a container's logs
+    fn show_container_logs(&mut self, id: &str, cx: &mut Context<Self>) {
+        self.navigate(NavItem::Containers, cx);
+        self.containers_view.update(cx, |view, cx| {
+            view.show_logs(id, cx);
+        });
+    }
+
+    /// Stop (or detach from) the local daemon, then exit
+    ///
+    /// A second quit request while waiting kills the daemon and exits at once.
+    fn quit(&mut self, cx: &mut Context<Self>) {
+        if self.quitting {
+            self.force_quit(cx);
+            return;
+        }
+
+        let settings = self.settings_store.read(cx).settings().clone();
+        if settings.keep_running_when_quit {
+            tracing::info!("Quitting, leaving the daemon running");
+            self.daemon_manager.update(cx, |mgr, _cx| {
+                mgr.detach();
+            });
+            cx.quit();
+            return;
+        }
+
+        tracing::info!("Quitting, stopping the daemon first");
+        self.quitting = true;
+        self.context_menu_open = false;
+        let shutdown = self.daemon_manager.update(cx, |mgr, cx| {
+            mgr.shutdown(settings.shutdown_timeout(), cx)
+        });
+
+        cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            shutdown.await;
+            let _ = cx.update(|cx| cx.quit());
+        })
+        .detach();
+
+        self._quit_ticker = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            loop {
+                cx.background_executor().timer(Duration::from_millis(250)).await;
+                if this.update(cx, |_, cx| cx.notify()).is_err() {
+                    break;
+                }
+            }
+        }));
+        cx.notify();
+    }
+
+    /// Give up on a clean daemon shutdown
+    fn force_quit(&mut self, cx: &mut Context<Self>) {
+        self.daemon_manager.update(cx, |mgr, _cx| {
+            mgr.kill_now();
+        });
+        cx.quit();
+    }
+
     fn navigate(&mut self, item: NavItem, cx: &mut Context<Self>) {
         self.current_nav = item;
         cx.notify();
@@ -208,6 +799,19 @@ impl ArcBoxApp {
         self.sidebar_collapsed = !self.sidebar_collapsed;
         cx.notify();
     }
+
+    fn toggle_context_menu(&mut self, cx: &mut Context<Self>) {
+        self.context_menu_open = !self.context_menu_open;
+        cx.notify();
+    }
+
+    fn select_context(&mut self, name: String, cx: &mut Context<Self>) {
+        self.context_menu_open = false;
+        self.settings_store.update(cx, |store, cx| {
+            store.set_active_context(&name, cx);
+        });
+        cx.notify();
+    }
 }
 
 impl Render for ArcBoxApp {
@@ -216,6 +820,7 @@ impl Render for ArcBoxApp {
         let sidebar_collapsed = self.sidebar_collapsed;
 
         div()
+            .relative()
             .size_full()
             .flex()
             .flex_row()
@@ -229,6 +834,9 @@ impl Render for ArcBoxApp {
             })
             // Main content (naturally expands as sidebar shrinks)
             .child(self.render_main_content_without_header())
+            // Toasts and notification history on top of everything
+            .child(self.notification_layer.clone())
+            .when(self.quitting, |el| el.child(self.render_shutdown_overlay(cx)))
     }
 }
 
@@ -238,6 +846,7 @@ impl ArcBoxApp {
         let collapsed = self.sidebar_collapsed;
         let expanded_width = self.sidebar_width;
         let collapsed_width = SIDEBAR_COLLAPSED_WIDTH;
+        let machines_supported = self.daemon_service.read(cx).supports(Capability::Machines);
 
         div()
             .id("sidebar")
@@ -287,16 +896,22 @@ impl ArcBoxApp {
             .child(self.render_nav_item(NavItem::Volumes, collapsed, cx))
             .child(self.render_nav_item(NavItem::Images, collapsed, cx))
             .child(self.render_nav_item(NavItem::Networks, collapsed, cx))
-            // Linux section header (hidden when collapsed)
-            .when(!collapsed, |el| {
-                el.child(Theme::sidebar_section_header("Linux"))
-            })
-            .when(collapsed, |el| {
-                el.child(div().h(px(16.0))) // Separator when collapsed
+            // Linux section (only when the daemon supports machines)
+            .when(machines_supported, |el| {
+                el.when(!collapsed, |el| {
+                    el.child(Theme::sidebar_section_header("Linux"))
+                })
+                .when(collapsed, |el| {
+                    el.child(div().h(px(16.0))) // Separator when collapsed
+                })
+                .child(self.render_nav_item(NavItem::Machines, collapsed, cx))
             })
-            .child(self.render_nav_item(NavItem::Machines, collapsed, cx))
             // Bottom spacer
             .child(div().flex_1())
+            // Notification history toggle
+            .child(self.render_notifications_button(collapsed, cx))
+            // Daemon context switcher
+            .child(self.render_context_switcher(collapsed, cx))
             // Animate width
             .with_animation(
                 ElementId::Name(
@@ -394,6 +1009,290 @@ impl ArcBoxApp {
             })
     }
 
+    /// Bell button with unread count that opens the notification history
+    fn render_notifications_button(&self, collapsed: bool, cx: &Context<Self>) -> impl IntoElement {
+        let unread = self.notifications.read(cx).unread_count();
+        let is_open = self.notification_layer.read(cx).is_history_open();
+
+        div()
+            .id("notifications-toggle")
+            .when(collapsed, |el| el.mx_auto().w(px(36.0)).justify_center())
+            .when(!collapsed, |el| el.mx_2().px_2().gap_2())
+            .h(px(28.0))
+            .rounded(px(6.0))
+            .flex()
+            .items_center()
+            .text_sm()
+            .cursor_pointer()
+            .when(is_open, |el| el.bg(colors::sidebar_item_selected()))
+            .when(!is_open, |el| el.hover(|el| el.bg(colors::sidebar_item_hover())))
+            .on_click(cx.listener(|this, _, _window, cx| {
+                this.notification_layer.update(cx, |layer, cx| {
+                    layer.toggle_history(cx);
+                });
+                cx.notify();
+            }))
+            .child(
+                svg()
+                    .path("icons/bell.svg")
+                    .size(px(16.0))
+                    .flex_shrink_0()
+                    .text_color(colors::text_secondary()),
+            )
+            .when(!collapsed, |el| {
+                el.child(div().flex_1().text_color(colors::text()).child("Notifications"))
+            })
+            .when(unread > 0, |el| {
+                el.child(
+                    div()
+                        .min_w(px(16.0))
+                        .h(px(16.0))
+                        .px_1()
+                        .rounded(px(8.0))
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(colors::error())
+                        .text_xs()
+                        .text_color(colors::on_accent())
+                        .child(if unread > 99 { "99+".to_string() } else { unread.to_string() }),
+                )
+            })
+    }
+
+    /// Context switcher pinned to the bottom of the sidebar
+    fn render_context_switcher(&self, collapsed: bool, cx: &Context<Self>) -> impl IntoElement {
+        let active = self.daemon_service.read(cx).context().name.clone();
+        let state_color = Self::connection_color(self.daemon_service.read(cx).state());
+        let contexts = self.settings_store.read(cx).settings().contexts.clone();
+        let menu_open = self.context_menu_open;
+        let daemon_status = self.daemon_status_summary(cx);
+
+        div()
+            .relative()
+            .pb_2()
+            .child(
+                div()
+                    .id("context-switcher")
+                    .when(collapsed, |el| el.mx_auto().w(px(36.0)).justify_center())
+                    .when(!collapsed, |el| el.mx_2().px_2().gap_2())
+                    .h(px(28.0))
+                    .rounded(px(6.0))
+                    .flex()
+                    .items_center()
+                    .text_sm()
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::sidebar_item_hover()))
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.toggle_context_menu(cx);
+                    }))
+                    .child(
+                        div()
+                            .w(px(8.0))
+                            .h(px(8.0))
+                            .rounded_full()
+                            .flex_shrink_0()
+                            .bg(state_color),
+                    )
+                    .when(!collapsed, |el| {
+                        el.child(
+                            div()
+                                .flex_1()
+                                .overflow_hidden()
+                                .text_ellipsis()
+                                .text_color(colors::text())
+                                .child(active.clone()),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(colors::text_secondary())
+                                .child("▾"),
+                        )
+                    }),
+            )
+            .when(menu_open, |el| {
+                el.child(
+                    div()
+                        .absolute()
+                        .bottom(px(40.0))
+                        .left(px(8.0))
+                        .min_w(px(180.0))
+                        .bg(colors::background())
+                        .border_1()
+                        .border_color(colors::border())
+                        .rounded_md()
+                        .shadow_lg()
+                        .overflow_hidden()
+                        .children(contexts.into_iter().map(|context| {
+                            let is_active = context.name == active;
+                            let name = context.name.clone();
+                            div()
+                                .id(SharedString::from(format!("context-{}", context.name)))
+                                .px_3()
+                                .py_2()
+                                .flex()
+                                .flex_col()
+                                .cursor_pointer()
+                                .hover(|el| el.bg(colors::hover()))
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    this.select_context(name.clone(), cx);
+                                }))
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(colors::text())
+                                        .when(is_active, |el| el.font_weight(FontWeight::SEMIBOLD))
+                                        .child(context.name.clone()),
+                                )
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(colors::text_secondary())
+                                        .child(context.endpoint.display()),
+                                )
+                                .when(context.is_managed(), |el| {
+                                    el.child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(colors::text_muted())
+                                            .child(daemon_status.clone()),
+                                    )
+                                })
+                        }))
+                        .child(div().h(px(1.0)).bg(colors::border()))
+                        .child(
+                            div()
+                                .id("context-daemon-logs")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(colors::text_secondary())
+                                .cursor_pointer()
+                                .hover(|el| el.bg(colors::hover()).text_color(colors::text()))
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.context_menu_open = false;
+                                    cx.notify();
+                                    cx.defer(open_daemon_logs);
+                                }))
+                                .child("Daemon Logs…"),
+                        )
+                        .child(
+                            div()
+                                .id("context-manage")
+                                .px_3()
+                                .py_2()
+                                .text_sm()
+                                .text_color(colors::text_secondary())
+                                .cursor_pointer()
+                                .hover(|el| el.bg(colors::hover()).text_color(colors::text()))
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.context_menu_open = false;
+                                    cx.notify();
+                                    cx.defer(|cx| open_settings_at(SettingsSection::Docker, cx));
+                                }))
+                                .child("Manage Contexts…"),
+                        ),
+                )
+            })
+    }
+
+    /// Blocks the window while the daemon shuts down on quit
+    fn render_shutdown_overlay(&self, cx: &Context<Self>) -> impl IntoElement {
+        let deadline = self.daemon_manager.read(cx).shutdown_deadline();
+        let status = match deadline.map(|d| d.saturating_duration_since(Instant::now())) {
+            Some(remaining) if !remaining.is_zero() => format!(
+                "Waiting for the daemon to stop containers and machines… ({}s)",
+                remaining.as_secs() + 1
+            ),
+            Some(_) => "The daemon didn't stop in time, forcing it to quit…".to_string(),
+            None => "Stopping the daemon…".to_string(),
+        };
+
+        div()
+            .id("shutdown-overlay")
+            .occlude()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(rgba(0x00000033))
+            .child(
+                Theme::card()
+                    .w(px(360.0))
+                    .p_5()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .shadow_lg()
+                    .child(
+                        div()
+                            .text_base()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child("Quitting ArcBox"),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(colors::text_secondary())
+                            .child(status),
+                    )
+                    .child(
+                        div().flex().justify_end().child(
+                            div()
+                                .id("force-quit")
+                                .px_3()
+                                .py_1()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(colors::border())
+                                .text_sm()
+                                .cursor_pointer()
+                                .hover(|el| el.bg(colors::hover()))
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.force_quit(cx);
+                                }))
+                                .child("Quit Now"),
+                        ),
+                    ),
+            )
+    }
+
+    /// Managed daemon state, uptime and restart count, e.g. "Running · up 2h 5m · 1 restart"
+    fn daemon_status_summary(&self, cx: &App) -> String {
+        let manager = self.daemon_manager.read(cx);
+        let mut parts = vec![match manager.state() {
+            DaemonState::Stopped => "Stopped".to_string(),
+            DaemonState::Starting => "Starting".to_string(),
+            DaemonState::Running => "Running".to_string(),
+            DaemonState::Stopping => "Stopping".to_string(),
+            DaemonState::Failed(_) if manager.is_restart_pending() => "Restarting".to_string(),
+            DaemonState::Failed(_) => "Failed".to_string(),
+        }];
+        if let Some(uptime) = manager.uptime() {
+            parts.push(format!("up {}", format_uptime(uptime)));
+        }
+        match manager.restart_count() {
+            0 => {}
+            1 => parts.push("1 restart".to_string()),
+            n => parts.push(format!("{} restarts", n)),
+        }
+        parts.join(" · ")
+    }
+
+    /// Status dot color for a connection state
+    fn connection_color(state: &ConnectionState) -> Rgba {
+        match state {
+            ConnectionState::Connected => colors::running(),
+            ConnectionState::Connecting | ConnectionState::Reconnecting { .. } => colors::warning(),
+            ConnectionState::Error(_) => colors::error(),
+            ConnectionState::Disconnected => colors::stopped(),
+        }
+    }
+
     /// Main content area
     fn render_main_content_without_header(&self) -> impl IntoElement {
         div()
@@ -411,6 +1310,54 @@ impl ArcBoxApp {
     }
 }
 
+/// Add an action that runs against the daemon service
+fn daemon_action(
+    notification: Notification,
+    label: &'static str,
+    daemon: &Entity<DaemonService>,
+    f: impl Fn(&mut DaemonService, &mut Context<DaemonService>) + 'static,
+) -> Notification {
+    let daemon = daemon.clone();
+    notification.action(label, move |_window, cx| {
+        daemon.update(cx, |svc, cx| f(svc, cx));
+    })
+}
+
+/// Compact duration, e.g. "45s", "12m", "3h 5m", "2d 4h"
+fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d {}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
+/// Uppercase the first letter of a daemon error message
+fn capitalize(message: &str) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+struct GlobalArcBoxApp(WeakEntity<ArcBoxApp>);
+
+impl Global for GlobalArcBoxApp {}
+
+/// Quit the app (Cmd+Q / menu), shutting the local daemon down first
+pub fn quit(cx: &mut App) {
+    let app = cx
+        .try_global::<GlobalArcBoxApp>()
+        .and_then(|global| global.0.upgrade());
+    match app {
+        Some(app) => app.update(cx, |app, cx| app.quit(cx)),
+        None => cx.quit(),
+    }
+}
+
 /// Visual element shown during drag (invisible)
 struct ResizeHandleVisual {
     #[allow(dead_code)]
@@ -428,11 +1375,17 @@ impl Render for ResizeHandleVisual {
 
 /// Open settings window (called from menu or Cmd+,)
 pub fn open_settings(cx: &mut App) {
+    open_settings_at(SettingsSection::General, cx);
+}
+
+/// Open settings window on a specific section
+pub fn open_settings_at(section: SettingsSection, cx: &mut App) {
     // Check if settings window already exists
     for window in cx.windows() {
         if let Some(handle) = window.downcast::<SettingsView>() {
             // Focus existing window
-            let _ = handle.update(cx, |_, window, _cx| {
+            let _ = handle.update(cx, |view, window, cx| {
+                view.set_section(section, cx);
                 window.activate_window();
             });
             return;
@@ -456,6 +1409,43 @@ pub fn open_settings(cx: &mut App) {
         ..Default::default()
     };
 
-    cx.open_window(window_options, |_window, cx| cx.new(SettingsView::new))
+    cx.open_window(window_options, |_window, cx| {
+        cx.new(|cx| {
+            let mut view = SettingsView::new(cx);
+            view.set_section(section, cx);
+            view
+        })
+    })
         .expect("Failed to open settings window");
 }
+
+/// Open the Daemon Logs window (or focus it if already open)
+pub fn open_daemon_logs(cx: &mut App) {
+    for window in cx.windows() {
+        if let Some(handle) = window.downcast::<DaemonLogsView>() {
+            let _ = handle.update(cx, |_view, window, _cx| {
+                window.activate_window();
+            });
+            return;
+        }
+    }
+
+    let bounds = Bounds::centered(None, size(px(900.0), px(600.0)), cx);
+    let window_options = WindowOptions {
+        titlebar: Some(TitlebarOptions {
+            title: Some("Daemon Logs".into()),
+            appears_transparent: true,
+            traffic_light_position: Some(point(px(9.0), px(9.0))),
+        }),
+        window_bounds: Some(WindowBounds::Windowed(bounds)),
+        focus: true,
+        show: true,
+        kind: WindowKind::Normal,
+        is_movable: true,
+        window_background: WindowBackgroundAppearance::Opaque,
+        ..Default::default()
+    };
+
+    cx.open_window(window_options, |_window, cx| cx.new(DaemonLogsView::new))
+        .expect("Failed to open daemon logs window");
+}
@@ -0,0 +1,104 @@
+//! ANSI escape sequences in log output.
+//!
+//! Programs that think they're writing to a terminal color their output
+//! with SGR sequences (`ESC [ ... m`). [`AnsiText::parse`] strips every
+//! escape sequence from a line and keeps the styles the SGR ones describe,
+//! using the terminal emulator's SGR handling; cursor movement, erasing,
+//! OSC titles and the like are dropped.
+
+use std::ops::Range;
+
+use vte::{Params, Parser, Perform};
+
+use crate::components::CellStyle;
+
+/// A line with its escape sequences removed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnsiText {
+    /// The printable text
+    pub text: String,
+    /// Styled byte ranges of `text`, in order; unstyled text has no run
+    pub runs: Vec<(Range<usize>, CellStyle)>,
+}
+
+impl AnsiText {
+    pub fn parse(input: &str) -> Self {
+        // Most lines have no escapes at all
+        if !input.contains('\x1b') && !input.contains('\r') {
+            return Self {
+                text: input.to_string(),
+                runs: Vec::new(),
+            };
+        }
+
+        let mut collector = Collector::default();
+        let mut parser = Parser::new();
+        for byte in input.bytes() {
+            parser.advance(&mut collector, byte);
+        }
+        collector.line
+    }
+}
+
+#[derive(Default)]
+struct Collector {
+    line: AnsiText,
+    style: CellStyle,
+    /// A carriage return was seen; the next character starts the line over
+    /// (progress bars redraw themselves this way)
+    carriage_return: bool,
+}
+
+impl Perform for Collector {
+    fn print(&mut self, c: char) {
+        if std::mem::take(&mut self.carriage_return) {
+            self.line = AnsiText::default();
+        }
+        let start = self.line.text.len();
+        self.line.text.push(c);
+        let end = self.line.text.len();
+        if self.style == CellStyle::default() {
+            return;
+        }
+        match self.line.runs.last_mut() {
+            Some((range, style)) if range.end == start && *style == self.style => range.end = end,
+            _ => self.line.runs.push((start..end, self.style)),
+        }
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\t' => self.print('\t'),
+            b'\r' => self.carriage_return = true,
+            _ => {}
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, _intermediates: &[u8], ignore: bool, action: char) {
+        if action == 'm' && !ignore {
+            let params: Vec<Vec<u16>> = params.iter().map(|group| group.to_vec()).collect();
+            self.style.apply_sgr(&params);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_styles_and_strips_other_sequences() {
+        let line = AnsiText::parse("\x1b[2K\x1b[1;31mERROR\x1b[0m done \x1b[38;5;208mhot\x1b[m\x1b]0;title\x07");
+        assert_eq!(line.text, "ERROR done hot");
+        assert_eq!(line.runs.len(), 2);
+        assert_eq!(line.runs[0].0, 0..5);
+        assert!(line.runs[0].1.bold);
+        assert_eq!(line.runs[1].0, 11..14);
+
+        let plain = AnsiText::parse("no escapes");
+        assert_eq!(plain.text, "no escapes");
+        assert!(plain.runs.is_empty());
+
+        assert_eq!(AnsiText::parse("10%\r\x1b[K50%").text, "50%");
+    }
+}
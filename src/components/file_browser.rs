@@ -0,0 +1,841 @@
+//! File browser component for a container's filesystem.
+//!
+//! Directories of running containers; a stopped one explains why it can't
+//! be browsed:
+//! - Directory tree, listed one level at a time with `find` in the container
+//! - Text and image previews
+//! - Download as a tar file or extracted into a host folder
+//! - Upload by dropping files onto the browser (or a folder row)
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use gpui::*;
+use gpui::prelude::*;
+
+use crate::models::format_bytes;
+use crate::services::{
+    normalize_path, join_path, parent_path, DaemonService, FileEntry, FileKind, Notification,
+    NotificationCenter,
+};
+use crate::theme::{colors, Theme, MONO_FONT};
+
+/// Most text read for a preview
+const TEXT_PREVIEW_LIMIT: usize = 256 * 1024;
+
+/// Larger images are not previewed
+const IMAGE_PREVIEW_LIMIT: u64 = 10 * 1024 * 1024;
+
+/// Bytes checked for NUL when deciding whether a file is text
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+const ROW_HEIGHT: f32 = 26.0;
+const INDENT: f32 = 16.0;
+
+/// What the preview pane shows for the selected entry
+enum Preview {
+    None,
+    Loading,
+    Text { content: String, truncated: bool },
+    Image(Arc<Image>),
+    Binary,
+    TooLarge,
+    /// Symlink with its resolved target (is_dir is None while stat is pending or failed)
+    Link { target: String, is_dir: Option<bool> },
+    Failed(String),
+}
+
+/// Browser for one container's filesystem
+pub struct FileBrowser {
+    container_id: String,
+    daemon_service: Entity<DaemonService>,
+    /// Directory listings by absolute path
+    listings: HashMap<String, Vec<FileEntry>>,
+    expanded: HashSet<String>,
+    /// Directories currently being listed
+    loading: HashSet<String>,
+    /// Listing failures by directory
+    errors: HashMap<String, String>,
+    selected: Option<FileEntry>,
+    /// Select this path once its parent has been listed
+    pending_select: Option<String>,
+    preview: Preview,
+    /// Transfers in progress, shown in the toolbar
+    transfers: usize,
+    /// Whether the root listing has been requested
+    requested: bool,
+    _preview_task: Option<Task<()>>,
+}
+
+impl FileBrowser {
+    pub fn new(
+        container_id: String,
+        daemon_service: Entity<DaemonService>,
+        _cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            container_id,
+            daemon_service,
+            listings: HashMap::new(),
+            expanded: HashSet::from(["/".to_string()]),
+            loading: HashSet::new(),
+            errors: HashMap::new(),
+            selected: None,
+            pending_select: None,
+            preview: Preview::None,
+            transfers: 0,
+            requested: false,
+            _preview_task: None,
+        }
+    }
+
+    /// List a directory
+    fn list(&mut self, path: String, cx: &mut Context<Self>) {
+        if !self.loading.insert(path.clone()) {
+            return;
+        }
+        self.errors.remove(&path);
+
+        let container_id = self.container_id.clone();
+        let task = self.daemon_service.update(cx, |svc, cx| {
+            svc.list_directory(container_id, path.clone(), cx)
+        });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.loading.remove(&path);
+                    match result {
+                        Ok(mut entries) => {
+                            // Folders first, then by name
+                            entries.sort_by(|a, b| {
+                                b.is_dir().cmp(&a.is_dir()).then_with(|| a.name.cmp(&b.name))
+                            });
+                            this.listings.insert(normalize_path(&path), entries);
+                            this.resolve_pending_select(cx);
+                        }
+                        Err(e) => {
+                            this.errors.insert(path, e.message().to_string());
+                        }
+                    }
+                    cx.notify();
+                })
+            }).ok();
+        }).detach();
+        cx.notify();
+    }
+
+    /// Re-list the directory uploads would go to
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        let dir = self.target_directory();
+        self.list(dir, cx);
+    }
+
+    /// Directory new files are uploaded into: the selected folder, or the selected file's folder
+    fn target_directory(&self) -> String {
+        match &self.selected {
+            Some(entry) if entry.is_dir() => entry.path.clone(),
+            Some(entry) => parent_path(&entry.path),
+            None => "/".to_string(),
+        }
+    }
+
+    fn toggle_dir(&mut self, path: String, cx: &mut Context<Self>) {
+        if !self.expanded.remove(&path) {
+            if !self.listings.contains_key(&path) {
+                self.list(path.clone(), cx);
+            }
+            self.expanded.insert(path);
+        }
+        cx.notify();
+    }
+
+    fn select(&mut self, entry: FileEntry, cx: &mut Context<Self>) {
+        self._preview_task = None;
+        self.preview = Preview::None;
+        match entry.kind {
+            FileKind::Directory => self.toggle_dir(entry.path.clone(), cx),
+            FileKind::File => self.load_preview(&entry, cx),
+            FileKind::Symlink => self.load_link(&entry, cx),
+            FileKind::Other => {}
+        }
+        self.selected = Some(entry);
+        cx.notify();
+    }
+
+    /// Expand every folder above `path` and select it once it is listed
+    fn reveal(&mut self, path: String, cx: &mut Context<Self>) {
+        let mut dir = parent_path(&path);
+        loop {
+            self.expanded.insert(dir.clone());
+            if dir == "/" {
+                break;
+            }
+            dir = parent_path(&dir);
+        }
+        let parent = parent_path(&path);
+        self.pending_select = Some(path);
+        if self.listings.contains_key(&parent) {
+            self.resolve_pending_select(cx);
+        } else {
+            self.list(parent, cx);
+        }
+    }
+
+    fn resolve_pending_select(&mut self, cx: &mut Context<Self>) {
+        let Some(path) = self.pending_select.as_ref() else {
+            return;
+        };
+        let entry = self
+            .listings
+            .get(&parent_path(path))
+            .and_then(|entries| entries.iter().find(|e| &e.path == path))
+            .cloned();
+        if let Some(entry) = entry {
+            self.pending_select = None;
+            // Selecting a folder toggles it; revealed folders should end up open
+            self.expanded.remove(&entry.path);
+            self.select(entry, cx);
+        }
+    }
+
+    fn load_preview(&mut self, entry: &FileEntry, cx: &mut Context<Self>) {
+        let format = image_format(&entry.name);
+        if format.is_some() && entry.size > IMAGE_PREVIEW_LIMIT {
+            self.preview = Preview::TooLarge;
+            return;
+        }
+        let limit = match format {
+            Some(_) => entry.size as usize,
+            None => TEXT_PREVIEW_LIMIT,
+        };
+
+        self.preview = Preview::Loading;
+        let container_id = self.container_id.clone();
+        let path = entry.path.clone();
+        let task = self.daemon_service.update(cx, |svc, cx| {
+            svc.read_file(container_id, path, limit, cx)
+        });
+
+        self._preview_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.preview = match (result, format) {
+                        (Ok(contents), Some(format)) => {
+                            Preview::Image(Arc::new(Image::from_bytes(format, contents.data)))
+                        }
+                        (Ok(contents), None) => {
+                            let sniff = &contents.data[..contents.data.len().min(BINARY_SNIFF_LEN)];
+                            if sniff.contains(&0) {
+                                Preview::Binary
+                            } else {
+                                Preview::Text {
+                                    content: String::from_utf8_lossy(&contents.data).into_owned(),
+                                    truncated: contents.truncated,
+                                }
+                            }
+                        }
+                        (Err(e), _) => Preview::Failed(e.message().to_string()),
+                    };
+                    cx.notify();
+                })
+            }).ok();
+        }));
+    }
+
+    /// Stat a symlink's target to tell whether it can be opened as a folder
+    fn load_link(&mut self, entry: &FileEntry, cx: &mut Context<Self>) {
+        let Some(target) = entry.link_target.as_deref() else {
+            return;
+        };
+        let target = if target.starts_with('/') {
+            normalize_path(target)
+        } else {
+            normalize_path(&join_path(&parent_path(&entry.path), target))
+        };
+        self.preview = Preview::Link {
+            target: target.clone(),
+            is_dir: None,
+        };
+
+        let container_id = self.container_id.clone();
+        let task = self.daemon_service.update(cx, |svc, cx| {
+            svc.stat_path(container_id, target, cx)
+        });
+        self._preview_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    if let (Preview::Link { is_dir, .. }, Ok(stat)) = (&mut this.preview, result) {
+                        *is_dir = Some(stat.is_dir());
+                        cx.notify();
+                    }
+                })
+            }).ok();
+        }));
+    }
+
+    /// Ask where to save `entry`, then download it
+    fn download(&mut self, entry: FileEntry, extract: bool, cx: &mut Context<Self>) {
+        let downloads = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/"));
+        let suggested = format!("{}.tar", if entry.path == "/" { "root" } else { entry.name.as_str() });
+        let folder = extract.then(|| {
+            cx.prompt_for_paths(PathPromptOptions {
+                files: false,
+                directories: true,
+                multiple: false,
+                prompt: Some("Extract Here".into()),
+            })
+        });
+        let file = (!extract).then(|| cx.prompt_for_new_path(&downloads, Some(&suggested)));
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let destination = match (folder, file) {
+                (Some(folder), _) => match folder.await {
+                    Ok(Ok(Some(paths))) => paths.into_iter().next(),
+                    _ => None,
+                },
+                (None, Some(file)) => match file.await {
+                    Ok(Ok(path)) => path,
+                    _ => None,
+                },
+                (None, None) => None,
+            };
+            let Some(destination) = destination else {
+                return;
+            };
+
+            let task = cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.transfers += 1;
+                    cx.notify();
+                    let container_id = this.container_id.clone();
+                    let path = entry.path.clone();
+                    let destination = destination.clone();
+                    this.daemon_service.update(cx, |svc, cx| {
+                        svc.download_path(container_id, path, destination, extract, cx)
+                    })
+                })
+            });
+            let Ok(Ok(task)) = task else {
+                return;
+            };
+            let result = task.await;
+
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.transfers -= 1;
+                    cx.notify();
+                })
+                .ok();
+                // Failures are reported by the daemon service
+                if result.is_ok() {
+                    let notification = Notification::success(format!("Downloaded {}", entry.name))
+                        .message(destination.display().to_string())
+                        .action("Reveal", move |_, cx| cx.reveal_path(&destination));
+                    NotificationCenter::global(cx).update(cx, |center, cx| {
+                        center.push(notification, cx);
+                    });
+                }
+            }).ok();
+        }).detach();
+    }
+
+    /// Upload host paths into a container directory
+    fn upload(&mut self, paths: Vec<PathBuf>, directory: String, cx: &mut Context<Self>) {
+        if paths.is_empty() {
+            return;
+        }
+        let count = paths.len();
+        self.transfers += 1;
+        let container_id = self.container_id.clone();
+        let task = self.daemon_service.update(cx, |svc, cx| {
+            svc.upload_paths(container_id, directory.clone(), paths, cx)
+        });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.transfers -= 1;
+                    if result.is_ok() {
+                        // Show the new files
+                        this.expanded.insert(directory.clone());
+                        this.listings.remove(&directory);
+                        this.list(directory.clone(), cx);
+                    }
+                    cx.notify();
+                })
+                .ok();
+                if result.is_ok() {
+                    let title = match count {
+                        1 => format!("Uploaded 1 item to {}", directory),
+                        n => format!("Uploaded {} items to {}", n, directory),
+                    };
+                    NotificationCenter::global(cx).update(cx, |center, cx| {
+                        center.push(Notification::success(title), cx);
+                    });
+                }
+            }).ok();
+        }).detach();
+        cx.notify();
+    }
+
+    /// Pick host files to upload into the target directory
+    fn choose_upload(&mut self, cx: &mut Context<Self>) {
+        let directory = self.target_directory();
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: true,
+            multiple: true,
+            prompt: Some("Upload".into()),
+        });
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let Ok(Ok(Some(paths))) = paths.await else {
+                return;
+            };
+            cx.update(|cx| {
+                this.update(cx, |this, cx| this.upload(paths, directory, cx))
+            }).ok();
+        }).detach();
+    }
+
+    /// Visible tree rows as (depth, entry)
+    fn visible_rows(&self) -> Vec<(usize, &FileEntry)> {
+        let mut rows = Vec::new();
+        self.collect_rows("/", 0, &mut rows);
+        rows
+    }
+
+    fn collect_rows<'a>(&'a self, dir: &str, depth: usize, rows: &mut Vec<(usize, &'a FileEntry)>) {
+        let Some(entries) = self.listings.get(dir) else {
+            return;
+        };
+        for entry in entries {
+            rows.push((depth, entry));
+            if entry.is_dir() && self.expanded.contains(&entry.path) {
+                self.collect_rows(&entry.path, depth + 1, rows);
+            }
+        }
+    }
+
+    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let target = self.target_directory();
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_2()
+            .h(px(36.0))
+            .px_3()
+            .border_b_1()
+            .border_color(colors::border_subtle())
+            .bg(colors::surface())
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .text_xs()
+                    .font_family(MONO_FONT)
+                    .text_color(colors::text_secondary())
+                    .child(target),
+            )
+            .when(self.transfers > 0, |el| {
+                el.child(
+                    div()
+                        .text_xs()
+                        .text_color(colors::text_secondary())
+                        .child(if self.transfers == 1 {
+                            "Transferring...".to_string()
+                        } else {
+                            format!("{} transfers...", self.transfers)
+                        }),
+                )
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(toolbar_button("files-upload", "Upload...").on_click(cx.listener(
+                        |this, _, _, cx| this.choose_upload(cx),
+                    )))
+                    .child(toolbar_button("files-refresh", "Refresh").on_click(cx.listener(
+                        |this, _, _, cx| this.refresh(cx),
+                    ))),
+            )
+    }
+
+    fn render_row(&self, depth: usize, entry: &FileEntry, cx: &Context<Self>) -> impl IntoElement {
+        let is_selected = self.selected.as_ref().is_some_and(|s| s.path == entry.path);
+        let is_expanded = self.expanded.contains(&entry.path);
+        let is_loading = self.loading.contains(&entry.path);
+        let icon = match entry.kind {
+            FileKind::Directory if is_expanded => "icons/chevron-down.svg",
+            FileKind::Directory => "icons/chevron-right.svg",
+            _ => "icons/box.svg",
+        };
+        let entry_for_click = entry.clone();
+        let drop_dir = entry.path.clone();
+
+        div()
+            .id(SharedString::from(format!("file-{}", entry.path)))
+            .flex()
+            .items_center()
+            .gap_1p5()
+            .h(px(ROW_HEIGHT))
+            .pl(px(8.0 + depth as f32 * INDENT))
+            .pr_2()
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .text_xs()
+            .when(is_selected, |el| {
+                el.bg(colors::selection()).text_color(colors::on_accent())
+            })
+            .when(!is_selected, |el| {
+                el.hover(|el| el.bg(colors::hover())).text_color(colors::text())
+            })
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.select(entry_for_click.clone(), cx);
+            }))
+            // Folders accept drops directly
+            .when(entry.is_dir(), |el| {
+                el.drag_over::<ExternalPaths>(|style, _, _, _| style.bg(colors::hover()))
+                    .on_drop(cx.listener(move |this, paths: &ExternalPaths, _, cx| {
+                        cx.stop_propagation();
+                        this.upload(paths.paths().to_vec(), drop_dir.clone(), cx);
+                    }))
+            })
+            .child(
+                svg()
+                    .path(icon)
+                    .size(px(12.0))
+                    .flex_shrink_0()
+                    .text_color(if is_selected {
+                        colors::on_accent()
+                    } else if entry.is_dir() {
+                        colors::accent()
+                    } else {
+                        colors::text_muted()
+                    }),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .child(entry.name.clone()),
+            )
+            .when_some(entry.link_target.clone().filter(|_| entry.kind == FileKind::Symlink), |el, target| {
+                el.child(
+                    div()
+                        .flex_shrink_0()
+                        .max_w(px(160.0))
+                        .overflow_hidden()
+                        .text_ellipsis()
+                        .whitespace_nowrap()
+                        .text_color(if is_selected { colors::on_accent() } else { colors::text_muted() })
+                        .child(format!("→ {}", target)),
+                )
+            })
+            .when(is_loading, |el| {
+                el.child(div().text_color(colors::text_muted()).child("Loading..."))
+            })
+            .when(!entry.is_dir() && entry.kind != FileKind::Symlink, |el| {
+                el.child(
+                    div()
+                        .flex_shrink_0()
+                        .text_color(if is_selected { colors::on_accent() } else { colors::text_muted() })
+                        .child(format_bytes(entry.size as f64)),
+                )
+            })
+    }
+
+    fn render_tree(&self, cx: &Context<Self>) -> impl IntoElement {
+        let rows = self.visible_rows();
+        let root_error = self.errors.get("/");
+
+        div()
+            .id("file-tree")
+            .w(px(300.0))
+            .flex_shrink_0()
+            .h_full()
+            .overflow_y_scroll()
+            .p_1()
+            .border_r_1()
+            .border_color(colors::border_subtle())
+            .when(rows.is_empty(), |el| {
+                el.child(
+                    div()
+                        .p_3()
+                        .text_xs()
+                        .text_color(if root_error.is_some() {
+                            colors::error()
+                        } else {
+                            colors::text_secondary()
+                        })
+                        .child(match root_error {
+                            Some(error) => error.clone(),
+                            None if self.loading.contains("/") => "Loading files...".to_string(),
+                            None => "Empty".to_string(),
+                        }),
+                )
+            })
+            .children(rows.into_iter().map(|(depth, entry)| self.render_row(depth, entry, cx)))
+            // Listing errors below the root show up under their folder's name
+            .children(
+                self.errors
+                    .iter()
+                    .filter(|(dir, _)| dir.as_str() != "/")
+                    .map(|(dir, error)| {
+                        div()
+                            .px_2()
+                            .py_1()
+                            .text_xs()
+                            .text_color(colors::error())
+                            .child(format!("{}: {}", dir, error))
+                    }),
+            )
+    }
+
+    fn render_preview(&self, cx: &Context<Self>) -> impl IntoElement {
+        let Some(entry) = self.selected.clone() else {
+            return div()
+                .flex_1()
+                .flex()
+                .flex_col()
+                .items_center()
+                .justify_center()
+                .gap_2()
+                .child(div().text_color(colors::text_secondary()).child("No file selected"))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(colors::text_muted())
+                        .child("Drop files here to upload them into the container"),
+                )
+                .into_any_element();
+        };
+
+        let entry_for_tar = entry.clone();
+        let entry_for_extract = entry.clone();
+
+        let body = match &self.preview {
+            Preview::None if entry.is_dir() => {
+                let count = self.listings.get(&entry.path).map(|e| e.len());
+                preview_message(match count {
+                    Some(1) => "1 item".to_string(),
+                    Some(n) => format!("{} items", n),
+                    None => "Folder".to_string(),
+                })
+            }
+            Preview::None => preview_message("No preview available".to_string()),
+            Preview::Loading => preview_message("Loading preview...".to_string()),
+            Preview::Binary => preview_message("Binary file".to_string()),
+            Preview::TooLarge => preview_message("File is too large to preview".to_string()),
+            Preview::Failed(error) => div()
+                .p_3()
+                .text_xs()
+                .text_color(colors::error())
+                .child(error.clone())
+                .into_any_element(),
+            Preview::Image(image) => div()
+                .flex_1()
+                .min_h_0()
+                .flex()
+                .items_center()
+                .justify_center()
+                .p_3()
+                .child(img(image.clone()).max_w_full().max_h_full().object_fit(ObjectFit::Contain))
+                .into_any_element(),
+            Preview::Text { content, truncated } => div()
+                .id("file-preview-text")
+                .flex_1()
+                .min_h_0()
+                .overflow_scroll()
+                .p_3()
+                .font_family(MONO_FONT)
+                .text_xs()
+                .text_color(colors::text())
+                .whitespace_nowrap()
+                .child(content.clone())
+                .when(*truncated, |el| {
+                    el.child(
+                        div()
+                            .pt_2()
+                            .text_color(colors::text_muted())
+                            .child("Preview truncated; download the file to see all of it"),
+                    )
+                })
+                .into_any_element(),
+            Preview::Link { target, is_dir } => {
+                let target_for_click = target.clone();
+                div()
+                    .p_3()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .text_xs()
+                    .child(
+                        div()
+                            .text_color(colors::text_secondary())
+                            .child(format!(
+                                "Link to {}{}",
+                                target,
+                                if *is_dir == Some(true) { " (folder)" } else { "" }
+                            )),
+                    )
+                    .child(
+                        toolbar_button("files-go-to-target", "Go to Target")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.reveal(target_for_click.clone(), cx);
+                            })),
+                    )
+                    .into_any_element()
+            }
+        };
+
+        div()
+            .flex_1()
+            .min_w_0()
+            .flex()
+            .flex_col()
+            // Details
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .p_3()
+                    .border_b_1()
+                    .border_color(colors::border_subtle())
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(colors::text())
+                            .child(entry.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_3()
+                            .text_xs()
+                            .font_family(MONO_FONT)
+                            .text_color(colors::text_secondary())
+                            .child(entry.permissions())
+                            .when(!entry.is_dir(), |el| el.child(format_bytes(entry.size as f64)))
+                            .child(format_modified(entry.modified)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_1()
+                            .pt_1()
+                            .child(
+                                toolbar_button("files-download-tar", "Download .tar").on_click(
+                                    cx.listener(move |this, _, _, cx| {
+                                        this.download(entry_for_tar.clone(), false, cx);
+                                    }),
+                                ),
+                            )
+                            .child(
+                                toolbar_button("files-download-extract", "Extract To...").on_click(
+                                    cx.listener(move |this, _, _, cx| {
+                                        this.download(entry_for_extract.clone(), true, cx);
+                                    }),
+                                ),
+                            ),
+                    ),
+            )
+            .child(body)
+            .into_any_element()
+    }
+}
+
+impl Render for FileBrowser {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // List the root on first render
+        if !self.requested {
+            self.requested = true;
+            self.list("/".to_string(), cx);
+        }
+
+        div()
+            .size_full()
+            .min_w_0()
+            .flex()
+            .flex_col()
+            .bg(colors::surface())
+            .rounded(px(8.0))
+            .border_1()
+            .border_color(colors::border_subtle())
+            .overflow_hidden()
+            // Drops outside a folder row go to the target directory
+            .drag_over::<ExternalPaths>(|style, _, _, _| style.border_color(colors::border_focused()))
+            .on_drop(cx.listener(|this, paths: &ExternalPaths, _, cx| {
+                let directory = this.target_directory();
+                this.upload(paths.paths().to_vec(), directory, cx);
+            }))
+            .child(self.render_toolbar(cx))
+            .child(
+                div()
+                    .flex_1()
+                    .min_h_0()
+                    .flex()
+                    .bg(colors::background())
+                    .child(self.render_tree(cx))
+                    .child(self.render_preview(cx)),
+            )
+    }
+}
+
+fn toolbar_button(id: &'static str, label: &'static str) -> Stateful<Div> {
+    Theme::button_ghost().id(id).px_2().py_1().text_xs().child(label)
+}
+
+fn preview_message(message: String) -> AnyElement {
+    div()
+        .flex_1()
+        .flex()
+        .items_center()
+        .justify_center()
+        .text_xs()
+        .text_color(colors::text_secondary())
+        .child(message)
+        .into_any_element()
+}
+
+/// Image format for previewable file names
+fn image_format(name: &str) -> Option<ImageFormat> {
+    let extension = name.rsplit_once('.')?.1.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some(ImageFormat::Png),
+        "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+        "gif" => Some(ImageFormat::Gif),
+        "webp" => Some(ImageFormat::Webp),
+        "bmp" => Some(ImageFormat::Bmp),
+        "svg" => Some(ImageFormat::Svg),
+        _ => None,
+    }
+}
+
+fn format_modified(modified: i64) -> String {
+    use chrono::{Local, TimeZone};
+    Local
+        .timestamp_opt(modified, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
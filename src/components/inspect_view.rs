@@ -0,0 +1,505 @@
+//! Full container configuration for the Info tab.
+//!
+//! Fetches the inspect document once per container (and again on Refresh or
+//! when the container changes state) and shows it two ways:
+//! - Collapsible sections for command, environment, mounts, networks, state,
+//!   health, restart policy and resource limits
+//! - The raw JSON, filterable by a search query and copyable as a whole
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use gpui::*;
+use gpui::prelude::*;
+use gpui_component::input::{Input, InputEvent, InputState};
+use gpui_component::tab::TabBar;
+use gpui_component::Sizable;
+
+use crate::models::format_bytes;
+use crate::services::{join_shell_words, ContainerInspect, DaemonService};
+use crate::theme::{colors, Theme, MONO_FONT};
+
+const JSON_LINE_HEIGHT: f32 = 18.0;
+
+/// Collapsible section of the structured view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Section {
+    Command,
+    Environment,
+    Mounts,
+    Networks,
+    State,
+    Health,
+    RestartPolicy,
+    Resources,
+}
+
+impl Section {
+    fn title(&self) -> &'static str {
+        match self {
+            Section::Command => "Command",
+            Section::Environment => "Environment",
+            Section::Mounts => "Mounts",
+            Section::Networks => "Networks",
+            Section::State => "State",
+            Section::Health => "Health",
+            Section::RestartPolicy => "Restart Policy",
+            Section::Resources => "Resource Limits",
+        }
+    }
+}
+
+enum InspectState {
+    Loading,
+    Loaded(ContainerInspect),
+    Failed(String),
+}
+
+/// Inspect sections and raw JSON for one container
+pub struct InspectView {
+    container_id: String,
+    daemon_service: Entity<DaemonService>,
+    state: InspectState,
+    /// Show the raw JSON instead of the sections
+    raw: bool,
+    collapsed: HashSet<Section>,
+    search: Entity<InputState>,
+    /// Whether the first fetch has been started
+    requested: bool,
+    _search_subscription: Subscription,
+    _task: Option<Task<()>>,
+}
+
+impl InspectView {
+    pub fn new(
+        container_id: String,
+        daemon_service: Entity<DaemonService>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let search = cx.new(|cx| InputState::new(window, cx).placeholder("Search JSON"));
+        let search_subscription = cx.subscribe(&search, |_, _, event: &InputEvent, cx| {
+            if let InputEvent::Change { .. } = event {
+                cx.notify();
+            }
+        });
+
+        Self {
+            container_id,
+            daemon_service,
+            state: InspectState::Loading,
+            raw: false,
+            // Environment lists can be long; start them folded
+            collapsed: HashSet::from([Section::Environment]),
+            search,
+            requested: false,
+            _search_subscription: search_subscription,
+            _task: None,
+        }
+    }
+
+    /// Fetch the document again, keeping the current one on screen until it arrives
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.requested = true;
+        let container_id = self.container_id.clone();
+        let task = self.daemon_service.update(cx, |svc, cx| svc.inspect_container(container_id, cx));
+
+        self._task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.state = match result {
+                        Ok(inspect) => InspectState::Loaded(inspect),
+                        Err(e) => InspectState::Failed(e.message().to_string()),
+                    };
+                    cx.notify();
+                })
+            }).ok();
+        }));
+    }
+
+    fn toggle_section(&mut self, section: Section, cx: &mut Context<Self>) {
+        if !self.collapsed.remove(&section) {
+            self.collapsed.insert(section);
+        }
+        cx.notify();
+    }
+
+    fn set_raw(&mut self, raw: bool, cx: &mut Context<Self>) {
+        self.raw = raw;
+        cx.notify();
+    }
+
+    fn copy_json(&self, cx: &mut Context<Self>) {
+        if let InspectState::Loaded(inspect) = &self.state {
+            cx.write_to_clipboard(ClipboardItem::new_string(inspect.json.clone()));
+        }
+    }
+
+    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let loaded = matches!(self.state, InspectState::Loaded(_));
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_2()
+            .child(
+                TabBar::new("inspect-mode")
+                    .segmented()
+                    .small()
+                    .children(["Details", "JSON"])
+                    .selected_index(usize::from(self.raw))
+                    .on_click(cx.listener(|this, index: &usize, _, cx| this.set_raw(*index == 1, cx))),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .when(self.raw, |el| {
+                        el.child(div().w(px(180.0)).child(Input::new(&self.search).small()))
+                    })
+                    .when(loaded, |el| {
+                        el.child(toolbar_button("inspect-copy", "Copy JSON").on_click(
+                            cx.listener(|this, _, _, cx| this.copy_json(cx)),
+                        ))
+                    })
+                    .child(toolbar_button("inspect-refresh", "Refresh").on_click(
+                        cx.listener(|this, _, _, cx| this.refresh(cx)),
+                    )),
+            )
+    }
+
+    fn render_sections(&self, inspect: &ContainerInspect, cx: &Context<Self>) -> impl IntoElement {
+        let command = |parts: &[String]| {
+            if parts.is_empty() { "—".to_string() } else { join_shell_words(parts) }
+        };
+
+        let mut command_rows = vec![
+            kv_row("Entrypoint", command(&inspect.entrypoint)),
+            kv_row("Cmd", command(&inspect.cmd)),
+        ];
+        if !inspect.working_dir.is_empty() {
+            command_rows.push(kv_row("Working Dir", inspect.working_dir.clone()));
+        }
+        if !inspect.user.is_empty() {
+            command_rows.push(kv_row("User", inspect.user.clone()));
+        }
+
+        let env_rows = inspect
+            .env
+            .iter()
+            .map(|(name, value)| kv_row(name.clone(), value.clone()))
+            .collect();
+
+        let mount_rows = inspect
+            .mounts
+            .iter()
+            .map(|m| {
+                let mode = if m.read_only { "ro" } else { "rw" };
+                kv_row(m.destination.clone(), format!("{} {} ({})", m.kind, m.source, mode))
+            })
+            .collect();
+
+        let mut network_rows: Vec<AnyElement> = inspect
+            .networks
+            .iter()
+            .map(|n| {
+                let mut value = n.ip_address.clone();
+                if !n.gateway.is_empty() {
+                    value.push_str(&format!(" via {}", n.gateway));
+                }
+                if !n.mac_address.is_empty() {
+                    value.push_str(&format!(" · {}", n.mac_address));
+                }
+                kv_row(n.name.clone(), value)
+            })
+            .collect();
+        if !inspect.hostname.is_empty() {
+            network_rows.insert(0, kv_row("Hostname", inspect.hostname.clone()));
+        }
+        network_rows.extend(inspect.ports.iter().map(|p| kv_row("Port", p.clone())));
+
+        let state = &inspect.state;
+        let mut state_rows = vec![kv_row("Status", state.status.clone())];
+        if state.pid > 0 {
+            state_rows.push(kv_row("PID", state.pid.to_string()));
+        }
+        state_rows.push(kv_row("Exit Code", state.exit_code.to_string()));
+        state_rows.push(kv_row("OOM Killed", if state.oom_killed { "Yes" } else { "No" }));
+        if !state.error.is_empty() {
+            state_rows.push(kv_row("Error", state.error.clone()));
+        }
+        if !state.started_at.is_empty() {
+            state_rows.push(kv_row("Started", state.started_at.clone()));
+        }
+        if !state.finished_at.is_empty() && !state.finished_at.starts_with("0001-") {
+            state_rows.push(kv_row("Finished", state.finished_at.clone()));
+        }
+        state_rows.push(kv_row("Restarts", state.restart_count.to_string()));
+
+        let health_rows = inspect
+            .health
+            .as_ref()
+            .map(|h| {
+                let mut rows = vec![
+                    kv_row("Status", h.status.clone()),
+                    kv_row("Failing Streak", h.failing_streak.to_string()),
+                ];
+                if !h.last_output.is_empty() {
+                    rows.push(kv_row("Last Probe", h.last_output.clone()));
+                }
+                rows
+            })
+            .unwrap_or_default();
+
+        let limits = &inspect.resources;
+        let limit = |value: i64, text: String| if value > 0 { text } else { "Unlimited".to_string() };
+        let resource_rows = vec![
+            kv_row("Memory", limit(limits.memory_bytes, format_bytes(limits.memory_bytes.max(0) as f64))),
+            kv_row(
+                "Memory + Swap",
+                limit(limits.memory_swap_bytes, format_bytes(limits.memory_swap_bytes.max(0) as f64)),
+            ),
+            kv_row("CPUs", limit((limits.cpus * 1e9) as i64, limits.cpus.to_string())),
+            kv_row("CPU Shares", limit(limits.cpu_shares, limits.cpu_shares.to_string())),
+            kv_row("PIDs", limit(limits.pids_limit, limits.pids_limit.to_string())),
+        ];
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(self.render_section(Section::Command, command_rows, cx))
+            .child(self.render_section(Section::Environment, env_rows, cx))
+            .child(self.render_section(Section::Mounts, mount_rows, cx))
+            .child(self.render_section(Section::Networks, network_rows, cx))
+            .child(self.render_section(Section::State, state_rows, cx))
+            .when(inspect.health.is_some(), |el| {
+                el.child(self.render_section(Section::Health, health_rows, cx))
+            })
+            .child(self.render_section(
+                Section::RestartPolicy,
+                vec![kv_row("Policy", inspect.restart_policy.display())],
+                cx,
+            ))
+            .child(self.render_section(Section::Resources, resource_rows, cx))
+    }
+
+    fn render_section(&self, section: Section, rows: Vec<AnyElement>, cx: &Context<Self>) -> impl IntoElement {
+        let expanded = !self.collapsed.contains(&section);
+        let count = rows.len();
+        let show_count = matches!(section, Section::Environment | Section::Mounts);
+
+        Theme::card()
+            .overflow_hidden()
+            .child(
+                div()
+                    .id(SharedString::from(format!("inspect-section-{:?}", section)))
+                    .flex()
+                    .items_center()
+                    .gap_1p5()
+                    .px_3()
+                    .py_2()
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()))
+                    .on_click(cx.listener(move |this, _, _, cx| this.toggle_section(section, cx)))
+                    .child(
+                        svg()
+                            .path(if expanded { "icons/chevron-down.svg" } else { "icons/chevron-right.svg" })
+                            .size(px(12.0))
+                            .text_color(colors::text_secondary()),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(colors::text())
+                            .child(if show_count {
+                                format!("{} ({})", section.title(), count)
+                            } else {
+                                section.title().to_string()
+                            }),
+                    ),
+            )
+            .when(expanded, |el| {
+                el.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .px_3()
+                        .pb_2()
+                        .when(rows.is_empty(), |el| {
+                            el.child(
+                                div()
+                                    .py_1()
+                                    .text_xs()
+                                    .text_color(colors::text_muted())
+                                    .child("None"),
+                            )
+                        })
+                        .children(rows),
+                )
+            })
+    }
+
+    fn render_json(&self, inspect: &ContainerInspect, cx: &Context<Self>) -> impl IntoElement {
+        let query = self.search.read(cx).value().trim().to_string();
+        let highlight = HighlightStyle {
+            background_color: Some(colors::warning().into()),
+            color: Some(colors::background().into()),
+            ..Default::default()
+        };
+
+        let mut lines = Vec::new();
+        let mut matches = 0;
+        for (index, line) in inspect.json.lines().enumerate() {
+            let ranges = match_ranges(line, &query);
+            if !query.is_empty() && ranges.is_empty() {
+                continue;
+            }
+            matches += ranges.len();
+            let highlights: Vec<(Range<usize>, HighlightStyle)> =
+                ranges.into_iter().map(|range| (range, highlight)).collect();
+            lines.push(
+                div()
+                    .flex()
+                    .h(px(JSON_LINE_HEIGHT))
+                    .whitespace_nowrap()
+                    .child(
+                        div()
+                            .w(px(40.0))
+                            .flex_shrink_0()
+                            .pr_2()
+                            .text_right()
+                            .text_color(colors::text_muted())
+                            .child((index + 1).to_string()),
+                    )
+                    .child(StyledText::new(line.to_string()).with_highlights(highlights)),
+            );
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .when(!query.is_empty(), |el| {
+                el.child(
+                    div()
+                        .text_xs()
+                        .text_color(colors::text_secondary())
+                        .child(match matches {
+                            0 => "No matches".to_string(),
+                            1 => "1 match".to_string(),
+                            n => format!("{} matches in {} lines", n, lines.len()),
+                        }),
+                )
+            })
+            .child(
+                div()
+                    .id("inspect-json")
+                    .p_2()
+                    .rounded(px(8.0))
+                    .bg(colors::surface())
+                    .border_1()
+                    .border_color(colors::border_subtle())
+                    .overflow_x_scroll()
+                    .font_family(MONO_FONT)
+                    .text_xs()
+                    .text_color(colors::text())
+                    .children(lines),
+            )
+    }
+}
+
+impl Render for InspectView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Fetch on first render
+        if !self.requested {
+            self.refresh(cx);
+        }
+
+        let body = match &self.state {
+            InspectState::Loading => message("Loading configuration...").into_any_element(),
+            InspectState::Failed(error) => message(format!("Failed to inspect container: {}", error)).into_any_element(),
+            InspectState::Loaded(inspect) if self.raw => self.render_json(inspect, cx).into_any_element(),
+            InspectState::Loaded(inspect) => self.render_sections(inspect, cx).into_any_element(),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(self.render_toolbar(cx))
+            .child(body)
+    }
+}
+
+/// Byte ranges of case-insensitive matches of `query` in `line`
+fn match_ranges(line: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // ASCII lowercasing keeps byte offsets aligned with the original line
+    let haystack = line.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, matched)| start..start + matched.len())
+        .collect()
+}
+
+fn kv_row(key: impl Into<SharedString>, value: impl Into<SharedString>) -> AnyElement {
+    div()
+        .flex()
+        .items_start()
+        .gap_3()
+        .py_1()
+        .border_b_1()
+        .border_color(colors::border_subtle())
+        .child(
+            div()
+                .w(px(120.0))
+                .flex_shrink_0()
+                .overflow_hidden()
+                .text_ellipsis()
+                .text_xs()
+                .text_color(colors::text_secondary())
+                .child(key.into()),
+        )
+        .child(
+            div()
+                .flex_1()
+                .min_w_0()
+                .text_xs()
+                .font_family(MONO_FONT)
+                .text_color(colors::text())
+                .child(value.into()),
+        )
+        .into_any_element()
+}
+
+fn toolbar_button(id: &'static str, label: &'static str) -> Stateful<Div> {
+    Theme::button_ghost().id(id).px_2().py_1().text_xs().child(label)
+}
+
+fn message(text: impl Into<SharedString>) -> Div {
+    div()
+        .py_4()
+        .text_sm()
+        .text_color(colors::text_secondary())
+        .child(text.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_matches_case_insensitively() {
+        assert_eq!(match_ranges(r#""Image": "nginx""#, "IMAGE"), [1..6]);
+        assert_eq!(match_ranges("aaa", "aa"), [0..2]);
+        assert!(match_ranges("anything", "").is_empty());
+    }
+}
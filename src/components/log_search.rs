@@ -0,0 +1,103 @@
+//! Searching log lines.
+//!
+//! A query is compiled once and then run against every line. Plain queries
+//! match literally; regex queries use the `regex` crate's syntax. Both
+//! ignore case unless the query has an uppercase letter in it.
+
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+/// How a search query is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    #[default]
+    Plain,
+    Regex,
+}
+
+/// A compiled search query (empty or invalid queries match nothing)
+#[derive(Debug, Clone, Default)]
+pub struct LogSearch {
+    pattern: Option<Regex>,
+    /// Why a regex query didn't compile
+    error: Option<String>,
+}
+
+impl LogSearch {
+    pub fn new(query: &str, mode: SearchMode) -> Self {
+        if query.is_empty() {
+            return Self::default();
+        }
+        let source = match mode {
+            SearchMode::Plain => regex::escape(query),
+            SearchMode::Regex => query.to_string(),
+        };
+        let ignore_case = !query.chars().any(char::is_uppercase);
+        match RegexBuilder::new(&source).case_insensitive(ignore_case).build() {
+            Ok(pattern) => Self {
+                pattern: Some(pattern),
+                error: None,
+            },
+            Err(e) => {
+                // Syntax errors point at the pattern over several lines; the last one says what's wrong
+                let message = e.to_string();
+                let reason = message.lines().last().unwrap_or_default();
+                Self {
+                    pattern: None,
+                    error: Some(reason.trim_start_matches("error: ").to_string()),
+                }
+            }
+        }
+    }
+
+    /// Whether there is a valid query to match against
+    pub fn is_active(&self) -> bool {
+        self.pattern.is_some()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        self.pattern.as_ref().is_some_and(|pattern| pattern.is_match(text))
+    }
+
+    /// Byte ranges of the (non-empty) matches in `text`
+    pub fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let Some(pattern) = &self.pattern else {
+            return Vec::new();
+        };
+        pattern
+            .find_iter(text)
+            .filter(|m| !m.is_empty())
+            .map(|m| m.range())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_plain_and_regex_queries() {
+        let plain = LogSearch::new("get /api", SearchMode::Plain);
+        assert_eq!(plain.ranges("GET /api/users and get /api"), [0..8, 19..27]);
+        // Regex characters are literal in plain mode
+        assert!(LogSearch::new("a.c", SearchMode::Plain).ranges("abc").is_empty());
+        // An uppercase letter makes the query case-sensitive
+        assert!(!LogSearch::new("Error", SearchMode::Plain).matches("error: disk full"));
+
+        let regex = LogSearch::new(r"status=(4|5)\d\d", SearchMode::Regex);
+        assert!(regex.matches("GET / status=503"));
+        assert!(!regex.matches("GET / status=200"));
+        assert!(LogSearch::new("x*", SearchMode::Regex).ranges("abc").is_empty());
+
+        let invalid = LogSearch::new("(unclosed", SearchMode::Regex);
+        assert!(!invalid.is_active());
+        assert_eq!(invalid.error(), Some("unclosed group"));
+        assert!(!LogSearch::new("", SearchMode::Plain).is_active());
+    }
+}
@@ -1,36 +1,135 @@
-//! Log viewer component for container logs.
+//! Log viewer component for container and daemon logs.
 //!
-//! Displays streaming logs from containers with support for:
+//! Displays streaming logs from containers, a whole compose project or the
+//! local daemon with support for:
 //! - Real-time log streaming (follow mode)
 //! - stdout/stderr differentiation
 //! - Timestamps display
+//! - Level filtering (daemon logs) and stream filtering
+//! - Search (plain or regex) with highlighting, match navigation and an
+//!   option to hide lines that don't match
+//! - ANSI colors and styles, with a toggle to show plain text instead
 //! - Auto-scroll to bottom
+//!
+//! Rows have a fixed height and don't wrap, so the list is virtualized: only
+//! the rows in view are laid out. Incoming lines are collected and added
+//! about once a frame.
+
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::time::Duration;
 
 use gpui::*;
 use gpui::prelude::*;
+use gpui_component::input::{Input, InputEvent, InputState};
+use gpui_component::Sizable;
 
-use crate::services::{DaemonEvent, DaemonService};
+use crate::components::{sgr_highlight, AnsiText, CellStyle, LogSearch, SearchMode};
+use crate::services::{
+    Capability, DaemonEvent, DaemonLogLine, DaemonLogs, DaemonLogsEvent, DaemonService, LogLevel,
+};
 use crate::theme::colors;
 
+/// Height of one row
+const ROW_HEIGHT: f32 = 20.0;
+
+/// How long incoming lines are collected before they are added
+const FLUSH_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Minimum levels offered by the level filter
+const LEVEL_FILTERS: [(Option<LogLevel>, &str); 5] = [
+    (None, "All"),
+    (Some(LogLevel::Debug), "Debug"),
+    (Some(LogLevel::Info), "Info"),
+    (Some(LogLevel::Warn), "Warn"),
+    (Some(LogLevel::Error), "Error"),
+];
+
+/// Which output streams to show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamFilter {
+    #[default]
+    All,
+    Stdout,
+    Stderr,
+}
+
+impl StreamFilter {
+    fn allows(&self, stream: &str) -> bool {
+        match self {
+            StreamFilter::All => true,
+            StreamFilter::Stdout => stream != "stderr",
+            StreamFilter::Stderr => stream == "stderr",
+        }
+    }
+}
+
+/// Options offered by the stream filter
+const STREAM_FILTERS: [(StreamFilter, &str); 3] = [
+    (StreamFilter::All, "All"),
+    (StreamFilter::Stdout, "stdout"),
+    (StreamFilter::Stderr, "stderr"),
+];
+
+/// The search box, while open
+struct SearchBar {
+    input: Entity<InputState>,
+    _subscription: Subscription,
+}
+
 /// A single log line with metadata
 #[derive(Clone, Debug)]
 pub struct LogLine {
-    /// Log content
+    /// Log content, with escape sequences stripped
     pub content: String,
+    /// ANSI styles of byte ranges of `content`
+    pub styles: Vec<(Range<usize>, CellStyle)>,
     /// Stream type: "stdout" or "stderr"
     pub stream: String,
     /// Unix timestamp in nanoseconds
     pub timestamp: i64,
+    /// Severity, when the source reports one (daemon logs)
+    pub level: Option<LogLevel>,
+    /// Which container the line came from, when several are merged
+    pub origin: Option<String>,
+}
+
+impl From<&DaemonLogLine> for LogLine {
+    fn from(line: &DaemonLogLine) -> Self {
+        let AnsiText { text, runs } = AnsiText::parse(&line.content);
+        Self {
+            content: text,
+            styles: runs,
+            stream: line.stream.as_str().to_string(),
+            timestamp: line.timestamp,
+            level: Some(line.level),
+            origin: None,
+        }
+    }
+}
+
+/// Where the viewer's lines come from
+enum LogSource {
+    /// A container's log stream, requested from the daemon on first render
+    Container {
+        container_id: String,
+        daemon_service: Entity<DaemonService>,
+    },
+    /// Several containers' streams merged by time, e.g. a compose project
+    Merged {
+        /// Container ID to the name its lines are tagged with
+        containers: HashMap<String, String>,
+        daemon_service: Entity<DaemonService>,
+    },
+    /// The local daemon's own output
+    Daemon(Entity<DaemonLogs>),
 }
 
 /// Log viewer component
 pub struct LogViewer {
-    /// Container ID being viewed
-    container_id: String,
-    /// Daemon service for log subscription
-    daemon_service: Entity<DaemonService>,
+    source: LogSource,
     /// Log lines buffer
-    lines: Vec<LogLine>,
+    lines: VecDeque<LogLine>,
     /// Whether to auto-scroll to bottom
     follow: bool,
     /// Whether to show timestamps
@@ -39,6 +138,33 @@ pub struct LogViewer {
     subscribed: bool,
     /// Maximum lines to keep in buffer
     max_lines: usize,
+    /// Hide lines below this level (None shows everything)
+    min_level: Option<LogLevel>,
+    /// Newest entry seen per container; a stream that is requested again
+    /// (by another viewer, or after a reconnect) replays its tail
+    newest: HashMap<String, i64>,
+    /// Only show lines from these streams
+    stream_filter: StreamFilter,
+    search_bar: Option<SearchBar>,
+    search_mode: SearchMode,
+    /// Compiled from the search box (inactive while it's closed or empty)
+    search: LogSearch,
+    /// Hide lines the search doesn't match
+    only_matching: bool,
+    /// Render ANSI colors and styles (off shows plain text)
+    ansi_colors: bool,
+    /// Line (index into `lines`) of the match navigated to
+    current_match: Option<usize>,
+    /// Indices into `lines` of the lines that pass the filters, one per row
+    rows: Vec<usize>,
+    /// Shown lines that match the search
+    matches: Vec<usize>,
+    /// Row with the longest line, which sets the list's width
+    widest_row: Option<usize>,
+    /// Lines received since the last flush
+    pending: Vec<LogLine>,
+    flush_task: Option<Task<()>>,
+    scroll_handle: UniformListScrollHandle,
 }
 
 impl LogViewer {
@@ -50,15 +176,71 @@ impl LogViewer {
         // Subscribe to daemon events for log entries
         cx.subscribe(&daemon_service, Self::on_daemon_event).detach();
 
-        Self {
-            container_id,
-            daemon_service,
-            lines: Vec::new(),
+        Self::with_source(
+            LogSource::Container {
+                container_id,
+                daemon_service,
+            },
+            VecDeque::new(),
+        )
+    }
+
+    /// Viewer merging several containers' logs, each line tagged with the
+    /// name given for its container
+    pub fn merged(
+        containers: HashMap<String, String>,
+        daemon_service: Entity<DaemonService>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        cx.subscribe(&daemon_service, Self::on_daemon_event).detach();
+
+        Self::with_source(
+            LogSource::Merged {
+                containers,
+                daemon_service,
+            },
+            VecDeque::new(),
+        )
+    }
+
+    /// Viewer for the local daemon's captured output
+    pub fn daemon(logs: Entity<DaemonLogs>, cx: &mut Context<Self>) -> Self {
+        cx.subscribe(&logs, Self::on_daemon_logs_event).detach();
+
+        // Start with what has been captured so far
+        let lines = logs.read(cx).lines().map(LogLine::from).collect();
+        let mut viewer = Self::with_source(LogSource::Daemon(logs), lines);
+        // Nothing to request; lines are pushed by the capture buffer
+        viewer.subscribed = true;
+        viewer
+    }
+
+    fn with_source(source: LogSource, lines: VecDeque<LogLine>) -> Self {
+        let mut viewer = Self {
+            source,
+            lines,
             follow: true,
             show_timestamps: true,
             subscribed: false,
             max_lines: 10000,
-        }
+            min_level: None,
+            newest: HashMap::new(),
+            stream_filter: StreamFilter::All,
+            search_bar: None,
+            search_mode: SearchMode::Plain,
+            search: LogSearch::default(),
+            only_matching: false,
+            ansi_colors: true,
+            current_match: None,
+            rows: Vec::new(),
+            matches: Vec::new(),
+            widest_row: None,
+            pending: Vec::new(),
+            flush_task: None,
+            scroll_handle: UniformListScrollHandle::new(),
+        };
+        viewer.refresh_rows();
+        viewer
     }
 
     /// Start subscribing to logs
@@ -68,21 +250,83 @@ impl LogViewer {
         }
         self.subscribed = true;
 
-        let container_id = self.container_id.clone();
-        self.daemon_service.update(cx, |svc, cx| {
-            svc.subscribe_logs(container_id, true, Some(100), cx);
-        });
+        match &self.source {
+            LogSource::Container {
+                container_id,
+                daemon_service,
+            } => {
+                let container_id = container_id.clone();
+                daemon_service.update(cx, |svc, cx| {
+                    svc.subscribe_logs(container_id, true, Some(100), cx);
+                });
+            }
+            LogSource::Merged {
+                containers,
+                daemon_service,
+            } => {
+                let ids: Vec<String> = containers.keys().cloned().collect();
+                daemon_service.update(cx, |svc, cx| {
+                    for id in ids {
+                        svc.subscribe_logs(id, true, Some(100), cx);
+                    }
+                });
+            }
+            LogSource::Daemon(_) => {}
+        }
+    }
+
+    /// Containers whose logs are shown
+    pub fn container_ids(&self) -> Vec<String> {
+        match &self.source {
+            LogSource::Container { container_id, .. } => vec![container_id.clone()],
+            LogSource::Merged { containers, .. } => containers.keys().cloned().collect(),
+            LogSource::Daemon(_) => Vec::new(),
+        }
+    }
+
+    /// Change which containers a merged viewer follows, streaming any new ones
+    pub fn set_containers(&mut self, containers: HashMap<String, String>, cx: &mut Context<Self>) {
+        let LogSource::Merged {
+            containers: current,
+            daemon_service,
+        } = &mut self.source
+        else {
+            return;
+        };
+        if *current == containers {
+            return;
+        }
+        let added: Vec<String> = containers
+            .keys()
+            .filter(|id| !current.contains_key(*id))
+            .cloned()
+            .collect();
+        *current = containers;
+        if self.subscribed && !added.is_empty() {
+            daemon_service.update(cx, |svc, cx| {
+                for id in added {
+                    svc.subscribe_logs(id, true, Some(100), cx);
+                }
+            });
+        }
+        cx.notify();
     }
 
     /// Clear all log lines
     pub fn clear(&mut self, cx: &mut Context<Self>) {
         self.lines.clear();
+        self.pending.clear();
+        self.current_match = None;
+        self.refresh_rows();
         cx.notify();
     }
 
     /// Toggle follow mode
     pub fn toggle_follow(&mut self, cx: &mut Context<Self>) {
         self.follow = !self.follow;
+        if self.follow {
+            self.scroll_to_end();
+        }
         cx.notify();
     }
 
@@ -92,37 +336,329 @@ impl LogViewer {
         cx.notify();
     }
 
+    /// Only show lines at or above `level` (None shows everything)
+    pub fn set_min_level(&mut self, level: Option<LogLevel>, cx: &mut Context<Self>) {
+        self.min_level = level;
+        self.refresh_rows();
+        cx.notify();
+    }
+
+    pub fn set_stream_filter(&mut self, filter: StreamFilter, cx: &mut Context<Self>) {
+        self.stream_filter = filter;
+        self.refresh_rows();
+        cx.notify();
+    }
+
+    /// Open the search box, or close it and drop the search
+    pub fn toggle_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.search_bar.take().is_some() {
+            self.search = LogSearch::default();
+            self.current_match = None;
+            self.refresh_rows();
+            cx.notify();
+            return;
+        }
+
+        let input = cx.new(|cx| InputState::new(window, cx).placeholder("Search logs"));
+        let subscription = cx.subscribe(&input, |this, _, event: &InputEvent, cx| match event {
+            InputEvent::Change { .. } => this.update_search(cx),
+            InputEvent::PressEnter { .. } => this.next_match(cx),
+            _ => {}
+        });
+        input.read(cx).focus_handle(cx).focus(window);
+        self.search_bar = Some(SearchBar {
+            input,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    pub fn set_search_mode(&mut self, mode: SearchMode, cx: &mut Context<Self>) {
+        self.search_mode = mode;
+        self.update_search(cx);
+    }
+
+    pub fn toggle_only_matching(&mut self, cx: &mut Context<Self>) {
+        self.only_matching = !self.only_matching;
+        self.refresh_rows();
+        cx.notify();
+    }
+
+    /// Toggle between ANSI-styled and plain text
+    pub fn toggle_ansi_colors(&mut self, cx: &mut Context<Self>) {
+        self.ansi_colors = !self.ansi_colors;
+        cx.notify();
+    }
+
+    /// Recompile the search from the box
+    fn update_search(&mut self, cx: &mut Context<Self>) {
+        let query = match &self.search_bar {
+            Some(bar) => bar.input.read(cx).value().to_string(),
+            None => String::new(),
+        };
+        self.search = LogSearch::new(&query, self.search_mode);
+        self.current_match = None;
+        self.refresh_rows();
+        cx.notify();
+    }
+
+    /// Recompute which lines are shown and which of those match the search
+    fn refresh_rows(&mut self) {
+        self.rows.clear();
+        self.matches.clear();
+        self.widest_row = None;
+        self.append_rows(0);
+        if self.follow {
+            self.scroll_to_end();
+        }
+    }
+
+    /// Add rows for the lines from `start` on
+    fn append_rows(&mut self, start: usize) {
+        for index in start..self.lines.len() {
+            let line = &self.lines[index];
+            if !self.is_visible(line) {
+                continue;
+            }
+            if self.search.is_active() && self.search.matches(&line.content) {
+                self.matches.push(index);
+            }
+            let widest = self.widest_row.map(|row| self.lines[self.rows[row]].content.len());
+            if widest.is_none_or(|widest| line.content.len() > widest) {
+                self.widest_row = Some(self.rows.len());
+            }
+            self.rows.push(index);
+        }
+    }
+
+    /// Drop the rows of the first `excess` lines, which were trimmed, and
+    /// shift the rest to the lines' new indices
+    fn trim_rows(&mut self, excess: usize) {
+        let dropped = self.rows.partition_point(|index| *index < excess);
+        self.rows.drain(..dropped);
+        self.rows.iter_mut().for_each(|index| *index -= excess);
+        let trimmed = self.matches.partition_point(|index| *index < excess);
+        self.matches.drain(..trimmed);
+        self.matches.iter_mut().for_each(|index| *index -= excess);
+        self.widest_row = match self.widest_row {
+            Some(row) if row >= dropped => Some(row - dropped),
+            // The widest line went; look for the next one
+            _ => self
+                .rows
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, index)| self.lines[**index].content.len())
+                .map(|(row, _)| row),
+        };
+    }
+
+    fn scroll_to_end(&self) {
+        if let Some(last) = self.rows.len().checked_sub(1) {
+            self.scroll_handle.scroll_to_item(last, ScrollStrategy::Top);
+        }
+    }
+
+    /// Line at the top of the viewport, and how far it is scrolled past
+    fn top_line(&self) -> Option<(usize, Pixels)> {
+        let offset = self.scroll_handle.0.borrow().base_handle.offset();
+        let rows = (-offset.y / px(ROW_HEIGHT)).max(0.0);
+        let line = *self.rows.get(rows.floor() as usize)?;
+        Some((line, px(ROW_HEIGHT) * rows.fract()))
+    }
+
+    /// Scroll so `line` (or the next shown one) is at the top of the viewport
+    fn scroll_to_line(&self, line: usize, within: Pixels) {
+        let row = self.rows.partition_point(|shown| *shown < line);
+        let state = self.scroll_handle.0.borrow();
+        let offset = state.base_handle.offset();
+        state.base_handle.set_offset(point(offset.x, -(px(ROW_HEIGHT) * row as f32 + within)));
+    }
+
+    /// Go to the next match, wrapping around to the first
+    pub fn next_match(&mut self, cx: &mut Context<Self>) {
+        let matches = &self.matches;
+        let next = match self.current_match {
+            Some(current) => matches.iter().find(|index| **index > current).or(matches.first()),
+            None => matches.first(),
+        }
+        .copied();
+        self.go_to_match(next, cx);
+    }
+
+    /// Go to the previous match, wrapping around to the last
+    pub fn previous_match(&mut self, cx: &mut Context<Self>) {
+        let matches = &self.matches;
+        let previous = match self.current_match {
+            Some(current) => matches.iter().rev().find(|index| **index < current).or(matches.last()),
+            None => matches.last(),
+        }
+        .copied();
+        self.go_to_match(previous, cx);
+    }
+
+    fn go_to_match(&mut self, line: Option<usize>, cx: &mut Context<Self>) {
+        let Some(line) = line else {
+            return;
+        };
+        self.current_match = Some(line);
+        // Following would scroll the match away as soon as a line arrives
+        self.follow = false;
+        let row = self.rows.partition_point(|shown| *shown < line);
+        self.scroll_handle.scroll_to_item(row, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    /// Whether the source reports levels, i.e. the level filter applies
+    fn has_levels(&self) -> bool {
+        matches!(self.source, LogSource::Daemon(_))
+    }
+
+    /// Whether the source can stream new lines (older daemons only return a snapshot)
+    fn can_follow(&self, cx: &App) -> bool {
+        match &self.source {
+            LogSource::Container { daemon_service, .. } | LogSource::Merged { daemon_service, .. } => {
+                daemon_service.read(cx).supports(Capability::LogsFollow)
+            }
+            LogSource::Daemon(_) => true,
+        }
+    }
+
+    fn is_visible(&self, line: &LogLine) -> bool {
+        let level = match (self.min_level, line.level) {
+            (Some(min), Some(level)) => level >= min,
+            _ => true,
+        };
+        let searched = !self.only_matching || !self.search.is_active() || self.search.matches(&line.content);
+        level && self.stream_filter.allows(&line.stream) && searched
+    }
+
     fn on_daemon_event(
         &mut self,
         _daemon: Entity<DaemonService>,
         event: &DaemonEvent,
         cx: &mut Context<Self>,
     ) {
-        if let DaemonEvent::LogsReceived { container_id, entry } = event {
-            if container_id == &self.container_id {
-                // Decode log data
-                let content = String::from_utf8_lossy(&entry.data).to_string();
-
-                // Split by newlines and add each line
-                for line_content in content.lines() {
-                    if !line_content.is_empty() {
-                        self.lines.push(LogLine {
-                            content: line_content.to_string(),
-                            stream: entry.stream.clone(),
-                            timestamp: entry.timestamp,
-                        });
-                    }
-                }
+        let DaemonEvent::LogsReceived { container_id, entry } = event else {
+            return;
+        };
+        let origin = match &self.source {
+            LogSource::Container { container_id: viewed_id, .. } if viewed_id == container_id => None,
+            LogSource::Merged { containers, .. } => match containers.get(container_id) {
+                Some(name) => Some(name.clone()),
+                None => return,
+            },
+            _ => return,
+        };
 
-                // Trim buffer if too large
-                if self.lines.len() > self.max_lines {
-                    let excess = self.lines.len() - self.max_lines;
-                    self.lines.drain(0..excess);
-                }
+        // Skip a replayed tail (entries without a timestamp can't be told apart)
+        if entry.timestamp > 0 {
+            let newest = self.newest.entry(container_id.clone()).or_default();
+            if entry.timestamp <= *newest {
+                return;
+            }
+            *newest = entry.timestamp;
+        }
+
+        // Decode log data
+        let content = String::from_utf8_lossy(&entry.data).to_string();
+
+        // Split by newlines and add each line
+        let lines: Vec<LogLine> = content
+            .lines()
+            .filter(|line_content| !line_content.is_empty())
+            .map(AnsiText::parse)
+            // A line that only moved the cursor or erased has nothing to show
+            .filter(|text| !text.text.is_empty())
+            .map(|AnsiText { text, runs }| LogLine {
+                content: text,
+                styles: runs,
+                stream: entry.stream.clone(),
+                timestamp: entry.timestamp,
+                level: None,
+                origin: origin.clone(),
+            })
+            .collect();
+
+        self.pending.extend(lines);
+        self.schedule_flush(cx);
+    }
+
+    fn on_daemon_logs_event(
+        &mut self,
+        _logs: Entity<DaemonLogs>,
+        event: &DaemonLogsEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            DaemonLogsEvent::Appended(lines) => {
+                self.pending.extend(lines.iter().map(LogLine::from));
+                self.schedule_flush(cx);
+            }
+        }
+    }
+
+    /// Add pending lines after a frame's worth of them has come in
+    fn schedule_flush(&mut self, cx: &mut Context<Self>) {
+        if self.flush_task.is_some() {
+            return;
+        }
+        self.flush_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            cx.background_executor().timer(FLUSH_INTERVAL).await;
+            this.update(cx, |this, cx| this.flush_pending(cx)).ok();
+        }));
+    }
+
+    /// Add the pending lines; unless following, the viewport stays on the
+    /// lines it was showing
+    fn flush_pending(&mut self, cx: &mut Context<Self>) {
+        self.flush_task = None;
+        let pending = std::mem::take(&mut self.pending);
+        if pending.is_empty() {
+            return;
+        }
+        let mut anchor = if self.follow { None } else { self.top_line() };
+
+        // Merged lines can land among the shown ones, so those rows are
+        // rebuilt; otherwise rows are only added for the new lines
+        let merged = matches!(self.source, LogSource::Merged { .. });
+        let mut appended = self.lines.len();
+        if merged {
+            // Streams interleave, and each one's tail arrives in a burst
+            let moved = merge_by_time(&mut self.lines, pending);
+            anchor = anchor.map(|(line, within)| (moved[line], within));
+            self.current_match = self.current_match.map(|current| moved[current]);
+        } else {
+            self.lines.extend(pending);
+        }
 
-                cx.notify();
+        // Trim buffer if too large
+        if self.lines.len() > self.max_lines {
+            let excess = self.lines.len() - self.max_lines;
+            self.lines.drain(..excess);
+            self.current_match = self.current_match.and_then(|current| current.checked_sub(excess));
+            // A trimmed anchor leaves the oldest remaining line at the top
+            anchor = anchor.map(|(line, within)| match line.checked_sub(excess) {
+                Some(line) => (line, within),
+                None => (0, px(0.0)),
+            });
+            if !merged {
+                self.trim_rows(excess);
+                appended = appended.saturating_sub(excess);
             }
         }
+
+        if merged {
+            self.refresh_rows();
+        } else {
+            self.append_rows(appended);
+            if self.follow {
+                self.scroll_to_end();
+            }
+        }
+        if let Some((line, within)) = anchor {
+            self.scroll_to_line(line, within);
+        }
+        cx.notify();
     }
 
     /// Format timestamp for display
@@ -137,7 +673,123 @@ impl LogViewer {
         }
     }
 
-    fn render_toolbar(&self, cx: &Context<Self>) -> impl IntoElement {
+    fn render_level_filter(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .gap_0p5()
+            .children(LEVEL_FILTERS.iter().map(|(level, label)| {
+                let level = *level;
+                let selected = self.min_level == level;
+                div()
+                    .id(SharedString::from(format!("log-level-{}", label)))
+                    .px_2()
+                    .py_1()
+                    .rounded(px(4.0))
+                    .cursor_pointer()
+                    .text_xs()
+                    .when(selected, |el| {
+                        el.bg(colors::selection())
+                            .text_color(colors::on_accent())
+                    })
+                    .when(!selected, |el| {
+                        el.hover(|el| el.bg(colors::hover()))
+                            .text_color(colors::text_secondary())
+                    })
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.set_min_level(level, cx);
+                    }))
+                    .child(*label)
+            }))
+    }
+
+    fn render_stream_filter(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .gap_0p5()
+            .children(STREAM_FILTERS.iter().map(|(filter, label)| {
+                let filter = *filter;
+                toggle_button(SharedString::from(format!("log-stream-{}", label)), *label, self.stream_filter == filter)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.set_stream_filter(filter, cx);
+                    }))
+            }))
+    }
+
+    fn render_search_bar(&self, bar: &SearchBar, cx: &Context<Self>) -> impl IntoElement {
+        let matches = &self.matches;
+        let (status, status_color) = match self.search.error() {
+            Some(error) => (error.to_string(), colors::error()),
+            None if !self.search.is_active() => (String::new(), colors::text_secondary()),
+            None => {
+                let position = self
+                    .current_match
+                    .and_then(|current| matches.iter().position(|line| *line == current));
+                let status = match (position, matches.len()) {
+                    (_, 0) => "No matches".to_string(),
+                    (Some(position), total) => format!("{} of {}", position + 1, total),
+                    (None, 1) => "1 matching line".to_string(),
+                    (None, total) => format!("{} matching lines", total),
+                };
+                (status, colors::text_secondary())
+            }
+        };
+        let regex = self.search_mode == SearchMode::Regex;
+
+        div()
+            .flex()
+            .items_center()
+            .gap_1()
+            .h(px(36.0))
+            .px_3()
+            .border_b_1()
+            .border_color(colors::border_subtle())
+            .bg(colors::surface())
+            .child(div().flex_1().min_w_0().child(Input::new(&bar.input).small()))
+            .child(
+                toggle_button("search-regex", ".*", regex).on_click(cx.listener(move |this, _, _, cx| {
+                    let mode = if regex { SearchMode::Plain } else { SearchMode::Regex };
+                    this.set_search_mode(mode, cx);
+                })),
+            )
+            .child(
+                toggle_button("search-only-matching", "Only Matching", self.only_matching)
+                    .on_click(cx.listener(|this, _, _, cx| this.toggle_only_matching(cx))),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .px_1()
+                    .text_xs()
+                    .text_color(status_color)
+                    .child(status),
+            )
+            .child(
+                toggle_button("search-previous", "↑", false)
+                    .on_click(cx.listener(|this, _, _, cx| this.previous_match(cx))),
+            )
+            .child(
+                toggle_button("search-next", "↓", false).on_click(cx.listener(|this, _, _, cx| this.next_match(cx))),
+            )
+            .child(
+                div()
+                    .id("close-search")
+                    .p_1()
+                    .rounded(px(4.0))
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()))
+                    .on_click(cx.listener(|this, _, window, cx| this.toggle_search(window, cx)))
+                    .child(
+                        svg()
+                            .path("icons/close.svg")
+                            .size(px(12.0))
+                            .text_color(colors::text_secondary()),
+                    ),
+            )
+    }
+
+    fn render_toolbar(&self, visible_lines: usize, cx: &Context<Self>) -> impl IntoElement {
         div()
             .flex()
             .items_center()
@@ -157,8 +809,10 @@ impl LogViewer {
                         div()
                             .text_xs()
                             .text_color(colors::text_secondary())
-                            .child(format!("{} lines", self.lines.len())),
-                    ),
+                            .child(format!("{} lines", visible_lines)),
+                    )
+                    .when(self.has_levels(), |el| el.child(self.render_level_filter(cx)))
+                    .child(self.render_stream_filter(cx)),
             )
             // Right side - controls
             .child(
@@ -166,6 +820,20 @@ impl LogViewer {
                     .flex()
                     .items_center()
                     .gap_1()
+                    // ANSI colors toggle
+                    .child(
+                        toggle_button("toggle-ansi", "Colors", self.ansi_colors)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toggle_ansi_colors(cx);
+                            })),
+                    )
+                    // Search toggle
+                    .child(
+                        toggle_button("toggle-search", "Search", self.search_bar.is_some())
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.toggle_search(window, cx);
+                            })),
+                    )
                     // Timestamps toggle
                     .child(
                         div()
@@ -188,28 +856,30 @@ impl LogViewer {
                             }))
                             .child("Time"),
                     )
-                    // Follow toggle
-                    .child(
-                        div()
-                            .id("toggle-follow")
-                            .px_2()
-                            .py_1()
-                            .rounded(px(4.0))
-                            .cursor_pointer()
-                            .text_xs()
-                            .when(self.follow, |el| {
-                                el.bg(colors::selection())
-                                    .text_color(colors::on_accent())
-                            })
-                            .when(!self.follow, |el| {
-                                el.hover(|el| el.bg(colors::hover()))
-                                    .text_color(colors::text_secondary())
-                            })
-                            .on_click(cx.listener(|this, _, _, cx| {
-                                this.toggle_follow(cx);
-                            }))
-                            .child("Follow"),
-                    )
+                    // Follow toggle (hidden when the daemon can't stream logs)
+                    .when(self.can_follow(cx), |el| {
+                        el.child(
+                            div()
+                                .id("toggle-follow")
+                                .px_2()
+                                .py_1()
+                                .rounded(px(4.0))
+                                .cursor_pointer()
+                                .text_xs()
+                                .when(self.follow, |el| {
+                                    el.bg(colors::selection())
+                                        .text_color(colors::on_accent())
+                                })
+                                .when(!self.follow, |el| {
+                                    el.hover(|el| el.bg(colors::hover()))
+                                        .text_color(colors::text_secondary())
+                                })
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.toggle_follow(cx);
+                                }))
+                                .child("Follow"),
+                        )
+                    })
                     // Clear button
                     .child(
                         div()
@@ -229,19 +899,52 @@ impl LogViewer {
             )
     }
 
-    fn render_log_line(&self, line: &LogLine) -> impl IntoElement {
+    fn render_log_line(&self, index: usize, line: &LogLine) -> impl IntoElement {
         let is_stderr = line.stream == "stderr";
+        // Daemon lines are colored by level; stderr alone doesn't mean an error there
+        let is_error = match line.level {
+            Some(level) => level == LogLevel::Error,
+            None => is_stderr,
+        };
+        let content_color = match line.level {
+            Some(LogLevel::Warn) => colors::warning(),
+            Some(LogLevel::Trace | LogLevel::Debug) => colors::text_secondary(),
+            _ if is_error => colors::error(),
+            _ => colors::text(),
+        };
+        let highlight = HighlightStyle {
+            background_color: Some(colors::warning().into()),
+            color: Some(colors::background().into()),
+            ..Default::default()
+        };
+        let matches: Vec<(Range<usize>, HighlightStyle)> = self
+            .search
+            .ranges(&line.content)
+            .into_iter()
+            .map(|range| (range, highlight))
+            .collect();
+        let highlights = if self.ansi_colors && !line.styles.is_empty() {
+            let styles = line
+                .styles
+                .iter()
+                .map(|(range, style)| (range.clone(), sgr_highlight(style, content_color.into())))
+                .collect();
+            overlay_highlights(styles, matches)
+        } else {
+            matches
+        };
 
         div()
             .w_full()
-            .min_w_0()
+            .h(px(ROW_HEIGHT))
             .flex()
-            .items_start()
+            .items_center()
             .gap_2()
             .px_3()
-            .py_0p5()
+            .whitespace_nowrap()
             .font_family("monospace")
             .text_xs()
+            .when(self.current_match == Some(index), |el| el.bg(colors::selection_inactive()))
             .hover(|el| el.bg(colors::hover()))
             // Timestamp
             .when(self.show_timestamps, |el| {
@@ -259,7 +962,6 @@ impl LogViewer {
                     .flex_shrink_0()
                     .w(px(8.0))
                     .h(px(8.0))
-                    .mt(px(4.0))
                     .rounded_full()
                     .bg(if is_stderr {
                         colors::error()
@@ -267,22 +969,62 @@ impl LogViewer {
                         colors::text_muted()
                     }),
             )
+            // Container, in merged logs
+            .when_some(line.origin.clone(), |el, origin| {
+                el.child(
+                    div()
+                        .flex_shrink_0()
+                        .w(px(96.0))
+                        .overflow_hidden()
+                        .text_ellipsis()
+                        .whitespace_nowrap()
+                        .text_color(colors::accent())
+                        .child(origin),
+                )
+            })
+            // Level
+            .when_some(line.level, |el, level| {
+                el.child(
+                    div()
+                        .flex_shrink_0()
+                        .w(px(40.0))
+                        .text_color(content_color)
+                        .child(level.label()),
+                )
+            })
             // Content
             .child(
                 div()
-                    .flex_1()
-                    .min_w_0()
-                    .overflow_x_hidden()
-                    .text_color(if is_stderr {
-                        colors::error()
-                    } else {
-                        colors::text()
-                    })
-                    .child(line.content.clone()),
+                    .flex_shrink_0()
+                    .text_color(content_color)
+                    .child(StyledText::new(line.content.clone()).with_highlights(highlights)),
             )
     }
 
     fn render_empty_state(&self) -> impl IntoElement {
+        let (title, hint) = match &self.source {
+            _ if !self.lines.is_empty() && !self.has_levels() => (
+                "No matching lines",
+                "Change the stream filter or the search to see more",
+            ),
+            LogSource::Container { .. } => (
+                "No logs yet",
+                "Logs will appear here when the container produces output",
+            ),
+            LogSource::Merged { .. } => (
+                "No logs yet",
+                "Logs from every container in the project will appear here",
+            ),
+            LogSource::Daemon(_) if !self.lines.is_empty() => (
+                "No matching lines",
+                "Lower the level filter or change the search to see more of the daemon's output",
+            ),
+            LogSource::Daemon(_) => (
+                "No daemon output yet",
+                "Output is captured from daemons ArcBox starts, including one kept running from an earlier session",
+            ),
+        };
+
         div()
             .flex_1()
             .flex()
@@ -293,13 +1035,13 @@ impl LogViewer {
             .child(
                 div()
                     .text_color(colors::text_secondary())
-                    .child("No logs yet"),
+                    .child(title),
             )
             .child(
                 div()
                     .text_xs()
                     .text_color(colors::text_muted())
-                    .child("Logs will appear here when the container produces output"),
+                    .child(hint),
             )
     }
 }
@@ -311,6 +1053,29 @@ impl Render for LogViewer {
             self.subscribe(cx);
         }
 
+        let content = if self.rows.is_empty() {
+            self.render_empty_state().into_any_element()
+        } else {
+            uniform_list(
+                "log-lines",
+                self.rows.len(),
+                cx.processor(|this, range: Range<usize>, _window, _cx| {
+                    range
+                        .map(|row| {
+                            let index = this.rows[row];
+                            this.render_log_line(index, &this.lines[index])
+                        })
+                        .collect::<Vec<_>>()
+                }),
+            )
+            // Lines don't wrap; the list scrolls sideways to the longest one
+            .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+            .with_width_from_item(self.widest_row)
+            .track_scroll(self.scroll_handle.clone())
+            .size_full()
+            .into_any_element()
+        };
+
         div()
             .size_full()
             .min_w_0()
@@ -322,35 +1087,135 @@ impl Render for LogViewer {
             .border_color(colors::border_subtle())
             .overflow_hidden()
             // Toolbar
-            .child(self.render_toolbar(cx))
+            .child(self.render_toolbar(self.rows.len(), cx))
+            .when_some(self.search_bar.as_ref(), |el, bar| el.child(self.render_search_bar(bar, cx)))
             // Log content
             .child(
                 div()
-                    .id("log-content")
                     .flex_1()
+                    .min_h_0()
                     .w_full()
-                    .min_w_0()
-                    .overflow_x_hidden()
-                    .overflow_y_scroll()
+                    .flex()
+                    .flex_col()
+                    .overflow_hidden()
                     .bg(colors::background())
-                    .when(self.lines.is_empty(), |el| {
-                        el.child(self.render_empty_state())
-                    })
-                    .when(!self.lines.is_empty(), |el| {
-                        el.child(
-                            div()
-                                .w_full()
-                                .min_w_0()
-                                .flex()
-                                .flex_col()
-                                .py_2()
-                                .children(
-                                    self.lines
-                                        .iter()
-                                        .map(|line| self.render_log_line(line)),
-                                ),
-                        )
-                    }),
+                    // Scrolling up to read stops following
+                    .on_scroll_wheel(cx.listener(|this, event: &ScrollWheelEvent, _, cx| {
+                        if this.follow && event.delta.pixel_delta(px(ROW_HEIGHT)).y > px(0.0) {
+                            this.follow = false;
+                            cx.notify();
+                        }
+                    }))
+                    .child(content),
             )
     }
 }
+
+/// Merge time-sorted lines from several streams, returning where each of
+/// the existing `lines` ended up
+///
+/// New lines go after existing ones with the same timestamp.
+fn merge_by_time(lines: &mut VecDeque<LogLine>, mut pending: Vec<LogLine>) -> Vec<usize> {
+    pending.sort_by_key(|line| line.timestamp);
+    let existing = std::mem::take(lines);
+    lines.reserve(existing.len() + pending.len());
+    let mut moved = Vec::with_capacity(existing.len());
+    let mut pending = pending.into_iter().peekable();
+    for line in existing {
+        while let Some(next) = pending.next_if(|next| next.timestamp < line.timestamp) {
+            lines.push_back(next);
+        }
+        moved.push(lines.len());
+        lines.push_back(line);
+    }
+    lines.extend(pending);
+    moved
+}
+
+/// Small text toggle for the toolbar and search bar, highlighted when on
+fn toggle_button(id: impl Into<ElementId>, label: &'static str, on: bool) -> Stateful<Div> {
+    div()
+        .id(id)
+        .flex_shrink_0()
+        .px_2()
+        .py_1()
+        .rounded(px(4.0))
+        .cursor_pointer()
+        .text_xs()
+        .when(on, |el| {
+            el.bg(colors::selection())
+                .text_color(colors::on_accent())
+        })
+        .when(!on, |el| {
+            el.hover(|el| el.bg(colors::hover()))
+                .text_color(colors::text_secondary())
+        })
+        .child(label)
+}
+
+/// Lay `top` over `base`, cutting the parts of `base` runs it covers
+///
+/// Both are sorted, non-overlapping ranges; so is the result.
+fn overlay_highlights(
+    base: Vec<(Range<usize>, HighlightStyle)>,
+    top: Vec<(Range<usize>, HighlightStyle)>,
+) -> Vec<(Range<usize>, HighlightStyle)> {
+    let mut result = Vec::with_capacity(base.len() + top.len());
+    for (range, style) in base {
+        let mut start = range.start;
+        for (cover, _) in top.iter().filter(|(cover, _)| cover.start < range.end && cover.end > range.start) {
+            if cover.start > start {
+                result.push((start..cover.start, style));
+            }
+            start = start.max(cover.end);
+        }
+        if start < range.end {
+            result.push((start..range.end, style));
+        }
+    }
+    result.extend(top);
+    result.sort_by_key(|(range, _)| range.start);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_cuts_base_runs() {
+        let base = HighlightStyle {
+            font_weight: Some(FontWeight::BOLD),
+            ..Default::default()
+        };
+        let top = HighlightStyle {
+            font_style: Some(FontStyle::Italic),
+            ..Default::default()
+        };
+        let result = overlay_highlights(vec![(0..10, base), (12..14, base)], vec![(2..4, top), (8..13, top)]);
+        let ranges: Vec<Range<usize>> = result.iter().map(|(range, _)| range.clone()).collect();
+        assert_eq!(ranges, vec![0..2, 2..4, 4..8, 8..13, 13..14]);
+        assert_eq!(result[1].1, top);
+        assert_eq!(result[4].1, base);
+    }
+
+    fn line(content: &str, timestamp: i64) -> LogLine {
+        LogLine {
+            content: content.to_string(),
+            styles: Vec::new(),
+            stream: "stdout".to_string(),
+            timestamp,
+            level: None,
+            origin: None,
+        }
+    }
+
+    #[test]
+    fn merge_keeps_time_order() {
+        let mut lines = VecDeque::from([line("a", 1), line("b", 3), line("c", 5)]);
+        let moved = merge_by_time(&mut lines, vec![line("y", 6), line("x", 3), line("w", 0)]);
+        let contents: Vec<&str> = lines.iter().map(|line| line.content.as_str()).collect();
+        assert_eq!(contents, vec!["w", "a", "b", "x", "c", "y"]);
+        assert_eq!(moved, vec![1, 2, 4]);
+    }
+}
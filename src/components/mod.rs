@@ -1,7 +1,19 @@
+mod ansi;
+mod file_browser;
+mod inspect_view;
+mod log_search;
 mod log_viewer;
+mod sparkline;
 mod status_badge;
+mod terminal;
 mod text_input;
 
+pub use ansi::*;
+pub use file_browser::*;
+pub use inspect_view::*;
+pub use log_search::*;
 pub use log_viewer::*;
+pub use sparkline::*;
 pub use status_badge::*;
+pub use terminal::*;
 pub use text_input::*;
@@ -0,0 +1,99 @@
+use gpui::*;
+
+/// Small line chart of recent values
+///
+/// Fills its parent, so size it with a wrapping div. Values are drawn right
+/// to left from the newest, so a short history grows in from the right edge.
+#[derive(IntoElement)]
+pub struct Sparkline {
+    values: Vec<f64>,
+    color: Hsla,
+    /// Top of the scale (defaults to the largest value)
+    max: Option<f64>,
+    /// Number of slots across the width (defaults to the number of values)
+    capacity: Option<usize>,
+    filled: bool,
+}
+
+impl Sparkline {
+    pub fn new(values: Vec<f64>, color: impl Into<Hsla>) -> Self {
+        Self {
+            values,
+            color: color.into(),
+            max: None,
+            capacity: None,
+            filled: false,
+        }
+    }
+
+    /// Fix the top of the scale, e.g. 100 for percentages
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Reserve room for this many values
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Shade the area under the line
+    pub fn filled(mut self) -> Self {
+        self.filled = true;
+        self
+    }
+}
+
+impl RenderOnce for Sparkline {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                if self.values.len() < 2 {
+                    return;
+                }
+                let largest = self.values.iter().copied().fold(0.0, f64::max);
+                let max = self.max.unwrap_or(largest).max(largest).max(f64::EPSILON);
+                let slots = self.capacity.unwrap_or(self.values.len()).max(self.values.len());
+                let step = bounds.size.width / (slots - 1) as f32;
+                let height = bounds.size.height;
+
+                let newest = self.values.len() - 1;
+                let points: Vec<Point<Pixels>> = self
+                    .values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| {
+                        let x = bounds.right() - step * (newest - i) as f32;
+                        let y = bounds.bottom() - height * (value.max(0.0) / max) as f32;
+                        point(x, y)
+                    })
+                    .collect();
+
+                if self.filled {
+                    let mut area = PathBuilder::fill();
+                    area.move_to(point(points[0].x, bounds.bottom()));
+                    for p in &points {
+                        area.line_to(*p);
+                    }
+                    area.line_to(point(bounds.right(), bounds.bottom()));
+                    area.close();
+                    if let Ok(path) = area.build() {
+                        window.paint_path(path, self.color.opacity(0.15));
+                    }
+                }
+
+                let mut line = PathBuilder::stroke(px(1.5));
+                line.move_to(points[0]);
+                for p in &points[1..] {
+                    line.line_to(*p);
+                }
+                if let Ok(path) = line.build() {
+                    window.paint_path(path, self.color);
+                }
+            },
+        )
+        .size_full()
+    }
+}
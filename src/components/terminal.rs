@@ -0,0 +1,772 @@
+//! Terminal component for interactive container shells.
+//!
+//! Opens a TTY exec session through the daemon and renders it with a small
+//! VT100/xterm emulator:
+//! - 16/256/true-color SGR, alternate screen, scroll regions
+//! - Resize follows the component's size
+//! - Scrollback (mouse wheel, Shift+PageUp/PageDown)
+//! - Mouse selection, copy (Cmd+C / Ctrl+Shift+C) and paste (Cmd+V / Ctrl+Shift+V)
+
+mod emulator;
+#[cfg(test)]
+mod tests;
+
+use std::ops::Range;
+use std::time::Duration;
+
+use gpui::*;
+use gpui::prelude::*;
+
+use crate::services::{DaemonService, ExecEvent, ExecSession};
+use crate::theme::{colors, MONO_FONT};
+use emulator::{Cell, Emulator, TermColor};
+pub(super) use emulator::CellStyle;
+
+/// Shell started in the container: bash when available, sh otherwise
+const SHELL_COMMAND: [&str; 3] = [
+    "/bin/sh",
+    "-c",
+    "if command -v bash >/dev/null 2>&1; then exec bash; else exec sh; fi",
+];
+
+const FONT_SIZE: f32 = 13.0;
+const LINE_HEIGHT: f32 = 18.0;
+
+/// How often PTY output is drained into the emulator
+const PUMP_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Size used until the first layout tells us the real one
+const DEFAULT_COLS: usize = 80;
+const DEFAULT_ROWS: usize = 24;
+
+/// Session lifecycle as shown to the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalStatus {
+    /// Waiting for layout or for the daemon to start the shell
+    Connecting,
+    Running,
+    /// The shell exited
+    Exited,
+    Failed(String),
+}
+
+/// Selected text, as (absolute line, column) positions
+#[derive(Debug, Clone, Copy)]
+struct Selection {
+    anchor: (usize, usize),
+    head: (usize, usize),
+}
+
+impl Selection {
+    fn ordered(&self) -> ((usize, usize), (usize, usize)) {
+        if self.anchor <= self.head {
+            (self.anchor, self.head)
+        } else {
+            (self.head, self.anchor)
+        }
+    }
+
+    fn contains(&self, line: usize, col: usize) -> bool {
+        let (start, end) = self.ordered();
+        (line, col) >= start && (line, col) <= end
+    }
+}
+
+/// Interactive terminal attached to a container
+pub struct TerminalView {
+    container_id: String,
+    daemon_service: Entity<DaemonService>,
+    focus_handle: FocusHandle,
+    emulator: Emulator,
+    session: Option<ExecSession>,
+    status: TerminalStatus,
+    /// Lines scrolled up from the bottom (0 follows output)
+    scroll_offset: usize,
+    /// Wheel movement not yet amounting to a whole line
+    pending_scroll: f32,
+    selection: Option<Selection>,
+    /// Mouse button held down over the grid
+    selecting: bool,
+    /// Grid bounds from the last layout
+    bounds: Option<Bounds<Pixels>>,
+    cell_width: Pixels,
+    /// Grab focus on the next render
+    focus_on_render: bool,
+    _pump_task: Option<Task<()>>,
+}
+
+impl TerminalView {
+    pub fn new(
+        container_id: String,
+        daemon_service: Entity<DaemonService>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            container_id,
+            daemon_service,
+            focus_handle: cx.focus_handle(),
+            emulator: Emulator::new(DEFAULT_COLS, DEFAULT_ROWS),
+            session: None,
+            status: TerminalStatus::Connecting,
+            scroll_offset: 0,
+            pending_scroll: 0.0,
+            selection: None,
+            selecting: false,
+            bounds: None,
+            cell_width: px(FONT_SIZE * 0.6),
+            focus_on_render: true,
+            _pump_task: None,
+        }
+    }
+
+    pub fn status(&self) -> &TerminalStatus {
+        &self.status
+    }
+
+    /// Window title set by the shell (OSC 0/2), if any
+    pub fn title(&self) -> Option<&str> {
+        self.emulator.title().filter(|title| !title.is_empty())
+    }
+
+    /// Start (or restart) the shell with the current grid size
+    fn open_session(&mut self, cx: &mut Context<Self>) {
+        let cols = self.emulator.cols();
+        let rows = self.emulator.rows();
+        self.emulator = Emulator::new(cols, rows);
+        self.session = None;
+        self.scroll_offset = 0;
+        self.selection = None;
+        self.status = TerminalStatus::Connecting;
+
+        let container_id = self.container_id.clone();
+        let cmd = SHELL_COMMAND.iter().map(|s| s.to_string()).collect();
+        let open = self.daemon_service.update(cx, |svc, cx| {
+            svc.open_exec(container_id, cmd, cols as u16, rows as u16, cx)
+        });
+
+        self._pump_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let session = open.await;
+            let started = cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    match session {
+                        Ok(session) => {
+                            this.session = Some(session);
+                            this.status = TerminalStatus::Running;
+                        }
+                        Err(e) => this.status = TerminalStatus::Failed(e.to_string()),
+                    }
+                    cx.notify();
+                    this.session.is_some()
+                })
+            }).ok().and_then(|r| r.ok()).unwrap_or(false);
+            if !started {
+                return;
+            }
+
+            loop {
+                cx.background_executor().timer(PUMP_INTERVAL).await;
+                let running = cx.update(|cx| {
+                    this.update(cx, |this, cx| this.pump(cx))
+                }).ok().and_then(|r| r.ok()).unwrap_or(false);
+                if !running {
+                    break;
+                }
+            }
+        }));
+        cx.notify();
+    }
+
+    /// Feed pending output into the emulator; false once the session is over
+    fn pump(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(session) = self.session.as_ref() else {
+            return false;
+        };
+
+        let mut received = false;
+        let mut ended = None;
+        loop {
+            match session.try_recv() {
+                Ok(ExecEvent::Output(bytes)) => {
+                    self.emulator.advance(&bytes);
+                    received = true;
+                }
+                Ok(ExecEvent::Exited) | Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    ended = Some(TerminalStatus::Exited);
+                    break;
+                }
+                Ok(ExecEvent::Failed(e)) => {
+                    ended = Some(TerminalStatus::Failed(e.to_string()));
+                    break;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+            }
+        }
+
+        // Answer device status / attribute queries
+        let responses = self.emulator.take_responses();
+        session.write(&responses);
+
+        if let Some(status) = ended {
+            tracing::debug!("Terminal session in {} ended: {:?}", self.container_id, status);
+            self.session = None;
+            self.status = status;
+            cx.notify();
+            return false;
+        }
+        if received {
+            cx.notify();
+        }
+        true
+    }
+
+    /// Send input to the shell, jumping back to the live screen
+    fn write(&mut self, bytes: &[u8], cx: &mut Context<Self>) {
+        let Some(session) = self.session.as_ref() else {
+            return;
+        };
+        session.write(bytes);
+        if self.scroll_offset != 0 || self.selection.is_some() {
+            self.scroll_offset = 0;
+            self.selection = None;
+            cx.notify();
+        }
+    }
+
+    /// The grid was laid out; resize the PTY if the cell count changed
+    fn on_layout(&mut self, bounds: Bounds<Pixels>, cell_width: Pixels, cx: &mut Context<Self>) {
+        self.bounds = Some(bounds);
+        self.cell_width = cell_width;
+
+        let width: f32 = bounds.size.width.into();
+        let height: f32 = bounds.size.height.into();
+        let cell_width: f32 = cell_width.into();
+        let cols = ((width / cell_width).floor() as usize).clamp(2, u16::MAX as usize);
+        let rows = ((height / LINE_HEIGHT).floor() as usize).clamp(1, u16::MAX as usize);
+
+        let first_layout = self.session.is_none() && self._pump_task.is_none();
+        if cols != self.emulator.cols() || rows != self.emulator.rows() {
+            self.emulator.resize(cols, rows);
+            self.scroll_offset = self.scroll_offset.min(self.emulator.scrollback_len());
+            if let Some(session) = self.session.as_ref() {
+                session.resize(cols as u16, rows as u16);
+            }
+            cx.notify();
+        }
+        if first_layout {
+            self.open_session(cx);
+        }
+    }
+
+    /// Absolute index of the first line on screen
+    fn first_visible_line(&self) -> usize {
+        self.emulator.scrollback_len() - self.scroll_offset.min(self.emulator.scrollback_len())
+    }
+
+    /// Grid cell under a window position
+    fn cell_at(&self, position: Point<Pixels>) -> Option<(usize, usize)> {
+        let bounds = self.bounds?;
+        let x: f32 = (position.x - bounds.origin.x).into();
+        let y: f32 = (position.y - bounds.origin.y).into();
+        let cell_width: f32 = self.cell_width.into();
+        let col = (x.max(0.0) / cell_width) as usize;
+        let row = (y.max(0.0) / LINE_HEIGHT) as usize;
+        Some((
+            self.first_visible_line() + row.min(self.emulator.rows() - 1),
+            col.min(self.emulator.cols() - 1),
+        ))
+    }
+
+    fn scroll_by(&mut self, lines: i32, cx: &mut Context<Self>) {
+        // Full-screen programs get arrow keys instead (no scrollback there)
+        if self.emulator.is_alternate_screen() {
+            let key: &[u8] = match (lines > 0, self.emulator.app_cursor_keys()) {
+                (true, true) => b"\x1bOA",
+                (true, false) => b"\x1b[A",
+                (false, true) => b"\x1bOB",
+                (false, false) => b"\x1b[B",
+            };
+            if let Some(session) = self.session.as_ref() {
+                for _ in 0..lines.unsigned_abs() {
+                    session.write(key);
+                }
+            }
+            return;
+        }
+
+        let max = self.emulator.scrollback_len() as i64;
+        let offset = (self.scroll_offset as i64 + i64::from(lines)).clamp(0, max) as usize;
+        if offset != self.scroll_offset {
+            self.scroll_offset = offset;
+            cx.notify();
+        }
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selection?.ordered();
+        let text = self.emulator.text_range(start, end);
+        (!text.is_empty()).then_some(text)
+    }
+
+    fn copy(&mut self, cx: &mut Context<Self>) {
+        if let Some(text) = self.selected_text() {
+            cx.write_to_clipboard(ClipboardItem::new_string(text));
+        }
+    }
+
+    fn paste(&mut self, cx: &mut Context<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        // Terminals expect carriage returns for line breaks
+        let text = text.replace("\r\n", "\r").replace('\n', "\r");
+        let bytes = if self.emulator.bracketed_paste() {
+            format!("\x1b[200~{}\x1b[201~", text).into_bytes()
+        } else {
+            text.into_bytes()
+        };
+        self.write(&bytes, cx);
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        let modifiers = &keystroke.modifiers;
+        // Cmd on macOS, Ctrl+Shift elsewhere
+        let shortcut = modifiers.platform || (modifiers.control && modifiers.shift);
+
+        match keystroke.key.as_str() {
+            "c" if shortcut => self.copy(cx),
+            "v" if shortcut => self.paste(cx),
+            "pageup" if modifiers.shift => {
+                self.scroll_by(self.emulator.rows() as i32, cx)
+            }
+            "pagedown" if modifiers.shift => {
+                self.scroll_by(-(self.emulator.rows() as i32), cx)
+            }
+            _ if modifiers.platform => return, // Leave app shortcuts alone
+            _ => match keystroke_bytes(keystroke, self.emulator.app_cursor_keys()) {
+                Some(bytes) => self.write(&bytes, cx),
+                None => return,
+            },
+        }
+        cx.stop_propagation();
+    }
+
+    fn on_scroll(&mut self, event: &ScrollWheelEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        let delta: f32 = event.delta.pixel_delta(px(LINE_HEIGHT)).y.into();
+        self.pending_scroll += delta / LINE_HEIGHT;
+        let lines = self.pending_scroll.trunc() as i32;
+        if lines != 0 {
+            self.pending_scroll -= lines as f32;
+            self.scroll_by(lines, cx);
+        }
+        cx.stop_propagation();
+    }
+
+    fn on_mouse_down(&mut self, event: &MouseDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.focus_handle);
+        if let Some(cell) = self.cell_at(event.position) {
+            self.selection = Some(Selection {
+                anchor: cell,
+                head: cell,
+            });
+            self.selecting = true;
+            cx.notify();
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        if !self.selecting || !event.dragging() {
+            return;
+        }
+        if let (Some(cell), Some(selection)) = (self.cell_at(event.position), self.selection.as_mut()) {
+            if selection.head != cell {
+                selection.head = cell;
+                cx.notify();
+            }
+        }
+    }
+
+    fn on_mouse_up(&mut self, _event: &MouseUpEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        self.selecting = false;
+        // A plain click leaves nothing selected
+        if let Some(selection) = self.selection {
+            if selection.anchor == selection.head {
+                self.selection = None;
+                cx.notify();
+            }
+        }
+    }
+
+    /// One grid line as styled text, with cursor and selection applied
+    fn render_line(&self, index: usize, cursor: Option<(usize, usize)>, focused: bool) -> impl IntoElement {
+        let mut text = String::new();
+        let mut highlights: Vec<(Range<usize>, HighlightStyle)> = Vec::new();
+
+        if let Some(line) = self.emulator.line(index) {
+            for (col, cell) in line.iter().enumerate() {
+                let selected = self.selection.is_some_and(|s| s.contains(index, col));
+                let is_cursor = focused && cursor == Some((index, col)) && self.status == TerminalStatus::Running;
+                let style = cell_highlight(cell, selected, is_cursor);
+
+                let start = text.len();
+                text.push(if cell.c == '\0' { ' ' } else { cell.c });
+                let end = text.len();
+                match highlights.last_mut() {
+                    Some((range, last)) if range.end == start && *last == style => range.end = end,
+                    _ => highlights.push((start..end, style)),
+                }
+            }
+        }
+
+        div()
+            .h(px(LINE_HEIGHT))
+            .whitespace_nowrap()
+            .child(StyledText::new(text).with_highlights(highlights))
+    }
+
+    fn render_status(&self, cx: &Context<Self>) -> Option<impl IntoElement> {
+        let (message, color, action) = match &self.status {
+            TerminalStatus::Running => return None,
+            TerminalStatus::Connecting => ("Starting shell...".to_string(), colors::text_secondary(), None),
+            TerminalStatus::Exited => ("Process exited".to_string(), colors::text_secondary(), Some("Restart")),
+            TerminalStatus::Failed(message) => (message.clone(), colors::error(), Some("Retry")),
+        };
+
+        Some(
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .gap_2()
+                .h(px(32.0))
+                .px_3()
+                .border_t_1()
+                .border_color(colors::border_subtle())
+                .bg(colors::surface())
+                .text_xs()
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w_0()
+                        .overflow_hidden()
+                        .text_ellipsis()
+                        .whitespace_nowrap()
+                        .text_color(color)
+                        .child(message),
+                )
+                .when_some(action, |el, label| {
+                    el.child(
+                        div()
+                            .id("terminal-restart")
+                            .px_2()
+                            .py_1()
+                            .rounded(px(4.0))
+                            .cursor_pointer()
+                            .text_color(colors::text_secondary())
+                            .hover(|el| el.bg(colors::hover()))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.open_session(cx);
+                                window.focus(&this.focus_handle);
+                            }))
+                            .child(label),
+                    )
+                }),
+        )
+    }
+}
+
+impl Focusable for TerminalView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for TerminalView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.focus_on_render {
+            self.focus_on_render = false;
+            window.focus(&self.focus_handle);
+        }
+
+        let focused = self.focus_handle.is_focused(window);
+        let cursor = self.emulator.cursor();
+        let first = self.first_visible_line();
+        let lines: Vec<_> = (first..first + self.emulator.rows())
+            .map(|index| self.render_line(index, cursor, focused))
+            .collect();
+        let entity = cx.entity();
+
+        div()
+            .size_full()
+            .min_w_0()
+            .flex()
+            .flex_col()
+            .bg(colors::surface())
+            .rounded(px(8.0))
+            .border_1()
+            .border_color(if focused {
+                colors::border_focused()
+            } else {
+                colors::border_subtle()
+            })
+            .overflow_hidden()
+            .child(
+                div()
+                    .id("terminal-grid")
+                    .track_focus(&self.focus_handle)
+                    .key_context("Terminal")
+                    .flex_1()
+                    .min_h_0()
+                    .relative()
+                    .p_2()
+                    .bg(colors::background())
+                    .cursor(CursorStyle::IBeam)
+                    .font_family(MONO_FONT)
+                    .text_size(px(FONT_SIZE))
+                    .line_height(px(LINE_HEIGHT))
+                    .text_color(colors::text())
+                    .on_key_down(cx.listener(Self::on_key_down))
+                    .on_scroll_wheel(cx.listener(Self::on_scroll))
+                    .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+                    .on_mouse_move(cx.listener(Self::on_mouse_move))
+                    .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+                    .child(
+                        div()
+                            .size_full()
+                            .relative()
+                            .overflow_hidden()
+                            // Measure the grid so the PTY can follow our size
+                            .child(
+                                canvas(
+                                    move |bounds, window, cx| {
+                                        let font_id = window.text_system().resolve_font(&font(MONO_FONT));
+                                        let cell_width = window
+                                            .text_system()
+                                            .advance(font_id, px(FONT_SIZE), 'm')
+                                            .map(|size| size.width)
+                                            .unwrap_or(px(FONT_SIZE * 0.6));
+                                        entity.update(cx, |this, cx| {
+                                            this.on_layout(bounds, cell_width, cx);
+                                        });
+                                    },
+                                    |_, _, _, _| {},
+                                )
+                                .absolute()
+                                .size_full(),
+                            )
+                            .children(lines),
+                    ),
+            )
+            .children(self.render_status(cx))
+    }
+}
+
+/// Bytes a key press sends to the PTY (None for keys the terminal ignores)
+///
+/// Option/Alt acts as Meta and prefixes the key with ESC.
+fn keystroke_bytes(keystroke: &Keystroke, app_cursor_keys: bool) -> Option<Vec<u8>> {
+    let modifiers = &keystroke.modifiers;
+    let key = keystroke.key.as_str();
+
+    // xterm modifier parameter: 1 + shift + 2*alt + 4*ctrl
+    let modifier_param = 1
+        + u8::from(modifiers.shift)
+        + 2 * u8::from(modifiers.alt)
+        + 4 * u8::from(modifiers.control);
+
+    let cursor_key = |final_byte: char| -> Vec<u8> {
+        if modifier_param > 1 {
+            format!("\x1b[1;{}{}", modifier_param, final_byte).into_bytes()
+        } else if app_cursor_keys {
+            format!("\x1bO{}", final_byte).into_bytes()
+        } else {
+            format!("\x1b[{}", final_byte).into_bytes()
+        }
+    };
+    let tilde_key = |code: u8| -> Vec<u8> {
+        if modifier_param > 1 {
+            format!("\x1b[{};{}~", code, modifier_param).into_bytes()
+        } else {
+            format!("\x1b[{}~", code).into_bytes()
+        }
+    };
+
+    let bytes = match key {
+        "enter" => b"\r".to_vec(),
+        "backspace" if modifiers.control => vec![0x08],
+        "backspace" => vec![0x7f],
+        "tab" if modifiers.shift => b"\x1b[Z".to_vec(),
+        "tab" => b"\t".to_vec(),
+        "escape" => vec![0x1b],
+        "up" => cursor_key('A'),
+        "down" => cursor_key('B'),
+        "right" => cursor_key('C'),
+        "left" => cursor_key('D'),
+        "home" => cursor_key('H'),
+        "end" => cursor_key('F'),
+        "insert" => tilde_key(2),
+        "delete" => tilde_key(3),
+        "pageup" => tilde_key(5),
+        "pagedown" => tilde_key(6),
+        "f1" => b"\x1bOP".to_vec(),
+        "f2" => b"\x1bOQ".to_vec(),
+        "f3" => b"\x1bOR".to_vec(),
+        "f4" => b"\x1bOS".to_vec(),
+        "f5" => tilde_key(15),
+        "f6" => tilde_key(17),
+        "f7" => tilde_key(18),
+        "f8" => tilde_key(19),
+        "f9" => tilde_key(20),
+        "f10" => tilde_key(21),
+        "f11" => tilde_key(23),
+        "f12" => tilde_key(24),
+        "space" if modifiers.control => vec![0x00],
+        _ if modifiers.control => {
+            let mut chars = key.chars();
+            let (Some(c), None) = (chars.next(), chars.next()) else {
+                return None;
+            };
+            let byte = match c.to_ascii_lowercase() {
+                c @ 'a'..='z' => c as u8 & 0x1f,
+                '@' | '2' => 0x00,
+                '[' | '3' => 0x1b,
+                '\\' | '4' => 0x1c,
+                ']' | '5' => 0x1d,
+                '^' | '6' => 0x1e,
+                '_' | '-' | '7' => 0x1f,
+                '?' | '8' => 0x7f,
+                _ => return None,
+            };
+            vec![byte]
+        }
+        "space" => b" ".to_vec(),
+        _ => {
+            let text = match (&keystroke.key_char, modifiers.alt) {
+                // Option composes characters on macOS; as Meta we want the base key
+                (Some(text), false) => text.clone(),
+                _ if key.chars().count() == 1 => {
+                    if modifiers.shift {
+                        key.to_uppercase()
+                    } else {
+                        key.to_string()
+                    }
+                }
+                _ => return None,
+            };
+            text.into_bytes()
+        }
+    };
+
+    // Cursor and function keys already carry Alt in their modifier parameter
+    let is_sequence = bytes.starts_with(b"\x1b[") || bytes.starts_with(b"\x1bO");
+    if modifiers.alt && !is_sequence {
+        let mut prefixed = vec![0x1b];
+        prefixed.extend(bytes);
+        return Some(prefixed);
+    }
+    Some(bytes)
+}
+
+/// Text style for one cell
+fn cell_highlight(cell: &Cell, selected: bool, is_cursor: bool) -> HighlightStyle {
+    let mut highlight = sgr_highlight(&cell.style, colors::text().into());
+    if selected {
+        highlight.background_color = Some(colors::selection_inactive().into());
+    }
+    if is_cursor {
+        highlight.background_color = highlight.color;
+        highlight.color = Some(colors::background().into());
+    }
+    highlight
+}
+
+/// Text style for SGR attributes, with `default_fg` for the default foreground
+///
+/// Shared with the log viewer, which renders colored container output.
+pub(super) fn sgr_highlight(style: &CellStyle, default_fg: Hsla) -> HighlightStyle {
+    let mut fg: Hsla = match style.fg {
+        // Bold brightens the eight base colors, as in xterm
+        TermColor::Indexed(index) if style.bold && index < 8 => ansi_color(index + 8).into(),
+        TermColor::Default => default_fg,
+        color => term_color(color).into(),
+    };
+    let mut bg: Option<Hsla> = match style.bg {
+        TermColor::Default => None,
+        color => Some(term_color(color).into()),
+    };
+
+    if style.inverse {
+        let background = bg.unwrap_or_else(|| colors::background().into());
+        bg = Some(fg);
+        fg = background;
+    }
+
+    HighlightStyle {
+        color: Some(fg),
+        background_color: bg,
+        font_weight: style.bold.then_some(FontWeight::BOLD),
+        font_style: style.italic.then_some(FontStyle::Italic),
+        underline: style.underline.then(|| UnderlineStyle {
+            thickness: px(1.0),
+            ..Default::default()
+        }),
+        strikethrough: style.strikethrough.then(|| StrikethroughStyle {
+            thickness: px(1.0),
+            ..Default::default()
+        }),
+        fade_out: style.dim.then_some(0.4),
+        ..Default::default()
+    }
+}
+
+fn term_color(color: TermColor) -> Rgba {
+    match color {
+        TermColor::Default => colors::text(),
+        TermColor::Indexed(index) => ansi_color(index),
+        TermColor::Rgb(r, g, b) => rgb_color(r, g, b),
+    }
+}
+
+fn rgb_color(r: u8, g: u8, b: u8) -> Rgba {
+    rgba(((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | 0xff)
+}
+
+/// xterm 256-color palette; the 16 ANSI colors are tuned for the light background
+fn ansi_color(index: u8) -> Rgba {
+    const ANSI: [u32; 16] = [
+        0x000000, // black
+        0xc91b00, // red
+        0x00a600, // green
+        0xa68a00, // yellow
+        0x0048d1, // blue
+        0xb200b2, // magenta
+        0x00a6b2, // cyan
+        0xbfbfbf, // white
+        0x666666, // bright black
+        0xe50000, // bright red
+        0x00c200, // bright green
+        0xc7a800, // bright yellow
+        0x0066ff, // bright blue
+        0xe500e5, // bright magenta
+        0x00c2cc, // bright cyan
+        0xe5e5e5, // bright white
+    ];
+
+    match index {
+        0..=15 => {
+            let value = ANSI[index as usize];
+            rgb_color((value >> 16) as u8, (value >> 8) as u8, value as u8)
+        }
+        16..=231 => {
+            // 6x6x6 color cube
+            let index = index - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            rgb_color(level(index / 36), level((index / 6) % 6), level(index % 6))
+        }
+        _ => {
+            // Grayscale ramp
+            let gray = 8 + (index - 232) * 10;
+            rgb_color(gray, gray, gray)
+        }
+    }
+}
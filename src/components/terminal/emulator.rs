@@ -0,0 +1,719 @@
+//! VT100/xterm screen model.
+//!
+//! Feeds PTY output through a `vte` parser and keeps the resulting grid of
+//! styled cells, the cursor, scrollback and the handful of private modes
+//! interactive programs rely on (alternate screen, application cursor keys,
+//! bracketed paste). Rendering is left to `TerminalView`.
+
+use std::collections::VecDeque;
+
+use vte::{Params, Parser, Perform};
+
+/// Lines kept above the screen once they scroll off
+pub const DEFAULT_SCROLLBACK: usize = 10_000;
+
+/// Terminal color as set by SGR sequences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TermColor {
+    #[default]
+    Default,
+    /// 256-color palette index (0-15 are the ANSI colors)
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// Character attributes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellStyle {
+    pub fg: TermColor,
+    pub bg: TermColor,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub inverse: bool,
+    pub strikethrough: bool,
+}
+
+impl CellStyle {
+    /// Apply one SGR sequence (`CSI ... m`)
+    ///
+    /// Accepts both `38;5;n` and the colon form `38:5:n` for extended colors.
+    pub fn apply_sgr(&mut self, params: &[Vec<u16>]) {
+        if params.is_empty() {
+            *self = CellStyle::default();
+            return;
+        }
+
+        // Flatten semicolon groups so extended colors can read ahead
+        let mut index = 0;
+        while index < params.len() {
+            let group = &params[index];
+            let code = group.first().copied().unwrap_or(0);
+            index += 1;
+
+            match code {
+                0 => *self = CellStyle::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                9 => self.strikethrough = true,
+                21 | 22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.fg = TermColor::Indexed((code - 30) as u8),
+                39 => self.fg = TermColor::Default,
+                40..=47 => self.bg = TermColor::Indexed((code - 40) as u8),
+                49 => self.bg = TermColor::Default,
+                90..=97 => self.fg = TermColor::Indexed((code - 90 + 8) as u8),
+                100..=107 => self.bg = TermColor::Indexed((code - 100 + 8) as u8),
+                38 | 48 => {
+                    let color = if group.len() > 1 {
+                        // Colon form: everything is in this group
+                        Self::extended_color(&group[1..])
+                    } else {
+                        let rest: Vec<u16> = params[index..]
+                            .iter()
+                            .map(|g| g.first().copied().unwrap_or(0))
+                            .collect();
+                        let (color, used) = Self::extended_color_with_len(&rest);
+                        index += used;
+                        color
+                    };
+                    if let Some(color) = color {
+                        if code == 38 {
+                            self.fg = color;
+                        } else {
+                            self.bg = color;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Colon form, where RGB carries a (usually empty) color space ID first
+    fn extended_color(values: &[u16]) -> Option<TermColor> {
+        match values {
+            [2, _, r, g, b, ..] => Some(TermColor::Rgb(*r as u8, *g as u8, *b as u8)),
+            _ => Self::extended_color_with_len(values).0,
+        }
+    }
+
+    /// `5;n` or `2;r;g;b`, returning how many values were consumed
+    fn extended_color_with_len(values: &[u16]) -> (Option<TermColor>, usize) {
+        match values {
+            [5, n, ..] => (Some(TermColor::Indexed(*n as u8)), 2),
+            [2, r, g, b, ..] => (Some(TermColor::Rgb(*r as u8, *g as u8, *b as u8)), 4),
+            [] => (None, 0),
+            _ => (None, values.len()),
+        }
+    }
+}
+
+/// One character cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub c: char,
+    pub style: CellStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            c: ' ',
+            style: CellStyle::default(),
+        }
+    }
+}
+
+/// A screen or scrollback line
+pub type Line = Vec<Cell>;
+
+/// Cursor position and the style used for new characters
+#[derive(Debug, Clone, Copy, Default)]
+struct Cursor {
+    row: usize,
+    col: usize,
+    style: CellStyle,
+}
+
+/// Main screen parked while a full-screen program uses the alternate one
+struct SavedScreen {
+    lines: Vec<Line>,
+    cursor: Cursor,
+}
+
+/// Terminal state driven by the parser
+pub struct Screen {
+    cols: usize,
+    rows: usize,
+    lines: Vec<Line>,
+    scrollback: VecDeque<Line>,
+    max_scrollback: usize,
+    cursor: Cursor,
+    saved_cursor: Option<Cursor>,
+    /// Set after writing the last column; the next character wraps first
+    wrap_pending: bool,
+    /// Scroll region, inclusive rows
+    scroll_top: usize,
+    scroll_bottom: usize,
+    alternate: Option<SavedScreen>,
+    autowrap: bool,
+    cursor_visible: bool,
+    app_cursor_keys: bool,
+    bracketed_paste: bool,
+    title: Option<String>,
+    /// Replies to device queries, to be written back to the PTY
+    responses: Vec<u8>,
+}
+
+impl Screen {
+    fn new(cols: usize, rows: usize) -> Self {
+        let cols = cols.max(1);
+        let rows = rows.max(1);
+        Self {
+            cols,
+            rows,
+            lines: vec![Self::blank_line(cols, CellStyle::default()); rows],
+            scrollback: VecDeque::new(),
+            max_scrollback: DEFAULT_SCROLLBACK,
+            cursor: Cursor::default(),
+            saved_cursor: None,
+            wrap_pending: false,
+            scroll_top: 0,
+            scroll_bottom: rows - 1,
+            alternate: None,
+            autowrap: true,
+            cursor_visible: true,
+            app_cursor_keys: false,
+            bracketed_paste: false,
+            title: None,
+            responses: Vec::new(),
+        }
+    }
+
+    fn blank_line(cols: usize, style: CellStyle) -> Line {
+        // Erased cells keep the background color only
+        let style = CellStyle {
+            bg: style.bg,
+            ..CellStyle::default()
+        };
+        vec![Cell { c: ' ', style }; cols]
+    }
+
+    fn blank(&self) -> Line {
+        Self::blank_line(self.cols, self.cursor.style)
+    }
+
+    fn resize(&mut self, cols: usize, rows: usize) {
+        let cols = cols.max(1);
+        let rows = rows.max(1);
+        if cols == self.cols && rows == self.rows {
+            return;
+        }
+
+        for line in self.lines.iter_mut() {
+            line.resize(cols, Cell::default());
+        }
+        // Shrinking: move lines above the cursor into scrollback first
+        while self.lines.len() > rows {
+            if self.cursor.row > 0 {
+                let line = self.lines.remove(0);
+                self.push_scrollback(line);
+                self.cursor.row -= 1;
+            } else {
+                self.lines.pop();
+            }
+        }
+        // Growing: pull lines back out of scrollback, then pad
+        while self.lines.len() < rows {
+            let restored = match self.alternate {
+                Some(_) => None,
+                None => self.scrollback.pop_back(),
+            };
+            match restored {
+                Some(mut line) => {
+                    line.resize(cols, Cell::default());
+                    self.lines.insert(0, line);
+                    self.cursor.row += 1;
+                }
+                None => self.lines.push(Self::blank_line(cols, CellStyle::default())),
+            }
+        }
+        if let Some(saved) = self.alternate.as_mut() {
+            saved.lines.resize(rows, Self::blank_line(cols, CellStyle::default()));
+            for line in saved.lines.iter_mut() {
+                line.resize(cols, Cell::default());
+            }
+            saved.cursor.row = saved.cursor.row.min(rows - 1);
+            saved.cursor.col = saved.cursor.col.min(cols - 1);
+        }
+
+        self.cols = cols;
+        self.rows = rows;
+        self.scroll_top = 0;
+        self.scroll_bottom = rows - 1;
+        self.cursor.row = self.cursor.row.min(rows - 1);
+        self.cursor.col = self.cursor.col.min(cols - 1);
+        self.wrap_pending = false;
+    }
+
+    fn push_scrollback(&mut self, line: Line) {
+        if self.alternate.is_some() {
+            return;
+        }
+        self.scrollback.push_back(line);
+        while self.scrollback.len() > self.max_scrollback {
+            self.scrollback.pop_front();
+        }
+    }
+
+    /// Scroll the region up by `n` lines (text moves up)
+    fn scroll_up(&mut self, n: usize) {
+        for _ in 0..n.min(self.scroll_bottom - self.scroll_top + 1) {
+            let line = self.lines.remove(self.scroll_top);
+            if self.scroll_top == 0 {
+                self.push_scrollback(line);
+            }
+            let blank = self.blank();
+            self.lines.insert(self.scroll_bottom, blank);
+        }
+    }
+
+    /// Scroll the region down by `n` lines (text moves down)
+    fn scroll_down(&mut self, n: usize) {
+        for _ in 0..n.min(self.scroll_bottom - self.scroll_top + 1) {
+            self.lines.remove(self.scroll_bottom);
+            let blank = self.blank();
+            self.lines.insert(self.scroll_top, blank);
+        }
+    }
+
+    fn linefeed(&mut self) {
+        self.wrap_pending = false;
+        if self.cursor.row == self.scroll_bottom {
+            self.scroll_up(1);
+        } else if self.cursor.row + 1 < self.rows {
+            self.cursor.row += 1;
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        self.wrap_pending = false;
+        if self.cursor.row == self.scroll_top {
+            self.scroll_down(1);
+        } else if self.cursor.row > 0 {
+            self.cursor.row -= 1;
+        }
+    }
+
+    fn move_to(&mut self, row: usize, col: usize) {
+        self.cursor.row = row.min(self.rows - 1);
+        self.cursor.col = col.min(self.cols - 1);
+        self.wrap_pending = false;
+    }
+
+    fn erase_cells(&mut self, row: usize, from: usize, to: usize) {
+        let blank = Cell {
+            c: ' ',
+            style: CellStyle {
+                bg: self.cursor.style.bg,
+                ..CellStyle::default()
+            },
+        };
+        let to = to.min(self.cols);
+        if let Some(line) = self.lines.get_mut(row) {
+            for cell in line.iter_mut().take(to).skip(from) {
+                *cell = blank;
+            }
+        }
+    }
+
+    fn erase_in_display(&mut self, mode: u16) {
+        let (row, col) = (self.cursor.row, self.cursor.col);
+        match mode {
+            0 => {
+                self.erase_cells(row, col, self.cols);
+                for r in row + 1..self.rows {
+                    self.erase_cells(r, 0, self.cols);
+                }
+            }
+            1 => {
+                for r in 0..row {
+                    self.erase_cells(r, 0, self.cols);
+                }
+                self.erase_cells(row, 0, col + 1);
+            }
+            2 => {
+                for r in 0..self.rows {
+                    self.erase_cells(r, 0, self.cols);
+                }
+            }
+            3 => self.scrollback.clear(),
+            _ => {}
+        }
+    }
+
+    fn erase_in_line(&mut self, mode: u16) {
+        let (row, col) = (self.cursor.row, self.cursor.col);
+        match mode {
+            0 => self.erase_cells(row, col, self.cols),
+            1 => self.erase_cells(row, 0, col + 1),
+            2 => self.erase_cells(row, 0, self.cols),
+            _ => {}
+        }
+    }
+
+    fn insert_lines(&mut self, n: usize) {
+        if self.cursor.row < self.scroll_top || self.cursor.row > self.scroll_bottom {
+            return;
+        }
+        for _ in 0..n.min(self.scroll_bottom - self.cursor.row + 1) {
+            self.lines.remove(self.scroll_bottom);
+            let blank = self.blank();
+            self.lines.insert(self.cursor.row, blank);
+        }
+    }
+
+    fn delete_lines(&mut self, n: usize) {
+        if self.cursor.row < self.scroll_top || self.cursor.row > self.scroll_bottom {
+            return;
+        }
+        for _ in 0..n.min(self.scroll_bottom - self.cursor.row + 1) {
+            self.lines.remove(self.cursor.row);
+            let blank = self.blank();
+            self.lines.insert(self.scroll_bottom, blank);
+        }
+    }
+
+    fn insert_chars(&mut self, n: usize) {
+        let (row, col, cols) = (self.cursor.row, self.cursor.col, self.cols);
+        let line = &mut self.lines[row];
+        for _ in 0..n.min(cols - col) {
+            line.pop();
+            line.insert(col, Cell::default());
+        }
+    }
+
+    fn delete_chars(&mut self, n: usize) {
+        let (row, col, cols) = (self.cursor.row, self.cursor.col, self.cols);
+        let line = &mut self.lines[row];
+        for _ in 0..n.min(cols - col) {
+            line.remove(col);
+            line.push(Cell::default());
+        }
+    }
+
+    fn set_alternate_screen(&mut self, enabled: bool) {
+        if enabled && self.alternate.is_none() {
+            let lines = std::mem::replace(
+                &mut self.lines,
+                vec![Self::blank_line(self.cols, CellStyle::default()); self.rows],
+            );
+            self.alternate = Some(SavedScreen {
+                lines,
+                cursor: self.cursor,
+            });
+        } else if !enabled {
+            if let Some(saved) = self.alternate.take() {
+                self.lines = saved.lines;
+                self.cursor = saved.cursor;
+            }
+        }
+        self.wrap_pending = false;
+    }
+
+    fn set_private_mode(&mut self, mode: u16, enabled: bool) {
+        match mode {
+            1 => self.app_cursor_keys = enabled,
+            7 => self.autowrap = enabled,
+            25 => self.cursor_visible = enabled,
+            47 | 1047 | 1049 => {
+                if mode == 1049 && enabled {
+                    self.saved_cursor = Some(self.cursor);
+                }
+                self.set_alternate_screen(enabled);
+                if mode == 1049 && !enabled {
+                    if let Some(saved) = self.saved_cursor {
+                        self.cursor = saved;
+                    }
+                }
+            }
+            2004 => self.bracketed_paste = enabled,
+            _ => {}
+        }
+    }
+
+    fn reset(&mut self) {
+        let (cols, rows, max_scrollback) = (self.cols, self.rows, self.max_scrollback);
+        let scrollback = std::mem::take(&mut self.scrollback);
+        *self = Self::new(cols, rows);
+        self.scrollback = scrollback;
+        self.max_scrollback = max_scrollback;
+    }
+}
+
+/// First value of each parameter group, defaulting to `default` when 0 or missing
+fn param(params: &[Vec<u16>], index: usize, default: u16) -> u16 {
+    match params.get(index).and_then(|g| g.first()).copied() {
+        Some(0) | None => default,
+        Some(value) => value,
+    }
+}
+
+impl Perform for Screen {
+    fn print(&mut self, c: char) {
+        if self.wrap_pending && self.autowrap {
+            self.cursor.col = 0;
+            self.linefeed();
+        }
+        let (row, col) = (self.cursor.row, self.cursor.col);
+        self.lines[row][col] = Cell {
+            c,
+            style: self.cursor.style,
+        };
+        if col + 1 < self.cols {
+            self.cursor.col += 1;
+        } else {
+            self.wrap_pending = true;
+        }
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' | 0x0b | 0x0c => self.linefeed(),
+            b'\r' => {
+                self.cursor.col = 0;
+                self.wrap_pending = false;
+            }
+            0x08 => {
+                self.cursor.col = self.cursor.col.saturating_sub(1);
+                self.wrap_pending = false;
+            }
+            b'\t' => {
+                let next = (self.cursor.col / 8 + 1) * 8;
+                self.cursor.col = next.min(self.cols - 1);
+            }
+            _ => {}
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], ignore: bool, action: char) {
+        if ignore {
+            return;
+        }
+        let params: Vec<Vec<u16>> = params.iter().map(|group| group.to_vec()).collect();
+        let private = intermediates.first() == Some(&b'?');
+        let n = param(&params, 0, 1) as usize;
+        let (row, col) = (self.cursor.row, self.cursor.col);
+
+        match (action, private) {
+            ('A', false) => self.move_to(row.saturating_sub(n).max(self.scroll_top.min(row)), col),
+            ('B', false) | ('e', false) => {
+                let limit = if row <= self.scroll_bottom { self.scroll_bottom } else { self.rows - 1 };
+                self.move_to((row + n).min(limit), col)
+            }
+            ('C', false) | ('a', false) => self.move_to(row, col + n),
+            ('D', false) => self.move_to(row, col.saturating_sub(n)),
+            ('E', false) => self.move_to(row + n, 0),
+            ('F', false) => self.move_to(row.saturating_sub(n), 0),
+            ('G', false) | ('`', false) => self.move_to(row, n - 1),
+            ('d', false) => self.move_to(n - 1, col),
+            ('H', false) | ('f', false) => {
+                let target_row = param(&params, 0, 1) as usize - 1;
+                let target_col = param(&params, 1, 1) as usize - 1;
+                self.move_to(target_row, target_col);
+            }
+            ('J', false) => self.erase_in_display(params.first().and_then(|g| g.first()).copied().unwrap_or(0)),
+            ('K', false) => self.erase_in_line(params.first().and_then(|g| g.first()).copied().unwrap_or(0)),
+            ('L', false) => self.insert_lines(n),
+            ('M', false) => self.delete_lines(n),
+            ('@', false) => self.insert_chars(n),
+            ('P', false) => self.delete_chars(n),
+            ('X', false) => self.erase_cells(row, col, col + n),
+            ('S', false) => self.scroll_up(n),
+            ('T', false) => self.scroll_down(n),
+            ('m', false) => self.cursor.style.apply_sgr(&params),
+            ('r', false) => {
+                let top = param(&params, 0, 1) as usize - 1;
+                let bottom = (param(&params, 1, self.rows as u16) as usize).min(self.rows) - 1;
+                if top < bottom {
+                    self.scroll_top = top;
+                    self.scroll_bottom = bottom;
+                    self.move_to(0, 0);
+                }
+            }
+            ('h', true) | ('l', true) => {
+                for group in &params {
+                    if let Some(mode) = group.first() {
+                        self.set_private_mode(*mode, action == 'h');
+                    }
+                }
+            }
+            ('n', false) => match param(&params, 0, 0) {
+                5 => self.responses.extend_from_slice(b"\x1b[0n"),
+                6 => self
+                    .responses
+                    .extend_from_slice(format!("\x1b[{};{}R", row + 1, col + 1).as_bytes()),
+                _ => {}
+            },
+            ('c', false) if param(&params, 0, 0) == 0 => {
+                // Identify as a VT220-ish terminal
+                self.responses.extend_from_slice(b"\x1b[?62;22c");
+            }
+            ('s', false) => self.saved_cursor = Some(self.cursor),
+            ('u', false) => {
+                if let Some(saved) = self.saved_cursor {
+                    self.cursor = saved;
+                    self.wrap_pending = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], ignore: bool, byte: u8) {
+        if ignore || !intermediates.is_empty() {
+            return;
+        }
+        match byte {
+            b'7' => self.saved_cursor = Some(self.cursor),
+            b'8' => {
+                if let Some(saved) = self.saved_cursor {
+                    self.cursor = saved;
+                    self.wrap_pending = false;
+                }
+            }
+            b'D' => self.linefeed(),
+            b'E' => {
+                self.cursor.col = 0;
+                self.linefeed();
+            }
+            b'M' => self.reverse_index(),
+            b'c' => self.reset(),
+            _ => {}
+        }
+    }
+
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        // OSC 0 / OSC 2: window title
+        if let [kind, title, ..] = params {
+            if *kind == b"0" || *kind == b"2" {
+                self.title = Some(String::from_utf8_lossy(title).into_owned());
+            }
+        }
+    }
+}
+
+/// Parser plus screen state for one terminal session
+pub struct Emulator {
+    parser: Parser,
+    screen: Screen,
+}
+
+impl Emulator {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            parser: Parser::new(),
+            screen: Screen::new(cols, rows),
+        }
+    }
+
+    /// Feed PTY output
+    pub fn advance(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.parser.advance(&mut self.screen, *byte);
+        }
+    }
+
+    pub fn resize(&mut self, cols: usize, rows: usize) {
+        self.screen.resize(cols, rows);
+    }
+
+    pub fn cols(&self) -> usize {
+        self.screen.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.screen.rows
+    }
+
+    /// Lines above the screen
+    pub fn scrollback_len(&self) -> usize {
+        self.screen.scrollback.len()
+    }
+
+    /// Line by absolute index (0 is the oldest scrollback line)
+    pub fn line(&self, index: usize) -> Option<&Line> {
+        let scrollback = self.screen.scrollback.len();
+        if index < scrollback {
+            self.screen.scrollback.get(index)
+        } else {
+            self.screen.lines.get(index - scrollback)
+        }
+    }
+
+    /// Cursor as (absolute line, column), if it should be drawn
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        self.screen.cursor_visible.then(|| {
+            (
+                self.screen.scrollback.len() + self.screen.cursor.row,
+                self.screen.cursor.col,
+            )
+        })
+    }
+
+    pub fn app_cursor_keys(&self) -> bool {
+        self.screen.app_cursor_keys
+    }
+
+    pub fn bracketed_paste(&self) -> bool {
+        self.screen.bracketed_paste
+    }
+
+    pub fn is_alternate_screen(&self) -> bool {
+        self.screen.alternate.is_some()
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.screen.title.as_deref()
+    }
+
+    /// Replies to device queries that must be written back to the PTY
+    pub fn take_responses(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.screen.responses)
+    }
+
+    /// Text between two (line, column) positions, inclusive, trailing blanks trimmed per line
+    pub fn text_range(&self, start: (usize, usize), end: (usize, usize)) -> String {
+        let (start, end) = if start <= end { (start, end) } else { (end, start) };
+        let mut text = String::new();
+        for index in start.0..=end.0 {
+            let Some(line) = self.line(index) else {
+                break;
+            };
+            let from = if index == start.0 { start.1 } else { 0 };
+            let to = if index == end.0 { (end.1 + 1).min(line.len()) } else { line.len() };
+            let chunk: String = line
+                .iter()
+                .take(to)
+                .skip(from)
+                .map(|cell| cell.c)
+                .collect();
+            text.push_str(chunk.trim_end());
+            if index != end.0 {
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,115 @@
+use gpui::Keystroke;
+
+use super::emulator::{Emulator, TermColor};
+use super::keystroke_bytes;
+
+fn line_text(emulator: &Emulator, index: usize) -> String {
+    emulator
+        .line(index)
+        .map(|line| line.iter().map(|cell| cell.c).collect::<String>())
+        .unwrap_or_default()
+        .trim_end()
+        .to_string()
+}
+
+fn screen_line(emulator: &Emulator, row: usize) -> String {
+    line_text(emulator, emulator.scrollback_len() + row)
+}
+
+fn key(spec: &str) -> Vec<u8> {
+    let keystroke = Keystroke::parse(spec).unwrap();
+    keystroke_bytes(&keystroke, false).unwrap_or_default()
+}
+
+#[test]
+fn prints_wraps_and_scrolls_into_scrollback() {
+    let mut term = Emulator::new(5, 2);
+    term.advance(b"hello world\r\nbye");
+
+    assert_eq!(term.scrollback_len(), 2);
+    assert_eq!(line_text(&term, 0), "hello");
+    assert_eq!(line_text(&term, 1), " worl");
+    assert_eq!(screen_line(&term, 0), "d");
+    assert_eq!(screen_line(&term, 1), "bye");
+}
+
+#[test]
+fn cursor_movement_and_erase() {
+    let mut term = Emulator::new(10, 3);
+    term.advance(b"abcdefghij\x1b[1;4H\x1b[K\x1b[3;2HX");
+
+    assert_eq!(screen_line(&term, 0), "abc");
+    assert_eq!(screen_line(&term, 2), " X");
+    assert_eq!(term.cursor(), Some((2, 2)));
+
+    term.advance(b"\x1b[2J");
+    assert_eq!(screen_line(&term, 0), "");
+}
+
+#[test]
+fn sgr_sets_colors_and_attributes() {
+    let mut term = Emulator::new(10, 1);
+    term.advance(b"\x1b[1;31ma\x1b[38;5;200mb\x1b[48:2::1:2:3mc\x1b[0md");
+
+    let line = term.line(0).unwrap();
+    assert!(line[0].style.bold);
+    assert_eq!(line[0].style.fg, TermColor::Indexed(1));
+    assert_eq!(line[1].style.fg, TermColor::Indexed(200));
+    assert_eq!(line[2].style.bg, TermColor::Rgb(1, 2, 3));
+    assert_eq!(line[3].style, Default::default());
+}
+
+#[test]
+fn alternate_screen_restores_main_screen() {
+    let mut term = Emulator::new(10, 2);
+    term.advance(b"shell$ ");
+    term.advance(b"\x1b[?1049h\x1b[Hvim");
+    assert!(term.is_alternate_screen());
+    assert_eq!(screen_line(&term, 0), "vim");
+
+    term.advance(b"\x1b[?1049l");
+    assert!(!term.is_alternate_screen());
+    assert_eq!(screen_line(&term, 0), "shell$");
+    assert_eq!(term.cursor(), Some((0, 7)));
+}
+
+#[test]
+fn scroll_region_keeps_lines_outside() {
+    let mut term = Emulator::new(4, 4);
+    term.advance(b"top\r\n1\r\n2\r\nbot\x1b[2;3r\x1b[3;1H\n");
+
+    assert_eq!(screen_line(&term, 0), "top");
+    assert_eq!(screen_line(&term, 1), "2");
+    assert_eq!(screen_line(&term, 2), "");
+    assert_eq!(screen_line(&term, 3), "bot");
+    assert_eq!(term.scrollback_len(), 0);
+}
+
+#[test]
+fn answers_cursor_position_report() {
+    let mut term = Emulator::new(10, 5);
+    term.advance(b"\x1b[3;4H\x1b[6n");
+    assert_eq!(term.take_responses(), b"\x1b[3;4R");
+    assert!(term.take_responses().is_empty());
+}
+
+#[test]
+fn selection_text_spans_lines() {
+    let mut term = Emulator::new(8, 2);
+    term.advance(b"one two\r\nthree");
+    assert_eq!(term.text_range((0, 4), (1, 2)), "two\nthr");
+}
+
+#[test]
+fn maps_keys_to_bytes() {
+    assert_eq!(key("enter"), b"\r");
+    assert_eq!(key("ctrl-c"), vec![0x03]);
+    assert_eq!(key("up"), b"\x1b[A");
+    assert_eq!(key("shift-up"), b"\x1b[1;2A");
+    assert_eq!(key("alt-b"), b"\x1bb");
+    assert_eq!(key("f5"), b"\x1b[15~");
+    assert_eq!(
+        keystroke_bytes(&Keystroke::parse("up").unwrap(), true),
+        Some(b"\x1bOA".to_vec())
+    );
+}
@@ -3,6 +3,8 @@ mod assets;
 mod components;
 mod models;
 mod services;
+#[cfg(test)]
+mod test_support;
 mod theme;
 mod tokio_bridge;
 mod views;
@@ -10,7 +12,7 @@ mod views;
 use gpui::*;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
-use app::{ArcBoxApp, OpenSettings, Quit, open_settings};
+use app::{ArcBoxApp, OpenDaemonLogs, OpenSettings, Quit, open_daemon_logs, open_settings};
 use assets::AppAssets;
 use components::register_text_input_bindings;
 
@@ -99,13 +101,26 @@ fn main() {
         // Register text input bindings
         register_text_input_bindings(cx);
 
+        // Load persisted settings (daemon contexts, etc.)
+        services::SettingsStore::init(cx);
+
+        // Shared notification center (toasts + history)
+        services::NotificationCenter::init(cx);
+
+        // Local daemon output (ring buffer + rotating log file)
+        services::DaemonLogs::init(cx);
+
         // Register global actions
         cx.on_action(|_: &OpenSettings, cx| {
             open_settings(cx);
         });
 
+        cx.on_action(|_: &OpenDaemonLogs, cx| {
+            open_daemon_logs(cx);
+        });
+
         cx.on_action(|_: &Quit, cx| {
-            cx.quit();
+            app::quit(cx);
         });
 
         // Bind Cmd+, to open settings
@@ -143,6 +158,8 @@ fn main() {
                 items: vec![
                     MenuItem::action("Minimize", gpui::NoAction),
                     MenuItem::action("Zoom", gpui::NoAction),
+                    MenuItem::separator(),
+                    MenuItem::action("Daemon Logs", OpenDaemonLogs),
                 ],
             },
         ]);
@@ -0,0 +1,33 @@
+//! Byte counts for display.
+
+/// Human-readable byte count, e.g. "12.3 MB"
+///
+/// Binary units (1 KB = 1024 bytes), the same ones `parse_byte_size` reads,
+/// so a `512m` memory limit shows as "512.0 MB".
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_binary_units() {
+        assert_eq!(format_bytes(1023.0), "1023 B");
+        assert_eq!(format_bytes(1536.0), "1.5 KB");
+        assert_eq!(format_bytes((512u64 << 20) as f64), "512.0 MB");
+        assert_eq!(format_bytes((3u64 << 50) as f64), "3072.0 TB");
+    }
+}
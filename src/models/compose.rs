@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+
+use crate::models::ContainerViewModel;
+
+/// Labels Docker Compose puts on the containers it creates
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+pub const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+/// Replica number within the service, from 1
+pub const COMPOSE_NUMBER_LABEL: &str = "com.docker.compose.container-number";
+/// Comma-separated compose files the project was started from
+pub const COMPOSE_CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+pub const COMPOSE_WORKING_DIR_LABEL: &str = "com.docker.compose.project.working_dir";
+
+/// How much of a compose project is running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectStatus {
+    Running,
+    /// Some containers running, some not
+    Partial,
+    Stopped,
+}
+
+impl ProjectStatus {
+    pub fn of<'a>(containers: impl IntoIterator<Item = &'a ContainerViewModel>) -> Self {
+        let (mut running, mut total) = (0, 0);
+        for container in containers {
+            total += 1;
+            if container.is_running() {
+                running += 1;
+            }
+        }
+        match running {
+            0 => ProjectStatus::Stopped,
+            n if n == total => ProjectStatus::Running,
+            _ => ProjectStatus::Partial,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProjectStatus::Running => "Running",
+            ProjectStatus::Partial => "Partially Running",
+            ProjectStatus::Stopped => "Stopped",
+        }
+    }
+}
+
+impl ContainerViewModel {
+    /// Compose service this container belongs to
+    pub fn compose_service(&self) -> Option<&str> {
+        self.labels.get(COMPOSE_SERVICE_LABEL).map(String::as_str)
+    }
+
+    /// Replica number within its compose service
+    pub fn compose_number(&self) -> Option<u32> {
+        self.labels.get(COMPOSE_NUMBER_LABEL)?.parse().ok()
+    }
+
+    /// Compose files the project was started from, relative ones resolved
+    /// against the project's working directory
+    pub fn compose_config_files(&self) -> Vec<PathBuf> {
+        let Some(files) = self.labels.get(COMPOSE_CONFIG_FILES_LABEL) else {
+            return Vec::new();
+        };
+        let working_dir = self.labels.get(COMPOSE_WORKING_DIR_LABEL).map(Path::new);
+        files
+            .split(',')
+            .map(str::trim)
+            .filter(|file| !file.is_empty())
+            .map(|file| match working_dir {
+                Some(dir) if Path::new(file).is_relative() => dir.join(file),
+                _ => PathBuf::from(file),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::models::ContainerState;
+
+    fn container(state: ContainerState, labels: &[(&str, &str)]) -> ContainerViewModel {
+        ContainerViewModel {
+            id: "abc".to_string(),
+            name: "shop-web-1".to_string(),
+            image: "nginx".to_string(),
+            state,
+            ports: Vec::new(),
+            created_at: Utc::now(),
+            compose_project: Some("shop".to_string()),
+            labels: labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>(),
+            cpu_percent: 0.0,
+            memory_mb: 0.0,
+            memory_limit_mb: 0.0,
+        }
+    }
+
+    #[test]
+    fn rolls_up_project_status() {
+        let running = container(ContainerState::Running, &[]);
+        let paused = container(ContainerState::Paused, &[]);
+        let stopped = container(ContainerState::Stopped, &[]);
+        assert_eq!(ProjectStatus::of([&running, &running]), ProjectStatus::Running);
+        assert_eq!(ProjectStatus::of([&running, &paused]), ProjectStatus::Partial);
+        assert_eq!(ProjectStatus::of([&stopped, &paused]), ProjectStatus::Stopped);
+        assert_eq!(ProjectStatus::of(std::iter::empty()), ProjectStatus::Stopped);
+    }
+
+    #[test]
+    fn reads_compose_labels() {
+        let web = container(
+            ContainerState::Running,
+            &[
+                (COMPOSE_SERVICE_LABEL, "web"),
+                (COMPOSE_NUMBER_LABEL, "2"),
+                (COMPOSE_WORKING_DIR_LABEL, "/src/shop"),
+                (COMPOSE_CONFIG_FILES_LABEL, "/src/shop/compose.yaml, compose.override.yaml"),
+            ],
+        );
+        assert_eq!(web.compose_service(), Some("web"));
+        assert_eq!(web.compose_number(), Some(2));
+        assert_eq!(
+            web.compose_config_files(),
+            [PathBuf::from("/src/shop/compose.yaml"), PathBuf::from("/src/shop/compose.override.yaml")]
+        );
+        assert!(container(ContainerState::Running, &[]).compose_config_files().is_empty());
+    }
+}
@@ -4,6 +4,8 @@ use arcbox_api::generated::{PortBinding as ProtoPortBinding, ContainerSummary};
 use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::models::COMPOSE_PROJECT_LABEL;
+
 /// Container state representation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ContainerState {
@@ -19,6 +21,17 @@ impl ContainerState {
         matches!(self, ContainerState::Running)
     }
 
+    /// Parse a Docker state string ("running", "exited", ...)
+    pub fn from_docker_state(state: &str) -> Self {
+        match state {
+            "running" => ContainerState::Running,
+            "paused" => ContainerState::Paused,
+            "restarting" => ContainerState::Restarting,
+            "dead" => ContainerState::Dead,
+            _ => ContainerState::Stopped,
+        }
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             ContainerState::Running => "Running",
@@ -30,6 +43,47 @@ impl ContainerState {
     }
 }
 
+/// Lifecycle action on a container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerAction {
+    Start,
+    Stop,
+    Pause,
+    Unpause,
+    Restart,
+    Kill,
+    Rename,
+    Remove,
+}
+
+impl ContainerAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContainerAction::Start => "Start",
+            ContainerAction::Stop => "Stop",
+            ContainerAction::Pause => "Pause",
+            ContainerAction::Unpause => "Resume",
+            ContainerAction::Restart => "Restart",
+            ContainerAction::Kill => "Kill",
+            ContainerAction::Rename => "Rename",
+            ContainerAction::Remove => "Remove",
+        }
+    }
+
+    /// Whether the daemon accepts this action in the given state
+    pub fn is_available(&self, state: ContainerState) -> bool {
+        use ContainerState::*;
+        match self {
+            ContainerAction::Start => matches!(state, Stopped | Dead),
+            ContainerAction::Stop | ContainerAction::Kill => matches!(state, Running | Paused | Restarting),
+            ContainerAction::Pause => matches!(state, Running),
+            ContainerAction::Unpause => matches!(state, Paused),
+            ContainerAction::Restart => matches!(state, Running | Paused | Stopped),
+            ContainerAction::Rename | ContainerAction::Remove => true,
+        }
+    }
+}
+
 /// Port mapping for container
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortMapping {
@@ -101,13 +155,7 @@ impl From<&ProtoPortBinding> for PortMapping {
 impl From<ContainerSummary> for ContainerViewModel {
     fn from(summary: ContainerSummary) -> Self {
         // Parse state from string
-        let state = match summary.state.as_str() {
-            "running" => ContainerState::Running,
-            "paused" => ContainerState::Paused,
-            "restarting" => ContainerState::Restarting,
-            "dead" => ContainerState::Dead,
-            _ => ContainerState::Stopped,
-        };
+        let state = ContainerState::from_docker_state(&summary.state);
 
         // Extract container name (remove leading /)
         let name = if summary.name.is_empty() {
@@ -120,7 +168,7 @@ impl From<ContainerSummary> for ContainerViewModel {
         let created_at = Utc.timestamp_opt(summary.created, 0).single().unwrap_or_else(Utc::now);
 
         // Check for compose project label
-        let compose_project = summary.labels.get("com.docker.compose.project").cloned();
+        let compose_project = summary.labels.get(COMPOSE_PROJECT_LABEL).cloned();
 
         Self {
             id: summary.id,
@@ -131,7 +179,7 @@ impl From<ContainerSummary> for ContainerViewModel {
             created_at,
             compose_project,
             labels: summary.labels,
-            // Resource usage requires separate stats call, default to 0
+            // Filled in from the stats stream while the container runs
             cpu_percent: 0.0,
             memory_mb: 0.0,
             memory_limit_mb: 0.0,
@@ -0,0 +1,267 @@
+use std::cmp::Ordering;
+
+use crate::models::{ContainerState, ContainerViewModel};
+
+/// Sort order for the container list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContainerSort {
+    #[default]
+    Name,
+    Created,
+    State,
+    Cpu,
+    Memory,
+}
+
+impl ContainerSort {
+    pub const ALL: [ContainerSort; 5] = [
+        ContainerSort::Name,
+        ContainerSort::Created,
+        ContainerSort::State,
+        ContainerSort::Cpu,
+        ContainerSort::Memory,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContainerSort::Name => "Name",
+            ContainerSort::Created => "Created",
+            ContainerSort::State => "State",
+            ContainerSort::Cpu => "CPU",
+            ContainerSort::Memory => "Memory",
+        }
+    }
+
+    /// Stable name for settings
+    pub fn key(&self) -> &'static str {
+        match self {
+            ContainerSort::Name => "name",
+            ContainerSort::Created => "created",
+            ContainerSort::State => "state",
+            ContainerSort::Cpu => "cpu",
+            ContainerSort::Memory => "memory",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sort| sort.key() == key)
+    }
+
+    /// Compare in this order's natural direction: names A to Z, newest first,
+    /// running first, busiest first. Ties fall back to the name.
+    pub fn compare(&self, a: &ContainerViewModel, b: &ContainerViewModel) -> Ordering {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let ordering = match self {
+            ContainerSort::Name => Ordering::Equal,
+            ContainerSort::Created => b.created_at.cmp(&a.created_at),
+            ContainerSort::State => state_rank(a.state).cmp(&state_rank(b.state)),
+            ContainerSort::Cpu => b.cpu_percent.total_cmp(&a.cpu_percent),
+            ContainerSort::Memory => b.memory_mb.total_cmp(&a.memory_mb),
+        };
+        ordering.then_with(by_name)
+    }
+}
+
+/// Live states before stopped ones
+fn state_rank(state: ContainerState) -> u8 {
+    match state {
+        ContainerState::Running => 0,
+        ContainerState::Restarting => 1,
+        ContainerState::Paused => 2,
+        ContainerState::Stopped => 3,
+        ContainerState::Dead => 4,
+    }
+}
+
+/// A parsed container filter such as `web status:running port:8080`
+///
+/// Free text matches the name, image or ID. `status:`, `label:`, `project:`
+/// and `port:` narrow further. Every part must match, except that repeating
+/// a key (`status:running status:paused`) accepts any of its values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerFilter {
+    terms: Vec<String>,
+    statuses: Vec<String>,
+    /// Key, and the value it must have if given
+    labels: Vec<(String, Option<String>)>,
+    projects: Vec<String>,
+    ports: Vec<u16>,
+}
+
+impl ContainerFilter {
+    pub fn parse(text: &str) -> Self {
+        let mut filter = Self::default();
+        for word in words(text) {
+            let Some((key, value)) = word.split_once(':') else {
+                filter.terms.push(word.to_lowercase());
+                continue;
+            };
+            let key = key.to_lowercase();
+            if value.is_empty() && KEYS.contains(&key.as_str()) {
+                // Still being typed
+                continue;
+            }
+            match key.as_str() {
+                "status" | "state" => filter.statuses.push(value.to_lowercase()),
+                "label" => filter.labels.push(match value.split_once('=') {
+                    Some((key, value)) => (key.to_string(), Some(value.to_string())),
+                    None => (value.to_string(), None),
+                }),
+                "project" => filter.projects.push(value.to_lowercase()),
+                "port" => match value.parse() {
+                    Ok(port) => filter.ports.push(port),
+                    Err(_) => filter.terms.push(word.to_lowercase()),
+                },
+                _ => filter.terms.push(word.to_lowercase()),
+            }
+        }
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn matches(&self, container: &ContainerViewModel) -> bool {
+        let name = container.name.to_lowercase();
+        let image = container.image.to_lowercase();
+        let text = |term: &String| name.contains(term) || image.contains(term) || container.id.starts_with(term);
+
+        let state = container.state.label().to_lowercase();
+        // Docker says "exited" where we say "stopped"
+        let status = |wanted: &String| *wanted == state || (wanted == "exited" && container.state == ContainerState::Stopped);
+
+        let label = |(key, value): &(String, Option<String>)| match (container.labels.get(key), value) {
+            (Some(actual), Some(value)) => actual == value,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+
+        let project = |wanted: &String| {
+            container
+                .compose_project
+                .as_ref()
+                .is_some_and(|project| project.to_lowercase() == *wanted)
+        };
+
+        let port = |wanted: &u16| {
+            container
+                .ports
+                .iter()
+                .any(|p| p.host_port == *wanted || p.container_port == *wanted)
+        };
+
+        self.terms.iter().all(text)
+            && any_or_empty(&self.statuses, status)
+            && self.labels.iter().all(label)
+            && any_or_empty(&self.projects, project)
+            && any_or_empty(&self.ports, port)
+    }
+}
+
+/// Keys with special meaning
+const KEYS: [&str; 5] = ["status", "state", "label", "project", "port"];
+
+/// Split on whitespace, keeping "quoted spans" together (label:"team=data eng")
+///
+/// An unclosed quote runs to the end, so half-typed filters still work.
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn any_or_empty<T>(values: &[T], matches: impl Fn(&T) -> bool) -> bool {
+    values.is_empty() || values.iter().any(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::models::PortMapping;
+
+    fn container(name: &str, state: ContainerState, created: i64) -> ContainerViewModel {
+        ContainerViewModel {
+            id: format!("{}0123456789", name),
+            name: name.to_string(),
+            image: "nginx:latest".to_string(),
+            state,
+            ports: Vec::new(),
+            created_at: Utc.timestamp_opt(created, 0).unwrap(),
+            compose_project: None,
+            labels: HashMap::new(),
+            cpu_percent: 0.0,
+            memory_mb: 0.0,
+            memory_limit_mb: 0.0,
+        }
+    }
+
+    #[test]
+    fn filters_by_text_and_keys() {
+        let mut web = container("web", ContainerState::Running, 0);
+        web.compose_project = Some("Shop".to_string());
+        web.labels.insert("tier".to_string(), "front".to_string());
+        web.ports.push(PortMapping {
+            host_port: 8080,
+            container_port: 80,
+            protocol: "tcp".to_string(),
+        });
+        let db = container("db", ContainerState::Stopped, 0);
+
+        let matches = |text: &str| -> Vec<&str> {
+            let filter = ContainerFilter::parse(text);
+            [&web, &db].into_iter().filter(|c| filter.matches(c)).map(|c| c.name.as_str()).collect()
+        };
+        assert_eq!(matches(""), ["web", "db"]);
+        assert_eq!(matches("NGINX we"), ["web"]);
+        assert_eq!(matches("db0123"), ["db"]);
+        assert_eq!(matches("status:exited"), ["db"]);
+        assert_eq!(matches("status:running status:stopped"), ["web", "db"]);
+        assert_eq!(matches("label:tier"), ["web"]);
+        assert_eq!(matches("label:tier=back"), Vec::<&str>::new());
+        assert_eq!(matches("project:shop port:8080"), ["web"]);
+        assert_eq!(matches("port:80 status:stopped"), Vec::<&str>::new());
+        // Half-typed keys and quotes don't hide everything
+        assert_eq!(matches("status:"), ["web", "db"]);
+        assert_eq!(matches("\"we"), ["web"]);
+        assert_eq!(matches("label:\"tier=front\""), ["web"]);
+    }
+
+    #[test]
+    fn sorts_in_natural_direction() {
+        let mut a = container("alpha", ContainerState::Stopped, 100);
+        let mut b = container("Beta", ContainerState::Running, 200);
+        a.cpu_percent = 50.0;
+        b.memory_mb = 64.0;
+        let sorted = |sort: ContainerSort| {
+            let mut list = [&a, &b];
+            list.sort_by(|x, y| sort.compare(x, y));
+            list.map(|c| c.name.as_str())
+        };
+        assert_eq!(sorted(ContainerSort::Name), ["alpha", "Beta"]);
+        assert_eq!(sorted(ContainerSort::Created), ["Beta", "alpha"]);
+        assert_eq!(sorted(ContainerSort::State), ["Beta", "alpha"]);
+        assert_eq!(sorted(ContainerSort::Cpu), ["alpha", "Beta"]);
+        assert_eq!(sorted(ContainerSort::Memory), ["Beta", "alpha"]);
+        assert_eq!(ContainerSort::from_key("cpu"), Some(ContainerSort::Cpu));
+    }
+}
@@ -2,6 +2,8 @@ use arcbox_api::generated::ImageSummary;
 use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::models::format_bytes;
+
 /// Image view model for UI display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageViewModel {
@@ -27,12 +29,7 @@ impl ImageViewModel {
 
     /// Display size in human readable format
     pub fn size_display(&self) -> String {
-        let mb = self.size_bytes as f64 / 1_000_000.0;
-        if mb >= 1000.0 {
-            format!("{:.1} GB", mb / 1000.0)
-        } else {
-            format!("{:.0} MB", mb)
-        }
+        format_bytes(self.size_bytes as f64)
     }
 
     /// Display relative time since creation
@@ -1,10 +1,16 @@
+mod byte_size;
+mod compose;
 mod container;
+mod container_filter;
 mod image;
 mod machine;
 mod network;
 mod volume;
 
+pub use byte_size::*;
+pub use compose::*;
 pub use container::*;
+pub use container_filter::*;
 pub use image::*;
 pub use machine::*;
 pub use network::*;
@@ -1,4 +1,5 @@
-use chrono::{DateTime, Utc};
+use arcbox_api::generated::NetworkSummary;
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Network view model for UI display
@@ -69,3 +70,24 @@ impl NetworkViewModel {
         matches!(self.name.as_str(), "bridge" | "host" | "none")
     }
 }
+
+impl From<NetworkSummary> for NetworkViewModel {
+    fn from(summary: NetworkSummary) -> Self {
+        // Parse created timestamp (Unix seconds)
+        let created_at = Utc
+            .timestamp_opt(summary.created, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        Self {
+            id: summary.id,
+            name: summary.name,
+            driver: summary.driver,
+            scope: summary.scope,
+            created_at,
+            internal: summary.internal,
+            attachable: summary.attachable,
+            container_count: summary.containers.len(),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::models::format_bytes;
+
 /// Volume view model for UI display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeViewModel {
@@ -17,16 +19,7 @@ impl VolumeViewModel {
     /// Display size in human readable format
     pub fn size_display(&self) -> String {
         match self.size_bytes {
-            Some(bytes) => {
-                let mb = bytes as f64 / 1_000_000.0;
-                if mb >= 1000.0 {
-                    format!("{:.1} GB", mb / 1000.0)
-                } else if mb >= 1.0 {
-                    format!("{:.0} MB", mb)
-                } else {
-                    format!("{:.0} KB", bytes as f64 / 1000.0)
-                }
-            }
+            Some(bytes) => format_bytes(bytes as f64),
             None => "N/A".to_string(),
         }
     }
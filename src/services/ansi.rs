@@ -0,0 +1,61 @@
+//! Plain text of terminal output.
+//!
+//! Daemon and container output often carries color codes even when it isn't
+//! written to a terminal. [`strip_ansi`] drops every escape sequence, for
+//! level detection and the app's own log; views that render the colors parse
+//! them with `AnsiText` instead.
+
+use vte::{Parser, Perform};
+
+/// `input` without escape sequences
+///
+/// A carriage return starts the line over, the way a progress bar redraws
+/// itself, so only what a terminal would finally show is kept.
+pub fn strip_ansi(input: &str) -> String {
+    // Most lines have no escapes at all
+    if !input.contains('\x1b') && !input.contains('\r') {
+        return input.to_string();
+    }
+
+    let mut stripper = Stripper::default();
+    let mut parser = Parser::new();
+    for byte in input.bytes() {
+        parser.advance(&mut stripper, byte);
+    }
+    stripper.text
+}
+
+#[derive(Default)]
+struct Stripper {
+    text: String,
+    carriage_return: bool,
+}
+
+impl Perform for Stripper {
+    fn print(&mut self, c: char) {
+        if std::mem::take(&mut self.carriage_return) {
+            self.text.clear();
+        }
+        self.text.push(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\t' => self.print('\t'),
+            b'\r' => self.carriage_return = true,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_escape_sequences() {
+        assert_eq!(strip_ansi("\x1b[2K\x1b[1;31mERROR\x1b[0m done\x1b]0;title\x07"), "ERROR done");
+        assert_eq!(strip_ansi("10%\r\x1b[K50%"), "50%");
+        assert_eq!(strip_ansi("no escapes"), "no escapes");
+    }
+}
@@ -0,0 +1,1133 @@
+//! Compose files.
+//!
+//! Reads the parts of the Compose specification the desktop can run itself
+//! (v2 and v3 files alike): services, networks, volumes, env files,
+//! `depends_on` and profiles. Each service becomes a `ContainerSpec` with
+//! the labels Docker Compose would put on it, so projects started here show
+//! up in the container list like any other compose project.
+//!
+//! `${VAR}` references are filled in from the shell environment and the
+//! project's `.env` file. Settings a service uses that have no equivalent
+//! here (`build`, `healthcheck`, ...) are listed as warnings rather than
+//! rejecting the whole file.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_yaml::{Mapping, Value};
+
+use crate::models::{
+    COMPOSE_CONFIG_FILES_LABEL, COMPOSE_NUMBER_LABEL, COMPOSE_PROJECT_LABEL, COMPOSE_SERVICE_LABEL,
+    COMPOSE_WORKING_DIR_LABEL,
+};
+use crate::services::{
+    parse_byte_size, parse_env_file, split_shell_words, ContainerSpec, MountKind, MountSpec, PortMapping,
+};
+
+/// File names looked for when a project folder is opened, in order
+pub const COMPOSE_FILE_NAMES: [&str; 4] = [
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// Network services join when they don't list any
+const DEFAULT_NETWORK: &str = "default";
+
+/// A parsed compose file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeProject {
+    pub name: String,
+    /// The compose file
+    pub file: PathBuf,
+    /// Where relative paths in the file point from
+    pub working_dir: PathBuf,
+    pub services: BTreeMap<String, ComposeService>,
+    /// Networks by their key in the file, including the implicit "default"
+    pub networks: BTreeMap<String, ComposeNetwork>,
+    /// Settings that were ignored, e.g. "web: build is not supported"
+    pub warnings: Vec<String>,
+}
+
+/// One service, ready to create
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeService {
+    pub name: String,
+    /// Networks and named volumes already carry their daemon-side names
+    pub spec: ContainerSpec,
+    pub depends_on: Vec<Dependency>,
+    /// Only started when one of these is enabled (empty means always)
+    pub profiles: Vec<String>,
+    pub pull_policy: PullPolicy,
+    /// Keys into `ComposeProject::networks`
+    pub networks: Vec<String>,
+}
+
+/// A service another one waits for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub service: String,
+    pub condition: DependencyCondition,
+}
+
+/// What "ready" means for a dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DependencyCondition {
+    #[default]
+    Started,
+    /// Its health check passes
+    Healthy,
+    /// It ran and exited with status 0
+    CompletedSuccessfully,
+}
+
+/// When `up` pulls a service's image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PullPolicy {
+    /// Only when the daemon doesn't have it
+    #[default]
+    Missing,
+    Always,
+    Never,
+}
+
+/// A network the project uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeNetwork {
+    /// Name on the daemon
+    pub name: String,
+    pub driver: Option<String>,
+    pub internal: bool,
+    /// Created outside the project; must already exist and is never removed
+    pub external: bool,
+    pub labels: Vec<(String, String)>,
+}
+
+impl ComposeProject {
+    /// Load a compose file, or the first of `COMPOSE_FILE_NAMES` in a folder
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = if path.is_dir() {
+            COMPOSE_FILE_NAMES
+                .iter()
+                .map(|name| path.join(name))
+                .find(|file| file.is_file())
+                .ok_or_else(|| format!("No compose file in {}", path.display()))?
+        } else {
+            path.to_path_buf()
+        };
+        let text = fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+
+        // The shell wins over the project's .env file
+        let working_dir = file.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let mut env: HashMap<String, String> = match fs::read_to_string(working_dir.join(".env")) {
+            Ok(contents) => parse_env_file(&contents).into_iter().collect(),
+            Err(_) => HashMap::new(),
+        };
+        env.extend(std::env::vars());
+
+        Self::parse(&text, &file, &env)
+    }
+
+    /// Parse compose YAML read from `file`, interpolating variables from `env`
+    pub fn parse(text: &str, file: &Path, env: &HashMap<String, String>) -> Result<Self, String> {
+        let mut value: Value = serde_yaml::from_str(text).map_err(|e| format!("Invalid YAML: {}", e))?;
+        value.apply_merge().map_err(|e| format!("Invalid YAML: {}", e))?;
+        let mut warnings = Vec::new();
+        interpolate_value(&mut value, env, &mut warnings)?;
+
+        let Value::Mapping(root) = value else {
+            return Err("A compose file must be a mapping".to_string());
+        };
+        let working_dir = file.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let name = match root.get("name") {
+            Some(name) => scalar(name, "name")?,
+            None => match env.get("COMPOSE_PROJECT_NAME") {
+                Some(name) => name.clone(),
+                None => working_dir
+                    .file_name()
+                    .map(|dir| dir.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            },
+        };
+        let name = project_name(&name).ok_or_else(|| format!("\"{}\" can't be used as a project name", name))?;
+
+        for key in root.keys() {
+            let key = key.as_str().unwrap_or_default();
+            if !matches!(key, "name" | "version" | "services" | "networks" | "volumes") && !key.starts_with("x-") {
+                warnings.push(format!("{} is not supported", key));
+            }
+        }
+
+        let mut parser = Parser {
+            project: name.clone(),
+            working_dir: working_dir.clone(),
+            env,
+            warnings,
+            networks: BTreeMap::new(),
+            volumes: BTreeMap::new(),
+        };
+        parser.parse_networks(root.get("networks"))?;
+        parser.parse_volumes(root.get("volumes"))?;
+
+        let mut services = BTreeMap::new();
+        for (key, value) in mapping(root.get("services"), "services")? {
+            let service = scalar(key, "service name")?;
+            let parsed = parser
+                .parse_service(&service, value, file)
+                .map_err(|e| format!("{}: {}", service, e))?;
+            services.insert(service, parsed);
+        }
+        if services.is_empty() {
+            return Err("The file defines no services".to_string());
+        }
+
+        for service in services.values() {
+            for dependency in &service.depends_on {
+                if !services.contains_key(&dependency.service) {
+                    return Err(format!(
+                        "{}: depends on undefined service \"{}\"",
+                        service.name, dependency.service
+                    ));
+                }
+            }
+        }
+        if let Some(cycle) = find_cycle(&services) {
+            return Err(format!("Services depend on each other: {}", cycle.join(" → ")));
+        }
+
+        Ok(Self {
+            name,
+            file: file.to_path_buf(),
+            working_dir,
+            services,
+            networks: parser.networks,
+            warnings: parser.warnings,
+        })
+    }
+
+    /// Every profile some service belongs to
+    pub fn profiles(&self) -> BTreeSet<String> {
+        self.services
+            .values()
+            .flat_map(|service| service.profiles.iter().cloned())
+            .collect()
+    }
+
+    /// Services to run with `profiles` enabled, dependencies first
+    ///
+    /// A service outside the enabled profiles still runs when an enabled
+    /// one depends on it.
+    pub fn active_services(&self, profiles: &BTreeSet<String>) -> Vec<String> {
+        let mut active: BTreeSet<&str> = self
+            .services
+            .values()
+            .filter(|service| service.profiles.is_empty() || service.profiles.iter().any(|p| profiles.contains(p)))
+            .map(|service| service.name.as_str())
+            .collect();
+        let mut pending: Vec<&str> = active.iter().copied().collect();
+        while let Some(name) = pending.pop() {
+            for dependency in &self.services[name].depends_on {
+                if active.insert(&dependency.service) {
+                    pending.push(&dependency.service);
+                }
+            }
+        }
+        self.start_order()
+            .into_iter()
+            .filter(|name| active.contains(name.as_str()))
+            .collect()
+    }
+
+    /// All services, each after the ones it depends on (ties by name)
+    pub fn start_order(&self) -> Vec<String> {
+        let mut order: Vec<String> = Vec::new();
+        let mut remaining: BTreeSet<&str> = self.services.keys().map(String::as_str).collect();
+        while !remaining.is_empty() {
+            let ready = remaining
+                .iter()
+                .copied()
+                .find(|name| {
+                    self.services[*name]
+                        .depends_on
+                        .iter()
+                        .all(|dependency| order.contains(&dependency.service))
+                })
+                // Cycles are rejected when parsing
+                .expect("dependency cycle");
+            remaining.remove(ready);
+            order.push(ready.to_string());
+        }
+        order
+    }
+
+    /// Networks `services` attach to
+    pub fn networks_for(&self, services: &[String]) -> Vec<&ComposeNetwork> {
+        let keys: BTreeSet<&String> = services
+            .iter()
+            .filter_map(|name| self.services.get(name))
+            .flat_map(|service| &service.networks)
+            .collect();
+        keys.into_iter().filter_map(|key| self.networks.get(key)).collect()
+    }
+}
+
+/// Lowercase letters, digits, `-` and `_`, starting with a letter or digit
+fn project_name(name: &str) -> Option<String> {
+    let name: String = name
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    name.starts_with(|c: char| c.is_ascii_alphanumeric()).then_some(name)
+}
+
+/// A dependency cycle as service names, first one repeated at the end
+fn find_cycle(services: &BTreeMap<String, ComposeService>) -> Option<Vec<String>> {
+    fn visit<'a>(
+        name: &'a str,
+        services: &'a BTreeMap<String, ComposeService>,
+        path: &mut Vec<&'a str>,
+        done: &mut BTreeSet<&'a str>,
+    ) -> Option<Vec<String>> {
+        if let Some(start) = path.iter().position(|n| *n == name) {
+            let mut cycle: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+            cycle.push(name.to_string());
+            return Some(cycle);
+        }
+        if done.contains(name) {
+            return None;
+        }
+        path.push(name);
+        for dependency in &services[name].depends_on {
+            if let Some(cycle) = visit(&dependency.service, services, path, done) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        done.insert(name);
+        None
+    }
+
+    let mut done = BTreeSet::new();
+    services
+        .keys()
+        .find_map(|name| visit(name, services, &mut Vec::new(), &mut done))
+}
+
+/// Turns the YAML of one file into services, networks and volumes
+struct Parser<'a> {
+    project: String,
+    working_dir: PathBuf,
+    env: &'a HashMap<String, String>,
+    warnings: Vec<String>,
+    networks: BTreeMap<String, ComposeNetwork>,
+    /// Daemon-side names of the declared volumes, by key
+    volumes: BTreeMap<String, String>,
+}
+
+impl Parser<'_> {
+    fn project_labels(&self) -> Vec<(String, String)> {
+        vec![(COMPOSE_PROJECT_LABEL.to_string(), self.project.clone())]
+    }
+
+    /// Name on the daemon: `name:` if set, the key for external resources,
+    /// otherwise prefixed with the project
+    fn resource_name(&self, key: &str, config: &Mapping, external: bool, what: &str) -> Result<String, String> {
+        match config.get("name") {
+            Some(name) => scalar(name, &format!("{} {} name", what, key)),
+            None if external => Ok(key.to_string()),
+            None => Ok(format!("{}_{}", self.project, key)),
+        }
+    }
+
+    fn parse_networks(&mut self, networks: Option<&Value>) -> Result<(), String> {
+        for (key, config) in mapping(networks, "networks")? {
+            let key = scalar(key, "network name")?;
+            let config = optional_mapping(config, &format!("network {}", key))?;
+            let external = external(&config, &format!("network {}", key))?;
+            let name = self.resource_name(&key, &config, external, "network")?;
+            let mut labels = self.project_labels();
+            labels.extend(pairs(config.get("labels"), &format!("network {} labels", key), self.env)?);
+            self.networks.insert(
+                key.clone(),
+                ComposeNetwork {
+                    name,
+                    driver: config.get("driver").map(|d| scalar(d, "driver")).transpose()?,
+                    internal: boolean(config.get("internal"), "internal")?,
+                    external,
+                    labels,
+                },
+            );
+        }
+        if !self.networks.contains_key(DEFAULT_NETWORK) {
+            self.networks.insert(
+                DEFAULT_NETWORK.to_string(),
+                ComposeNetwork {
+                    name: format!("{}_{}", self.project, DEFAULT_NETWORK),
+                    driver: None,
+                    internal: false,
+                    external: false,
+                    labels: self.project_labels(),
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Named volumes are created by the daemon the first time they're mounted
+    fn parse_volumes(&mut self, volumes: Option<&Value>) -> Result<(), String> {
+        for (key, config) in mapping(volumes, "volumes")? {
+            let key = scalar(key, "volume name")?;
+            let config = optional_mapping(config, &format!("volume {}", key))?;
+            let external = external(&config, &format!("volume {}", key))?;
+            if config.contains_key("driver") || config.contains_key("driver_opts") {
+                self.warnings.push(format!("volume {}: driver options are not supported", key));
+            }
+            let name = self.resource_name(&key, &config, external, "volume")?;
+            self.volumes.insert(key, name);
+        }
+        Ok(())
+    }
+
+    fn parse_service(&mut self, service: &str, value: &Value, file: &Path) -> Result<ComposeService, String> {
+        let config = optional_mapping(value, "service")?;
+        let get = |key: &str| config.get(key);
+
+        let image = match (get("image"), get("build")) {
+            (Some(image), build) => {
+                if build.is_some() {
+                    self.warnings.push(format!("{}: build is not supported; using the image", service));
+                }
+                scalar(image, "image")?
+            }
+            (None, Some(_)) => return Err("build is not supported; set an image".to_string()),
+            (None, None) => return Err("image is required".to_string()),
+        };
+
+        let mut env: Vec<(String, String)> = Vec::new();
+        for entry in list(get("env_file"), "env_file")? {
+            let (path, required) = match entry {
+                Value::Mapping(entry) => (
+                    scalar(entry.get("path").unwrap_or(&Value::Null), "env_file path")?,
+                    match entry.get("required") {
+                        Some(required) => boolean(Some(required), "env_file required")?,
+                        None => true,
+                    },
+                ),
+                entry => (scalar(entry, "env_file")?, true),
+            };
+            let path = self.resolve_path(&path);
+            match fs::read_to_string(&path) {
+                Ok(contents) => merge_pairs(&mut env, parse_env_file(&contents)),
+                Err(_) if !required => {}
+                Err(e) => return Err(format!("Failed to read env file {}: {}", path.display(), e)),
+            }
+        }
+        merge_pairs(&mut env, pairs(get("environment"), "environment", self.env)?);
+
+        let mut ports = Vec::new();
+        for port in list(get("ports"), "ports")? {
+            ports.extend(parse_port(port)?);
+        }
+
+        let mut mounts = Vec::new();
+        for volume in list(get("volumes"), "volumes")? {
+            if let Some(mount) = self.parse_mount(service, volume)? {
+                mounts.push(mount);
+            }
+        }
+
+        let networks: Vec<String> = match get("networks") {
+            None | Some(Value::Null) => vec![DEFAULT_NETWORK.to_string()],
+            Some(Value::Mapping(networks)) => {
+                if networks.values().any(|config| !config.is_null()) {
+                    self.warnings.push(format!("{}: network aliases and addresses are not supported", service));
+                }
+                networks
+                    .keys()
+                    .map(|key| scalar(key, "network"))
+                    .collect::<Result<_, _>>()?
+            }
+            networks => list(networks, "networks")?
+                .iter()
+                .map(|network| scalar(network, "network"))
+                .collect::<Result<_, _>>()?,
+        };
+        for network in &networks {
+            if !self.networks.contains_key(network) {
+                return Err(format!("network \"{}\" is not declared", network));
+            }
+        }
+
+        let depends_on = match get("depends_on") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Mapping(dependencies)) => dependencies
+                .iter()
+                .map(|(name, config)| {
+                    let config = optional_mapping(config, "depends_on")?;
+                    let condition = match config.get("condition").map(|c| scalar(c, "condition")).transpose()? {
+                        None => DependencyCondition::Started,
+                        Some(condition) => match condition.as_str() {
+                            "service_started" => DependencyCondition::Started,
+                            "service_healthy" => DependencyCondition::Healthy,
+                            "service_completed_successfully" => DependencyCondition::CompletedSuccessfully,
+                            other => return Err(format!("unknown depends_on condition \"{}\"", other)),
+                        },
+                    };
+                    Ok(Dependency {
+                        service: scalar(name, "depends_on")?,
+                        condition,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            dependencies => list(dependencies, "depends_on")?
+                .iter()
+                .map(|name| {
+                    Ok(Dependency {
+                        service: scalar(name, "depends_on")?,
+                        condition: DependencyCondition::Started,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+        };
+
+        let pull_policy = match get("pull_policy").map(|p| scalar(p, "pull_policy")).transpose()?.as_deref() {
+            None | Some("missing") | Some("if_not_present") => PullPolicy::Missing,
+            Some("always") => PullPolicy::Always,
+            Some("never") => PullPolicy::Never,
+            Some(other) => {
+                self.warnings.push(format!("{}: pull_policy \"{}\" is not supported", service, other));
+                PullPolicy::Missing
+            }
+        };
+
+        let restart_policy = match get("restart").map(|r| scalar(r, "restart")).transpose()? {
+            None => None,
+            Some(restart) if restart.starts_with("on-failure:") => {
+                self.warnings.push(format!("{}: on-failure retry limits are not supported", service));
+                Some("on-failure".to_string())
+            }
+            Some(restart) if matches!(restart.as_str(), "no" | "always" | "on-failure" | "unless-stopped") => {
+                Some(restart)
+            }
+            Some(restart) => return Err(format!("unknown restart policy \"{}\"", restart)),
+        };
+
+        // `deploy.resources.limits` (v3) and `cpus`/`mem_limit` (v2) mean the same
+        let limits = get("deploy")
+            .and_then(|deploy| deploy.get("resources"))
+            .and_then(|resources| resources.get("limits"));
+        let cpus = match get("cpus").or_else(|| limits.and_then(|l| l.get("cpus"))) {
+            Some(cpus) => Some(
+                scalar(cpus, "cpus")?
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|cpus| *cpus > 0.0)
+                    .ok_or("cpus must be a positive number")?,
+            ),
+            None => None,
+        };
+        let memory_bytes = match get("mem_limit").or_else(|| limits.and_then(|l| l.get("memory"))) {
+            Some(memory) => Some(parse_byte_size(&scalar(memory, "mem_limit")?).ok_or("invalid memory limit")?),
+            None => None,
+        };
+        if get("deploy").and_then(|deploy| deploy.get("replicas")).is_some() {
+            self.warnings.push(format!("{}: deploy.replicas is not supported; scale from the project", service));
+        }
+
+        let mut labels = pairs(get("labels"), "labels", self.env)?;
+        labels.extend([
+            (COMPOSE_PROJECT_LABEL.to_string(), self.project.clone()),
+            (COMPOSE_SERVICE_LABEL.to_string(), service.to_string()),
+            (COMPOSE_NUMBER_LABEL.to_string(), "1".to_string()),
+            (COMPOSE_CONFIG_FILES_LABEL.to_string(), file.display().to_string()),
+            (COMPOSE_WORKING_DIR_LABEL.to_string(), self.working_dir.display().to_string()),
+            ("com.docker.compose.oneoff".to_string(), "False".to_string()),
+        ]);
+
+        for key in config.keys() {
+            let key = key.as_str().unwrap_or_default();
+            if !SERVICE_KEYS.contains(&key) && !key.starts_with("x-") {
+                self.warnings.push(format!("{}: {} is not supported", service, key));
+            }
+        }
+
+        let spec = ContainerSpec {
+            image,
+            name: Some(match get("container_name") {
+                Some(name) => scalar(name, "container_name")?,
+                None => format!("{}-{}-1", self.project, service),
+            }),
+            cmd: words(get("command"), "command")?,
+            entrypoint: words(get("entrypoint"), "entrypoint")?,
+            working_dir: get("working_dir").map(|dir| scalar(dir, "working_dir")).transpose()?,
+            platform: get("platform").map(|p| scalar(p, "platform")).transpose()?,
+            restart_policy,
+            auto_remove: false,
+            privileged: boolean(get("privileged"), "privileged")?,
+            read_only: boolean(get("read_only"), "read_only")?,
+            init: boolean(get("init"), "init")?,
+            ports,
+            env,
+            mounts,
+            networks: networks.iter().map(|key| self.networks[key].name.clone()).collect(),
+            labels,
+            cpus,
+            memory_bytes,
+        };
+
+        Ok(ComposeService {
+            name: service.to_string(),
+            spec,
+            depends_on,
+            profiles: list(get("profiles"), "profiles")?
+                .iter()
+                .map(|profile| scalar(profile, "profile"))
+                .collect::<Result<_, _>>()?,
+            pull_policy,
+            networks,
+        })
+    }
+
+    /// `./data:/data:ro`, `cache:/cache`, `/tmp` or the long form; tmpfs is skipped
+    fn parse_mount(&mut self, service: &str, value: &Value) -> Result<Option<MountSpec>, String> {
+        let (kind, source, target, read_only) = match value {
+            Value::Mapping(mount) => {
+                let kind = scalar(mount.get("type").unwrap_or(&Value::Null), "volume type")?;
+                let source = match mount.get("source") {
+                    Some(source) => scalar(source, "volume source")?,
+                    None => String::new(),
+                };
+                let target = scalar(mount.get("target").unwrap_or(&Value::Null), "volume target")?;
+                let read_only = boolean(mount.get("read_only"), "read_only")?;
+                let kind = match kind.as_str() {
+                    "volume" => MountKind::Volume,
+                    "bind" => MountKind::Bind,
+                    other => {
+                        self.warnings.push(format!("{}: {} mounts are not supported", service, other));
+                        return Ok(None);
+                    }
+                };
+                (kind, source, target, read_only)
+            }
+            value => {
+                let text = scalar(value, "volume")?;
+                let parts: Vec<&str> = text.split(':').collect();
+                match parts.as_slice() {
+                    [target] => (MountKind::Volume, String::new(), target.to_string(), false),
+                    [source, target] => (MountKind::for_source(source), source.to_string(), target.to_string(), false),
+                    [source, target, mode] => (
+                        MountKind::for_source(source),
+                        source.to_string(),
+                        target.to_string(),
+                        mode.split(',').any(|option| option == "ro"),
+                    ),
+                    _ => return Err(format!("invalid volume \"{}\"", text)),
+                }
+            }
+        };
+
+        let source = match kind {
+            MountKind::Bind => self.resolve_path(&source).display().to_string(),
+            // Anonymous
+            MountKind::Volume if source.is_empty() => source,
+            MountKind::Volume => self
+                .volumes
+                .get(&source)
+                .cloned()
+                .ok_or_else(|| format!("volume \"{}\" is not declared", source))?,
+        };
+        Ok(Some(MountSpec {
+            kind,
+            source,
+            target,
+            read_only,
+        }))
+    }
+
+    /// Relative to the compose file's folder, `~` for the home folder
+    fn resolve_path(&self, path: &str) -> PathBuf {
+        if let Some(rest) = path.strip_prefix("~/") {
+            if let Some(home) = dirs::home_dir() {
+                return home.join(rest);
+            }
+        }
+        let path = Path::new(path);
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        let mut resolved = self.working_dir.clone();
+        for component in path.components() {
+            match component {
+                std::path::Component::CurDir => {}
+                std::path::Component::ParentDir => {
+                    resolved.pop();
+                }
+                component => resolved.push(component),
+            }
+        }
+        resolved
+    }
+}
+
+/// Service settings that are understood (anything else becomes a warning)
+const SERVICE_KEYS: [&str; 24] = [
+    "image",
+    "build",
+    "container_name",
+    "command",
+    "entrypoint",
+    "environment",
+    "env_file",
+    "ports",
+    "volumes",
+    "networks",
+    "depends_on",
+    "profiles",
+    "pull_policy",
+    "restart",
+    "working_dir",
+    "labels",
+    "privileged",
+    "read_only",
+    "init",
+    "platform",
+    "cpus",
+    "mem_limit",
+    "deploy",
+    "expose",
+];
+
+/// `8080:80`, `127.0.0.1:8080:80/udp`, `8000-8001:80-81`, `80` or the long form
+///
+/// A port without a host side is published on a port the daemon picks (0).
+fn parse_port(value: &Value) -> Result<Vec<PortMapping>, String> {
+    let (host_ip, published, target, protocol) = match value {
+        Value::Mapping(port) => (
+            port.get("host_ip").map(|ip| scalar(ip, "host_ip")).transpose()?.unwrap_or_default(),
+            port.get("published").map(|p| scalar(p, "published")).transpose()?.unwrap_or_default(),
+            scalar(port.get("target").unwrap_or(&Value::Null), "port target")?,
+            port.get("protocol")
+                .map(|p| scalar(p, "protocol"))
+                .transpose()?
+                .unwrap_or_else(|| "tcp".to_string()),
+        ),
+        value => {
+            let text = scalar(value, "port")?;
+            let (rest, protocol) = match text.rsplit_once('/') {
+                Some((rest, protocol)) => (rest.to_string(), protocol.to_string()),
+                None => (text.clone(), "tcp".to_string()),
+            };
+            // The host address may be a bracketed IPv6 one
+            let (host_ip, rest) = match rest.strip_prefix('[').and_then(|r| r.split_once("]:")) {
+                Some((ip, rest)) => (ip.to_string(), rest.to_string()),
+                None => (String::new(), rest),
+            };
+            let parts: Vec<&str> = rest.split(':').collect();
+            match (host_ip.is_empty(), parts.as_slice()) {
+                (_, [target]) => (host_ip, String::new(), target.to_string(), protocol),
+                (_, [published, target]) => (host_ip, published.to_string(), target.to_string(), protocol),
+                (true, [ip, published, target]) => (ip.to_string(), published.to_string(), target.to_string(), protocol),
+                _ => return Err(format!("invalid port \"{}\"", text)),
+            }
+        }
+    };
+
+    let range = |text: &str| -> Result<(u16, u16), String> {
+        let port = |p: &str| match p.trim().parse::<u16>() {
+            Ok(port) if port > 0 => Ok(port),
+            _ => Err(format!("invalid port \"{}\"", p)),
+        };
+        match text.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (port(start)?, port(end)?);
+                if end < start {
+                    return Err(format!("invalid port range \"{}\"", text));
+                }
+                Ok((start, end))
+            }
+            None => port(text).map(|port| (port, port)),
+        }
+    };
+    let (target_start, target_end) = range(&target)?;
+    let count = target_end - target_start;
+    let published_start = if published.is_empty() {
+        None
+    } else {
+        let (start, end) = range(&published)?;
+        // A host range for a single port means "any of these"; take the first
+        if end - start != count && count != 0 {
+            return Err(format!("port ranges \"{}\" and \"{}\" differ in size", published, target));
+        }
+        Some(start)
+    };
+
+    let protocol = protocol.to_ascii_lowercase();
+    if !matches!(protocol.as_str(), "tcp" | "udp" | "sctp") {
+        return Err(format!("unknown protocol \"{}\"", protocol));
+    }
+    Ok((0..=count)
+        .map(|offset| PortMapping {
+            host_ip: host_ip.clone(),
+            host_port: published_start.map_or(0, |start| start + offset),
+            container_port: target_start + offset,
+            protocol: protocol.clone(),
+        })
+        .collect())
+}
+
+/// Later pairs replace earlier ones with the same key, keeping the first position
+fn merge_pairs(pairs: &mut Vec<(String, String)>, more: Vec<(String, String)>) {
+    for (key, value) in more {
+        match pairs.iter_mut().find(|(existing, _)| *existing == key) {
+            Some(pair) => pair.1 = value,
+            None => pairs.push((key, value)),
+        }
+    }
+}
+
+/// A string, number or boolean as text
+fn scalar(value: &Value, what: &str) -> Result<String, String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        Value::Null => Err(format!("{} is required", what)),
+        _ => Err(format!("{} must be a single value", what)),
+    }
+}
+
+fn boolean(value: Option<&Value>, what: &str) -> Result<bool, String> {
+    match value {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Bool(flag)) => Ok(*flag),
+        Some(Value::String(text)) if text == "true" || text == "false" => Ok(text == "true"),
+        Some(_) => Err(format!("{} must be true or false", what)),
+    }
+}
+
+/// A mapping's entries (missing or null is empty)
+fn mapping<'a>(value: Option<&'a Value>, what: &str) -> Result<Vec<(&'a Value, &'a Value)>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Mapping(mapping)) => Ok(mapping.iter().collect()),
+        Some(_) => Err(format!("{} must be a mapping", what)),
+    }
+}
+
+/// A mapping, where null (`db:` with nothing after it) is an empty one
+fn optional_mapping(value: &Value, what: &str) -> Result<Mapping, String> {
+    match value {
+        Value::Null => Ok(Mapping::new()),
+        Value::Mapping(mapping) => Ok(mapping.clone()),
+        _ => Err(format!("{} must be a mapping", what)),
+    }
+}
+
+/// A list, where a single value is a list of one
+fn list<'a>(value: Option<&'a Value>, what: &str) -> Result<Vec<&'a Value>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Sequence(items)) => Ok(items.iter().collect()),
+        Some(Value::Mapping(_)) => Err(format!("{} must be a list", what)),
+        Some(value) => Ok(vec![value]),
+    }
+}
+
+/// A command as a list, or a string split like a shell would
+fn words(value: Option<&Value>, what: &str) -> Result<Vec<String>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Sequence(items)) => items.iter().map(|item| scalar(item, what)).collect(),
+        Some(value) => split_shell_words(&scalar(value, what)?).map_err(|e| format!("{}: {}", what, e)),
+    }
+}
+
+/// `KEY: value` mappings or `KEY=value` lists; a key without a value is
+/// taken from the environment (and dropped if it isn't set there)
+fn pairs(value: Option<&Value>, what: &str, env: &HashMap<String, String>) -> Result<Vec<(String, String)>, String> {
+    let from_env = |key: String| env.get(&key).map(|value| (key, value.clone()));
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Mapping(mapping)) => {
+            let mut pairs = Vec::new();
+            for (key, value) in mapping {
+                let key = scalar(key, what)?;
+                match value {
+                    Value::Null => pairs.extend(from_env(key)),
+                    value => pairs.push((key, scalar(value, what)?)),
+                }
+            }
+            Ok(pairs)
+        }
+        Some(Value::Sequence(items)) => {
+            let mut pairs = Vec::new();
+            for item in items {
+                let item = scalar(item, what)?;
+                match item.split_once('=') {
+                    Some((key, value)) => pairs.push((key.to_string(), value.to_string())),
+                    None => pairs.extend(from_env(item)),
+                }
+            }
+            Ok(pairs)
+        }
+        Some(_) => Err(format!("{} must be a mapping or a list", what)),
+    }
+}
+
+/// `external: true` (or the old `external: {name: ...}`)
+fn external(config: &Mapping, what: &str) -> Result<bool, String> {
+    match config.get("external") {
+        Some(Value::Mapping(_)) => Ok(true),
+        external => boolean(external, &format!("{} external", what)),
+    }
+}
+
+/// Interpolate every string in the document
+fn interpolate_value(value: &mut Value, env: &HashMap<String, String>, warnings: &mut Vec<String>) -> Result<(), String> {
+    match value {
+        Value::String(text) => *text = interpolate(text, env, warnings)?,
+        Value::Sequence(items) => {
+            for item in items {
+                interpolate_value(item, env, warnings)?;
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (_, item) in mapping.iter_mut() {
+                interpolate_value(item, env, warnings)?;
+            }
+        }
+        Value::Tagged(tagged) => interpolate_value(&mut tagged.value, env, warnings)?,
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+/// Fill in `$VAR`, `${VAR}`, `${VAR:-default}`, `${VAR-default}`,
+/// `${VAR:?error}`, `${VAR?error}`, `${VAR:+other}` and `${VAR+other}`;
+/// `$$` is a literal `$`
+///
+/// An unset variable without a default becomes empty, with a warning.
+fn interpolate(text: &str, env: &HashMap<String, String>, warnings: &mut Vec<String>) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find('$') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+
+        if let Some(after) = rest.strip_prefix('$') {
+            result.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            // Find the matching brace; defaults may hold ${...} themselves
+            let mut depth = 1;
+            let end = after
+                .char_indices()
+                .find(|(_, c)| {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    depth == 0
+                })
+                .map(|(end, _)| end)
+                .ok_or_else(|| format!("unclosed \"${{\" in \"{}\"", text))?;
+            result.push_str(&expand(&after[..end], env, warnings)?);
+            rest = &after[end + 1..];
+        } else {
+            let length = rest
+                .char_indices()
+                .find(|(i, c)| !(c.is_ascii_alphanumeric() || *c == '_') || (*i == 0 && c.is_ascii_digit()))
+                .map_or(rest.len(), |(i, _)| i);
+            if length == 0 {
+                // A lone `$`
+                result.push('$');
+                continue;
+            }
+            result.push_str(&lookup(&rest[..length], env, warnings));
+            rest = &rest[length..];
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// The inside of `${...}`
+fn expand(expression: &str, env: &HashMap<String, String>, warnings: &mut Vec<String>) -> Result<String, String> {
+    let name_end = expression
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(expression.len());
+    let (name, operation) = expression.split_at(name_end);
+    if name.is_empty() {
+        return Err(format!("invalid variable \"${{{}}}\"", expression));
+    }
+    let value = env.get(name);
+    let set = value.is_some();
+    let non_empty = value.is_some_and(|value| !value.is_empty());
+
+    // An optional `:` and one of `-`, `?` or `+`
+    let operator_length = match operation.as_bytes() {
+        [b':', b'-' | b'?' | b'+', ..] => 2,
+        [b'-' | b'?' | b'+', ..] => 1,
+        _ => operation.len(),
+    };
+    let (operator, argument) = operation.split_at(operator_length);
+    match operator {
+        "" => Ok(lookup(name, env, warnings)),
+        ":-" if non_empty => Ok(value.cloned().unwrap_or_default()),
+        "-" if set => Ok(value.cloned().unwrap_or_default()),
+        ":-" | "-" => interpolate(argument, env, warnings),
+        ":?" if !non_empty => Err(required_message(name, argument)),
+        "?" if !set => Err(required_message(name, argument)),
+        ":?" | "?" => Ok(value.cloned().unwrap_or_default()),
+        ":+" if non_empty => interpolate(argument, env, warnings),
+        "+" if set => interpolate(argument, env, warnings),
+        ":+" | "+" => Ok(String::new()),
+        _ => Err(format!("invalid variable \"${{{}}}\"", expression)),
+    }
+}
+
+fn lookup(name: &str, env: &HashMap<String, String>, warnings: &mut Vec<String>) -> String {
+    match env.get(name) {
+        Some(value) => value.clone(),
+        None => {
+            let warning = format!("{} is not set; using an empty string", name);
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+            String::new()
+        }
+    }
+}
+
+fn required_message(name: &str, message: &str) -> String {
+    if message.is_empty() {
+        format!("{} must be set", name)
+    } else {
+        format!("{}: {}", name, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    const FILE: &str = r#"
+version: "3.8"
+name: Shop
+services:
+  web:
+    image: "nginx:${TAG:-latest}"
+    command: nginx -g 'daemon off;'
+    ports: ["8080:80", "127.0.0.1:8443:443/tcp", "9000-9001:9000-9001"]
+    environment:
+      MODE: prod
+      HOME_DIR:
+    volumes: ["./site:/usr/share/nginx/html:ro", "cache:/cache"]
+    depends_on:
+      api: {condition: service_healthy}
+    networks: [front]
+    healthcheck: {test: ["CMD", "true"]}
+  api:
+    image: shop/api
+    depends_on: [db]
+    deploy: {resources: {limits: {cpus: "0.5", memory: 256M}}}
+    restart: unless-stopped
+  db:
+    image: postgres:16
+    environment: ["POSTGRES_PASSWORD=$$ecret"]
+  debug:
+    image: busybox
+    profiles: [tools]
+    depends_on: [web]
+networks:
+  front: {}
+volumes:
+  cache:
+"#;
+
+    #[test]
+    fn parses_services() {
+        let project = ComposeProject::parse(
+            FILE,
+            Path::new("/src/shop/compose.yaml"),
+            &env(&[("HOME_DIR", "/home/me")]),
+        )
+        .unwrap();
+        assert_eq!(project.name, "shop");
+
+        let web = &project.services["web"].spec;
+        assert_eq!(web.image, "nginx:latest");
+        assert_eq!(web.name.as_deref(), Some("shop-web-1"));
+        assert_eq!(web.cmd, ["nginx", "-g", "daemon off;"]);
+        assert_eq!(web.env, [("MODE".to_string(), "prod".to_string()), ("HOME_DIR".to_string(), "/home/me".to_string())]);
+        let ports: Vec<(&str, u16, u16)> = web.ports.iter().map(|p| (p.host_ip.as_str(), p.host_port, p.container_port)).collect();
+        assert_eq!(ports, [("", 8080, 80), ("127.0.0.1", 8443, 443), ("", 9000, 9000), ("", 9001, 9001)]);
+        assert_eq!(web.mounts[0].source, "/src/shop/site");
+        assert!(web.mounts[0].read_only);
+        assert_eq!(web.mounts[1].source, "shop_cache");
+        assert_eq!(web.networks, ["shop_front"]);
+        assert!(web.labels.contains(&(COMPOSE_SERVICE_LABEL.to_string(), "web".to_string())));
+        assert_eq!(
+            project.services["web"].depends_on,
+            [Dependency { service: "api".to_string(), condition: DependencyCondition::Healthy }]
+        );
+
+        let api = &project.services["api"].spec;
+        assert_eq!(api.cpus, Some(0.5));
+        assert_eq!(api.memory_bytes, Some(256 << 20));
+        assert_eq!(api.restart_policy.as_deref(), Some("unless-stopped"));
+        assert_eq!(api.networks, ["shop_default"]);
+        assert_eq!(project.services["db"].spec.env, [("POSTGRES_PASSWORD".to_string(), "$ecret".to_string())]);
+
+        assert!(project.warnings.contains(&"web: healthcheck is not supported".to_string()));
+        // A default covers an unset variable without a warning
+        assert!(!project.warnings.iter().any(|w| w.starts_with("TAG")));
+    }
+
+    #[test]
+    fn orders_services_by_dependency_and_profile() {
+        let project = ComposeProject::parse(FILE, Path::new("/src/shop/compose.yaml"), &HashMap::new()).unwrap();
+        assert_eq!(project.start_order(), ["db", "api", "web", "debug"]);
+        assert_eq!(project.active_services(&BTreeSet::new()), ["db", "api", "web"]);
+        assert_eq!(
+            project.active_services(&BTreeSet::from(["tools".to_string()])),
+            ["db", "api", "web", "debug"]
+        );
+        let networks: Vec<&str> = project
+            .networks_for(&project.active_services(&BTreeSet::new()))
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(networks, ["shop_default", "shop_front"]);
+
+        let cyclic = "services:\n  a: {image: x, depends_on: [b]}\n  b: {image: x, depends_on: [a]}\n";
+        let error = ComposeProject::parse(cyclic, Path::new("/p/compose.yaml"), &HashMap::new()).unwrap_err();
+        assert_eq!(error, "Services depend on each other: a → b → a");
+        let missing = "services:\n  a: {image: x, depends_on: [c]}\n";
+        assert!(ComposeProject::parse(missing, Path::new("/p/compose.yaml"), &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn interpolates_variables() {
+        let env = env(&[("SET", "yes"), ("EMPTY", "")]);
+        let mut warnings = Vec::new();
+        let mut run = |text: &str| interpolate(text, &env, &mut warnings);
+        assert_eq!(run("$SET-${SET}").unwrap(), "yes-yes");
+        assert_eq!(run("${EMPTY:-a} ${EMPTY-b} ${NOPE-c}").unwrap(), "a  c");
+        assert_eq!(run("${NOPE:-${SET}} ${NOPE:--x}").unwrap(), "yes -x");
+        assert_eq!(run("${SET:+on}${NOPE:+off}").unwrap(), "on");
+        assert_eq!(run("cost: $$5, $ 1").unwrap(), "cost: $5, $ 1");
+        assert_eq!(run("${NOPE:?needs a value}").unwrap_err(), "NOPE: needs a value");
+        assert!(run("${SET").is_err());
+        assert_eq!(run("[$NOPE]").unwrap(), "[]");
+        assert_eq!(warnings, ["NOPE is not set; using an empty string"]);
+    }
+}
@@ -2,7 +2,37 @@
 //!
 //! Provides connection management and RPC client access to the arcbox-daemon.
 
-use std::path::PathBuf;
+mod bulk;
+mod compose;
+mod compose_run;
+mod create;
+mod docker_run;
+mod error;
+mod events;
+mod exec;
+mod files;
+mod handshake;
+mod inspect;
+mod stats;
+#[cfg(test)]
+mod tests;
+mod transport;
+
+pub use bulk::*;
+pub use compose_run::*;
+pub use create::*;
+pub use docker_run::*;
+pub use error::*;
+pub use events::*;
+pub use exec::*;
+pub use files::*;
+pub use handshake::*;
+pub use inspect::*;
+pub use stats::*;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
 
 use arcbox_api::generated::{
     container_service_client::ContainerServiceClient,
@@ -10,19 +40,34 @@ use arcbox_api::generated::{
     machine_service_client::MachineServiceClient,
     network_service_client::NetworkServiceClient,
     ListContainersRequest, ListContainersResponse,
-    CreateContainerRequest, CreateContainerResponse,
     StartContainerRequest, StopContainerRequest, RemoveContainerRequest,
+    PauseContainerRequest, UnpauseContainerRequest, RestartContainerRequest,
+    KillContainerRequest, RenameContainerRequest,
     ListImagesRequest, ListImagesResponse,
     ListMachinesRequest, ListMachinesResponse,
     ListNetworksRequest, ListNetworksResponse,
     CreateNetworkRequest, RemoveNetworkRequest,
     ContainerLogsRequest, LogEntry,
+    ContainerSummary, ImageSummary,
 };
 use futures::StreamExt;
 use gpui::*;
-use tokio::net::UnixStream;
-use tonic::transport::{Channel, Endpoint, Uri};
-use tower::service_fn;
+use tonic::transport::Channel;
+
+use crate::services::DaemonContext;
+use transport::SshTunnel;
+
+/// Delay before the first reconnect attempt
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound for the reconnect backoff delay
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Give up reconnecting after this many consecutive failures
+const MAX_RECONNECT_ATTEMPTS: u32 = 20;
+
+/// Interval between socket health checks while connected
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Connection state
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,6 +75,11 @@ pub enum ConnectionState {
     Disconnected,
     Connecting,
     Connected,
+    /// Connection lost or failed, waiting to retry
+    Reconnecting {
+        attempt: u32,
+        next_retry_at: Instant,
+    },
     Error(String),
 }
 
@@ -39,41 +89,62 @@ impl Default for ConnectionState {
     }
 }
 
+/// An active log stream that should survive reconnects
+#[derive(Debug, Clone)]
+struct LogSubscription {
+    follow: bool,
+    tail: Option<u32>,
+    /// Timestamp (ns) of the last entry delivered, used to resume without duplicates
+    last_timestamp: i64,
+    /// Bumped on every (re)subscribe so stale stream readers stop
+    generation: u64,
+}
+
 /// ArcBox daemon client service
 ///
-/// Manages connection to the daemon and provides access to gRPC clients.
+/// Manages the connection to one daemon context and provides access to gRPC
+/// clients. Each context gets its own service, channel and state. Once
+/// connected, the socket is health-checked periodically; if the daemon goes
+/// away the service reconnects with exponential backoff, refreshes all lists
+/// and resumes any active log streams.
 pub struct DaemonService {
     /// Current connection state
     state: ConnectionState,
     /// gRPC channel (when connected)
     channel: Option<Channel>,
-    /// Socket path
-    socket_path: PathBuf,
+    /// Context this service connects to
+    context: DaemonContext,
+    /// SSH port forward backing the channel for SSH contexts
+    ssh_tunnel: Option<SshTunnel>,
     /// Tokio runtime for gRPC operations
     tokio_runtime: std::sync::Arc<tokio::runtime::Runtime>,
+    /// Consecutive failed connection attempts
+    reconnect_attempt: u32,
+    /// Whether we have been connected before (reconnects trigger a refresh)
+    was_connected: bool,
+    /// Pending reconnect timer (dropping it cancels the retry)
+    reconnect_task: Option<Task<()>>,
+    /// Health check loop while connected
+    health_task: Option<Task<()>>,
+    /// Daemon event stream while connected
+    events_task: Option<Task<()>>,
+    /// Active log subscriptions by container ID
+    log_subscriptions: HashMap<String, LogSubscription>,
+    /// Active stats streams: container ID to the generation of its reader
+    stats_subscriptions: HashMap<String, u64>,
+    next_stats_generation: u64,
+    /// Version and capabilities reported by the daemon (after the handshake)
+    daemon_info: Option<DaemonInfo>,
 }
 
 impl DaemonService {
-    pub fn new(_cx: &mut Context<Self>) -> Self {
-        // Default to user directory socket path
-        let socket_path = Self::default_socket_path();
-        let tokio_runtime = std::sync::Arc::new(
-            tokio::runtime::Builder::new_multi_thread()
-                .worker_threads(2)
-                .enable_all()
-                .build()
-                .expect("Failed to create tokio runtime")
-        );
-        Self {
-            state: ConnectionState::Disconnected,
-            channel: None,
-            socket_path,
-            tokio_runtime,
-        }
+    /// Create a service for the built-in local context
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self::for_context(DaemonContext::local(), cx)
     }
 
-    /// Create with custom socket path
-    pub fn with_socket_path(socket_path: PathBuf, _cx: &mut Context<Self>) -> Self {
+    /// Create a service for the given context
+    pub fn for_context(context: DaemonContext, _cx: &mut Context<Self>) -> Self {
         let tokio_runtime = std::sync::Arc::new(
             tokio::runtime::Builder::new_multi_thread()
                 .worker_threads(2)
@@ -84,22 +155,24 @@ impl DaemonService {
         Self {
             state: ConnectionState::Disconnected,
             channel: None,
-            socket_path,
+            context,
+            ssh_tunnel: None,
             tokio_runtime,
+            reconnect_attempt: 0,
+            was_connected: false,
+            reconnect_task: None,
+            health_task: None,
+            events_task: None,
+            log_subscriptions: HashMap::new(),
+            stats_subscriptions: HashMap::new(),
+            next_stats_generation: 0,
+            daemon_info: None,
         }
     }
 
-    /// Get default socket path (matches daemon default: ~/.arcbox/arcbox.sock)
-    fn default_socket_path() -> PathBuf {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("/tmp"))
-            .join(".arcbox")
-            .join("arcbox.sock")
-    }
-
-    /// Set socket path (must be called before connect)
-    pub fn set_socket_path(&mut self, path: PathBuf) {
-        self.socket_path = path;
+    /// Get the context this service connects to
+    pub fn context(&self) -> &DaemonContext {
+        &self.context
     }
 
     /// Get current connection state
@@ -113,84 +186,197 @@ impl DaemonService {
     }
 
     /// Connect to the daemon
+    ///
+    /// Failed attempts are retried with exponential backoff until the
+    /// connection succeeds, `disconnect` is called, or the retry limit is hit.
     pub fn connect(&mut self, cx: &mut Context<Self>) {
         if matches!(self.state, ConnectionState::Connecting | ConnectionState::Connected) {
             return;
         }
 
+        self.reconnect_attempt = 0;
+        self.attempt_connect(cx);
+    }
+
+    /// Run a single connection attempt
+    fn attempt_connect(&mut self, cx: &mut Context<Self>) {
+        self.reconnect_task = None;
         self.state = ConnectionState::Connecting;
         cx.notify();
 
-        let socket_path = self.socket_path.clone();
+        let context = self.context.clone();
         let runtime = self.tokio_runtime.clone();
 
         // Use background executor for the connection
         cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
-            let socket_path_str = socket_path.display().to_string();
+            let address = context.endpoint.display();
 
             // Run the tokio-dependent code in background thread with our runtime
             let result = cx
                 .background_executor()
                 .spawn(async move {
-                    Self::create_channel_with_runtime(&socket_path, &runtime)
+                    Self::create_channel_with_runtime(&context, &runtime)
                 })
                 .await;
 
             cx.update(|cx| {
                 this.update(cx, |this, cx| {
+                    // disconnect() was called while the attempt was in flight
+                    if this.state != ConnectionState::Connecting {
+                        return;
+                    }
                     match result {
-                        Ok(channel) => {
-                            tracing::info!("Connected to daemon at {}", socket_path_str);
-                            this.channel = Some(channel);
-                            this.state = ConnectionState::Connected;
+                        Ok((channel, tunnel)) => {
+                            tracing::info!("Connected to daemon at {}", address);
+                            this.handshake(channel, tunnel, cx);
                         }
                         Err(e) => {
                             tracing::error!("Failed to connect to daemon: {}", e);
-                            this.state = ConnectionState::Error(e);
+                            this.schedule_reconnect(e, cx);
                         }
                     }
-                    cx.notify();
                 })
             }).ok();
         }).detach();
     }
 
-    /// Create gRPC channel over Unix socket using provided runtime
-    fn create_channel_with_runtime(
-        socket_path: &PathBuf,
-        runtime: &tokio::runtime::Runtime,
-    ) -> Result<Channel, String> {
-        use std::os::unix::net::UnixStream as StdUnixStream;
+    /// Transition to `Connected`, start health checks and recover state after a reconnect
+    fn on_connected(&mut self, channel: Channel, cx: &mut Context<Self>) {
+        let is_reconnect = self.was_connected;
+
+        self.channel = Some(channel);
+        self.state = ConnectionState::Connected;
+        self.reconnect_attempt = 0;
+        self.was_connected = true;
+        self.start_health_check(cx);
+        if self.supports(Capability::Events) {
+            self.start_event_stream(cx);
+        }
 
-        let socket_path_str = socket_path.to_string_lossy().to_string();
+        if is_reconnect {
+            tracing::info!("Reconnected to daemon, refreshing state");
+            cx.emit(DaemonEvent::Reconnected);
+            self.refresh_all(cx);
+            self.resume_log_subscriptions(cx);
+            self.resume_stats_subscriptions(cx);
+        }
+        cx.notify();
+    }
 
-        // First verify the socket exists and is connectable
-        if StdUnixStream::connect(&socket_path_str).is_err() {
-            return Err(format!("Cannot connect to socket: {}", socket_path_str));
+    /// Schedule the next connection attempt with exponential backoff
+    fn schedule_reconnect(&mut self, error: String, cx: &mut Context<Self>) {
+        self.reconnect_attempt += 1;
+        if self.reconnect_attempt > MAX_RECONNECT_ATTEMPTS {
+            tracing::error!(
+                "Giving up on daemon connection after {} attempts",
+                MAX_RECONNECT_ATTEMPTS
+            );
+            self.state = ConnectionState::Error(error);
+            cx.notify();
+            return;
         }
 
-        // Use the provided runtime
-        runtime.block_on(async move {
-            let channel = Endpoint::try_from("http://[::]:50051")
-                .map_err(|e| format!("Invalid endpoint: {}", e))?
-                .connect_with_connector(service_fn(move |_: Uri| {
-                    let path = socket_path_str.clone();
-                    async move {
-                        UnixStream::connect(path).await.map(|s| {
-                            hyper_util::rt::TokioIo::new(s)
-                        })
+        let attempt = self.reconnect_attempt;
+        let delay = Self::backoff_delay(attempt);
+        tracing::info!("Reconnecting to daemon in {:?} (attempt {})", delay, attempt);
+
+        self.state = ConnectionState::Reconnecting {
+            attempt,
+            next_retry_at: Instant::now() + delay,
+        };
+        self.reconnect_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            cx.background_executor().timer(delay).await;
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    if matches!(this.state, ConnectionState::Reconnecting { .. }) {
+                        this.attempt_connect(cx);
                     }
-                }))
-                .await
-                .map_err(|e| format!("Failed to connect: {}", e))?;
+                })
+            }).ok();
+        }));
+        cx.notify();
+    }
+
+    /// Backoff delay for the given attempt (1-based): 0.5s, 1s, 2s, ... capped at 30s
+    fn backoff_delay(attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        RECONNECT_INITIAL_DELAY
+            .saturating_mul(factor)
+            .min(RECONNECT_MAX_DELAY)
+    }
+
+    /// Periodically verify the daemon endpoint is still accepting connections
+    fn start_health_check(&mut self, cx: &mut Context<Self>) {
+        let probe = Self::health_probe(&self.context, self.ssh_tunnel.as_ref());
+
+        self.health_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            loop {
+                cx.background_executor().timer(HEALTH_CHECK_INTERVAL).await;
+
+                let probe = probe.clone();
+                let alive = cx
+                    .background_executor()
+                    .spawn(async move { probe.is_alive() })
+                    .await;
 
-            Ok(channel)
-        })
+                if !alive {
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            this.connection_lost("Daemon is no longer reachable".to_string(), cx);
+                        })
+                    }).ok();
+                    break;
+                }
+            }
+        }));
     }
 
-    /// Disconnect from the daemon
+    /// Handle a lost connection: drop the channel and start reconnecting
+    fn connection_lost(&mut self, reason: String, cx: &mut Context<Self>) {
+        if self.state != ConnectionState::Connected {
+            return;
+        }
+
+        tracing::warn!("Lost connection to daemon: {}", reason);
+        self.channel = None;
+        self.ssh_tunnel = None;
+        self.health_task = None;
+        self.stop_event_stream();
+        cx.emit(DaemonEvent::ConnectionLost(reason.clone()));
+
+        self.reconnect_attempt = 0;
+        self.schedule_reconnect(reason, cx);
+    }
+
+    /// Report a failed operation, reconnecting first if the daemon is unreachable
+    fn operation_failed(&mut self, error: DaemonError, cx: &mut Context<Self>) {
+        match &error {
+            DaemonError::Unavailable { message, .. } => self.connection_lost(message.clone(), cx),
+            DaemonError::Unsupported { operation, .. } => self.capability_unsupported(*operation, cx),
+            _ => {}
+        }
+        cx.emit(DaemonEvent::OperationFailed(error));
+    }
+
+    /// Re-list containers, images, networks and machines
+    pub fn refresh_all(&self, cx: &mut Context<Self>) {
+        self.list_containers(true, cx);
+        self.list_images(cx);
+        self.list_networks(cx);
+        if self.supports(Capability::Machines) {
+            self.list_machines(cx);
+        }
+    }
+
+    /// Disconnect from the daemon and stop any pending reconnect
     pub fn disconnect(&mut self, cx: &mut Context<Self>) {
         self.channel = None;
+        self.ssh_tunnel = None;
+        self.reconnect_task = None;
+        self.health_task = None;
+        self.stop_event_stream();
+        self.reconnect_attempt = 0;
+        self.daemon_info = None;
         self.state = ConnectionState::Disconnected;
         cx.notify();
     }
@@ -219,6 +405,10 @@ impl DaemonService {
     pub fn list_machines(&self, cx: &mut Context<Self>) {
         let Some(mut client) = self.machine_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::ListMachines,
+                None,
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -243,6 +433,11 @@ impl DaemonService {
                 }
                 Err(e) => {
                     tracing::error!("Failed to list machines: {}", e);
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(DaemonError::from_status(DaemonOperation::ListMachines, None, &e), cx)
+                        })
+                    }).ok();
                 }
             }
         }).detach();
@@ -252,6 +447,10 @@ impl DaemonService {
     pub fn list_containers(&self, all: bool, cx: &mut Context<Self>) {
         let Some(mut client) = self.container_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::ListContainers,
+                None,
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -280,6 +479,11 @@ impl DaemonService {
                 }
                 Err(e) => {
                     tracing::error!("Failed to list containers: {}", e);
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(DaemonError::from_status(DaemonOperation::ListContainers, None, &e), cx)
+                        })
+                    }).ok();
                 }
             }
         }).detach();
@@ -289,6 +493,10 @@ impl DaemonService {
     pub fn start_container(&self, id: String, cx: &mut Context<Self>) {
         let Some(mut client) = self.container_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::StartContainer,
+                Some(id),
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -316,8 +524,11 @@ impl DaemonService {
                 Err(e) => {
                     tracing::error!("Failed to start container {}: {}", id, e);
                     cx.update(|cx| {
-                        this.update(cx, |_this, cx| {
-                            cx.emit(DaemonEvent::OperationFailed(format!("Failed to start container: {}", e)));
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(
+                                DaemonError::from_status(DaemonOperation::StartContainer, Some(id), &e),
+                                cx,
+                            );
                         })
                     }).ok();
                 }
@@ -329,6 +540,10 @@ impl DaemonService {
     pub fn stop_container(&self, id: String, timeout: u32, cx: &mut Context<Self>) {
         let Some(mut client) = self.container_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::StopContainer,
+                Some(id),
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -356,8 +571,11 @@ impl DaemonService {
                 Err(e) => {
                     tracing::error!("Failed to stop container {}: {}", id, e);
                     cx.update(|cx| {
-                        this.update(cx, |_this, cx| {
-                            cx.emit(DaemonEvent::OperationFailed(format!("Failed to stop container: {}", e)));
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(
+                                DaemonError::from_status(DaemonOperation::StopContainer, Some(id), &e),
+                                cx,
+                            );
                         })
                     }).ok();
                 }
@@ -369,6 +587,10 @@ impl DaemonService {
     pub fn remove_container(&self, id: String, force: bool, cx: &mut Context<Self>) {
         let Some(mut client) = self.container_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::RemoveContainer,
+                Some(id),
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -400,8 +622,11 @@ impl DaemonService {
                 Err(e) => {
                     tracing::error!("Failed to remove container {}: {}", id, e);
                     cx.update(|cx| {
-                        this.update(cx, |_this, cx| {
-                            cx.emit(DaemonEvent::OperationFailed(format!("Failed to remove container: {}", e)));
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(
+                                DaemonError::from_status(DaemonOperation::RemoveContainer, Some(id), &e),
+                                cx,
+                            );
                         })
                     }).ok();
                 }
@@ -409,36 +634,157 @@ impl DaemonService {
         }).detach();
     }
 
-    /// Create a container
-    pub fn create_container(
+    /// Pause a running container
+    pub fn pause_container(&self, id: String, cx: &mut Context<Self>) {
+        self.container_action(
+            DaemonOperation::PauseContainer,
+            id,
+            |mut client, id| async move {
+                client.pause_container(tonic::Request::new(PauseContainerRequest { id })).await?;
+                Ok(())
+            },
+            DaemonEvent::ContainerPaused,
+            cx,
+        );
+    }
+
+    /// Resume a paused container
+    pub fn unpause_container(&self, id: String, cx: &mut Context<Self>) {
+        self.container_action(
+            DaemonOperation::UnpauseContainer,
+            id,
+            |mut client, id| async move {
+                client.unpause_container(tonic::Request::new(UnpauseContainerRequest { id })).await?;
+                Ok(())
+            },
+            DaemonEvent::ContainerUnpaused,
+            cx,
+        );
+    }
+
+    /// Restart a container, killing it if it doesn't stop within `timeout` seconds
+    pub fn restart_container(&self, id: String, timeout: u32, cx: &mut Context<Self>) {
+        self.container_action(
+            DaemonOperation::RestartContainer,
+            id,
+            move |mut client, id| async move {
+                client
+                    .restart_container(tonic::Request::new(RestartContainerRequest { id, timeout }))
+                    .await?;
+                Ok(())
+            },
+            DaemonEvent::ContainerRestarted,
+            cx,
+        );
+    }
+
+    /// Send a signal (e.g. "SIGKILL", "SIGHUP") to a container's main process
+    pub fn kill_container(&self, id: String, signal: String, cx: &mut Context<Self>) {
+        let signal_for_event = signal.clone();
+        self.container_action(
+            DaemonOperation::KillContainer,
+            id,
+            move |mut client, id| async move {
+                client
+                    .kill_container(tonic::Request::new(KillContainerRequest { id, signal }))
+                    .await?;
+                Ok(())
+            },
+            move |id| DaemonEvent::ContainerKilled {
+                id,
+                signal: signal_for_event,
+            },
+            cx,
+        );
+    }
+
+    /// Rename a container
+    pub fn rename_container(&self, id: String, name: String, cx: &mut Context<Self>) {
+        let name_for_event = name.clone();
+        self.container_action(
+            DaemonOperation::RenameContainer,
+            id,
+            move |mut client, id| async move {
+                client
+                    .rename_container(tonic::Request::new(RenameContainerRequest { id, name }))
+                    .await?;
+                Ok(())
+            },
+            move |id| DaemonEvent::ContainerRenamed {
+                id,
+                name: name_for_event,
+            },
+            cx,
+        );
+    }
+
+    /// Run a single container RPC, then emit `done` and refresh the list
+    fn container_action<F, Fut>(
         &self,
-        image: String,
-        name: Option<String>,
-        cmd: Option<Vec<String>>,
-        entrypoint: Option<Vec<String>>,
-        working_dir: Option<String>,
-        start: bool,
+        operation: DaemonOperation,
+        id: String,
+        call: F,
+        done: impl FnOnce(String) -> DaemonEvent + 'static,
         cx: &mut Context<Self>,
-    ) {
+    ) where
+        F: FnOnce(ContainerServiceClient<Channel>, String) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), tonic::Status>>,
+    {
+        let Some(client) = self.container_client() else {
+            tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(operation, Some(id))));
+            return;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let id_clone = id.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime.block_on(call(client, id_clone))
+            }).await;
+
+            match result {
+                Ok(()) => {
+                    tracing::info!("Completed {}", operation.describe(Some(&id)));
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            cx.emit(done(id));
+                            // Refresh container list
+                            this.list_containers(true, cx);
+                        })
+                    }).ok();
+                }
+                Err(e) => {
+                    tracing::error!("Failed to {}: {}", operation.describe(Some(&id)), e);
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(DaemonError::from_status(operation, Some(id), &e), cx);
+                        })
+                    }).ok();
+                }
+            }
+        }).detach();
+    }
+
+    /// Create a container
+    pub fn create_container(&self, spec: ContainerSpec, start: bool, cx: &mut Context<Self>) {
         let Some(mut client) = self.container_client() else {
             tracing::warn!("Not connected to daemon");
-            cx.emit(DaemonEvent::OperationFailed("Not connected to daemon".to_string()));
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::CreateContainer,
+                spec.name,
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
 
         cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
-            let image_clone = image.clone();
+            let image = spec.image.clone();
+            // The container has no ID yet, so failures are reported against its name
+            let error_resource = spec.name.clone();
             let result = cx.background_executor().spawn(async move {
                 runtime.block_on(async {
-                    let request = tonic::Request::new(CreateContainerRequest {
-                        image: image_clone,
-                        name: name.unwrap_or_default(),
-                        cmd: cmd.unwrap_or_default(),
-                        entrypoint: entrypoint.unwrap_or_default(),
-                        working_dir: working_dir.unwrap_or_default(),
-                        ..Default::default()
-                    });
+                    let request = tonic::Request::new(spec.into_request());
                     client.create_container(request).await
                 })
             }).await;
@@ -465,8 +811,11 @@ impl DaemonService {
                 Err(e) => {
                     tracing::error!("Failed to create container from {}: {}", image, e);
                     cx.update(|cx| {
-                        this.update(cx, |_this, cx| {
-                            cx.emit(DaemonEvent::OperationFailed(format!("Failed to create container: {}", e)));
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(
+                                DaemonError::from_status(DaemonOperation::CreateContainer, error_resource, &e),
+                                cx,
+                            );
                         })
                     }).ok();
                 }
@@ -478,6 +827,10 @@ impl DaemonService {
     pub fn list_images(&self, cx: &mut Context<Self>) {
         let Some(mut client) = self.image_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::ListImages,
+                None,
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -502,6 +855,11 @@ impl DaemonService {
                 }
                 Err(e) => {
                     tracing::error!("Failed to list images: {}", e);
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(DaemonError::from_status(DaemonOperation::ListImages, None, &e), cx)
+                        })
+                    }).ok();
                 }
             }
         }).detach();
@@ -511,21 +869,85 @@ impl DaemonService {
     ///
     /// Emits `LogsReceived` events as log entries arrive.
     /// Returns immediately; logs are delivered asynchronously via events.
+    /// Followed streams are resumed automatically after a reconnect.
     pub fn subscribe_logs(
-        &self,
+        &mut self,
         container_id: String,
         follow: bool,
         tail: Option<u32>,
         cx: &mut Context<Self>,
     ) {
+        let generation = self
+            .log_subscriptions
+            .get(&container_id)
+            .map(|sub| sub.generation + 1)
+            .unwrap_or(0);
+        self.log_subscriptions.insert(
+            container_id.clone(),
+            LogSubscription {
+                follow,
+                tail,
+                last_timestamp: 0,
+                generation,
+            },
+        );
+
+        tracing::info!("Subscribing to logs for container {}", container_id);
+        self.spawn_log_stream(container_id, cx);
+    }
+
+    /// Stop delivering logs for a container
+    pub fn unsubscribe_logs(&mut self, container_id: &str) {
+        if self.log_subscriptions.remove(container_id).is_some() {
+            tracing::debug!("Unsubscribed from logs for container {}", container_id);
+        }
+    }
+
+    /// Restart every followed log stream after a reconnect
+    fn resume_log_subscriptions(&mut self, cx: &mut Context<Self>) {
+        let ids: Vec<String> = self
+            .log_subscriptions
+            .iter()
+            .filter(|(_, sub)| sub.follow)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in ids {
+            if let Some(sub) = self.log_subscriptions.get_mut(&id) {
+                sub.generation += 1;
+            }
+            tracing::info!("Resuming log stream for container {}", id);
+            self.spawn_log_stream(id, cx);
+        }
+    }
+
+    /// Open the log stream for an existing subscription and pump entries into events
+    fn spawn_log_stream(&self, container_id: String, cx: &mut Context<Self>) {
+        let Some(sub) = self.log_subscriptions.get(&container_id).cloned() else {
+            return;
+        };
         let Some(mut client) = self.container_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::ContainerLogs,
+                Some(container_id),
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
-        let id = container_id.clone();
-
-        tracing::info!("Subscribing to logs for container {}", container_id);
+        let id = container_id;
+        let generation = sub.generation;
+        // Older daemons can only return what has been written so far
+        let follow = sub.follow && self.supports(Capability::LogsFollow);
+        // When resuming, ask for everything since the last delivered second
+        // (no tail cap, or a long gap would be cut short) and skip the
+        // entries of that second that were already delivered
+        let resume_after = sub.last_timestamp;
+        let (since, tail) = if resume_after > 0 {
+            (resume_after / 1_000_000_000, 0)
+        } else {
+            (0, sub.tail.map(i64::from).unwrap_or(100))
+        };
 
         cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
             let id_for_request = id.clone();
@@ -540,9 +962,9 @@ impl DaemonService {
                         stdout: true,
                         stderr: true,
                         timestamps: true,
-                        since: 0,
+                        since,
                         until: 0,
-                        tail: tail.map(i64::from).unwrap_or(100),
+                        tail,
                     });
                     client.container_logs(request).await
                 })
@@ -552,59 +974,76 @@ impl DaemonService {
                 Ok(response) => {
                     tracing::debug!("Log stream started for container {}", id);
 
-                    // Process stream entries in background using channels
                     let id_for_stream = id.clone();
-                    let stream = response.into_inner();
-
-                    // Use std channel for cross-thread communication
-                    let (tx, rx) = std::sync::mpsc::channel();
-
-                    // Spawn the stream processing in the tokio runtime
-                    cx.background_executor().spawn({
-                        let runtime = runtime.clone();
-                        let id_for_bg = id_for_stream.clone();
-                        async move {
-                            runtime.block_on(async {
-                                use futures::StreamExt as _;
-                                let mut stream = stream;
-                                while let Some(entry_result) = stream.next().await {
-                                    match entry_result {
-                                        Ok(log_entry) => {
-                                            if tx.send(log_entry).is_err() {
-                                                break; // Receiver dropped
-                                            }
-                                        }
-                                        Err(e) => {
-                                            tracing::error!("Log stream error for {}: {}", id_for_bg, e);
-                                            break;
-                                        }
-                                    }
-                                }
-                                tracing::debug!("Log stream ended for {}", id_for_bg);
-                            });
-                        }
-                    }).detach();
+                    let rx = read_stream(&runtime, response.into_inner(), format!("logs for {}", id));
 
                     // Process received log entries from std channel using non-blocking recv
                     loop {
                         match rx.try_recv() {
-                            Ok(log_entry) => {
+                            Ok(Ok(log_entry)) => {
+                                if log_entry.timestamp <= resume_after {
+                                    continue;
+                                }
                                 let container_id = id_for_stream.clone();
-                                cx.update(|cx| {
-                                    this.update(cx, |_this, cx| {
+                                let still_subscribed = cx.update(|cx| {
+                                    this.update(cx, |this, cx| {
+                                        let Some(sub) = this.log_subscriptions.get_mut(&container_id) else {
+                                            return false;
+                                        };
+                                        if sub.generation != generation {
+                                            return false;
+                                        }
+                                        sub.last_timestamp = sub.last_timestamp.max(log_entry.timestamp);
                                         cx.emit(DaemonEvent::LogsReceived {
                                             container_id,
                                             entry: log_entry,
                                         });
+                                        true
+                                    })
+                                }).ok().and_then(|r| r.ok()).unwrap_or(false);
+
+                                if !still_subscribed {
+                                    // Dropping the receiver stops the stream reader
+                                    break;
+                                }
+                            }
+                            Ok(Err(status)) => {
+                                let container_id = id_for_stream.clone();
+                                cx.update(|cx| {
+                                    this.update(cx, |this, cx| {
+                                        this.operation_failed(
+                                            DaemonError::from_status(DaemonOperation::ContainerLogs, Some(container_id), &status),
+                                            cx,
+                                        )
                                     })
                                 }).ok();
+                                break;
                             }
                             Err(std::sync::mpsc::TryRecvError::Empty) => {
+                                // Unsubscribed while idle: drop the reader now rather
+                                // than when the next line arrives
+                                let still_subscribed = cx.update(|cx| {
+                                    this.update(cx, |this, _cx| {
+                                        this.log_subscriptions.get(&id_for_stream).map(|s| s.generation) == Some(generation)
+                                    })
+                                }).ok().and_then(|r| r.ok()).unwrap_or(false);
+                                if !still_subscribed {
+                                    break;
+                                }
                                 // No message available, yield and try again
-                                cx.background_executor().timer(std::time::Duration::from_millis(10)).await;
+                                cx.background_executor().timer(Duration::from_millis(10)).await;
                             }
                             Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                                // Channel closed, stream ended
+                                // Channel closed, stream ended; one-shot streams are done
+                                if !follow {
+                                    cx.update(|cx| {
+                                        this.update(cx, |this, _cx| {
+                                            if this.log_subscriptions.get(&id_for_stream).map(|s| s.generation) == Some(generation) {
+                                                this.log_subscriptions.remove(&id_for_stream);
+                                            }
+                                        })
+                                    }).ok();
+                                }
                                 break;
                             }
                         }
@@ -612,6 +1051,11 @@ impl DaemonService {
                 }
                 Err(e) => {
                     tracing::error!("Failed to subscribe to logs for {}: {}", id, e);
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(DaemonError::from_status(DaemonOperation::ContainerLogs, Some(id), &e), cx)
+                        })
+                    }).ok();
                 }
             }
         }).detach();
@@ -621,6 +1065,10 @@ impl DaemonService {
     pub fn list_networks(&self, cx: &mut Context<Self>) {
         let Some(mut client) = self.network_client() else {
             tracing::warn!("Not connected to daemon");
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::ListNetworks,
+                None,
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -645,6 +1093,11 @@ impl DaemonService {
                 }
                 Err(e) => {
                     tracing::error!("Failed to list networks: {}", e);
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(DaemonError::from_status(DaemonOperation::ListNetworks, None, &e), cx)
+                        })
+                    }).ok();
                 }
             }
         }).detach();
@@ -659,7 +1112,10 @@ impl DaemonService {
     ) {
         let Some(mut client) = self.network_client() else {
             tracing::warn!("Not connected to daemon");
-            cx.emit(DaemonEvent::OperationFailed("Not connected to daemon".to_string()));
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::CreateNetwork,
+                Some(name),
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -693,8 +1149,11 @@ impl DaemonService {
                 Err(e) => {
                     tracing::error!("Failed to create network {}: {}", name, e);
                     cx.update(|cx| {
-                        this.update(cx, |_this, cx| {
-                            cx.emit(DaemonEvent::OperationFailed(format!("Failed to create network: {}", e)));
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(
+                                DaemonError::from_status(DaemonOperation::CreateNetwork, Some(name), &e),
+                                cx,
+                            );
                         })
                     }).ok();
                 }
@@ -706,7 +1165,10 @@ impl DaemonService {
     pub fn remove_network(&self, id: String, cx: &mut Context<Self>) {
         let Some(mut client) = self.network_client() else {
             tracing::warn!("Not connected to daemon");
-            cx.emit(DaemonEvent::OperationFailed("Not connected to daemon".to_string()));
+            cx.emit(DaemonEvent::OperationFailed(DaemonError::not_connected(
+                DaemonOperation::RemoveNetwork,
+                Some(id),
+            )));
             return;
         };
         let runtime = self.tokio_runtime.clone();
@@ -734,8 +1196,11 @@ impl DaemonService {
                 Err(e) => {
                     tracing::error!("Failed to remove network {}: {}", id, e);
                     cx.update(|cx| {
-                        this.update(cx, |_this, cx| {
-                            cx.emit(DaemonEvent::OperationFailed(format!("Failed to remove network: {}", e)));
+                        this.update(cx, |this, cx| {
+                            this.operation_failed(
+                                DaemonError::from_status(DaemonOperation::RemoveNetwork, Some(id), &e),
+                                cx,
+                            );
                         })
                     }).ok();
                 }
@@ -759,17 +1224,122 @@ pub enum DaemonEvent {
     ContainerStopped(String),
     /// Container removed successfully
     ContainerRemoved(String),
+    /// Container paused successfully
+    ContainerPaused(String),
+    /// Container unpaused successfully
+    ContainerUnpaused(String),
+    /// Container restarted successfully
+    ContainerRestarted(String),
+    /// Signal delivered to a container
+    ContainerKilled {
+        id: String,
+        signal: String,
+    },
+    /// Container renamed successfully
+    ContainerRenamed {
+        id: String,
+        name: String,
+    },
     /// Network created successfully
     NetworkCreated(String),
     /// Network removed successfully
     NetworkRemoved(String),
-    /// Operation failed with error message
-    OperationFailed(String),
+    /// An operation failed; match on the error to tell causes apart
+    OperationFailed(DaemonError),
+    /// Connection to the daemon was lost; reconnecting in the background
+    ConnectionLost(String),
+    /// Connection re-established after a loss; lists are being refreshed
+    Reconnected,
+    /// The daemon speaks an incompatible API version; the connection was refused
+    Incompatible(String),
+    /// The daemon's advertised capabilities differ from what the UI assumed
+    CapabilitiesChanged,
+    /// Container created or changed outside the app (from the event stream)
+    ContainerUpdated(ContainerSummary),
+    /// Container state changed, e.g. "exited" or "paused" (from the event stream)
+    ContainerStateChanged {
+        id: String,
+        state: String,
+    },
+    /// Container destroyed (from the event stream)
+    ContainerDestroyed(String),
+    /// Image pulled or tagged (from the event stream)
+    ImageUpdated(ImageSummary),
+    /// Image deleted (from the event stream)
+    ImageDeleted(String),
+    /// Network created, removed, connected or disconnected (from the event stream)
+    NetworkEventReceived(ResourceEvent),
+    /// Volume created, removed, mounted or unmounted (from the event stream)
+    VolumeEventReceived(ResourceEvent),
+    /// Names of the containers that use a volume
+    VolumeUsageLoaded {
+        name: String,
+        containers: Vec<String>,
+    },
     /// Log entry received from container
     LogsReceived {
         container_id: String,
         entry: LogEntry,
     },
+    /// Resource usage sample for a watched container
+    StatsReceived {
+        container_id: String,
+        stats: ContainerStats,
+    },
 }
 
 impl EventEmitter<DaemonEvent> for DaemonService {}
+
+/// Items of a server stream read by a runtime task (see `read_stream`)
+///
+/// Dropping it aborts the reader task, so an abandoned stream is closed
+/// right away instead of when its next item arrives.
+struct StreamReader<T> {
+    items: std::sync::mpsc::Receiver<Result<T, tonic::Status>>,
+    task: tokio::task::AbortHandle,
+}
+
+impl<T> StreamReader<T> {
+    /// Next item, without blocking; `Disconnected` once the stream ended
+    fn try_recv(&self) -> Result<Result<T, tonic::Status>, std::sync::mpsc::TryRecvError> {
+        self.items.try_recv()
+    }
+}
+
+impl<T> Drop for StreamReader<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Read a server stream on the tokio runtime, passing its items to the
+/// returned reader until it ends, fails (the error is the last item) or
+/// the reader is dropped
+///
+/// The reader is a runtime task rather than a `block_on` on a background
+/// executor thread, so a long-lived stream doesn't hold a thread for its
+/// whole life (and the test executor, which runs background work on the
+/// test thread, isn't blocked by one).
+fn read_stream<T: Send + 'static>(
+    runtime: &tokio::runtime::Runtime,
+    mut stream: tonic::Streaming<T>,
+    description: String,
+) -> StreamReader<T> {
+    let (tx, items) = std::sync::mpsc::channel();
+    let task = runtime.spawn(async move {
+        while let Some(item) = stream.next().await {
+            if let Err(status) = &item {
+                tracing::debug!("Stream of {} failed: {}", description, status);
+            }
+            let failed = item.is_err();
+            if tx.send(item).is_err() || failed {
+                return;
+            }
+        }
+        tracing::debug!("Stream of {} ended", description);
+    });
+    StreamReader {
+        items,
+        task: task.abort_handle(),
+    }
+}
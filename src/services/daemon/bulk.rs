@@ -0,0 +1,173 @@
+//! Lifecycle actions on many containers at once.
+//!
+//! The RPCs run concurrently, but only a few at a time so a large selection
+//! doesn't flood the daemon. Each container's outcome is sent back as soon
+//! as it finishes, so the caller can show progress. No per-container events
+//! are emitted; the caller summarises instead of raising one toast each.
+
+use arcbox_api::generated::{
+    container_service_client::ContainerServiceClient, RemoveContainerRequest,
+    RestartContainerRequest, StartContainerRequest, StopContainerRequest,
+};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use futures::StreamExt;
+use gpui::*;
+use tonic::transport::Channel;
+
+use super::{DaemonError, DaemonOperation, DaemonService};
+
+/// Most RPCs a bulk action has in flight at once
+pub const BULK_CONCURRENCY: usize = 4;
+
+/// Seconds to wait for a container to stop before killing it
+pub(super) const STOP_TIMEOUT: u32 = 10;
+
+/// An action that can be applied to a selection of containers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    Start,
+    Stop,
+    Restart,
+    /// Remove; running containers refuse with a conflict
+    Remove,
+    /// Remove, killing running containers first (offered after conflicts)
+    ForceRemove,
+}
+
+impl BulkAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BulkAction::Start => "Start",
+            BulkAction::Stop => "Stop",
+            BulkAction::Restart => "Restart",
+            BulkAction::Remove => "Remove",
+            BulkAction::ForceRemove => "Force Remove",
+        }
+    }
+
+    /// "Starting", for progress
+    pub fn progressive(&self) -> &'static str {
+        match self {
+            BulkAction::Start => "Starting",
+            BulkAction::Stop => "Stopping",
+            BulkAction::Restart => "Restarting",
+            BulkAction::Remove | BulkAction::ForceRemove => "Removing",
+        }
+    }
+
+    /// "Started", for the summary
+    pub fn past(&self) -> &'static str {
+        match self {
+            BulkAction::Start => "Started",
+            BulkAction::Stop => "Stopped",
+            BulkAction::Restart => "Restarted",
+            BulkAction::Remove | BulkAction::ForceRemove => "Removed",
+        }
+    }
+
+    pub fn operation(&self) -> DaemonOperation {
+        match self {
+            BulkAction::Start => DaemonOperation::StartContainer,
+            BulkAction::Stop => DaemonOperation::StopContainer,
+            BulkAction::Restart => DaemonOperation::RestartContainer,
+            BulkAction::Remove | BulkAction::ForceRemove => DaemonOperation::RemoveContainer,
+        }
+    }
+
+    async fn call(self, mut client: ContainerServiceClient<Channel>, id: String) -> Result<(), tonic::Status> {
+        match self {
+            BulkAction::Start => {
+                client.start_container(tonic::Request::new(StartContainerRequest { id })).await?;
+            }
+            BulkAction::Stop => {
+                let request = StopContainerRequest { id, timeout: STOP_TIMEOUT };
+                client.stop_container(tonic::Request::new(request)).await?;
+            }
+            BulkAction::Restart => {
+                let request = RestartContainerRequest { id, timeout: STOP_TIMEOUT };
+                client.restart_container(tonic::Request::new(request)).await?;
+            }
+            BulkAction::Remove | BulkAction::ForceRemove => {
+                let request = RemoveContainerRequest {
+                    id,
+                    force: self == BulkAction::ForceRemove,
+                    remove_volumes: false,
+                };
+                client.remove_container(tonic::Request::new(request)).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How one container in a bulk action fared
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkResult {
+    pub id: String,
+    pub result: Result<(), DaemonError>,
+}
+
+impl DaemonService {
+    /// Apply `action` to each container, at most `BULK_CONCURRENCY` at a time
+    ///
+    /// Results arrive in completion order and the channel closes once every
+    /// container is done. The container list is refreshed afterwards.
+    pub fn bulk_container_action(
+        &self,
+        action: BulkAction,
+        ids: Vec<String>,
+        cx: &mut Context<Self>,
+    ) -> UnboundedReceiver<BulkResult> {
+        let (results, receiver) = unbounded();
+        let Some(client) = self.container_client() else {
+            tracing::warn!("Not connected to daemon");
+            for id in ids {
+                let result = Err(DaemonError::not_connected(action.operation(), Some(id.clone())));
+                results.unbounded_send(BulkResult { id, result }).ok();
+            }
+            return receiver;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            tracing::info!("{} {} containers", action.progressive(), ids.len());
+            let lost = cx.background_executor().spawn(async move {
+                runtime.block_on(async move {
+                    let mut lost = None;
+                    let mut outcomes = futures::stream::iter(ids)
+                        .map(|id| {
+                            let client = client.clone();
+                            async move {
+                                let result = action.call(client, id.clone()).await;
+                                (id, result)
+                            }
+                        })
+                        .buffer_unordered(BULK_CONCURRENCY);
+                    while let Some((id, result)) = outcomes.next().await {
+                        let result = result.map_err(|status| {
+                            tracing::error!("Failed to {}: {}", action.operation().describe(Some(&id)), status);
+                            DaemonError::from_status(action.operation(), Some(id.clone()), &status)
+                        });
+                        if let Err(DaemonError::Unavailable { message, .. }) = &result {
+                            lost = Some(message.clone());
+                        }
+                        results.unbounded_send(BulkResult { id, result }).ok();
+                    }
+                    lost
+                })
+            }).await;
+
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    if let Some(message) = lost {
+                        this.connection_lost(message, cx);
+                    } else {
+                        this.list_containers(true, cx);
+                    }
+                })
+            }).ok();
+        }).detach();
+
+        receiver
+    }
+}
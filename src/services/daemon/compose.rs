@@ -0,0 +1,263 @@
+//! Scaling compose services.
+//!
+//! A compose service here is just the containers sharing its project and
+//! service labels. New replicas are copied from the lowest-numbered one and
+//! named the way Compose names them (`<project>-<service>-<n>`); surplus
+//! replicas are removed highest number first. Published host ports are not
+//! copied, since they would clash with the replica they came from.
+
+use arcbox_api::generated::{
+    InspectContainerRequest, RemoveContainerRequest, StartContainerRequest, StopContainerRequest,
+};
+use gpui::*;
+
+use super::bulk::STOP_TIMEOUT;
+use super::{ContainerInspect, ContainerSpec, DaemonError, DaemonOperation, DaemonService, MountKind, MountSpec};
+use crate::models::{ContainerViewModel, COMPOSE_NUMBER_LABEL};
+
+impl DaemonService {
+    /// Run `count` replicas of the compose service `replicas` belong to
+    ///
+    /// Stops at the first failure. The container list is refreshed afterwards
+    /// either way, so whatever did change shows up.
+    pub fn scale_service(
+        &self,
+        replicas: Vec<ContainerViewModel>,
+        count: usize,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<(), DaemonError>> {
+        let mut numbered: Vec<(u32, ContainerViewModel)> = replicas
+            .into_iter()
+            .map(|c| (c.compose_number().unwrap_or(0), c))
+            .collect();
+        numbered.sort_by_key(|(number, _)| *number);
+        let Some((_, template)) = numbered.first().cloned() else {
+            return Task::ready(Ok(()));
+        };
+        let Some(mut client) = self.container_client() else {
+            return Task::ready(Err(DaemonError::not_connected(
+                DaemonOperation::CreateContainer,
+                Some(template.name),
+            )));
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        let next = numbered.last().map_or(1, |(number, _)| number + 1);
+        let missing = count.saturating_sub(numbered.len()) as u32;
+        let surplus: Vec<ContainerViewModel> = numbered.into_iter().skip(count).rev().map(|(_, c)| c).collect();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            tracing::info!("Scaling {} to {} replicas", template.name, count);
+            let result = cx.background_executor().spawn(async move {
+                runtime.block_on(async move {
+                    for container in surplus {
+                        let id = container.id;
+                        if container.state.is_running() {
+                            let request = StopContainerRequest { id: id.clone(), timeout: STOP_TIMEOUT };
+                            client.stop_container(tonic::Request::new(request)).await.map_err(|status| {
+                                DaemonError::from_status(DaemonOperation::StopContainer, Some(id.clone()), &status)
+                            })?;
+                        }
+                        let request = RemoveContainerRequest {
+                            id: id.clone(),
+                            force: true,
+                            remove_volumes: false,
+                        };
+                        client.remove_container(tonic::Request::new(request)).await.map_err(|status| {
+                            DaemonError::from_status(DaemonOperation::RemoveContainer, Some(id.clone()), &status)
+                        })?;
+                    }
+                    if missing == 0 {
+                        return Ok(());
+                    }
+
+                    let id = template.id.clone();
+                    let response = client
+                        .inspect_container(tonic::Request::new(InspectContainerRequest { id: id.clone() }))
+                        .await
+                        .map_err(|status| {
+                            DaemonError::from_status(DaemonOperation::InspectContainer, Some(id.clone()), &status)
+                        })?;
+                    let inspect = ContainerInspect::from_json(&response.into_inner().json).map_err(|e| {
+                        DaemonError::Internal {
+                            operation: DaemonOperation::InspectContainer,
+                            resource: Some(id),
+                            code: tonic::Code::DataLoss,
+                            message: format!("invalid inspect document: {}", e),
+                        }
+                    })?;
+
+                    for number in next..next + missing {
+                        let spec = replica_spec(&template, &inspect, number);
+                        let name = spec.name.clone();
+                        let response = client
+                            .create_container(tonic::Request::new(spec.into_request()))
+                            .await
+                            .map_err(|status| {
+                                DaemonError::from_status(DaemonOperation::CreateContainer, name, &status)
+                            })?;
+                        let id = response.into_inner().id;
+                        client
+                            .start_container(tonic::Request::new(StartContainerRequest { id: id.clone() }))
+                            .await
+                            .map_err(|status| {
+                                DaemonError::from_status(DaemonOperation::StartContainer, Some(id), &status)
+                            })?;
+                    }
+                    Ok(())
+                })
+            }).await;
+
+            if let Err(error) = &result {
+                tracing::error!("Failed to scale {}: {}", template.name, error.message());
+            }
+            cx.update(|cx| {
+                this.update(cx, |this, cx| match &result {
+                    Err(DaemonError::Unavailable { message, .. }) => this.connection_lost(message.clone(), cx),
+                    _ => this.list_containers(true, cx),
+                })
+            }).ok();
+            result
+        })
+    }
+}
+
+/// Another replica of `template`, numbered `number`
+///
+/// Everything a spec can hold is copied from the inspect document except
+/// published host ports, which are already taken by the template.
+fn replica_spec(template: &ContainerViewModel, inspect: &ContainerInspect, number: u32) -> ContainerSpec {
+    let name = match (&template.compose_project, template.compose_service()) {
+        (Some(project), Some(service)) => format!("{}-{}-{}", project, service, number),
+        _ => format!("{}-{}", template.name, number),
+    };
+
+    let mut labels: Vec<(String, String)> = template
+        .labels
+        .iter()
+        .filter(|(key, _)| key.as_str() != COMPOSE_NUMBER_LABEL)
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    labels.push((COMPOSE_NUMBER_LABEL.to_string(), number.to_string()));
+    labels.sort();
+
+    let mounts = inspect
+        .mounts
+        .iter()
+        .filter_map(|mount| {
+            let (kind, source) = match mount.kind.as_str() {
+                // Anonymous volumes belong to one container; give the replica its own
+                "volume" if is_anonymous_volume(&mount.source) => (MountKind::Volume, String::new()),
+                "volume" => (MountKind::Volume, mount.source.clone()),
+                "bind" => (MountKind::Bind, mount.source.clone()),
+                _ => return None,
+            };
+            Some(MountSpec {
+                kind,
+                source,
+                target: mount.destination.clone(),
+                read_only: mount.read_only,
+            })
+        })
+        .collect();
+
+    let non_empty = |text: &str| (!text.is_empty()).then(|| text.to_string());
+    let resources = &inspect.resources;
+
+    ContainerSpec {
+        image: template.image.clone(),
+        name: Some(name),
+        cmd: inspect.cmd.clone(),
+        entrypoint: inspect.entrypoint.clone(),
+        working_dir: non_empty(&inspect.working_dir),
+        platform: non_empty(&inspect.platform),
+        restart_policy: non_empty(&inspect.restart_policy.name),
+        env: inspect.env.clone(),
+        mounts,
+        networks: inspect.networks.iter().map(|n| n.name.clone()).collect(),
+        labels,
+        cpus: (resources.cpus > 0.0).then_some(resources.cpus),
+        memory_bytes: (resources.memory_bytes > 0).then_some(resources.memory_bytes),
+        auto_remove: inspect.auto_remove,
+        privileged: inspect.privileged,
+        read_only: inspect.read_only,
+        init: inspect.init,
+        ..Default::default()
+    }
+}
+
+/// Anonymous volumes are named with 64 hex digits
+fn is_anonymous_volume(name: &str) -> bool {
+    name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::models::{ContainerState, COMPOSE_PROJECT_LABEL, COMPOSE_SERVICE_LABEL};
+    use crate::services::{MountInfo, NetworkEndpoint};
+
+    #[test]
+    fn copies_replica_from_template() {
+        let labels = HashMap::from([
+            (COMPOSE_PROJECT_LABEL.to_string(), "shop".to_string()),
+            (COMPOSE_SERVICE_LABEL.to_string(), "web".to_string()),
+            (COMPOSE_NUMBER_LABEL.to_string(), "1".to_string()),
+        ]);
+        let template = ContainerViewModel {
+            id: "abc".to_string(),
+            name: "shop-web-1".to_string(),
+            image: "nginx:latest".to_string(),
+            state: ContainerState::Running,
+            ports: Vec::new(),
+            created_at: Utc::now(),
+            compose_project: Some("shop".to_string()),
+            labels,
+            cpu_percent: 0.0,
+            memory_mb: 0.0,
+            memory_limit_mb: 0.0,
+        };
+        let mount = |kind: &str, source: &str, destination: &str| MountInfo {
+            kind: kind.to_string(),
+            source: source.to_string(),
+            destination: destination.to_string(),
+            read_only: false,
+        };
+        let inspect = ContainerInspect {
+            cmd: vec!["nginx".to_string()],
+            env: vec![("MODE".to_string(), "prod".to_string())],
+            mounts: vec![
+                mount("volume", "shop_data", "/data"),
+                mount("volume", &"a1".repeat(32), "/cache"),
+                mount("bind", "/src/shop", "/app"),
+                mount("tmpfs", "", "/tmp"),
+            ],
+            networks: vec![NetworkEndpoint {
+                name: "shop_default".to_string(),
+                ..Default::default()
+            }],
+            privileged: true,
+            init: true,
+            platform: "linux/arm64".to_string(),
+            ..Default::default()
+        };
+
+        let spec = replica_spec(&template, &inspect, 3);
+        assert_eq!(spec.name.as_deref(), Some("shop-web-3"));
+        assert_eq!(spec.image, "nginx:latest");
+        assert_eq!(spec.cmd, ["nginx"]);
+        assert_eq!(spec.networks, ["shop_default"]);
+        assert!(spec.labels.contains(&(COMPOSE_NUMBER_LABEL.to_string(), "3".to_string())));
+        assert_eq!(spec.labels.iter().filter(|(key, _)| key == COMPOSE_NUMBER_LABEL).count(), 1);
+        let mounts: Vec<(&str, &str)> = spec.mounts.iter().map(|m| (m.source.as_str(), m.target.as_str())).collect();
+        assert_eq!(mounts, [("shop_data", "/data"), ("", "/cache"), ("/src/shop", "/app")]);
+        assert!(spec.ports.is_empty());
+        assert_eq!(spec.working_dir, None);
+        assert!(spec.privileged && spec.init && !spec.read_only);
+        assert_eq!(spec.platform.as_deref(), Some("linux/arm64"));
+    }
+}
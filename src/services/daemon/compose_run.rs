@@ -0,0 +1,554 @@
+//! Running compose projects.
+//!
+//! `up`, `down` and `pull` for a parsed `ComposeProject`, done with the
+//! daemon's own RPCs instead of a compose binary. Networks come first, then
+//! services in dependency order; a service waits for `service_healthy` and
+//! `service_completed_successfully` dependencies by polling inspect. Each
+//! network and service reports its progress as it goes, and one failing
+//! skips the services that depend on it rather than stopping the run.
+//!
+//! `up` starts an existing container of the same name as it is; it doesn't
+//! recreate containers whose configuration changed. `down` keeps volumes.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use arcbox_api::generated::{
+    container_service_client::ContainerServiceClient, image_service_client::ImageServiceClient,
+    network_service_client::NetworkServiceClient, ContainerSummary, CreateNetworkRequest, InspectContainerRequest,
+    ListContainersRequest, ListImagesRequest, ListNetworksRequest, PullImageRequest, RemoveContainerRequest,
+    RemoveNetworkRequest, StartContainerRequest, StopContainerRequest,
+};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use gpui::*;
+use tonic::transport::Channel;
+
+use super::bulk::STOP_TIMEOUT;
+use super::{ContainerInspect, ContainerSpec, DaemonError, DaemonOperation, DaemonService};
+use crate::models::{COMPOSE_PROJECT_LABEL, COMPOSE_SERVICE_LABEL};
+use crate::services::{ComposeProject, DependencyCondition, PullPolicy};
+
+/// How often to check on a dependency that has to be healthy or finished
+const DEPENDENCY_POLL: Duration = Duration::from_secs(1);
+
+/// Give up on such a dependency after this long
+const DEPENDENCY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// What to do with a compose project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComposeCommand {
+    /// Create and start services, with their networks
+    Up,
+    /// Stop and remove services, then their networks
+    Down,
+    /// Pull the services' images
+    Pull,
+}
+
+impl ComposeCommand {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComposeCommand::Up => "Up",
+            ComposeCommand::Down => "Down",
+            ComposeCommand::Pull => "Pull",
+        }
+    }
+}
+
+/// Something a compose command works on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ComposeStep {
+    /// A network, by its name on the daemon
+    Network(String),
+    /// A service, by its name in the compose file
+    Service(String),
+}
+
+/// Where a step is at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComposeStatus {
+    /// In progress, e.g. "Pulling image"
+    Working(String),
+    /// Finished, e.g. "Started"
+    Done(String),
+    Failed(String),
+    /// Not attempted, with the reason
+    Skipped(String),
+}
+
+impl ComposeStatus {
+    pub fn message(&self) -> &str {
+        match self {
+            ComposeStatus::Working(message)
+            | ComposeStatus::Done(message)
+            | ComposeStatus::Failed(message)
+            | ComposeStatus::Skipped(message) => message,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, ComposeStatus::Working(_))
+    }
+}
+
+/// A step's new status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeProgress {
+    pub step: ComposeStep,
+    pub status: ComposeStatus,
+}
+
+impl DaemonService {
+    /// Run `command` on `services` of `project` (names in start order, e.g.
+    /// from `ComposeProject::active_services`)
+    ///
+    /// Progress arrives as each step changes and the channel closes when the
+    /// command is done. The container and network lists are refreshed
+    /// afterwards.
+    pub fn run_compose(
+        &self,
+        command: ComposeCommand,
+        project: ComposeProject,
+        services: Vec<String>,
+        cx: &mut Context<Self>,
+    ) -> UnboundedReceiver<ComposeProgress> {
+        let (progress, receiver) = unbounded();
+        let (Some(containers), Some(images), Some(networks)) =
+            (self.container_client(), self.image_client(), self.network_client())
+        else {
+            tracing::warn!("Not connected to daemon");
+            let message = DaemonError::not_connected(DaemonOperation::ListContainers, None).message().to_string();
+            for service in services {
+                let status = ComposeStatus::Failed(message.clone());
+                progress.unbounded_send(ComposeProgress { step: ComposeStep::Service(service), status }).ok();
+            }
+            return receiver;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            tracing::info!("Compose {} for {} ({} services)", command.label(), project.name, services.len());
+            let lost = cx.background_executor().spawn(async move {
+                runtime.block_on(async move {
+                    let mut runner = Runner {
+                        project,
+                        containers,
+                        images,
+                        networks,
+                        progress,
+                        lost: None,
+                    };
+                    match command {
+                        ComposeCommand::Up => runner.up(&services).await,
+                        ComposeCommand::Down => runner.down(&services).await,
+                        ComposeCommand::Pull => runner.pull(&services).await,
+                    }
+                    runner.lost
+                })
+            }).await;
+
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    if let Some(message) = lost {
+                        this.connection_lost(message, cx);
+                    } else {
+                        this.list_containers(true, cx);
+                        this.list_networks(cx);
+                    }
+                })
+            }).ok();
+        }).detach();
+
+        receiver
+    }
+}
+
+/// One compose command in flight
+struct Runner {
+    project: ComposeProject,
+    containers: ContainerServiceClient<Channel>,
+    images: ImageServiceClient<Channel>,
+    networks: NetworkServiceClient<Channel>,
+    progress: UnboundedSender<ComposeProgress>,
+    /// Set when the daemon went away; later steps are failed without trying
+    lost: Option<String>,
+}
+
+impl Runner {
+    fn report(&self, step: &ComposeStep, status: ComposeStatus) {
+        let update = ComposeProgress { step: step.clone(), status };
+        self.progress.unbounded_send(update).ok();
+    }
+
+    /// Report a failed step, noting a lost connection
+    fn fail(&mut self, step: &ComposeStep, error: DaemonError) {
+        tracing::error!("Compose step {:?} failed: {}", step, error.message());
+        if let DaemonError::Unavailable { message, .. } = &error {
+            self.lost = Some(message.clone());
+        }
+        self.report(step, ComposeStatus::Failed(error.message().to_string()));
+    }
+
+    /// Fail the step up front if the connection is already gone
+    fn connected(&self, step: &ComposeStep) -> bool {
+        if let Some(message) = &self.lost {
+            self.report(step, ComposeStatus::Failed(message.clone()));
+            return false;
+        }
+        true
+    }
+
+    async fn up(&mut self, services: &[String]) {
+        let existing_networks = match self.list_networks().await {
+            Ok(networks) => networks,
+            Err(error) => return self.fail_all(services, error),
+        };
+        let mut failed_networks = HashSet::new();
+        let networks: Vec<_> = self.project.networks_for(services).into_iter().cloned().collect();
+        for network in networks {
+            let step = ComposeStep::Network(network.name.clone());
+            if !self.connected(&step) {
+                failed_networks.insert(network.name);
+                continue;
+            }
+            if existing_networks.contains(&network.name) {
+                self.report(&step, ComposeStatus::Done("Exists".to_string()));
+                continue;
+            }
+            if network.external {
+                self.report(&step, ComposeStatus::Failed("External network not found".to_string()));
+                failed_networks.insert(network.name);
+                continue;
+            }
+            self.report(&step, ComposeStatus::Working("Creating".to_string()));
+            let request = CreateNetworkRequest {
+                name: network.name.clone(),
+                driver: network.driver.clone().unwrap_or_default(),
+                internal: network.internal,
+                labels: network.labels.iter().cloned().collect(),
+            };
+            match self.networks.create_network(tonic::Request::new(request)).await {
+                Ok(_) => self.report(&step, ComposeStatus::Done("Created".to_string())),
+                Err(status) => {
+                    let error = DaemonError::from_status(DaemonOperation::CreateNetwork, Some(network.name.clone()), &status);
+                    self.fail(&step, error);
+                    failed_networks.insert(network.name);
+                }
+            }
+        }
+
+        let existing = match self.list_containers().await {
+            Ok(containers) => containers,
+            Err(error) => return self.fail_all(services, error),
+        };
+        let mut failed: HashSet<String> = HashSet::new();
+        for name in services {
+            let step = ComposeStep::Service(name.clone());
+            let service = self.project.services[name].clone();
+            if !self.connected(&step) {
+                failed.insert(name.clone());
+                continue;
+            }
+            if let Some(dependency) = service.depends_on.iter().find(|d| failed.contains(&d.service)) {
+                let reason = format!("{} failed", dependency.service);
+                self.report(&step, ComposeStatus::Skipped(reason));
+                failed.insert(name.clone());
+                continue;
+            }
+            if service.spec.networks.iter().any(|network| failed_networks.contains(network)) {
+                self.report(&step, ComposeStatus::Skipped("Its network failed".to_string()));
+                failed.insert(name.clone());
+                continue;
+            }
+
+            let mut ready = true;
+            for dependency in &service.depends_on {
+                if dependency.condition == DependencyCondition::Started {
+                    continue;
+                }
+                self.report(&step, ComposeStatus::Working(format!("Waiting for {}", dependency.service)));
+                if let Err(reason) = self.wait_for(&dependency.service, dependency.condition).await {
+                    self.report(&step, ComposeStatus::Skipped(reason));
+                    ready = false;
+                    break;
+                }
+            }
+            if !ready {
+                failed.insert(name.clone());
+                continue;
+            }
+
+            let container_name = service.spec.name.clone().unwrap_or_default();
+            let result = match existing.iter().find(|c| c.name.trim_start_matches('/') == container_name) {
+                Some(container) if container.state == "running" => {
+                    self.report(&step, ComposeStatus::Done("Running".to_string()));
+                    continue;
+                }
+                Some(container) => self.start(&step, container.id.clone()).await,
+                None => self.create(&step, service.pull_policy, service.spec).await,
+            };
+            match result {
+                Ok(()) => self.report(&step, ComposeStatus::Done("Started".to_string())),
+                Err(error) => {
+                    self.fail(&step, error);
+                    failed.insert(name.clone());
+                }
+            }
+        }
+    }
+
+    /// Pull if `policy` asks for it, then create and start
+    async fn create(&mut self, step: &ComposeStep, policy: PullPolicy, spec: ContainerSpec) -> Result<(), DaemonError> {
+        let pull = match policy {
+            PullPolicy::Always => true,
+            PullPolicy::Never => false,
+            PullPolicy::Missing => !self.has_image(&spec.image).await?,
+        };
+        if pull {
+            self.report(step, ComposeStatus::Working("Pulling image".to_string()));
+            self.pull_image(&spec.image).await?;
+        }
+
+        self.report(step, ComposeStatus::Working("Creating".to_string()));
+        let name = spec.name.clone();
+        let response = self
+            .containers
+            .create_container(tonic::Request::new(spec.into_request()))
+            .await
+            .map_err(|status| DaemonError::from_status(DaemonOperation::CreateContainer, name, &status))?;
+        self.start(step, response.into_inner().id).await
+    }
+
+    async fn start(&mut self, step: &ComposeStep, id: String) -> Result<(), DaemonError> {
+        self.report(step, ComposeStatus::Working("Starting".to_string()));
+        self.containers
+            .start_container(tonic::Request::new(StartContainerRequest { id: id.clone() }))
+            .await
+            .map_err(|status| DaemonError::from_status(DaemonOperation::StartContainer, Some(id), &status))?;
+        Ok(())
+    }
+
+    /// Wait until `service`'s container meets `condition`, or say why it won't
+    async fn wait_for(&mut self, service: &str, condition: DependencyCondition) -> Result<(), String> {
+        let name = self.project.services[service].spec.name.clone().unwrap_or_default();
+        let deadline = Instant::now() + DEPENDENCY_TIMEOUT;
+        loop {
+            let request = InspectContainerRequest { id: name.clone() };
+            let inspect = match self.containers.inspect_container(tonic::Request::new(request)).await {
+                Ok(response) => ContainerInspect::from_json(&response.into_inner().json)
+                    .map_err(|e| format!("Can't read {}'s state: {}", service, e))?,
+                Err(status) => {
+                    let error = DaemonError::from_status(DaemonOperation::InspectContainer, Some(name), &status);
+                    if let DaemonError::Unavailable { message, .. } = &error {
+                        self.lost = Some(message.clone());
+                    }
+                    return Err(error.message().to_string());
+                }
+            };
+
+            let exited = matches!(inspect.state.status.as_str(), "exited" | "dead");
+            match condition {
+                DependencyCondition::Started => return Ok(()),
+                DependencyCondition::Healthy => match inspect.health.as_ref().map(|h| h.status.as_str()) {
+                    None => return Err(format!("{} has no health check", service)),
+                    Some("healthy") => return Ok(()),
+                    Some("unhealthy") => return Err(format!("{} is unhealthy", service)),
+                    Some(_) if exited => return Err(format!("{} exited", service)),
+                    Some(_) => {}
+                },
+                DependencyCondition::CompletedSuccessfully if exited => {
+                    return match inspect.state.exit_code {
+                        0 => Ok(()),
+                        code => Err(format!("{} exited with code {}", service, code)),
+                    };
+                }
+                DependencyCondition::CompletedSuccessfully => {}
+            }
+
+            if Instant::now() >= deadline {
+                return Err(format!("Timed out waiting for {}", service));
+            }
+            tokio::time::sleep(DEPENDENCY_POLL).await;
+        }
+    }
+
+    async fn down(&mut self, services: &[String]) {
+        let existing = match self.list_containers().await {
+            Ok(containers) => containers,
+            Err(error) => return self.fail_all(services, error),
+        };
+        let project = self.project.name.clone();
+        for name in services.iter().rev() {
+            let step = ComposeStep::Service(name.clone());
+            if !self.connected(&step) {
+                continue;
+            }
+            let replicas: Vec<&ContainerSummary> = existing
+                .iter()
+                .filter(|c| {
+                    c.labels.get(COMPOSE_PROJECT_LABEL) == Some(&project)
+                        && c.labels.get(COMPOSE_SERVICE_LABEL) == Some(name)
+                })
+                .collect();
+            if replicas.is_empty() {
+                self.report(&step, ComposeStatus::Skipped("Not created".to_string()));
+                continue;
+            }
+
+            self.report(&step, ComposeStatus::Working("Removing".to_string()));
+            let mut result = Ok(());
+            for container in replicas {
+                result = self.remove(container).await;
+                if result.is_err() {
+                    break;
+                }
+            }
+            match result {
+                Ok(()) => self.report(&step, ComposeStatus::Done("Removed".to_string())),
+                Err(error) => self.fail(&step, error),
+            }
+        }
+
+        let existing_networks = match self.list_networks().await {
+            Ok(networks) => networks,
+            Err(error) => {
+                if let DaemonError::Unavailable { message, .. } = &error {
+                    self.lost = Some(message.clone());
+                }
+                return;
+            }
+        };
+        let networks: Vec<_> = self.project.networks_for(services).into_iter().cloned().collect();
+        for network in networks.into_iter().filter(|n| !n.external) {
+            let step = ComposeStep::Network(network.name.clone());
+            if !self.connected(&step) {
+                continue;
+            }
+            if !existing_networks.contains(&network.name) {
+                self.report(&step, ComposeStatus::Skipped("Not found".to_string()));
+                continue;
+            }
+            self.report(&step, ComposeStatus::Working("Removing".to_string()));
+            let request = RemoveNetworkRequest { id: network.name.clone() };
+            match self.networks.remove_network(tonic::Request::new(request)).await {
+                Ok(_) => self.report(&step, ComposeStatus::Done("Removed".to_string())),
+                Err(status) => {
+                    let error = DaemonError::from_status(DaemonOperation::RemoveNetwork, Some(network.name), &status);
+                    self.fail(&step, error);
+                }
+            }
+        }
+    }
+
+    /// Stop if running, then remove (keeping volumes)
+    async fn remove(&mut self, container: &ContainerSummary) -> Result<(), DaemonError> {
+        let id = container.id.clone();
+        if container.state == "running" {
+            let request = StopContainerRequest { id: id.clone(), timeout: STOP_TIMEOUT };
+            self.containers
+                .stop_container(tonic::Request::new(request))
+                .await
+                .map_err(|status| DaemonError::from_status(DaemonOperation::StopContainer, Some(id.clone()), &status))?;
+        }
+        let request = RemoveContainerRequest {
+            id: id.clone(),
+            force: true,
+            remove_volumes: false,
+        };
+        self.containers
+            .remove_container(tonic::Request::new(request))
+            .await
+            .map_err(|status| DaemonError::from_status(DaemonOperation::RemoveContainer, Some(id), &status))?;
+        Ok(())
+    }
+
+    async fn pull(&mut self, services: &[String]) {
+        // Services sharing an image only pull it once
+        let mut pulled: HashMap<String, Result<(), DaemonError>> = HashMap::new();
+        for name in services {
+            let step = ComposeStep::Service(name.clone());
+            if !self.connected(&step) {
+                continue;
+            }
+            let service = &self.project.services[name];
+            if service.pull_policy == PullPolicy::Never {
+                self.report(&step, ComposeStatus::Skipped("pull_policy is never".to_string()));
+                continue;
+            }
+            let image = service.spec.image.clone();
+            self.report(&step, ComposeStatus::Working("Pulling image".to_string()));
+            let result = match pulled.get(&image) {
+                Some(result) => result.clone(),
+                None => {
+                    let result = self.pull_image(&image).await;
+                    pulled.insert(image, result.clone());
+                    result
+                }
+            };
+            match result {
+                Ok(()) => self.report(&step, ComposeStatus::Done("Pulled".to_string())),
+                Err(error) => self.fail(&step, error),
+            }
+        }
+    }
+
+    /// Pull `image`, waiting for the progress stream to finish
+    async fn pull_image(&mut self, image: &str) -> Result<(), DaemonError> {
+        let error = |status: &tonic::Status| DaemonError::from_status(DaemonOperation::PullImage, Some(image.to_string()), status);
+        let request = PullImageRequest {
+            reference: image.to_string(),
+            ..Default::default()
+        };
+        let mut stream = self
+            .images
+            .pull_image(tonic::Request::new(request))
+            .await
+            .map_err(|status| error(&status))?
+            .into_inner();
+        while stream.message().await.map_err(|status| error(&status))?.is_some() {}
+        Ok(())
+    }
+
+    async fn has_image(&mut self, image: &str) -> Result<bool, DaemonError> {
+        let request = ListImagesRequest {
+            filters: HashMap::from([("reference".to_string(), image.to_string())]),
+            ..Default::default()
+        };
+        let response = self
+            .images
+            .list_images(tonic::Request::new(request))
+            .await
+            .map_err(|status| DaemonError::from_status(DaemonOperation::ListImages, Some(image.to_string()), &status))?;
+        Ok(!response.into_inner().images.is_empty())
+    }
+
+    async fn list_containers(&mut self) -> Result<Vec<ContainerSummary>, DaemonError> {
+        let request = ListContainersRequest {
+            all: true,
+            ..Default::default()
+        };
+        let response = self
+            .containers
+            .list_containers(tonic::Request::new(request))
+            .await
+            .map_err(|status| DaemonError::from_status(DaemonOperation::ListContainers, None, &status))?;
+        Ok(response.into_inner().containers)
+    }
+
+    /// Names of the daemon's networks
+    async fn list_networks(&mut self) -> Result<HashSet<String>, DaemonError> {
+        let response = self
+            .networks
+            .list_networks(tonic::Request::new(ListNetworksRequest::default()))
+            .await
+            .map_err(|status| DaemonError::from_status(DaemonOperation::ListNetworks, None, &status))?;
+        Ok(response.into_inner().networks.into_iter().map(|n| n.name).collect())
+    }
+
+    /// Nothing could be done; fail every service with `error`
+    fn fail_all(&mut self, services: &[String], error: DaemonError) {
+        for name in services {
+            self.fail(&ComposeStep::Service(name.clone()), error.clone());
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! Container creation options.
+//!
+//! `ContainerSpec` holds everything the New Container dialog can set, in
+//! the same terms as `docker run`. It is turned into a `CreateContainerRequest`
+//! only when sent, so the dialog (and anything else that builds containers)
+//! never deals with the wire format.
+
+use std::collections::HashMap;
+
+use arcbox_api::generated::{CreateContainerRequest, Mount, PortBinding};
+
+/// How to create a container
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContainerSpec {
+    pub image: String,
+    pub name: Option<String>,
+    /// Empty means the image's default
+    pub cmd: Vec<String>,
+    /// Empty means the image's default
+    pub entrypoint: Vec<String>,
+    pub working_dir: Option<String>,
+    /// e.g. "linux/arm64" (None lets the daemon pick)
+    pub platform: Option<String>,
+    /// "no", "always", "on-failure" or "unless-stopped"
+    pub restart_policy: Option<String>,
+    /// Remove the container when it exits (`--rm`)
+    pub auto_remove: bool,
+    pub privileged: bool,
+    /// Mount the root filesystem read-only
+    pub read_only: bool,
+    /// Run an init process as PID 1 (`--init`)
+    pub init: bool,
+    pub ports: Vec<PortMapping>,
+    /// Environment as (name, value)
+    pub env: Vec<(String, String)>,
+    pub mounts: Vec<MountSpec>,
+    /// Networks to attach, by name
+    pub networks: Vec<String>,
+    pub labels: Vec<(String, String)>,
+    /// Number of CPUs, e.g. 1.5
+    pub cpus: Option<f64>,
+    pub memory_bytes: Option<i64>,
+}
+
+/// A published port
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortMapping {
+    /// Host address to bind (empty for all interfaces)
+    pub host_ip: String,
+    pub host_port: u16,
+    pub container_port: u16,
+    /// "tcp" or "udp"
+    pub protocol: String,
+}
+
+impl PortMapping {
+    /// Parse a host port and a container port with optional protocol (`80/tcp`)
+    pub fn parse(host_port: &str, container_port: &str) -> Result<Self, String> {
+        let (container_port, protocol) = match container_port.split_once('/') {
+            Some((port, protocol)) => (port, protocol.to_ascii_lowercase()),
+            None => (container_port, "tcp".to_string()),
+        };
+        if !matches!(protocol.as_str(), "tcp" | "udp" | "sctp") {
+            return Err(format!("Unknown protocol \"{}\"", protocol));
+        }
+        let port = |text: &str, which: &str| match text.parse::<u32>() {
+            Ok(port) if (1..=65535).contains(&port) => Ok(port as u16),
+            Ok(_) => Err(format!("{} port must be between 1 and 65535", which)),
+            Err(_) if text.is_empty() => Err(format!("{} port is required", which)),
+            Err(_) => Err(format!("{} port \"{}\" is not a number", which, text)),
+        };
+        Ok(Self {
+            host_ip: String::new(),
+            host_port: port(host_port, "Host")?,
+            container_port: port(container_port, "Container")?,
+            protocol,
+        })
+    }
+}
+
+/// Kind of a mount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountKind {
+    /// Named volume (created if missing)
+    Volume,
+    /// Host path
+    Bind,
+}
+
+impl MountKind {
+    /// Docker's rule: a source that looks like a path is a bind mount
+    pub fn for_source(source: &str) -> Self {
+        if source.starts_with('/') || source.starts_with('.') || source.starts_with('~') {
+            MountKind::Bind
+        } else {
+            MountKind::Volume
+        }
+    }
+
+    /// The absolute host path a bind source names, with `~` expanded
+    ///
+    /// The daemon can't resolve relative paths or `~` (its working directory
+    /// and home aren't ours), and the app has no meaningful working directory
+    /// to resolve them against, so relative sources are rejected.
+    pub fn bind_source(source: &str) -> Result<String, String> {
+        let home = || dirs::home_dir().ok_or_else(|| "Can't find the home folder to expand ~".to_string());
+        if source == "~" {
+            return Ok(home()?.display().to_string());
+        }
+        if let Some(rest) = source.strip_prefix("~/") {
+            return Ok(home()?.join(rest).display().to_string());
+        }
+        if source.starts_with('/') {
+            Ok(source.to_string())
+        } else {
+            Err("Host path must be absolute".to_string())
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            MountKind::Volume => "volume",
+            MountKind::Bind => "bind",
+        }
+    }
+}
+
+/// A volume or bind mount
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountSpec {
+    pub kind: MountKind,
+    /// Volume name or host path
+    pub source: String,
+    /// Path inside the container
+    pub target: String,
+    pub read_only: bool,
+}
+
+impl ContainerSpec {
+    pub(super) fn into_request(self) -> CreateContainerRequest {
+        let pairs = |pairs: Vec<(String, String)>| -> Vec<String> {
+            pairs.into_iter().map(|(key, value)| format!("{}={}", key, value)).collect()
+        };
+
+        CreateContainerRequest {
+            image: self.image,
+            name: self.name.unwrap_or_default(),
+            cmd: self.cmd,
+            entrypoint: self.entrypoint,
+            working_dir: self.working_dir.unwrap_or_default(),
+            platform: self.platform.unwrap_or_default(),
+            restart_policy: self.restart_policy.unwrap_or_default(),
+            auto_remove: self.auto_remove,
+            privileged: self.privileged,
+            read_only: self.read_only,
+            init: self.init,
+            port_bindings: self
+                .ports
+                .into_iter()
+                .map(|p| PortBinding {
+                    host_ip: p.host_ip,
+                    host_port: u32::from(p.host_port),
+                    container_port: u32::from(p.container_port),
+                    protocol: p.protocol,
+                })
+                .collect(),
+            env: pairs(self.env),
+            mounts: self
+                .mounts
+                .into_iter()
+                .map(|m| Mount {
+                    r#type: m.kind.name().to_string(),
+                    source: m.source,
+                    target: m.target,
+                    read_only: m.read_only,
+                })
+                .collect(),
+            networks: self.networks,
+            labels: self.labels.into_iter().collect::<HashMap<_, _>>(),
+            nano_cpus: self.cpus.map(|cpus| (cpus * 1e9) as i64).unwrap_or(0),
+            memory: self.memory_bytes.unwrap_or(0),
+            ..Default::default()
+        }
+    }
+}
+
+/// Check an image reference such as `nginx`, `ghcr.io/org/app:1.2` or `alpine@sha256:...`
+///
+/// Follows the distribution reference grammar: an optional registry host,
+/// lowercase path components, then an optional tag and digest.
+pub fn validate_image_reference(reference: &str) -> Result<(), String> {
+    if reference.is_empty() {
+        return Err("Image is required".to_string());
+    }
+    if reference.chars().any(char::is_whitespace) {
+        return Err("Image reference cannot contain spaces".to_string());
+    }
+
+    let (rest, digest) = match reference.split_once('@') {
+        Some((rest, digest)) => (rest, Some(digest)),
+        None => (reference, None),
+    };
+    if let Some(digest) = digest {
+        let valid = digest.split_once(':').is_some_and(|(algorithm, hex)| {
+            !algorithm.is_empty()
+                && algorithm.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+._-".contains(c))
+                && hex.len() >= 32
+                && hex.chars().all(|c| c.is_ascii_hexdigit())
+        });
+        if !valid {
+            return Err(format!("Invalid digest \"{}\"", digest));
+        }
+    }
+
+    // A colon after the last slash starts the tag (earlier ones are a registry port)
+    let (name, tag) = match rest.rfind(':') {
+        Some(index) if !rest[index..].contains('/') => (&rest[..index], Some(&rest[index + 1..])),
+        _ => (rest, None),
+    };
+    if let Some(tag) = tag {
+        let valid = (1..=128).contains(&tag.len())
+            && tag.chars().next().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+            && tag.chars().all(|c| c.is_ascii_alphanumeric() || "_.-".contains(c));
+        if !valid {
+            return Err(format!("Invalid tag \"{}\"", tag));
+        }
+    }
+    if name.is_empty() {
+        return Err("Image name is missing".to_string());
+    }
+    if name.len() > 255 {
+        return Err("Image name is longer than 255 characters".to_string());
+    }
+
+    let mut components: Vec<&str> = name.split('/').collect();
+    if components.len() > 1 {
+        let first = components[0];
+        if first.contains('.') || first.contains(':') || first == "localhost" {
+            let (host, port) = match first.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (first, None),
+            };
+            let host_ok = !host.is_empty()
+                && host.split('.').all(|label| {
+                    !label.is_empty()
+                        && !label.starts_with('-')
+                        && !label.ends_with('-')
+                        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                });
+            let port_ok = port.is_none_or(|port| port.parse::<u16>().is_ok());
+            if !host_ok || !port_ok {
+                return Err(format!("Invalid registry \"{}\"", first));
+            }
+            components.remove(0);
+        }
+    }
+    for component in components {
+        if component.chars().any(|c| c.is_ascii_uppercase()) {
+            return Err("Repository name must be lowercase".to_string());
+        }
+        if !is_path_component(component) {
+            return Err(format!("Invalid repository name \"{}\"", name));
+        }
+    }
+    Ok(())
+}
+
+/// Lowercase alphanumerics joined by ".", "_", "__" or runs of "-"
+fn is_path_component(component: &str) -> bool {
+    let alphanumeric = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+    let bytes = component.as_bytes();
+    if bytes.is_empty() || !alphanumeric(bytes[0] as char) || !alphanumeric(bytes[bytes.len() - 1] as char) {
+        return false;
+    }
+    let mut separator = String::new();
+    for c in component.chars() {
+        if alphanumeric(c) {
+            let valid = separator.is_empty()
+                || separator == "."
+                || separator == "_"
+                || separator == "__"
+                || separator.chars().all(|c| c == '-');
+            if !valid {
+                return false;
+            }
+            separator.clear();
+        } else {
+            separator.push(c);
+        }
+    }
+    true
+}
+
+/// Check a container name (`[a-zA-Z0-9][a-zA-Z0-9_.-]+`, as the daemon requires)
+pub fn validate_container_name(name: &str) -> Result<(), String> {
+    let name = name.strip_prefix('/').unwrap_or(name);
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return Err("Name is empty".to_string());
+    };
+    if !first.is_ascii_alphanumeric() {
+        return Err("Name must start with a letter or digit".to_string());
+    }
+    if name.len() < 2 {
+        return Err("Name must be at least 2 characters".to_string());
+    }
+    if let Some(c) = chars.find(|c| !c.is_ascii_alphanumeric() && !"_.-".contains(*c)) {
+        return Err(format!("Name cannot contain \"{}\"", c));
+    }
+    Ok(())
+}
+
+/// Parse a `.env` file into (name, value) pairs
+///
+/// Follows the compose rules: blank lines and `#` comments are skipped, an
+/// optional `export ` prefix is allowed, and values may be wrapped in single
+/// or double quotes. Lines without `=` are skipped.
+pub fn parse_env_file(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            let value = value.trim();
+            let value = if value.len() >= 2
+                && ((value.starts_with('"') && value.ends_with('"'))
+                    || (value.starts_with('\'') && value.ends_with('\'')))
+            {
+                value[1..value.len() - 1].to_string()
+            } else {
+                // Unquoted values end at an inline comment
+                match value.find(" #") {
+                    Some(index) => value[..index].trim_end().to_string(),
+                    None => value.to_string(),
+                }
+            };
+            Some((name.to_string(), value))
+        })
+        .collect()
+}
+
+/// Parse a byte size like `512m`, `1.5g` or `1048576` (binary units, as `docker run -m`)
+pub fn parse_byte_size(text: &str) -> Option<i64> {
+    let text = text.trim().to_ascii_lowercase();
+    let text = text.strip_suffix('b').unwrap_or(&text);
+    let (number, multiplier) = match text.chars().last()? {
+        'k' => (&text[..text.len() - 1], 1i64 << 10),
+        'm' => (&text[..text.len() - 1], 1 << 20),
+        'g' => (&text[..text.len() - 1], 1 << 30),
+        't' => (&text[..text.len() - 1], 1 << 40),
+        _ => (text, 1),
+    };
+    let value: f64 = number.trim().parse().ok()?;
+    (value.is_finite() && value > 0.0).then(|| (value * multiplier as f64) as i64)
+}
+
+/// Format bytes in the largest exact unit, so `parse_byte_size` reads it back (`2g`, `1536m`)
+pub fn format_byte_size(bytes: i64) -> String {
+    for (suffix, size) in [("t", 1i64 << 40), ("g", 1 << 30), ("m", 1 << 20), ("k", 1 << 10)] {
+        if bytes != 0 && bytes % size == 0 {
+            return format!("{}{}", bytes / size, suffix);
+        }
+    }
+    bytes.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_env_files() {
+        let env = parse_env_file(
+            "# comment\n\nexport A=1\nB = two words # note\nC=\"quoted # kept\"\nD='single'\nnot a pair\nE=\n",
+        );
+        let env: Vec<(&str, &str)> = env.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(
+            env,
+            [("A", "1"), ("B", "two words"), ("C", "quoted # kept"), ("D", "single"), ("E", "")]
+        );
+    }
+
+    #[test]
+    fn parses_byte_sizes() {
+        assert_eq!(parse_byte_size("512m"), Some(512 << 20));
+        assert_eq!(parse_byte_size("1.5G"), Some(3 << 29));
+        assert_eq!(parse_byte_size("64kb"), Some(64 << 10));
+        assert_eq!(parse_byte_size("1024"), Some(1024));
+        assert_eq!(parse_byte_size("lots"), None);
+        assert_eq!(parse_byte_size("-1m"), None);
+        assert_eq!(format_byte_size(3 << 29), "1536m");
+        assert_eq!(format_byte_size(1000), "1000");
+    }
+
+    #[test]
+    fn validates_image_references() {
+        for valid in [
+            "nginx",
+            "nginx:1.25-alpine",
+            "library/nginx",
+            "ghcr.io/org/app:v1",
+            "localhost:5000/app",
+            "my-registry.local:5000/team/app_name:latest",
+            "a__b/c.d--e",
+            "alpine@sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        ] {
+            assert_eq!(validate_image_reference(valid), Ok(()), "{}", valid);
+        }
+        for invalid in ["", "Nginx", "nginx:", "nginx:-x", "-nginx", "a___b", "nginx alpine", "host:99999/app", "app@sha256:xyz"] {
+            assert!(validate_image_reference(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn validates_container_names() {
+        assert!(validate_container_name("web").is_ok());
+        assert!(validate_container_name("/my_app.v2-1").is_ok());
+        assert!(validate_container_name("a").is_err());
+        assert!(validate_container_name("_web").is_err());
+        assert!(validate_container_name("web app").is_err());
+    }
+
+    #[test]
+    fn parses_port_mappings() {
+        let port = PortMapping::parse("8080", "80/UDP").unwrap();
+        assert_eq!((port.host_port, port.container_port, port.protocol.as_str()), (8080, 80, "udp"));
+        assert_eq!(PortMapping::parse("8080", "80").unwrap().protocol, "tcp");
+        assert!(PortMapping::parse("0", "80").is_err());
+        assert!(PortMapping::parse("70000", "80").is_err());
+        assert!(PortMapping::parse("", "80").is_err());
+        assert!(PortMapping::parse("80", "80/icmp").is_err());
+    }
+
+    #[test]
+    fn mount_kind_follows_source() {
+        assert_eq!(MountKind::for_source("/srv/data"), MountKind::Bind);
+        assert_eq!(MountKind::for_source("./data"), MountKind::Bind);
+        assert_eq!(MountKind::for_source("pgdata"), MountKind::Volume);
+    }
+
+    #[test]
+    fn bind_sources_are_absolute() {
+        assert_eq!(MountKind::bind_source("/srv/data").unwrap(), "/srv/data");
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(MountKind::bind_source("~").unwrap(), home.display().to_string());
+        assert_eq!(MountKind::bind_source("~/data").unwrap(), home.join("data").display().to_string());
+        assert!(MountKind::bind_source("./data").is_err());
+        assert!(MountKind::bind_source(".").is_err());
+    }
+}
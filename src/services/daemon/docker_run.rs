@@ -0,0 +1,417 @@
+//! `docker run` command lines.
+//!
+//! Converts between a pasted `docker run ...` line and a `ContainerSpec`.
+//! Only the options a spec can hold are imported; anything else is reported
+//! back so the caller can tell the user what was dropped rather than
+//! silently creating a different container.
+
+use crate::services::{join_shell_words, quote_shell_word, split_shell_words};
+
+use super::{format_byte_size, parse_byte_size, ContainerSpec, MountKind, MountSpec, PortMapping};
+
+/// A parsed `docker run` command
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DockerRunCommand {
+    pub spec: ContainerSpec,
+    /// Options that were recognised but can't be represented, as written
+    pub ignored: Vec<String>,
+}
+
+/// Options that take a value, so an unknown one doesn't swallow the image name
+const VALUE_OPTIONS: &[&str] = &[
+    "-a", "--attach", "--add-host", "--annotation", "--blkio-weight", "--cap-add", "--cap-drop",
+    "--cgroup-parent", "--cgroupns", "--cidfile", "--cpu-period", "--cpu-quota", "--cpu-shares",
+    "-c", "--cpuset-cpus", "--cpuset-mems", "--detach-keys", "--device", "--dns", "--dns-option",
+    "--dns-search", "--domainname", "--env-file", "--expose", "--gpus", "--group-add",
+    "--health-cmd", "--health-interval", "--health-retries", "--health-start-period",
+    "--health-timeout", "-h", "--hostname", "--ip", "--ip6", "--ipc", "--isolation",
+    "--label-file", "--link", "--log-driver", "--log-opt", "--mac-address", "--memory-reservation",
+    "--memory-swap", "--memory-swappiness", "--mount", "--network-alias", "--pid", "--pids-limit",
+    "--pull", "--runtime", "--security-opt", "--shm-size", "--stop-signal", "--stop-timeout",
+    "--storage-opt", "--sysctl", "--tmpfs", "-u", "--user", "--ulimit", "--userns", "--uts",
+    "--volume-driver", "--volumes-from",
+];
+
+/// Flags that only affect how the CLI attaches, which the app always does detached
+const ATTACH_FLAGS: &[&str] = &["-d", "--detach", "-i", "--interactive", "-t", "--tty"];
+
+impl DockerRunCommand {
+    /// Parse `docker run [OPTIONS] IMAGE [COMMAND] [ARG...]`
+    ///
+    /// The leading `docker run` (or `docker container run`) is optional.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let words = split_shell_words(line).map_err(|e| format!("Invalid command line: {}", e))?;
+        let mut words = words.into_iter().peekable();
+        if words.peek().is_some_and(|w| w == "docker") {
+            words.next();
+        }
+        if words.peek().is_some_and(|w| w == "container") {
+            words.next();
+        }
+        if words.peek().is_some_and(|w| w == "run") {
+            words.next();
+        }
+
+        let mut command = DockerRunCommand::default();
+        let spec = &mut command.spec;
+        let mut image = None;
+
+        while let Some(word) = words.next() {
+            if word == "--" {
+                image = words.next();
+                break;
+            }
+            if !word.starts_with('-') {
+                image = Some(word);
+                break;
+            }
+
+            // Split `--name=web` and `-p8080:80` into option and inline value
+            let (option, inline) = match word.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => (option.to_string(), Some(value.to_string())),
+                // The option letter may be any character, so split after it
+                // rather than at byte 2
+                _ => match word.char_indices().nth(2) {
+                    Some((split, _)) if !word.starts_with("--") => {
+                        let (option, rest) = word.split_at(split);
+                        if is_flag(option) && rest.chars().all(|c| is_flag(&format!("-{}", c))) {
+                            // Bundled short flags such as -dit
+                            for c in word[1..].chars() {
+                                apply_flag(spec, &mut command.ignored, &format!("-{}", c), None)?;
+                            }
+                            continue;
+                        }
+                        (option.to_string(), Some(rest.to_string()))
+                    }
+                    _ => (word.clone(), None),
+                },
+            };
+
+            if is_flag(&option) {
+                apply_flag(spec, &mut command.ignored, &option, inline.as_deref())?;
+                continue;
+            }
+
+            let takes_value = is_spec_option(&option) || VALUE_OPTIONS.contains(&option.as_str());
+            let value = match inline {
+                Some(value) => value,
+                None if takes_value => words.next().ok_or_else(|| format!("{} needs a value", option))?,
+                None => {
+                    // Unknown option; assume it's a flag
+                    command.ignored.push(option);
+                    continue;
+                }
+            };
+            if !apply_option(spec, &option, &value)? {
+                command.ignored.push(format!("{} {}", option, quote_shell_word(&value)));
+            }
+        }
+
+        spec.image = image.ok_or("No image given")?;
+        spec.cmd = words.collect();
+        Ok(command)
+    }
+}
+
+impl ContainerSpec {
+    /// The equivalent `docker run` command line
+    pub fn to_docker_run(&self) -> String {
+        let mut words: Vec<String> = vec!["docker".into(), "run".into()];
+        let mut option = |name: &str, value: String| {
+            words.push(name.to_string());
+            words.push(value);
+        };
+
+        if let Some(name) = &self.name {
+            option("--name", name.clone());
+        }
+        if let Some(platform) = &self.platform {
+            option("--platform", platform.clone());
+        }
+        if let Some(policy) = &self.restart_policy {
+            option("--restart", policy.clone());
+        }
+        if let Some(dir) = &self.working_dir {
+            option("-w", dir.clone());
+        }
+        // Docker's --entrypoint takes one executable; its arguments lead the command
+        let (entrypoint, arguments) = match self.entrypoint.split_first() {
+            Some((entrypoint, arguments)) => (Some(entrypoint), arguments),
+            None => (None, &[][..]),
+        };
+        if let Some(entrypoint) = entrypoint {
+            option("--entrypoint", entrypoint.clone());
+        }
+        for port in &self.ports {
+            let host = if port.host_ip.is_empty() {
+                port.host_port.to_string()
+            } else if port.host_ip.contains(':') {
+                format!("[{}]:{}", port.host_ip, port.host_port)
+            } else {
+                format!("{}:{}", port.host_ip, port.host_port)
+            };
+            let container = if port.protocol == "tcp" {
+                port.container_port.to_string()
+            } else {
+                format!("{}/{}", port.container_port, port.protocol)
+            };
+            option("-p", format!("{}:{}", host, container));
+        }
+        for (name, value) in &self.env {
+            option("-e", format!("{}={}", name, value));
+        }
+        for mount in &self.mounts {
+            let suffix = if mount.read_only { ":ro" } else { "" };
+            option("-v", format!("{}:{}{}", mount.source, mount.target, suffix));
+        }
+        for network in &self.networks {
+            option("--network", network.clone());
+        }
+        for (key, value) in &self.labels {
+            option("--label", format!("{}={}", key, value));
+        }
+        if let Some(cpus) = self.cpus {
+            option("--cpus", cpus.to_string());
+        }
+        if let Some(bytes) = self.memory_bytes {
+            option("--memory", format_byte_size(bytes));
+        }
+
+        for (set, flag) in [
+            (self.auto_remove, "--rm"),
+            (self.privileged, "--privileged"),
+            (self.read_only, "--read-only"),
+            (self.init, "--init"),
+        ] {
+            if set {
+                words.push(flag.to_string());
+            }
+        }
+
+        words.push(self.image.clone());
+        words.extend(arguments.iter().cloned());
+        words.extend(self.cmd.iter().cloned());
+        join_shell_words(&words)
+    }
+}
+
+fn is_flag(option: &str) -> bool {
+    ATTACH_FLAGS.contains(&option)
+        || matches!(option, "--rm" | "--privileged" | "--read-only" | "--init" | "-P" | "--publish-all")
+}
+
+/// Options that map onto a spec field
+fn is_spec_option(option: &str) -> bool {
+    matches!(
+        option,
+        "-p" | "--publish"
+            | "-e"
+            | "--env"
+            | "-v"
+            | "--volume"
+            | "--name"
+            | "--restart"
+            | "--platform"
+            | "-w"
+            | "--workdir"
+            | "--entrypoint"
+            | "--network"
+            | "--net"
+            | "-l"
+            | "--label"
+            | "--cpus"
+            | "-m"
+            | "--memory"
+    )
+}
+
+/// Apply a flag, with an optional `=true`/`=false`
+fn apply_flag(spec: &mut ContainerSpec, ignored: &mut Vec<String>, flag: &str, value: Option<&str>) -> Result<(), String> {
+    let set = match value {
+        None | Some("true") => true,
+        Some("false") => false,
+        Some(other) => return Err(format!("{} expects true or false, not \"{}\"", flag, other)),
+    };
+    match flag {
+        "--rm" => spec.auto_remove = set,
+        "--privileged" => spec.privileged = set,
+        "--read-only" => spec.read_only = set,
+        "--init" => spec.init = set,
+        "-P" | "--publish-all" if set => ignored.push(flag.to_string()),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Apply an option with a value; false if it isn't one a spec can hold
+fn apply_option(spec: &mut ContainerSpec, option: &str, value: &str) -> Result<bool, String> {
+    match option {
+        "--name" => spec.name = Some(value.to_string()),
+        "--platform" => spec.platform = Some(value.to_string()),
+        "--restart" => spec.restart_policy = Some(value.to_string()),
+        "-w" | "--workdir" => spec.working_dir = Some(value.to_string()),
+        "--entrypoint" => spec.entrypoint = vec![value.to_string()],
+        "--network" | "--net" => spec.networks.push(value.to_string()),
+        "-p" | "--publish" => match parse_publish(value)? {
+            Some(port) => spec.ports.push(port),
+            None => return Ok(false),
+        },
+        "-e" | "--env" => match value.split_once('=') {
+            Some((name, value)) => spec.env.push((name.to_string(), value.to_string())),
+            // Copied from the caller's environment, which we don't have
+            None => return Ok(false),
+        },
+        "-l" | "--label" => {
+            let (key, value) = value.split_once('=').unwrap_or((value, ""));
+            spec.labels.push((key.to_string(), value.to_string()));
+        }
+        "-v" | "--volume" => match parse_volume(value)? {
+            Some(mount) => spec.mounts.push(mount),
+            None => return Ok(false),
+        },
+        "--cpus" => {
+            let cpus = value
+                .parse::<f64>()
+                .ok()
+                .filter(|cpus| cpus.is_finite() && *cpus > 0.0)
+                .ok_or_else(|| format!("Invalid --cpus \"{}\"", value))?;
+            spec.cpus = Some(cpus);
+        }
+        "-m" | "--memory" => {
+            let bytes = parse_byte_size(value).ok_or_else(|| format!("Invalid --memory \"{}\"", value))?;
+            spec.memory_bytes = Some(bytes);
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Parse `-p [ip:]host:container[/protocol]`
+///
+/// None for the forms a spec can't hold: no host port (`-p 80`,
+/// `-p 127.0.0.1::80`), where the daemon picks one, and port ranges.
+fn parse_publish(value: &str) -> Result<Option<PortMapping>, String> {
+    let Some((host, container)) = value.rsplit_once(':') else {
+        return Ok(None);
+    };
+    // An IPv6 address is bracketed, e.g. [::1]:8080
+    let (host_ip, host_port) = match host.rsplit_once(':') {
+        Some((ip, port)) => (ip.trim_start_matches('[').trim_end_matches(']'), port),
+        None => ("", host),
+    };
+    if host_port.is_empty() || host_port.contains('-') || container.contains('-') {
+        return Ok(None);
+    }
+    let mut port = PortMapping::parse(host_port, container).map_err(|e| format!("-p {}: {}", value, e))?;
+    port.host_ip = host_ip.to_string();
+    Ok(Some(port))
+}
+
+/// Parse `-v source:target[:options]`; None for an anonymous volume
+fn parse_volume(value: &str) -> Result<Option<MountSpec>, String> {
+    let parts: Vec<&str> = value.split(':').collect();
+    let (source, target, options) = match parts.as_slice() {
+        [_] => return Ok(None),
+        [source, target] => (*source, *target, ""),
+        [source, target, options] => (*source, *target, *options),
+        _ => return Err(format!("-v {}: expected source:target[:options]", value)),
+    };
+    if source.is_empty() || !target.starts_with('/') {
+        return Err(format!("-v {}: expected source:/container/path", value));
+    }
+    Ok(Some(MountSpec {
+        kind: MountKind::for_source(source),
+        source: source.to_string(),
+        target: target.to_string(),
+        read_only: options.split(',').any(|option| option == "ro"),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_docker_run() {
+        let command = DockerRunCommand::parse(
+            "docker run -dit --rm --name=web -p 8080:80 -p127.0.0.1:5353:53/udp \\\n \
+             -e 'GREETING=hello world' -e HOME -v data:/var/lib/data:ro -v ./site:/srv \\\n \
+             --restart unless-stopped --platform linux/arm64 -w /srv --entrypoint nginx \\\n \
+             --hostname box -m 512m nginx:1.25 -g 'daemon off;'",
+        )
+        .unwrap();
+        let spec = &command.spec;
+        assert_eq!(spec.image, "nginx:1.25");
+        assert_eq!(spec.cmd, ["-g", "daemon off;"]);
+        assert_eq!(spec.name.as_deref(), Some("web"));
+        assert!(spec.auto_remove && !spec.privileged);
+        assert_eq!(spec.ports[0], PortMapping::parse("8080", "80").unwrap());
+        assert_eq!(spec.ports[1].host_ip, "127.0.0.1");
+        assert_eq!(spec.ports[1].protocol, "udp");
+        assert_eq!(spec.env, [("GREETING".to_string(), "hello world".to_string())]);
+        assert_eq!(spec.mounts[0].kind, MountKind::Volume);
+        assert!(spec.mounts[0].read_only);
+        assert_eq!(spec.mounts[1].kind, MountKind::Bind);
+        assert_eq!(spec.restart_policy.as_deref(), Some("unless-stopped"));
+        assert_eq!(spec.platform.as_deref(), Some("linux/arm64"));
+        assert_eq!(spec.working_dir.as_deref(), Some("/srv"));
+        assert_eq!(spec.entrypoint, ["nginx"]);
+        assert_eq!(spec.memory_bytes, Some(512 << 20));
+        assert_eq!(command.ignored, ["-e HOME", "--hostname box"]);
+    }
+
+    #[test]
+    fn rejects_bad_docker_run() {
+        assert!(DockerRunCommand::parse("docker run --name web").is_err());
+        assert!(DockerRunCommand::parse("docker run -p 8080:99999 nginx").is_err());
+        assert!(DockerRunCommand::parse("docker run --name").is_err());
+        assert!(DockerRunCommand::parse("docker run 'nginx").is_err());
+    }
+
+    #[test]
+    fn ignores_what_a_spec_cant_hold() {
+        let command = DockerRunCommand::parse(
+            "docker run -p 80 -p 127.0.0.1::53/udp -p 8000-8010:8000-8010 -éx -ü nginx",
+        )
+        .unwrap();
+        assert_eq!(command.spec.image, "nginx");
+        assert!(command.spec.ports.is_empty());
+        assert_eq!(
+            command.ignored,
+            ["-p 80", "-p 127.0.0.1::53/udp", "-p 8000-8010:8000-8010", "-é x", "-ü"]
+        );
+    }
+
+    #[test]
+    fn docker_run_round_trips() {
+        let spec = ContainerSpec {
+            image: "ghcr.io/org/app:1.2".into(),
+            name: Some("api".into()),
+            cmd: vec!["serve".into(), "--port".into(), "80".into()],
+            entrypoint: vec!["/bin/app".into()],
+            working_dir: Some("/app".into()),
+            restart_policy: Some("always".into()),
+            auto_remove: true,
+            init: true,
+            ports: vec![PortMapping::parse("8080", "80").unwrap(), PortMapping::parse("53", "53/udp").unwrap()],
+            env: vec![("MOTD".into(), "it's up".into())],
+            mounts: vec![MountSpec {
+                kind: MountKind::Bind,
+                source: "/Users/me/app".into(),
+                target: "/app".into(),
+                read_only: true,
+            }],
+            networks: vec!["backend".into()],
+            labels: vec![("tier".into(), "api".into())],
+            cpus: Some(1.5),
+            memory_bytes: Some(2 << 30),
+            ..Default::default()
+        };
+        let line = spec.to_docker_run();
+        assert!(line.starts_with("docker run --name api "), "{}", line);
+        assert!(line.contains("--memory 2g"), "{}", line);
+        let parsed = DockerRunCommand::parse(&line).unwrap();
+        assert_eq!(parsed.spec, spec);
+        assert!(parsed.ignored.is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+//! Typed errors for daemon operations.
+//!
+//! RPC failures are classified by their `tonic::Code` and tagged with the
+//! operation and target resource, so views can tell a missing container from
+//! a name conflict or a dropped connection and react accordingly.
+
+use std::fmt;
+
+/// The daemon operation that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonOperation {
+    ListMachines,
+    ListContainers,
+    ListImages,
+    ListNetworks,
+    PullImage,
+    CreateContainer,
+    StartContainer,
+    StopContainer,
+    RemoveContainer,
+    PauseContainer,
+    UnpauseContainer,
+    RestartContainer,
+    KillContainer,
+    RenameContainer,
+    InspectContainer,
+    ContainerLogs,
+    ContainerStats,
+    Exec,
+    BrowseFiles,
+    DownloadFiles,
+    UploadFiles,
+    CreateNetwork,
+    RemoveNetwork,
+    Events,
+}
+
+impl DaemonOperation {
+    /// Lowercase description that reads on its own, e.g. "start container"
+    pub fn label(&self) -> &'static str {
+        match self {
+            DaemonOperation::ListMachines => "list machines",
+            DaemonOperation::ListContainers => "list containers",
+            DaemonOperation::ListImages => "list images",
+            DaemonOperation::ListNetworks => "list networks",
+            DaemonOperation::PullImage => "pull image",
+            DaemonOperation::CreateContainer => "create container",
+            DaemonOperation::StartContainer => "start container",
+            DaemonOperation::StopContainer => "stop container",
+            DaemonOperation::RemoveContainer => "remove container",
+            DaemonOperation::PauseContainer => "pause container",
+            DaemonOperation::UnpauseContainer => "unpause container",
+            DaemonOperation::RestartContainer => "restart container",
+            DaemonOperation::KillContainer => "kill container",
+            DaemonOperation::RenameContainer => "rename container",
+            DaemonOperation::InspectContainer => "inspect container",
+            DaemonOperation::ContainerLogs => "stream logs",
+            DaemonOperation::ContainerStats => "stream stats",
+            DaemonOperation::Exec => "open terminal",
+            DaemonOperation::BrowseFiles => "browse files",
+            DaemonOperation::DownloadFiles => "download files",
+            DaemonOperation::UploadFiles => "upload files",
+            DaemonOperation::CreateNetwork => "create network",
+            DaemonOperation::RemoveNetwork => "remove network",
+            DaemonOperation::Events => "subscribe to events",
+        }
+    }
+
+    /// The label with the resource it applied to, e.g. "start container web"
+    /// or "browse files in web"
+    pub fn describe(&self, resource: Option<&str>) -> String {
+        let Some(resource) = resource else {
+            return self.label().to_string();
+        };
+        let preposition = match self {
+            DaemonOperation::ContainerLogs | DaemonOperation::ContainerStats => " for",
+            DaemonOperation::Exec | DaemonOperation::BrowseFiles => " in",
+            DaemonOperation::DownloadFiles => " from",
+            DaemonOperation::UploadFiles => " to",
+            _ => "",
+        };
+        format!("{}{} {}", self.label(), preposition, resource)
+    }
+}
+
+/// A failed daemon operation
+///
+/// Every variant carries the operation and, when there is one, the target
+/// resource (container ID, image reference, network name, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaemonError {
+    /// No channel to the daemon; the request was never sent
+    NotConnected {
+        operation: DaemonOperation,
+        resource: Option<String>,
+    },
+    /// The daemon went away mid-request (triggers a reconnect)
+    Unavailable {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        message: String,
+    },
+    /// The target resource does not exist
+    NotFound {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        message: String,
+    },
+    /// Name already in use, or the resource is in the wrong state
+    Conflict {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        message: String,
+    },
+    /// The request was rejected as malformed
+    InvalidArgument {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        message: String,
+    },
+    /// Not authenticated or not allowed
+    PermissionDenied {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        message: String,
+    },
+    /// The daemon did not answer in time
+    Timeout {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        message: String,
+    },
+    /// The daemon does not support this operation
+    Unsupported {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        message: String,
+    },
+    /// Any other daemon-side failure
+    Internal {
+        operation: DaemonOperation,
+        resource: Option<String>,
+        code: tonic::Code,
+        message: String,
+    },
+}
+
+impl DaemonError {
+    /// Classify a failed RPC
+    pub fn from_status(
+        operation: DaemonOperation,
+        resource: Option<String>,
+        status: &tonic::Status,
+    ) -> Self {
+        use tonic::Code;
+
+        let message = status.message().to_string();
+        match status.code() {
+            Code::Unavailable => DaemonError::Unavailable { operation, resource, message },
+            Code::NotFound => DaemonError::NotFound { operation, resource, message },
+            Code::AlreadyExists | Code::FailedPrecondition | Code::Aborted => {
+                DaemonError::Conflict { operation, resource, message }
+            }
+            Code::InvalidArgument | Code::OutOfRange => {
+                DaemonError::InvalidArgument { operation, resource, message }
+            }
+            Code::PermissionDenied | Code::Unauthenticated => {
+                DaemonError::PermissionDenied { operation, resource, message }
+            }
+            Code::DeadlineExceeded => DaemonError::Timeout { operation, resource, message },
+            Code::Unimplemented => DaemonError::Unsupported { operation, resource, message },
+            code => DaemonError::Internal { operation, resource, code, message },
+        }
+    }
+
+    /// Request could not be sent because there is no connection
+    pub fn not_connected(operation: DaemonOperation, resource: Option<String>) -> Self {
+        DaemonError::NotConnected { operation, resource }
+    }
+
+    /// The operation that failed
+    pub fn operation(&self) -> DaemonOperation {
+        match self {
+            DaemonError::NotConnected { operation, .. }
+            | DaemonError::Unavailable { operation, .. }
+            | DaemonError::NotFound { operation, .. }
+            | DaemonError::Conflict { operation, .. }
+            | DaemonError::InvalidArgument { operation, .. }
+            | DaemonError::PermissionDenied { operation, .. }
+            | DaemonError::Timeout { operation, .. }
+            | DaemonError::Unsupported { operation, .. }
+            | DaemonError::Internal { operation, .. } => *operation,
+        }
+    }
+
+    /// The target resource, if the operation had one
+    pub fn resource(&self) -> Option<&str> {
+        match self {
+            DaemonError::NotConnected { resource, .. }
+            | DaemonError::Unavailable { resource, .. }
+            | DaemonError::NotFound { resource, .. }
+            | DaemonError::Conflict { resource, .. }
+            | DaemonError::InvalidArgument { resource, .. }
+            | DaemonError::PermissionDenied { resource, .. }
+            | DaemonError::Timeout { resource, .. }
+            | DaemonError::Unsupported { resource, .. }
+            | DaemonError::Internal { resource, .. } => resource.as_deref(),
+        }
+    }
+
+    /// Message reported by the daemon
+    pub fn message(&self) -> &str {
+        match self {
+            DaemonError::NotConnected { .. } => "not connected to daemon",
+            DaemonError::Unavailable { message, .. }
+            | DaemonError::NotFound { message, .. }
+            | DaemonError::Conflict { message, .. }
+            | DaemonError::InvalidArgument { message, .. }
+            | DaemonError::PermissionDenied { message, .. }
+            | DaemonError::Timeout { message, .. }
+            | DaemonError::Unsupported { message, .. }
+            | DaemonError::Internal { message, .. } => message,
+        }
+    }
+
+    /// Whether retrying the same request may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            DaemonError::NotConnected { .. }
+                | DaemonError::Unavailable { .. }
+                | DaemonError::Timeout { .. }
+        )
+    }
+}
+
+impl fmt::Display for DaemonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operation = self.operation().describe(self.resource().map(short_id));
+        write!(f, "Failed to {}: {}", operation, self.message())
+    }
+}
+
+impl std::error::Error for DaemonError {}
+
+/// Shorten a full container/image ID like `docker ps` does; names and
+/// anything else that isn't a 64-digit hex ID are left as they are
+pub fn short_id(resource: &str) -> &str {
+    let resource_id = resource.strip_prefix("sha256:").unwrap_or(resource);
+    if resource_id.len() == 64 && resource_id.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        &resource_id[..12]
+    } else {
+        resource
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortens_only_full_ids() {
+        let id = "4f2a9c0e1b7d3a5f6e8c9b0a1d2e3f4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e";
+        assert_eq!(short_id(id), "4f2a9c0e1b7d");
+        assert_eq!(short_id(&format!("sha256:{id}")), "4f2a9c0e1b7d");
+        assert_eq!(short_id("my-long-container-name"), "my-long-container-name");
+        assert_eq!(short_id("nginx:latest"), "nginx:latest");
+    }
+
+    #[test]
+    fn reads_with_and_without_a_resource() {
+        let error = |resource: Option<&str>| DaemonError::Internal {
+            operation: DaemonOperation::BrowseFiles,
+            resource: resource.map(str::to_string),
+            code: tonic::Code::Unknown,
+            message: "boom".to_string(),
+        };
+        assert_eq!(error(Some("web")).to_string(), "Failed to browse files in web: boom");
+        assert_eq!(error(None).to_string(), "Failed to browse files: boom");
+        assert_eq!(DaemonOperation::StartContainer.describe(Some("web")), "start container web");
+    }
+}
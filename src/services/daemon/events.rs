@@ -0,0 +1,294 @@
+//! Daemon event stream.
+//!
+//! Keeps a long-lived `Events` subscription open while connected and turns
+//! daemon container/image/network/volume events into incremental
+//! `DaemonEvent`s, so changes made from the `docker` CLI show up without
+//! re-listing everything.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use arcbox_api::generated::{
+    system_service_client::SystemServiceClient,
+    Event, EventsRequest, ListContainersRequest, ListImagesRequest,
+};
+use gpui::*;
+use tonic::transport::Channel;
+
+use super::{read_stream, DaemonError, DaemonEvent, DaemonOperation, DaemonService};
+
+/// A network or volume event, applied directly by the views
+#[derive(Debug, Clone)]
+pub struct ResourceEvent {
+    /// Resource ID (network ID or volume name)
+    pub id: String,
+    /// Event action, e.g. "create", "destroy", "connect"
+    pub action: String,
+    /// Actor attributes reported by the daemon (name, driver, container, ...)
+    pub attributes: HashMap<String, String>,
+    /// Unix timestamp in seconds
+    pub time: i64,
+}
+
+impl ResourceEvent {
+    fn from_event(event: &Event) -> Self {
+        let (id, attributes) = event
+            .actor
+            .as_ref()
+            .map(|actor| (actor.id.clone(), actor.attributes.clone()))
+            .unwrap_or_default();
+
+        Self {
+            id,
+            action: event.action.clone(),
+            attributes,
+            time: event.time,
+        }
+    }
+}
+
+impl DaemonService {
+    /// Get system service client
+    pub fn system_client(&self) -> Option<SystemServiceClient<Channel>> {
+        self.channel.clone().map(SystemServiceClient::new)
+    }
+
+    /// Open the daemon event stream (called on every successful connect)
+    ///
+    /// Whenever the stream ends it is reopened with backoff, and everything is
+    /// re-listed to catch what happened in between. An unreachable daemon goes
+    /// through `connection_lost` instead, which restarts the stream on reconnect.
+    pub(super) fn start_event_stream(&mut self, cx: &mut Context<Self>) {
+        let Some(client) = self.system_client() else {
+            return;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        self.events_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let mut attempt = 0;
+            loop {
+                let (mut client, runtime_clone) = (client.clone(), runtime.clone());
+                let result = cx.background_executor().spawn(async move {
+                    runtime_clone.block_on(async {
+                        let request = tonic::Request::new(EventsRequest::default());
+                        client.events(request).await
+                    })
+                }).await;
+
+                let ended = match result {
+                    Ok(response) => {
+                        tracing::debug!("Daemon event stream started");
+                        if attempt > 0 {
+                            cx.update(|cx| this.update(cx, |this, cx| this.refresh_all(cx))).ok();
+                        }
+                        let rx = read_stream(&runtime, response.into_inner(), "daemon events".to_string());
+                        loop {
+                            match rx.try_recv() {
+                                Ok(Ok(event)) => {
+                                    attempt = 0;
+                                    cx.update(|cx| {
+                                        this.update(cx, |this, cx| this.handle_daemon_event(event, cx))
+                                    }).ok();
+                                }
+                                Ok(Err(status)) => break Some(status),
+                                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                                    cx.background_executor().timer(Duration::from_millis(50)).await;
+                                }
+                                Err(std::sync::mpsc::TryRecvError::Disconnected) => break None,
+                            }
+                        }
+                    }
+                    Err(status) => Some(status),
+                };
+
+                match ended {
+                    Some(status) if matches!(status.code(), tonic::Code::Unavailable | tonic::Code::Unimplemented) => {
+                        tracing::error!("Daemon event stream failed: {}", status);
+                        cx.update(|cx| {
+                            this.update(cx, |this, cx| {
+                                this.operation_failed(DaemonError::from_status(DaemonOperation::Events, None, &status), cx)
+                            })
+                        }).ok();
+                        return;
+                    }
+                    Some(status) => tracing::warn!("Daemon event stream error: {}", status),
+                    None => tracing::warn!("Daemon event stream ended"),
+                }
+
+                attempt += 1;
+                let delay = Self::backoff_delay(attempt);
+                tracing::info!("Resubscribing to daemon events in {:?}", delay);
+                cx.background_executor().timer(delay).await;
+            }
+        }));
+    }
+
+    /// Close the daemon event stream
+    pub(super) fn stop_event_stream(&mut self) {
+        self.events_task = None;
+    }
+
+    /// Translate a raw daemon event into incremental `DaemonEvent`s
+    fn handle_daemon_event(&mut self, event: Event, cx: &mut Context<Self>) {
+        let resource = ResourceEvent::from_event(&event);
+        tracing::debug!("Daemon event: {} {} {}", event.r#type, resource.action, resource.id);
+
+        match event.r#type.as_str() {
+            "container" => match resource.action.as_str() {
+                "destroy" => cx.emit(DaemonEvent::ContainerDestroyed(resource.id)),
+                "die" | "stop" => cx.emit(DaemonEvent::ContainerStateChanged {
+                    id: resource.id,
+                    state: "exited".to_string(),
+                }),
+                "pause" => cx.emit(DaemonEvent::ContainerStateChanged {
+                    id: resource.id,
+                    state: "paused".to_string(),
+                }),
+                "unpause" => cx.emit(DaemonEvent::ContainerStateChanged {
+                    id: resource.id,
+                    state: "running".to_string(),
+                }),
+                // Ports, names and state may all have changed: fetch just this container.
+                // `kill` is any signal (the container may well keep running) and
+                // `oom` is followed by `die` if it stopped anything.
+                "create" | "start" | "restart" | "rename" | "update" | "kill" | "oom" => {
+                    self.fetch_container(resource.id, cx);
+                }
+                _ => {}
+            },
+            "image" => match resource.action.as_str() {
+                "delete" => cx.emit(DaemonEvent::ImageDeleted(resource.id)),
+                "pull" | "tag" | "untag" | "import" | "load" => {
+                    self.fetch_image(resource.id, cx);
+                }
+                _ => {}
+            },
+            "network" => cx.emit(DaemonEvent::NetworkEventReceived(resource)),
+            "volume" => {
+                // Unmounting only means the container stopped; it still uses the volume
+                if matches!(resource.action.as_str(), "mount" | "unmount") {
+                    self.fetch_volume_usage(resource.id.clone(), cx);
+                }
+                cx.emit(DaemonEvent::VolumeEventReceived(resource));
+            }
+            _ => {}
+        }
+    }
+
+    /// Fetch a single container summary and emit `ContainerUpdated`
+    fn fetch_container(&self, id: String, cx: &mut Context<Self>) {
+        let Some(mut client) = self.container_client() else {
+            return;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let id_clone = id.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime.block_on(async {
+                    let request = tonic::Request::new(ListContainersRequest {
+                        all: true,
+                        limit: 0,
+                        filters: HashMap::from([("id".to_string(), id_clone)]),
+                    });
+                    client.list_containers(request).await
+                })
+            }).await;
+
+            match result {
+                Ok(response) => {
+                    let containers = response.into_inner().containers;
+                    cx.update(|cx| {
+                        this.update(cx, |_this, cx| {
+                            for summary in containers.into_iter().filter(|c| c.id == id) {
+                                cx.emit(DaemonEvent::ContainerUpdated(summary));
+                            }
+                        })
+                    }).ok();
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to fetch container {}: {}", id, e);
+                }
+            }
+        }).detach();
+    }
+
+    /// Look up the containers (running or not) that use a volume and emit
+    /// `VolumeUsageLoaded`
+    pub fn fetch_volume_usage(&self, name: String, cx: &mut Context<Self>) {
+        let Some(mut client) = self.container_client() else {
+            return;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let name_clone = name.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime.block_on(async {
+                    let request = tonic::Request::new(ListContainersRequest {
+                        all: true,
+                        limit: 0,
+                        filters: HashMap::from([("volume".to_string(), name_clone)]),
+                    });
+                    client.list_containers(request).await
+                })
+            }).await;
+
+            match result {
+                Ok(response) => {
+                    let containers = response
+                        .into_inner()
+                        .containers
+                        .into_iter()
+                        .map(|c| c.name.trim_start_matches('/').to_string())
+                        .collect();
+                    cx.update(|cx| {
+                        this.update(cx, |_this, cx| {
+                            cx.emit(DaemonEvent::VolumeUsageLoaded { name, containers });
+                        })
+                    }).ok();
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to fetch usage of volume {}: {}", name, e);
+                }
+            }
+        }).detach();
+    }
+
+    /// Fetch the image matching a reference and emit `ImageUpdated`
+    fn fetch_image(&self, reference: String, cx: &mut Context<Self>) {
+        let Some(mut client) = self.image_client() else {
+            return;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let reference_clone = reference.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime.block_on(async {
+                    let request = tonic::Request::new(ListImagesRequest {
+                        filters: HashMap::from([("reference".to_string(), reference_clone)]),
+                        ..Default::default()
+                    });
+                    client.list_images(request).await
+                })
+            }).await;
+
+            match result {
+                Ok(response) => {
+                    let images = response.into_inner().images;
+                    cx.update(|cx| {
+                        this.update(cx, |_this, cx| {
+                            for summary in images {
+                                cx.emit(DaemonEvent::ImageUpdated(summary));
+                            }
+                        })
+                    }).ok();
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to fetch image {}: {}", reference, e);
+                }
+            }
+        }).detach();
+    }
+}
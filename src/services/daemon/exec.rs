@@ -0,0 +1,222 @@
+//! Interactive exec sessions.
+//!
+//! A session is an `ExecCreate` with a TTY followed by a bidirectional
+//! `ExecStart` stream: keystrokes go up as stdin chunks, PTY output comes
+//! back as raw bytes. Both directions are bridged off the tokio runtime with
+//! channels so the terminal view can poll them from the UI thread, the same
+//! way log streams are pumped. Other services run one-off commands through
+//! `exec_output`.
+
+use std::sync::Arc;
+use std::sync::mpsc::TryRecvError;
+
+use arcbox_api::generated::{
+    container_service_client::ContainerServiceClient,
+    ExecCreateRequest, ExecResizeRequest, ExecStartRequest, ExecStartResponse,
+};
+use futures::channel::mpsc::{unbounded, UnboundedSender};
+use futures::StreamExt;
+use gpui::*;
+use tonic::transport::Channel;
+
+use super::{read_stream, DaemonError, DaemonOperation, DaemonService, StreamReader};
+
+/// Something that happened on an exec session
+#[derive(Debug)]
+pub enum ExecEvent {
+    /// Raw PTY output
+    Output(Vec<u8>),
+    /// The process exited or the daemon closed the stream
+    Exited,
+    /// The stream broke
+    Failed(DaemonError),
+}
+
+/// A running exec process with a TTY
+pub struct ExecSession {
+    exec_id: String,
+    container_id: String,
+    input: UnboundedSender<Vec<u8>>,
+    /// Dropping the session stops reading its output
+    output: StreamReader<ExecStartResponse>,
+    client: ContainerServiceClient<Channel>,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl ExecSession {
+    /// Send bytes to the process's stdin
+    pub fn write(&self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        if self.input.unbounded_send(bytes.to_vec()).is_err() {
+            tracing::debug!("Exec {} input closed, dropping {} bytes", self.exec_id, bytes.len());
+        }
+    }
+
+    /// Tell the daemon the terminal size changed
+    pub fn resize(&self, cols: u16, rows: u16) {
+        let mut client = self.client.clone();
+        let exec_id = self.exec_id.clone();
+        self.runtime.spawn(async move {
+            let request = tonic::Request::new(ExecResizeRequest {
+                id: exec_id.clone(),
+                width: u32::from(cols),
+                height: u32::from(rows),
+            });
+            if let Err(e) = client.exec_resize(request).await {
+                tracing::debug!("Failed to resize exec {}: {}", exec_id, e);
+            }
+        });
+    }
+
+    /// Next pending event, without blocking
+    pub fn try_recv(&self) -> Result<ExecEvent, TryRecvError> {
+        match self.output.try_recv() {
+            Ok(Ok(output)) => Ok(ExecEvent::Output(output.data)),
+            Ok(Err(status)) => Ok(ExecEvent::Failed(DaemonError::from_status(
+                DaemonOperation::Exec,
+                Some(self.container_id.clone()),
+                &status,
+            ))),
+            Err(TryRecvError::Disconnected) => Ok(ExecEvent::Exited),
+            Err(TryRecvError::Empty) => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl DaemonService {
+    /// Start `cmd` in a container with a TTY of the given size
+    ///
+    /// Failures are also reported like any other operation, so a dropped
+    /// connection still triggers a reconnect.
+    pub fn open_exec(
+        &self,
+        container_id: String,
+        cmd: Vec<String>,
+        cols: u16,
+        rows: u16,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ExecSession, DaemonError>> {
+        let Some(client) = self.container_client() else {
+            tracing::warn!("Not connected to daemon");
+            return Task::ready(Err(DaemonError::not_connected(
+                DaemonOperation::Exec,
+                Some(container_id),
+            )));
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let (input, input_rx) = unbounded::<Vec<u8>>();
+
+            let mut create_client = client.clone();
+            let id = container_id.clone();
+            let runtime_clone = runtime.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime_clone.block_on(async {
+                    let request = tonic::Request::new(ExecCreateRequest {
+                        container_id: id,
+                        cmd,
+                        attach_stdin: true,
+                        attach_stdout: true,
+                        attach_stderr: true,
+                        tty: true,
+                        env: vec!["TERM=xterm-256color".to_string()],
+                        ..Default::default()
+                    });
+                    let exec_id = create_client.exec_create(request).await?.into_inner().id;
+
+                    // Size the PTY before the shell draws its first prompt
+                    create_client
+                        .exec_resize(tonic::Request::new(ExecResizeRequest {
+                            id: exec_id.clone(),
+                            width: u32::from(cols),
+                            height: u32::from(rows),
+                        }))
+                        .await
+                        .ok();
+
+                    // The first message names the exec, the rest carry stdin
+                    let first = ExecStartRequest {
+                        exec_id: exec_id.clone(),
+                        tty: true,
+                        ..Default::default()
+                    };
+                    let requests = futures::stream::once(async move { first }).chain(
+                        input_rx.map(|stdin| ExecStartRequest {
+                            stdin,
+                            ..Default::default()
+                        }),
+                    );
+                    let stream = create_client
+                        .exec_start(tonic::Request::new(requests))
+                        .await?
+                        .into_inner();
+                    Ok::<_, tonic::Status>((exec_id, stream))
+                })
+            }).await;
+
+            let (exec_id, stream) = match result {
+                Ok(started) => started,
+                Err(e) => {
+                    tracing::error!("Failed to open exec in {}: {}", container_id, e);
+                    let error = DaemonError::from_status(DaemonOperation::Exec, Some(container_id), &e);
+                    cx.update(|cx| {
+                        this.update(cx, |this, cx| this.operation_failed(error.clone(), cx))
+                    }).ok();
+                    return Err(error);
+                }
+            };
+            tracing::info!("Exec {} started in container {}", exec_id, container_id);
+
+            let output = read_stream(&runtime, stream, format!("exec {}", exec_id));
+
+            Ok(ExecSession {
+                exec_id,
+                container_id,
+                input,
+                output,
+                client,
+                runtime,
+            })
+        })
+    }
+
+    /// Run `cmd` in a container and collect its output (on the tokio runtime)
+    ///
+    /// Uses a TTY so stdout and stderr come back as one plain stream; lines
+    /// end in "\r\n".
+    pub(super) async fn exec_output(
+        mut client: ContainerServiceClient<Channel>,
+        container_id: String,
+        cmd: Vec<String>,
+    ) -> Result<Vec<u8>, tonic::Status> {
+        let request = tonic::Request::new(ExecCreateRequest {
+            container_id,
+            cmd,
+            attach_stdout: true,
+            attach_stderr: true,
+            tty: true,
+            ..Default::default()
+        });
+        let exec_id = client.exec_create(request).await?.into_inner().id;
+
+        // Nothing to send after the first message; closing the stream closes stdin
+        let start = ExecStartRequest {
+            exec_id,
+            tty: true,
+            ..Default::default()
+        };
+        let mut stream = client
+            .exec_start(tonic::Request::new(futures::stream::iter([start])))
+            .await?
+            .into_inner();
+        let mut output = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            output.extend(chunk?.data);
+        }
+        Ok(output)
+    }
+}
+
@@ -0,0 +1,703 @@
+//! Container filesystem access.
+//!
+//! Built on the daemon's archive and stat APIs. `GetArchive` streams a tar of
+//! a path and `PutArchive` extracts a tar into a directory.
+//!
+//! There is no listing RPC. A running container lists one directory with
+//! `find -maxdepth 1` and each entry is stat'ed, so nothing below it is
+//! read. Stopped containers (and images without `find`) can't be browsed:
+//! the only other way to list them is an archive of the whole directory.
+//!
+//! The gRPC streams are bridged to blocking `Read`/`Write` adapters so the
+//! `tar` crate can consume and produce archives on a background thread.
+
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, Sender};
+
+use arcbox_api::generated::{
+    container_service_client::ContainerServiceClient,
+    GetArchiveRequest, PutArchiveRequest, StatPathRequest, StatPathResponse,
+};
+use futures::{SinkExt, StreamExt};
+use gpui::*;
+use tonic::transport::Channel;
+
+use super::{DaemonError, DaemonEvent, DaemonOperation, DaemonService};
+
+/// Size of the chunks uploads are split into
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Entries stat'ed at once when listing a directory
+const STAT_CONCURRENCY: usize = 32;
+
+/// Kind of a filesystem entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Directory,
+    File,
+    Symlink,
+    /// Devices, FIFOs, sockets
+    Other,
+}
+
+/// A file or directory inside a container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    /// Absolute path inside the container
+    pub path: String,
+    pub name: String,
+    pub kind: FileKind,
+    pub size: u64,
+    /// Unix permission bits
+    pub mode: u32,
+    /// Modification time (unix seconds)
+    pub modified: i64,
+    /// Symlink target as stored in the link
+    pub link_target: Option<String>,
+}
+
+impl FileEntry {
+    pub fn is_dir(&self) -> bool {
+        self.kind == FileKind::Directory
+    }
+
+    /// `ls -l` style permissions, e.g. "drwxr-xr-x"
+    pub fn permissions(&self) -> String {
+        let kind = match self.kind {
+            FileKind::Directory => 'd',
+            FileKind::Symlink => 'l',
+            FileKind::File => '-',
+            FileKind::Other => '?',
+        };
+        let mut text = String::from(kind);
+        for shift in [6, 3, 0] {
+            let bits = (self.mode >> shift) & 0o7;
+            text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+            text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+            text.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+        }
+        text
+    }
+}
+
+/// What the daemon reports about a single path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathStat {
+    pub name: String,
+    pub size: u64,
+    pub mode: u32,
+    pub modified: i64,
+    /// Resolved target when the path is a symlink
+    pub link_target: Option<String>,
+}
+
+impl From<StatPathResponse> for PathStat {
+    fn from(stat: StatPathResponse) -> Self {
+        Self {
+            name: stat.name,
+            size: stat.size.max(0) as u64,
+            mode: stat.mode,
+            modified: stat.mtime,
+            link_target: Some(stat.link_target).filter(|t| !t.is_empty()),
+        }
+    }
+}
+
+impl PathStat {
+    pub fn is_dir(&self) -> bool {
+        self.kind() == FileKind::Directory
+    }
+
+    pub fn kind(&self) -> FileKind {
+        match self.mode & 0o170000 {
+            0o040000 => FileKind::Directory,
+            0o120000 => FileKind::Symlink,
+            _ if self.link_target.is_some() => FileKind::Symlink,
+            0o100000 => FileKind::File,
+            _ => FileKind::Other,
+        }
+    }
+
+    fn into_entry(self, path: String) -> FileEntry {
+        FileEntry {
+            name: path.rsplit('/').next().unwrap_or_default().to_string(),
+            kind: self.kind(),
+            size: self.size,
+            mode: self.mode & 0o7777,
+            modified: self.modified,
+            link_target: self.link_target,
+            path,
+        }
+    }
+}
+
+/// Up to `limit` bytes of a file
+#[derive(Debug, Clone)]
+pub struct FileContents {
+    pub data: Vec<u8>,
+    /// The file is longer than what was read
+    pub truncated: bool,
+}
+
+/// Join a directory and a name into an absolute path
+pub fn join_path(dir: &str, name: &str) -> String {
+    let name = name.trim_start_matches('/');
+    if dir.ends_with('/') {
+        format!("{}{}", dir, name)
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// Parent directory of an absolute path ("/" for top-level entries and "/" itself)
+pub fn parent_path(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => trimmed[..index].to_string(),
+    }
+}
+
+/// Resolve "." and ".." components of an absolute path
+pub fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+/// Paths printed by `find DIR -mindepth 1 -maxdepth 1`
+///
+/// None when the output isn't a listing of `dir`, e.g. the shell reporting
+/// that `find` doesn't exist. `find`'s own complaints are skipped.
+fn paths_from_find(dir: &str, output: &[u8]) -> Option<Vec<String>> {
+    let prefix = join_path(dir, "");
+    let mut paths = Vec::new();
+    for line in String::from_utf8_lossy(output).lines() {
+        let line = line.trim_end_matches('\r');
+        match line.strip_prefix(&prefix) {
+            Some(name) if !name.is_empty() && !name.contains('/') => paths.push(line.to_string()),
+            _ if line.is_empty() || line.starts_with("find:") => {}
+            _ => return None,
+        }
+    }
+    Some(paths)
+}
+
+/// List `dir` by running `find` in the container and stat'ing each entry
+///
+/// None when the container has no usable `find`.
+async fn list_with_find(
+    client: ContainerServiceClient<Channel>,
+    id: String,
+    dir: String,
+) -> Result<Option<Vec<FileEntry>>, tonic::Status> {
+    let cmd = vec![
+        "find".to_string(),
+        dir.clone(),
+        "-mindepth".to_string(),
+        "1".to_string(),
+        "-maxdepth".to_string(),
+        "1".to_string(),
+    ];
+    let output = DaemonService::exec_output(client.clone(), id.clone(), cmd).await?;
+    let Some(paths) = paths_from_find(&dir, &output) else {
+        return Ok(None);
+    };
+
+    let entries: Vec<FileEntry> = futures::stream::iter(paths)
+        .map(|path| {
+            let mut client = client.clone();
+            let id = id.clone();
+            async move {
+                let request = tonic::Request::new(StatPathRequest { id, path: path.clone() });
+                match client.stat_path(request).await {
+                    Ok(response) => Some(PathStat::from(response.into_inner()).into_entry(path)),
+                    Err(status) => {
+                        // Deleted since `find` ran, or not ours to look at
+                        tracing::debug!("Failed to stat {}: {}", path, status.message());
+                        None
+                    }
+                }
+            }
+        })
+        .buffer_unordered(STAT_CONCURRENCY)
+        .filter_map(futures::future::ready)
+        .collect()
+        .await;
+    Ok(Some(entries))
+}
+
+/// Up to `limit` bytes of the single file in an archive
+///
+/// Archives hold a symlink itself rather than what it points to, so links
+/// are an error instead of empty contents.
+fn contents_from_archive(reader: impl Read, limit: usize) -> io::Result<FileContents> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = archive.entries()?;
+    let Some(entry) = entries.next() else {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "empty archive"));
+    };
+    let entry = entry?;
+    if entry.header().entry_type().is_symlink() {
+        let target = entry.link_name()?.unwrap_or_default().display().to_string();
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is a symlink to {}", entry.path()?.display(), target),
+        ));
+    }
+    let size = entry.header().size().unwrap_or(0);
+    let mut data = Vec::new();
+    entry.take(limit as u64).read_to_end(&mut data)?;
+    Ok(FileContents {
+        truncated: size > data.len() as u64,
+        data,
+    })
+}
+
+/// Blocking reader over the chunks of a `GetArchive` stream
+struct ArchiveReader {
+    chunks: Receiver<Result<Vec<u8>, tonic::Status>>,
+    current: Vec<u8>,
+    position: usize,
+    /// The RPC error that ended the stream, if any
+    status: Option<tonic::Status>,
+}
+
+impl Read for ArchiveReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.current.len() {
+            match self.chunks.recv() {
+                Ok(Ok(chunk)) => {
+                    self.current = chunk;
+                    self.position = 0;
+                }
+                Ok(Err(status)) => {
+                    let error = io::Error::other(status.message().to_string());
+                    self.status = Some(status);
+                    return Err(error);
+                }
+                // Stream finished
+                Err(_) => return Ok(0),
+            }
+        }
+        let count = buf.len().min(self.current.len() - self.position);
+        buf[..count].copy_from_slice(&self.current[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+}
+
+/// Blocking writer feeding a `PutArchive` request stream
+struct ArchiveWriter {
+    chunks: futures::channel::mpsc::Sender<Vec<u8>>,
+    buffer: Vec<u8>,
+}
+
+impl ArchiveWriter {
+    fn send_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::take(&mut self.buffer);
+        futures::executor::block_on(self.chunks.send(chunk))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "upload stream closed"))
+    }
+}
+
+impl Write for ArchiveWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= UPLOAD_CHUNK_SIZE {
+            self.send_buffer()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_buffer()
+    }
+}
+
+/// Tar host files and folders, each under its own name
+fn write_archive(writer: impl Write, paths: &[PathBuf]) -> io::Result<()> {
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+    for path in paths {
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+        if path.is_dir() {
+            builder.append_dir_all(name, path)?;
+        } else {
+            builder.append_path_with_name(path, name)?;
+        }
+    }
+    builder.into_inner()?.flush()
+}
+
+/// A local I/O failure during a transfer, reported like a daemon error
+fn io_error(operation: DaemonOperation, container_id: &str, error: &io::Error) -> DaemonError {
+    DaemonError::Internal {
+        operation,
+        resource: Some(container_id.to_string()),
+        code: tonic::Code::Unknown,
+        message: error.to_string(),
+    }
+}
+
+impl DaemonService {
+    /// Stat a path inside a container
+    pub fn stat_path(
+        &self,
+        container_id: String,
+        path: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<PathStat, DaemonError>> {
+        let Some(mut client) = self.container_client() else {
+            return Task::ready(Err(DaemonError::not_connected(
+                DaemonOperation::BrowseFiles,
+                Some(container_id),
+            )));
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let id = container_id.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime.block_on(async {
+                    client
+                        .stat_path(tonic::Request::new(StatPathRequest { id, path }))
+                        .await
+                })
+            }).await;
+
+            match result {
+                Ok(response) => Ok(PathStat::from(response.into_inner())),
+                Err(status) => {
+                    let error = DaemonError::from_status(DaemonOperation::BrowseFiles, Some(container_id), &status);
+                    Self::report_files_error(&this, &error, false, cx);
+                    Err(error)
+                }
+            }
+        })
+    }
+
+    /// List a directory of a running container
+    ///
+    /// Stopped containers are reported as unsupported rather than read
+    /// through `GetArchive`, which would stream everything below `path`.
+    pub fn list_directory(
+        &self,
+        container_id: String,
+        path: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<FileEntry>, DaemonError>> {
+        let Some(client) = self.container_client() else {
+            return Task::ready(Err(DaemonError::not_connected(
+                DaemonOperation::BrowseFiles,
+                Some(container_id),
+            )));
+        };
+        let runtime = self.tokio_runtime.clone();
+        let dir = normalize_path(&path);
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let id = container_id.clone();
+            let listed = cx.background_executor().spawn(async move {
+                runtime.block_on(list_with_find(client, id, dir))
+            }).await;
+
+            let error = match listed {
+                Ok(Some(entries)) => return Ok(entries),
+                Ok(None) => DaemonError::Unsupported {
+                    operation: DaemonOperation::BrowseFiles,
+                    resource: Some(container_id),
+                    message: "The container has no `find` command to list files with".to_string(),
+                },
+                // Exec needs a running container
+                Err(status) if status.code() == tonic::Code::FailedPrecondition => DaemonError::Unsupported {
+                    operation: DaemonOperation::BrowseFiles,
+                    resource: Some(container_id),
+                    message: "Start the container to browse its files".to_string(),
+                },
+                Err(status) => DaemonError::from_status(DaemonOperation::BrowseFiles, Some(container_id), &status),
+            };
+            Self::report_files_error(&this, &error, false, cx);
+            Err(error)
+        })
+    }
+
+    /// Read up to `limit` bytes of a regular file
+    pub fn read_file(
+        &self,
+        container_id: String,
+        path: String,
+        limit: usize,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<FileContents, DaemonError>> {
+        self.with_archive(DaemonOperation::BrowseFiles, container_id, path, false, cx, move |reader| {
+            contents_from_archive(reader, limit)
+        })
+    }
+
+    /// Save a path as a tar file, or extract it into a host directory
+    pub fn download_path(
+        &self,
+        container_id: String,
+        path: String,
+        destination: PathBuf,
+        extract: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<(), DaemonError>> {
+        tracing::info!(
+            "Downloading {} from {} to {}",
+            path,
+            container_id,
+            destination.display()
+        );
+        self.with_archive(DaemonOperation::DownloadFiles, container_id, path, true, cx, move |reader| {
+            if extract {
+                std::fs::create_dir_all(&destination)?;
+                tar::Archive::new(reader).unpack(&destination)
+            } else {
+                let mut file = std::fs::File::create(&destination)?;
+                io::copy(reader, &mut file)?;
+                file.flush()
+            }
+        })
+    }
+
+    /// Copy host files and folders into a container directory
+    pub fn upload_paths(
+        &self,
+        container_id: String,
+        directory: String,
+        paths: Vec<PathBuf>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<(), DaemonError>> {
+        let Some(client) = self.container_client() else {
+            let error = DaemonError::not_connected(DaemonOperation::UploadFiles, Some(container_id));
+            cx.emit(DaemonEvent::OperationFailed(error.clone()));
+            return Task::ready(Err(error));
+        };
+        let runtime = self.tokio_runtime.clone();
+        tracing::info!("Uploading {} paths to {}:{}", paths.len(), container_id, directory);
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let id = container_id.clone();
+            let result = cx.background_executor().spawn(async move {
+                Self::upload_archive(client, &runtime, id, directory, &paths)
+            }).await;
+
+            result.map_err(|failure| {
+                let error = match failure {
+                    Ok(status) => DaemonError::from_status(DaemonOperation::UploadFiles, Some(container_id), &status),
+                    Err(e) => io_error(DaemonOperation::UploadFiles, &container_id, &e),
+                };
+                Self::report_files_error(&this, &error, true, cx);
+                error
+            })
+        })
+    }
+
+    /// Tar `paths` straight into a `PutArchive` stream (blocking)
+    ///
+    /// Errors are the RPC status when the daemon rejected the upload, or the
+    /// local I/O error when reading the files failed.
+    fn upload_archive(
+        mut client: ContainerServiceClient<Channel>,
+        runtime: &tokio::runtime::Runtime,
+        id: String,
+        directory: String,
+        paths: &[PathBuf],
+    ) -> Result<(), Result<tonic::Status, io::Error>> {
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<u8>>(8);
+        let requests = rx.map(move |data| PutArchiveRequest {
+            id: id.clone(),
+            path: directory.clone(),
+            data,
+        });
+        let rpc = runtime.spawn(async move {
+            client.put_archive(tonic::Request::new(requests)).await
+        });
+
+        // Dropping the writer at the end ends the request stream
+        let built = write_archive(
+            ArchiveWriter {
+                chunks: tx,
+                buffer: Vec::new(),
+            },
+            paths,
+        );
+
+        let response = runtime
+            .block_on(rpc)
+            .unwrap_or_else(|e| Err(tonic::Status::internal(e.to_string())));
+        match (response, built) {
+            // The daemon's reason is more useful than our broken pipe
+            (Err(status), _) => Err(Ok(status)),
+            (Ok(_), Err(e)) => Err(Err(e)),
+            (Ok(_), Ok(())) => Ok(()),
+        }
+    }
+
+    /// Stream the archive of `path` into `work` on a background thread
+    fn with_archive<T: Send + 'static>(
+        &self,
+        operation: DaemonOperation,
+        container_id: String,
+        path: String,
+        notify: bool,
+        cx: &mut Context<Self>,
+        work: impl FnOnce(&mut ArchiveReader) -> io::Result<T> + Send + 'static,
+    ) -> Task<Result<T, DaemonError>> {
+        let Some(mut client) = self.container_client() else {
+            let error = DaemonError::not_connected(operation, Some(container_id));
+            if notify {
+                cx.emit(DaemonEvent::OperationFailed(error.clone()));
+            }
+            return Task::ready(Err(error));
+        };
+
+        let (tx, rx): (Sender<Result<Vec<u8>, tonic::Status>>, _) = std::sync::mpsc::channel();
+        let id = container_id.clone();
+        self.tokio_runtime.spawn(async move {
+            let request = tonic::Request::new(GetArchiveRequest { id, path });
+            match client.get_archive(request).await {
+                Ok(response) => {
+                    let mut stream = response.into_inner();
+                    while let Some(chunk) = stream.next().await {
+                        let failed = chunk.is_err();
+                        if tx.send(chunk.map(|c| c.data)).is_err() || failed {
+                            // Reader finished early or the stream broke
+                            break;
+                        }
+                    }
+                }
+                Err(status) => {
+                    let _ = tx.send(Err(status));
+                }
+            }
+        });
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = cx.background_executor().spawn(async move {
+                let mut reader = ArchiveReader {
+                    chunks: rx,
+                    current: Vec::new(),
+                    position: 0,
+                    status: None,
+                };
+                work(&mut reader).map_err(|e| reader.status.take().ok_or(e))
+            }).await;
+
+            result.map_err(|failure| {
+                let error = match failure {
+                    Ok(status) => DaemonError::from_status(operation, Some(container_id), &status),
+                    Err(e) => io_error(operation, &container_id, &e),
+                };
+                Self::report_files_error(&this, &error, notify, cx);
+                error
+            })
+        })
+    }
+
+    /// Surface a failed file operation
+    ///
+    /// Transfers are user actions and get a notification; browsing errors
+    /// are shown in place by the caller. A lost connection reconnects either way.
+    fn report_files_error(this: &WeakEntity<Self>, error: &DaemonError, notify: bool, cx: &mut AsyncApp) {
+        tracing::warn!("{}: {}", error, error.message());
+        let error = error.clone();
+        cx.update(|cx| {
+            this.update(cx, |this, cx| {
+                if notify {
+                    this.operation_failed(error, cx);
+                } else if let DaemonError::Unavailable { message, .. } = &error {
+                    this.connection_lost(message.clone(), cx);
+                }
+            })
+        }).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn append(builder: &mut tar::Builder<Vec<u8>>, path: &str, kind: tar::EntryType, data: &[u8]) {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(kind);
+        header.set_size(data.len() as u64);
+        header.set_mode(if kind == tar::EntryType::Directory { 0o755 } else { 0o644 });
+        header.set_cksum();
+        builder.append_data(&mut header, path, data).unwrap();
+    }
+
+    #[test]
+    fn reads_one_file_from_the_archive() {
+        let mut builder = tar::Builder::new(Vec::new());
+        append(&mut builder, "hosts", tar::EntryType::Regular, b"127.0.0.1 localhost\n");
+        let archive = builder.into_inner().unwrap();
+
+        let contents = contents_from_archive(archive.as_slice(), 9).unwrap();
+        assert_eq!(contents.data, b"127.0.0.1");
+        assert!(contents.truncated);
+    }
+
+    #[test]
+    fn refuses_to_read_a_symlink() {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_cksum();
+        builder.append_link(&mut header, "localtime", "/usr/share/zoneinfo/UTC").unwrap();
+        let archive = builder.into_inner().unwrap();
+
+        let error = contents_from_archive(archive.as_slice(), 1024).unwrap_err();
+        assert_eq!(error.to_string(), "localtime is a symlink to /usr/share/zoneinfo/UTC");
+    }
+
+    #[test]
+    fn parses_find_output() {
+        let output = b"/etc/hosts\r\n/etc/ssl\r\nfind: '/etc/secret': Permission denied\r\n";
+        assert_eq!(paths_from_find("/etc", output), Some(vec!["/etc/hosts".to_string(), "/etc/ssl".to_string()]));
+        assert_eq!(paths_from_find("/", b"/bin\r\n/etc\r\n"), Some(vec!["/bin".to_string(), "/etc".to_string()]));
+        assert_eq!(paths_from_find("/", b""), Some(Vec::new()));
+        assert_eq!(paths_from_find("/", b"sh: find: not found\r\n"), None);
+    }
+
+    #[test]
+    fn path_helpers() {
+        assert_eq!(join_path("/", "etc"), "/etc");
+        assert_eq!(join_path("/etc", "hosts"), "/etc/hosts");
+        assert_eq!(parent_path("/etc/hosts"), "/etc");
+        assert_eq!(parent_path("/etc"), "/");
+        assert_eq!(parent_path("/"), "/");
+        assert_eq!(normalize_path("/usr/bin/../lib/./x"), "/usr/lib/x");
+    }
+
+    #[test]
+    fn permissions_string() {
+        let entry = FileEntry {
+            path: "/bin".into(),
+            name: "bin".into(),
+            kind: FileKind::Directory,
+            size: 0,
+            mode: 0o755,
+            modified: 0,
+            link_target: None,
+        };
+        assert_eq!(entry.permissions(), "drwxr-xr-x");
+    }
+}
@@ -0,0 +1,299 @@
+//! Version and capability handshake.
+//!
+//! Right after the channel comes up the service asks the daemon for its
+//! version. A daemon speaking a different major API version than the
+//! `arcbox-api` protos this build was generated from is refused; otherwise
+//! the capabilities it advertises in the `arcbox-capabilities` response
+//! metadata decide which UI features are offered. Daemons that predate the
+//! handshake answer `Unimplemented` and are assumed to support the baseline
+//! feature set; a capability is dropped later if its RPC turns out to be
+//! unsupported.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use arcbox_api::generated::{system_service_client::SystemServiceClient, VersionRequest};
+use gpui::*;
+use tonic::transport::Channel;
+
+use super::transport::SshTunnel;
+use super::{ConnectionState, DaemonEvent, DaemonOperation, DaemonService};
+
+/// API version of the `arcbox-api` protos this build was generated from
+///
+/// Bump together with the `arcbox-api` dependency.
+pub const CLIENT_API_VERSION: ApiVersion = ApiVersion { major: 1, minor: 0 };
+
+/// Response metadata key listing the daemon's capabilities (comma-separated)
+const CAPABILITIES_METADATA_KEY: &str = "arcbox-capabilities";
+
+/// `major.minor` API version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ApiVersion {
+    pub major: u64,
+    pub minor: u64,
+}
+
+impl ApiVersion {
+    /// Parse "1", "1.4", "1.4.2" or "v1.4"
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.trim().trim_start_matches('v').split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = match parts.next() {
+            Some(minor) => minor.parse().ok()?,
+            None => 0,
+        };
+        Some(Self { major, minor })
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Optional daemon features the UI is gated on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Linux machines (Machines section)
+    Machines,
+    /// Following container logs as they are written
+    LogsFollow,
+    /// Incremental event stream (otherwise lists are refreshed on demand)
+    Events,
+    /// Live container resource stats
+    Stats,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 4] = [
+        Capability::Machines,
+        Capability::LogsFollow,
+        Capability::Events,
+        Capability::Stats,
+    ];
+
+    /// Name used in the `arcbox-capabilities` metadata
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::Machines => "machines",
+            Capability::LogsFollow => "logs.follow",
+            Capability::Events => "events",
+            Capability::Stats => "stats",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+
+    /// Capability an operation depends on, if its failure should turn the feature off
+    fn required_by(operation: DaemonOperation) -> Option<Self> {
+        match operation {
+            DaemonOperation::ListMachines => Some(Capability::Machines),
+            DaemonOperation::Events => Some(Capability::Events),
+            DaemonOperation::ContainerStats => Some(Capability::Stats),
+            _ => None,
+        }
+    }
+}
+
+/// What the daemon reported about itself during the handshake
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaemonInfo {
+    /// Daemon release version, e.g. "0.4.1" (empty if unknown)
+    pub version: String,
+    /// None when the daemon predates the handshake
+    pub api_version: Option<ApiVersion>,
+    pub capabilities: HashSet<Capability>,
+}
+
+impl DaemonInfo {
+    /// A daemon without the version RPC: assume the baseline feature set
+    fn legacy() -> Self {
+        Self {
+            version: String::new(),
+            api_version: None,
+            capabilities: Capability::ALL.into_iter().collect(),
+        }
+    }
+
+    /// Build from the version response and its metadata
+    ///
+    /// Without the capabilities header every known capability is assumed.
+    pub fn from_response(version: String, api_version: &str, capabilities: Option<&str>) -> Self {
+        let capabilities = match capabilities {
+            Some(list) => list
+                .split(',')
+                .filter_map(|name| Capability::from_name(name.trim()))
+                .collect(),
+            None => Capability::ALL.into_iter().collect(),
+        };
+        Self {
+            version,
+            api_version: ApiVersion::parse(api_version),
+            capabilities,
+        }
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+
+    /// Refuse a daemon whose major API version differs from ours
+    pub fn check_compatible(&self) -> Result<(), String> {
+        match self.api_version {
+            Some(api) if api.major != CLIENT_API_VERSION.major => Err(format!(
+                "Daemon {}speaks API {}, but this version of ArcBox needs API {}.x. Update {} to match.",
+                if self.version.is_empty() {
+                    String::new()
+                } else {
+                    format!("{} ", self.version)
+                },
+                api,
+                CLIENT_API_VERSION.major,
+                if api.major > CLIENT_API_VERSION.major {
+                    "ArcBox Desktop"
+                } else {
+                    "the daemon"
+                },
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl DaemonService {
+    /// What the connected daemon reported in the handshake
+    pub fn daemon_info(&self) -> Option<&DaemonInfo> {
+        self.daemon_info.as_ref()
+    }
+
+    /// Whether the daemon supports a feature
+    ///
+    /// Optimistic until the handshake has run, so the UI doesn't flicker
+    /// while connecting.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.daemon_info
+            .as_ref()
+            .is_none_or(|info| info.supports(capability))
+    }
+
+    /// Ask the daemon for its version, then finish connecting or refuse it
+    pub(super) fn handshake(
+        &mut self,
+        channel: Channel,
+        tunnel: Option<SshTunnel>,
+        cx: &mut Context<Self>,
+    ) {
+        let mut client = SystemServiceClient::new(channel.clone());
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = cx
+                .background_executor()
+                .spawn(async move { Self::fetch_daemon_info(&mut client, &runtime) })
+                .await;
+
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    // disconnect() was called while the handshake was in flight
+                    if this.state != ConnectionState::Connecting {
+                        return;
+                    }
+                    match result {
+                        Ok(info) => {
+                            if let Err(reason) = info.check_compatible() {
+                                tracing::error!("Refusing incompatible daemon: {}", reason);
+                                this.refuse_incompatible(reason, cx);
+                                return;
+                            }
+                            tracing::info!(
+                                "Daemon {} (API {}), capabilities: {:?}",
+                                if info.version.is_empty() { "unknown" } else { &info.version },
+                                info.api_version.map(|v| v.to_string()).unwrap_or_else(|| "unknown".into()),
+                                info.capabilities,
+                            );
+                            let changed = Capability::ALL
+                                .into_iter()
+                                .any(|c| this.supports(c) != info.supports(c));
+                            this.daemon_info = Some(info);
+                            this.ssh_tunnel = tunnel;
+                            this.on_connected(channel, cx);
+                            if changed {
+                                cx.emit(DaemonEvent::CapabilitiesChanged);
+                            }
+                        }
+                        Err(status) => {
+                            tracing::error!("Daemon handshake failed: {}", status);
+                            this.schedule_reconnect(
+                                format!("Handshake failed: {}", status.message()),
+                                cx,
+                            );
+                        }
+                    }
+                })
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Version RPC; a daemon without it is treated as a legacy daemon
+    fn fetch_daemon_info(
+        client: &mut SystemServiceClient<Channel>,
+        runtime: &Arc<tokio::runtime::Runtime>,
+    ) -> Result<DaemonInfo, tonic::Status> {
+        let result = runtime.block_on(async {
+            client.version(tonic::Request::new(VersionRequest::default())).await
+        });
+
+        match result {
+            Ok(response) => {
+                let capabilities = response
+                    .metadata()
+                    .get(CAPABILITIES_METADATA_KEY)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let version = response.into_inner();
+                Ok(DaemonInfo::from_response(
+                    version.version,
+                    &version.api_version,
+                    capabilities.as_deref(),
+                ))
+            }
+            Err(status) if status.code() == tonic::Code::Unimplemented => {
+                tracing::warn!("Daemon predates the version handshake, assuming baseline features");
+                Ok(DaemonInfo::legacy())
+            }
+            Err(status) => Err(status),
+        }
+    }
+
+    /// Drop the connection to a daemon we can't talk to; no retries
+    fn refuse_incompatible(&mut self, reason: String, cx: &mut Context<Self>) {
+        self.channel = None;
+        self.ssh_tunnel = None;
+        self.daemon_info = None;
+        self.reconnect_task = None;
+        self.state = ConnectionState::Error(reason.clone());
+        cx.emit(DaemonEvent::Incompatible(reason));
+        cx.notify();
+    }
+
+    /// An RPC came back `Unimplemented`: stop offering the feature behind it
+    pub(super) fn capability_unsupported(&mut self, operation: DaemonOperation, cx: &mut Context<Self>) {
+        let Some(capability) = Capability::required_by(operation) else {
+            return;
+        };
+        if let Some(info) = self.daemon_info.as_mut() {
+            if info.capabilities.remove(&capability) {
+                tracing::info!("Daemon does not support {}, disabling it", capability.name());
+                cx.emit(DaemonEvent::CapabilitiesChanged);
+                cx.notify();
+            }
+        }
+    }
+}
@@ -0,0 +1,528 @@
+//! Full container configuration.
+//!
+//! `InspectContainer` returns the Docker-compatible inspect document as
+//! JSON. It is kept verbatim (pretty-printed) for the raw view, and the parts
+//! the Info tab shows or scaling copies are pulled out into
+//! `ContainerInspect`. Fields missing from older daemons simply come back
+//! empty.
+
+use std::collections::BTreeMap;
+
+use arcbox_api::generated::InspectContainerRequest;
+use gpui::*;
+use serde::Deserialize;
+
+use super::{DaemonError, DaemonOperation, DaemonService};
+
+/// A container as reported by inspect
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContainerInspect {
+    /// The whole document, pretty-printed
+    pub json: String,
+    pub entrypoint: Vec<String>,
+    pub cmd: Vec<String>,
+    pub working_dir: String,
+    pub user: String,
+    pub hostname: String,
+    /// Environment as (name, value), in the order the daemon lists it
+    pub env: Vec<(String, String)>,
+    pub mounts: Vec<MountInfo>,
+    /// Attached networks by name
+    pub networks: Vec<NetworkEndpoint>,
+    /// Published ports, e.g. "0.0.0.0:8080 → 80/tcp"
+    pub ports: Vec<String>,
+    pub state: StateInfo,
+    /// None when the image defines no health check
+    pub health: Option<HealthInfo>,
+    pub restart_policy: RestartPolicy,
+    pub resources: ResourceLimits,
+    /// Removed when it exits (`--rm`)
+    pub auto_remove: bool,
+    pub privileged: bool,
+    /// Root filesystem mounted read-only
+    pub read_only: bool,
+    /// Runs an init process as PID 1
+    pub init: bool,
+    /// e.g. "linux/arm64"; only the OS ("linux") when the daemon doesn't
+    /// report the image's platform
+    pub platform: String,
+}
+
+/// A volume, bind or tmpfs mount
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MountInfo {
+    /// "volume", "bind", "tmpfs", ...
+    pub kind: String,
+    /// Host path or volume name
+    pub source: String,
+    pub destination: String,
+    pub read_only: bool,
+}
+
+/// The container's address on one network
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NetworkEndpoint {
+    pub name: String,
+    pub ip_address: String,
+    pub gateway: String,
+    pub mac_address: String,
+}
+
+/// Process state and the outcome of the last run
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StateInfo {
+    pub status: String,
+    pub pid: i64,
+    pub exit_code: i64,
+    pub oom_killed: bool,
+    /// Last runtime error, if any
+    pub error: String,
+    /// RFC 3339 timestamps (empty if never started or still running)
+    pub started_at: String,
+    pub finished_at: String,
+    pub restart_count: i64,
+}
+
+/// Health check status
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HealthInfo {
+    /// "starting", "healthy" or "unhealthy"
+    pub status: String,
+    pub failing_streak: i64,
+    /// Output of the most recent probe
+    pub last_output: String,
+}
+
+/// When the daemon restarts the container
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RestartPolicy {
+    /// "no", "always", "unless-stopped" or "on-failure" (empty means "no")
+    pub name: String,
+    pub max_retries: i64,
+}
+
+impl RestartPolicy {
+    /// e.g. "on-failure (max 3)"
+    pub fn display(&self) -> String {
+        match self.name.as_str() {
+            "" => "no".to_string(),
+            "on-failure" if self.max_retries > 0 => format!("on-failure (max {})", self.max_retries),
+            name => name.to_string(),
+        }
+    }
+}
+
+/// Resource limits (0 means unlimited)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResourceLimits {
+    pub memory_bytes: i64,
+    pub memory_swap_bytes: i64,
+    /// Number of CPUs, e.g. 1.5
+    pub cpus: f64,
+    pub cpu_shares: i64,
+    pub pids_limit: i64,
+}
+
+impl ContainerInspect {
+    /// Parse an inspect document
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let raw: RawInspect = serde_json::from_value(value.clone())?;
+        let config = raw.config;
+        let host = raw.host_config;
+        let state = raw.state;
+
+        let env = config
+            .env
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| match entry.split_once('=') {
+                Some((name, value)) => (name.to_string(), value.to_string()),
+                None => (entry, String::new()),
+            })
+            .collect();
+
+        let mounts = raw
+            .mounts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| MountInfo {
+                // Volumes by name rather than where the daemon keeps them
+                source: if m.source.is_empty() || (m.kind == "volume" && !m.name.is_empty()) {
+                    m.name
+                } else {
+                    m.source
+                },
+                kind: m.kind,
+                destination: m.destination,
+                read_only: !m.rw,
+            })
+            .collect();
+
+        let networks = raw
+            .network_settings
+            .networks
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, n)| NetworkEndpoint {
+                name,
+                ip_address: n.ip_address,
+                gateway: n.gateway,
+                mac_address: n.mac_address,
+            })
+            .collect();
+
+        let mut ports = Vec::new();
+        for (port, bindings) in raw.network_settings.ports.unwrap_or_default() {
+            match bindings {
+                Some(bindings) if !bindings.is_empty() => {
+                    for b in bindings {
+                        ports.push(format!("{}:{} → {}", b.host_ip, b.host_port, port));
+                    }
+                }
+                // Exposed but not published
+                _ => ports.push(port),
+            }
+        }
+
+        // The image's platform is only reported by newer daemons
+        let platform = match raw.image_manifest_descriptor.platform {
+            Some(p) if !p.os.is_empty() && !p.architecture.is_empty() => [p.os, p.architecture, p.variant]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("/"),
+            _ => raw.platform,
+        };
+
+        let health = state.health.map(|h| HealthInfo {
+            status: h.status,
+            failing_streak: h.failing_streak,
+            last_output: h
+                .log
+                .unwrap_or_default()
+                .last()
+                .map(|probe| probe.output.trim_end().to_string())
+                .unwrap_or_default(),
+        });
+
+        Ok(Self {
+            json: serde_json::to_string_pretty(&value)?,
+            entrypoint: config.entrypoint.map(Vec::from).unwrap_or_default(),
+            cmd: config.cmd.map(Vec::from).unwrap_or_default(),
+            working_dir: config.working_dir,
+            user: config.user,
+            hostname: config.hostname,
+            env,
+            mounts,
+            networks,
+            ports,
+            state: StateInfo {
+                status: state.status,
+                pid: state.pid,
+                exit_code: state.exit_code,
+                oom_killed: state.oom_killed,
+                error: state.error,
+                started_at: state.started_at,
+                finished_at: state.finished_at,
+                restart_count: raw.restart_count,
+            },
+            health,
+            restart_policy: RestartPolicy {
+                name: host.restart_policy.name,
+                max_retries: host.restart_policy.maximum_retry_count,
+            },
+            resources: ResourceLimits {
+                memory_bytes: host.memory,
+                memory_swap_bytes: host.memory_swap,
+                cpus: host.nano_cpus as f64 / 1e9,
+                cpu_shares: host.cpu_shares,
+                pids_limit: host.pids_limit.unwrap_or(0).max(0),
+            },
+            auto_remove: host.auto_remove,
+            privileged: host.privileged,
+            read_only: host.readonly_rootfs,
+            init: host.init.unwrap_or(false),
+            platform,
+        })
+    }
+}
+
+// Subset of the Docker inspect schema. Everything defaults, and nullable
+// fields are Options, so partial documents still parse.
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawInspect {
+    config: RawConfig,
+    host_config: RawHostConfig,
+    state: RawState,
+    mounts: Option<Vec<RawMount>>,
+    network_settings: RawNetworkSettings,
+    restart_count: i64,
+    platform: String,
+    image_manifest_descriptor: RawDescriptor,
+}
+
+/// OCI descriptor of the image the container was created from
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawDescriptor {
+    platform: Option<RawPlatform>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawPlatform {
+    os: String,
+    architecture: String,
+    variant: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawConfig {
+    env: Option<Vec<String>>,
+    entrypoint: Option<StringOrList>,
+    cmd: Option<StringOrList>,
+    working_dir: String,
+    user: String,
+    hostname: String,
+}
+
+/// `Cmd` and `Entrypoint` are lists, but some producers send a plain string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrList {
+    String(String),
+    List(Vec<String>),
+}
+
+impl From<StringOrList> for Vec<String> {
+    fn from(value: StringOrList) -> Self {
+        match value {
+            StringOrList::String(s) if s.is_empty() => Vec::new(),
+            StringOrList::String(s) => vec![s],
+            StringOrList::List(list) => list,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawHostConfig {
+    restart_policy: RawRestartPolicy,
+    memory: i64,
+    memory_swap: i64,
+    nano_cpus: i64,
+    cpu_shares: i64,
+    pids_limit: Option<i64>,
+    auto_remove: bool,
+    privileged: bool,
+    readonly_rootfs: bool,
+    init: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawRestartPolicy {
+    name: String,
+    maximum_retry_count: i64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawState {
+    status: String,
+    pid: i64,
+    exit_code: i64,
+    #[serde(rename = "OOMKilled")]
+    oom_killed: bool,
+    error: String,
+    started_at: String,
+    finished_at: String,
+    health: Option<RawHealth>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawHealth {
+    status: String,
+    failing_streak: i64,
+    log: Option<Vec<RawHealthProbe>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawHealthProbe {
+    output: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawMount {
+    #[serde(rename = "Type")]
+    kind: String,
+    name: String,
+    source: String,
+    destination: String,
+    #[serde(rename = "RW")]
+    rw: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawNetworkSettings {
+    networks: Option<BTreeMap<String, RawEndpoint>>,
+    ports: Option<BTreeMap<String, Option<Vec<RawPortBinding>>>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawEndpoint {
+    #[serde(rename = "IPAddress")]
+    ip_address: String,
+    gateway: String,
+    mac_address: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RawPortBinding {
+    host_ip: String,
+    host_port: String,
+}
+
+impl DaemonService {
+    /// Fetch a container's full configuration
+    ///
+    /// Errors are shown in place by the caller; a lost connection still
+    /// triggers a reconnect.
+    pub fn inspect_container(
+        &self,
+        container_id: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ContainerInspect, DaemonError>> {
+        let Some(mut client) = self.container_client() else {
+            return Task::ready(Err(DaemonError::not_connected(
+                DaemonOperation::InspectContainer,
+                Some(container_id),
+            )));
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let id = container_id.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime.block_on(async {
+                    client
+                        .inspect_container(tonic::Request::new(InspectContainerRequest { id }))
+                        .await
+                })
+            }).await;
+
+            match result {
+                Ok(response) => ContainerInspect::from_json(&response.into_inner().json).map_err(|e| {
+                    tracing::warn!("Invalid inspect document for {}: {}", container_id, e);
+                    DaemonError::Internal {
+                        operation: DaemonOperation::InspectContainer,
+                        resource: Some(container_id),
+                        code: tonic::Code::DataLoss,
+                        message: format!("invalid inspect document: {}", e),
+                    }
+                }),
+                Err(status) => {
+                    let error = DaemonError::from_status(DaemonOperation::InspectContainer, Some(container_id), &status);
+                    tracing::warn!("{}: {}", error, error.message());
+                    if let DaemonError::Unavailable { message, .. } = &error {
+                        let message = message.clone();
+                        cx.update(|cx| {
+                            this.update(cx, |this, cx| this.connection_lost(message, cx))
+                        }).ok();
+                    }
+                    Err(error)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"{
+        "Id": "abc",
+        "Config": {
+            "Env": ["PATH=/usr/bin", "EMPTY=", "FLAG"],
+            "Entrypoint": null,
+            "Cmd": ["nginx", "-g", "daemon off;"],
+            "WorkingDir": "/srv"
+        },
+        "HostConfig": {
+            "RestartPolicy": {"Name": "on-failure", "MaximumRetryCount": 3},
+            "Memory": 536870912,
+            "NanoCpus": 1500000000,
+            "PidsLimit": null,
+            "ReadonlyRootfs": true,
+            "Init": null
+        },
+        "State": {
+            "Status": "exited",
+            "ExitCode": 137,
+            "OOMKilled": true,
+            "Health": {"Status": "unhealthy", "FailingStreak": 2, "Log": [{"Output": "ok\n"}, {"Output": "refused\n"}]}
+        },
+        "Mounts": [
+            {"Type": "volume", "Name": "data", "Source": "", "Destination": "/data", "RW": true},
+            {"Type": "bind", "Source": "/etc/app", "Destination": "/config", "RW": false}
+        ],
+        "NetworkSettings": {
+            "Networks": {"bridge": {"IPAddress": "172.17.0.2", "Gateway": "172.17.0.1"}},
+            "Ports": {"80/tcp": [{"HostIp": "0.0.0.0", "HostPort": "8080"}], "443/tcp": null}
+        },
+        "RestartCount": 4,
+        "Platform": "linux",
+        "ImageManifestDescriptor": {"platform": {"architecture": "arm64", "os": "linux", "variant": "v8"}}
+    }"#;
+
+    #[test]
+    fn parses_docker_inspect_document() {
+        let inspect = ContainerInspect::from_json(DOCUMENT).unwrap();
+
+        assert!(inspect.entrypoint.is_empty());
+        assert_eq!(inspect.cmd, ["nginx", "-g", "daemon off;"]);
+        assert_eq!(
+            inspect.env,
+            [
+                ("PATH".to_string(), "/usr/bin".to_string()),
+                ("EMPTY".to_string(), String::new()),
+                ("FLAG".to_string(), String::new()),
+            ]
+        );
+        assert_eq!(inspect.mounts[0].source, "data");
+        assert!(!inspect.mounts[0].read_only);
+        assert!(inspect.mounts[1].read_only);
+        assert_eq!(inspect.networks[0].ip_address, "172.17.0.2");
+        assert_eq!(inspect.ports, ["0.0.0.0:8080 → 80/tcp", "443/tcp"]);
+        assert_eq!(inspect.state.exit_code, 137);
+        assert!(inspect.state.oom_killed);
+        assert_eq!(inspect.state.restart_count, 4);
+        assert_eq!(inspect.health.as_ref().unwrap().last_output, "refused");
+        assert_eq!(inspect.restart_policy.display(), "on-failure (max 3)");
+        assert_eq!(inspect.resources.cpus, 1.5);
+        assert_eq!(inspect.resources.pids_limit, 0);
+        assert!(inspect.read_only && !inspect.privileged && !inspect.init);
+        assert_eq!(inspect.platform, "linux/arm64/v8");
+        assert!(inspect.json.contains("\"WorkingDir\": \"/srv\""));
+    }
+
+    #[test]
+    fn tolerates_sparse_documents() {
+        let inspect = ContainerInspect::from_json(r#"{"Config": {"Cmd": "sh"}}"#).unwrap();
+        assert_eq!(inspect.cmd, ["sh"]);
+        assert!(inspect.health.is_none());
+        assert_eq!(inspect.restart_policy.display(), "no");
+        assert!(ContainerInspect::from_json("not json").is_err());
+    }
+}
@@ -0,0 +1,312 @@
+//! Live container resource stats.
+//!
+//! Each running container the UI is watching gets a streaming
+//! `ContainerStats` RPC. The daemon reports cumulative counters (CPU time,
+//! bytes transferred); consecutive reports are turned into CPU percentages
+//! and per-second rates here, then delivered as `StatsReceived` events.
+//! Streams end on their own when a container stops and are reopened after a
+//! reconnect, like followed log streams.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use arcbox_api::generated::{ContainerStatsRequest, ContainerStatsResponse};
+use gpui::*;
+
+use super::{read_stream, Capability, DaemonError, DaemonEvent, DaemonOperation, DaemonService};
+
+/// Samples kept per container (one per second from the daemon)
+pub const STATS_HISTORY_LEN: usize = 60;
+
+/// One resource usage sample
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ContainerStats {
+    /// Share of one CPU, so a busy 4-core container can reach 400
+    pub cpu_percent: f64,
+    pub memory_bytes: u64,
+    /// 0 when the container has no limit
+    pub memory_limit_bytes: u64,
+    /// Network receive rate in bytes per second
+    pub net_rx_per_sec: f64,
+    /// Network transmit rate in bytes per second
+    pub net_tx_per_sec: f64,
+    /// Block device read rate in bytes per second
+    pub block_read_per_sec: f64,
+    /// Block device write rate in bytes per second
+    pub block_write_per_sec: f64,
+}
+
+impl ContainerStats {
+    /// Derive a sample from two consecutive daemon reports
+    ///
+    /// Returns None if the reports are out of order (or from a restarted
+    /// container), since no rate can be computed from them.
+    pub fn from_reports(previous: &ContainerStatsResponse, current: &ContainerStatsResponse) -> Option<Self> {
+        let elapsed_ns = current.timestamp.checked_sub(previous.timestamp).filter(|ns| *ns > 0)?;
+        let seconds = elapsed_ns as f64 / 1e9;
+        let rate = |previous: u64, current: u64| current.checked_sub(previous).map(|delta| delta as f64 / seconds);
+
+        // Same formula as `docker stats`
+        let cpu_delta = current.cpu_total_usage.checked_sub(previous.cpu_total_usage)?;
+        let system_delta = current.system_cpu_usage.saturating_sub(previous.system_cpu_usage);
+        let cpu_percent = if system_delta > 0 {
+            cpu_delta as f64 / system_delta as f64 * f64::from(current.online_cpus.max(1)) * 100.0
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            cpu_percent,
+            memory_bytes: current.memory_usage,
+            memory_limit_bytes: current.memory_limit,
+            net_rx_per_sec: rate(previous.network_rx_bytes, current.network_rx_bytes)?,
+            net_tx_per_sec: rate(previous.network_tx_bytes, current.network_tx_bytes)?,
+            block_read_per_sec: rate(previous.block_read_bytes, current.block_read_bytes)?,
+            block_write_per_sec: rate(previous.block_write_bytes, current.block_write_bytes)?,
+        })
+    }
+
+    pub fn memory_percent(&self) -> f64 {
+        if self.memory_limit_bytes == 0 {
+            return 0.0;
+        }
+        self.memory_bytes as f64 / self.memory_limit_bytes as f64 * 100.0
+    }
+}
+
+/// Rolling window of samples for one container
+#[derive(Debug, Clone, Default)]
+pub struct StatsHistory {
+    samples: VecDeque<ContainerStats>,
+}
+
+impl StatsHistory {
+    pub fn push(&mut self, stats: ContainerStats) {
+        if self.samples.len() == STATS_HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(stats);
+    }
+
+    pub fn latest(&self) -> Option<&ContainerStats> {
+        self.samples.back()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// One value per sample, oldest first
+    pub fn series(&self, value: impl Fn(&ContainerStats) -> f64) -> Vec<f64> {
+        self.samples.iter().map(value).collect()
+    }
+}
+
+impl DaemonService {
+    /// Stream stats for exactly these containers
+    ///
+    /// Streams for containers not in `ids` are dropped and missing ones are
+    /// opened. Pass the running containers whenever the list changes.
+    pub fn watch_stats(&mut self, ids: impl IntoIterator<Item = String>, cx: &mut Context<Self>) {
+        if !self.supports(Capability::Stats) {
+            self.stats_subscriptions.clear();
+            return;
+        }
+
+        let ids: Vec<String> = ids.into_iter().collect();
+        self.stats_subscriptions.retain(|id, _| ids.contains(id));
+        for id in ids {
+            if self.stats_subscriptions.contains_key(&id) {
+                continue;
+            }
+            self.next_stats_generation += 1;
+            self.stats_subscriptions.insert(id.clone(), self.next_stats_generation);
+            self.spawn_stats_stream(id, cx);
+        }
+    }
+
+    /// Reopen every stats stream after a reconnect
+    pub(super) fn resume_stats_subscriptions(&mut self, cx: &mut Context<Self>) {
+        let ids: Vec<String> = self.stats_subscriptions.keys().cloned().collect();
+        for id in ids {
+            self.next_stats_generation += 1;
+            self.stats_subscriptions.insert(id.clone(), self.next_stats_generation);
+            self.spawn_stats_stream(id, cx);
+        }
+    }
+
+    /// Open the stats stream for a subscription and pump samples into events
+    fn spawn_stats_stream(&self, container_id: String, cx: &mut Context<Self>) {
+        let Some(generation) = self.stats_subscriptions.get(&container_id).copied() else {
+            return;
+        };
+        let Some(mut client) = self.container_client() else {
+            return;
+        };
+        let runtime = self.tokio_runtime.clone();
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let id_for_request = container_id.clone();
+            let runtime_clone = runtime.clone();
+            let result = cx.background_executor().spawn(async move {
+                runtime_clone.block_on(async {
+                    let request = tonic::Request::new(ContainerStatsRequest {
+                        id: id_for_request,
+                        stream: true,
+                    });
+                    client.container_stats(request).await
+                })
+            }).await;
+
+            let stream = match result {
+                Ok(response) => response.into_inner(),
+                Err(status) => {
+                    let error = DaemonError::from_status(DaemonOperation::ContainerStats, Some(container_id.clone()), &status);
+                    Self::stats_stream_ended(&this, &container_id, generation, Some(error), cx);
+                    return;
+                }
+            };
+
+            let rx = read_stream(&runtime, stream, format!("stats for {}", container_id));
+
+            let mut previous: Option<ContainerStatsResponse> = None;
+            loop {
+                match rx.try_recv() {
+                    Ok(Ok(report)) => {
+                        let stats = previous
+                            .as_ref()
+                            .and_then(|previous| ContainerStats::from_reports(previous, &report));
+                        previous = Some(report);
+                        let Some(stats) = stats else {
+                            continue;
+                        };
+
+                        let id = container_id.clone();
+                        let still_watched = cx.update(|cx| {
+                            this.update(cx, |this, cx| {
+                                if this.stats_subscriptions.get(&id) != Some(&generation) {
+                                    return false;
+                                }
+                                cx.emit(DaemonEvent::StatsReceived { container_id: id, stats });
+                                true
+                            })
+                        }).ok().and_then(|r| r.ok()).unwrap_or(false);
+
+                        if !still_watched {
+                            // Dropping the receiver stops the stream reader
+                            break;
+                        }
+                    }
+                    Ok(Err(status)) => {
+                        let error = DaemonError::from_status(DaemonOperation::ContainerStats, Some(container_id.clone()), &status);
+                        Self::stats_stream_ended(&this, &container_id, generation, Some(error), cx);
+                        break;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {
+                        // Unwatched while idle: drop the reader now rather than
+                        // when the next sample arrives
+                        let id = container_id.clone();
+                        let still_watched = cx.update(|cx| {
+                            this.update(cx, |this, _cx| this.stats_subscriptions.get(&id) == Some(&generation))
+                        }).ok().and_then(|r| r.ok()).unwrap_or(false);
+                        if !still_watched {
+                            break;
+                        }
+                        cx.background_executor().timer(Duration::from_millis(50)).await;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        // Container stopped; watch_stats reopens it if it starts again
+                        Self::stats_stream_ended(&this, &container_id, generation, None, cx);
+                        break;
+                    }
+                }
+            }
+        }).detach();
+    }
+
+    /// Forget a finished stream; failures are logged, not shown
+    ///
+    /// Stats are ambient, so a container stopping mid-stream is not worth a
+    /// toast. A lost connection still triggers a reconnect and an
+    /// unimplemented RPC turns the feature off.
+    fn stats_stream_ended(
+        this: &WeakEntity<Self>,
+        container_id: &str,
+        generation: u64,
+        error: Option<DaemonError>,
+        cx: &mut AsyncApp,
+    ) {
+        if let Some(error) = &error {
+            tracing::debug!("{}", error);
+        }
+        cx.update(|cx| {
+            this.update(cx, |this, cx| {
+                if this.stats_subscriptions.get(container_id) == Some(&generation) {
+                    this.stats_subscriptions.remove(container_id);
+                }
+                match error {
+                    Some(DaemonError::Unavailable { message, .. }) => this.connection_lost(message, cx),
+                    Some(DaemonError::Unsupported { operation, .. }) => {
+                        this.stats_subscriptions.clear();
+                        this.capability_unsupported(operation, cx);
+                    }
+                    _ => {}
+                }
+            })
+        }).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(seconds: i64, cpu: u64, system: u64, rx: u64) -> ContainerStatsResponse {
+        ContainerStatsResponse {
+            timestamp: seconds * 1_000_000_000,
+            cpu_total_usage: cpu,
+            system_cpu_usage: system,
+            online_cpus: 4,
+            memory_usage: 256 << 20,
+            memory_limit: 1 << 30,
+            network_rx_bytes: rx,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn computes_cpu_percent_and_rates() {
+        let stats = ContainerStats::from_reports(
+            &report(10, 1_000, 10_000, 0),
+            &report(12, 3_000, 18_000, 4_096),
+        )
+        .unwrap();
+
+        // 2000 of 8000 system ticks on 4 CPUs
+        assert_eq!(stats.cpu_percent, 100.0);
+        assert_eq!(stats.net_rx_per_sec, 2_048.0);
+        assert_eq!(stats.net_tx_per_sec, 0.0);
+        assert_eq!(stats.memory_percent(), 25.0);
+    }
+
+    #[test]
+    fn skips_out_of_order_or_reset_counters() {
+        assert!(ContainerStats::from_reports(&report(12, 0, 0, 0), &report(10, 0, 0, 0)).is_none());
+        assert!(ContainerStats::from_reports(&report(10, 0, 0, 100), &report(11, 0, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn history_keeps_a_rolling_window() {
+        let mut history = StatsHistory::default();
+        for i in 0..STATS_HISTORY_LEN + 5 {
+            history.push(ContainerStats {
+                cpu_percent: i as f64,
+                ..Default::default()
+            });
+        }
+        let cpu = history.series(|s| s.cpu_percent);
+        assert_eq!(cpu.len(), STATS_HISTORY_LEN);
+        assert_eq!(cpu[0], 5.0);
+        assert_eq!(history.latest().unwrap().cpu_percent, (STATS_HISTORY_LEN + 4) as f64);
+    }
+}
@@ -0,0 +1,801 @@
+//! End-to-end tests for `DaemonService` against the in-process fake daemon.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use arcbox_api::generated::{
+    ContainerStatsResponse, ContainerSummary, ImageSummary, LogEntry, NetworkSummary,
+    StatPathResponse,
+};
+use futures::StreamExt;
+use gpui::{AppContext as _, Entity, TestAppContext};
+use tonic::Status;
+
+use super::{
+    BulkAction, BulkResult, Capability, ComposeCommand, ComposeStatus, ComposeStep, ConnectionState, ContainerSpec, ContainerStats, DaemonError, DaemonEvent,
+    DaemonOperation, DaemonService, MountKind, MountSpec, PortMapping,
+};
+use crate::services::{ComposeProject, DaemonContext, DaemonEndpoint};
+use crate::test_support::{resource_event, FakeDaemon};
+
+/// Give up waiting for a condition after this long (wall clock)
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Events = Rc<RefCell<Vec<DaemonEvent>>>;
+
+/// Create a service pointed at the fake and record its events
+fn service_for(cx: &mut TestAppContext, daemon: &FakeDaemon) -> (Entity<DaemonService>, Events) {
+    let context = DaemonContext {
+        name: "test".to_string(),
+        endpoint: DaemonEndpoint::Local {
+            socket_path: daemon.socket_path().to_path_buf(),
+        },
+    };
+    let service = cx.new(|cx| DaemonService::for_context(context, cx));
+
+    let events: Events = Rc::default();
+    let recorded = events.clone();
+    cx.update(|cx| {
+        cx.subscribe(&service, move |_, event: &DaemonEvent, _| {
+            recorded.borrow_mut().push(event.clone());
+        })
+        .detach();
+    });
+    (service, events)
+}
+
+/// Create a service pointed at the fake, record its events and connect
+fn connect(cx: &mut TestAppContext, daemon: &FakeDaemon) -> (Entity<DaemonService>, Events) {
+    let (service, events) = service_for(cx, daemon);
+    service.update(cx, |svc, cx| svc.connect(cx));
+    wait_until(cx, "connected", |cx| service.read_with(cx, |svc, _| svc.is_connected()));
+    (service, events)
+}
+
+/// Drive timers and background work until `done` holds
+///
+/// RPCs complete on real tokio threads, so this advances the fake clock in
+/// small steps and sleeps briefly between polls.
+fn wait_until(cx: &mut TestAppContext, what: &str, mut done: impl FnMut(&mut TestAppContext) -> bool) {
+    let deadline = Instant::now() + WAIT_TIMEOUT;
+    loop {
+        cx.run_until_parked();
+        if done(cx) {
+            return;
+        }
+        assert!(Instant::now() < deadline, "timed out waiting for {}", what);
+        cx.executor().advance_clock(Duration::from_millis(10));
+        std::thread::sleep(Duration::from_millis(2));
+    }
+}
+
+fn has_event(events: &Events, matches: impl Fn(&DaemonEvent) -> bool) -> bool {
+    events.borrow().iter().any(matches)
+}
+
+/// Containers from the most recent `ContainersLoaded`
+fn last_containers(events: &Events) -> Option<Vec<ContainerSummary>> {
+    events.borrow().iter().rev().find_map(|e| match e {
+        DaemonEvent::ContainersLoaded(response) => Some(response.containers.clone()),
+        _ => None,
+    })
+}
+
+fn container(id: &str, name: &str, state: &str) -> ContainerSummary {
+    ContainerSummary {
+        id: id.to_string(),
+        name: format!("/{}", name),
+        image: "alpine:latest".to_string(),
+        state: state.to_string(),
+        ..Default::default()
+    }
+}
+
+fn log_entry(timestamp_secs: i64, line: &str) -> LogEntry {
+    LogEntry {
+        stream: "stdout".to_string(),
+        data: format!("{}\n", line).into_bytes(),
+        timestamp: timestamp_secs * 1_000_000_000,
+    }
+}
+
+#[gpui::test]
+fn lists_resources_after_connect(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    {
+        let mut state = daemon.state();
+        state.containers.push(container("c1", "web", "running"));
+        state.containers.push(container("c2", "worker", "exited"));
+        state.images.push(ImageSummary {
+            id: "sha256:abc".to_string(),
+            repo_tags: vec!["alpine:latest".to_string()],
+            ..Default::default()
+        });
+        state.networks.push(NetworkSummary {
+            id: "n1".to_string(),
+            name: "bridge".to_string(),
+            ..Default::default()
+        });
+    }
+
+    let (service, events) = connect(cx, &daemon);
+    service.update(cx, |svc, cx| svc.refresh_all(cx));
+
+    wait_until(cx, "all lists", |_| {
+        let events = events.borrow();
+        events.iter().any(|e| matches!(e, DaemonEvent::ContainersLoaded(_)))
+            && events.iter().any(|e| matches!(e, DaemonEvent::ImagesLoaded(_)))
+            && events.iter().any(|e| matches!(e, DaemonEvent::NetworksLoaded(_)))
+            && events.iter().any(|e| matches!(e, DaemonEvent::MachinesLoaded(_)))
+    });
+
+    assert_eq!(last_containers(&events).unwrap().len(), 2);
+    assert!(has_event(&events, |e| matches!(
+        e,
+        DaemonEvent::ImagesLoaded(r) if r.images.len() == 1
+    )));
+    assert!(has_event(&events, |e| matches!(
+        e,
+        DaemonEvent::NetworksLoaded(r) if r.networks[0].name == "bridge"
+    )));
+}
+
+#[gpui::test]
+fn container_lifecycle(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    let (service, events) = connect(cx, &daemon);
+
+    let spec = ContainerSpec {
+        image: "nginx:latest".to_string(),
+        name: Some("web".to_string()),
+        restart_policy: Some("unless-stopped".to_string()),
+        ports: vec![PortMapping {
+            host_ip: String::new(),
+            host_port: 8080,
+            container_port: 80,
+            protocol: "tcp".to_string(),
+        }],
+        env: vec![("MODE".to_string(), "prod".to_string())],
+        mounts: vec![MountSpec {
+            kind: MountKind::Volume,
+            source: "html".to_string(),
+            target: "/usr/share/nginx/html".to_string(),
+            read_only: true,
+        }],
+        memory_bytes: Some(256 << 20),
+        ..Default::default()
+    };
+    service.update(cx, |svc, cx| svc.create_container(spec, true, cx));
+    wait_until(cx, "container started", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerStarted(_)))
+    });
+
+    let id = daemon.state().containers[0].id.clone();
+    assert!(has_event(&events, |e| matches!(e, DaemonEvent::ContainerCreated(c) if *c == id)));
+    let request = daemon.state().created[0].clone();
+    assert_eq!(request.restart_policy, "unless-stopped");
+    assert_eq!(request.port_bindings[0].host_port, 8080);
+    assert_eq!(request.env, ["MODE=prod"]);
+    assert_eq!(request.mounts[0].r#type, "volume");
+    assert!(request.mounts[0].read_only);
+    assert_eq!(request.memory, 256 << 20);
+    wait_until(cx, "running container listed", |_| {
+        last_containers(&events).is_some_and(|c| c.len() == 1 && c[0].state == "running")
+    });
+
+    service.update(cx, |svc, cx| svc.stop_container(id.clone(), 10, cx));
+    wait_until(cx, "container stopped", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerStopped(c) if *c == id))
+    });
+    assert_eq!(daemon.state().containers[0].state, "exited");
+
+    service.update(cx, |svc, cx| svc.remove_container(id.clone(), false, cx));
+    wait_until(cx, "container removed", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerRemoved(c) if *c == id))
+    });
+    wait_until(cx, "empty list", |_| last_containers(&events).is_some_and(|c| c.is_empty()));
+    assert!(daemon.state().containers.is_empty());
+}
+
+#[gpui::test]
+fn pause_restart_kill_and_rename(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    daemon.state().containers.push(container("c2", "db", "running"));
+    let (service, events) = connect(cx, &daemon);
+    let state = |daemon: &FakeDaemon| daemon.state().containers[0].state.clone();
+
+    service.update(cx, |svc, cx| svc.pause_container("c1".to_string(), cx));
+    wait_until(cx, "paused", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerPaused(c) if c == "c1"))
+    });
+    assert_eq!(state(&daemon), "paused");
+
+    service.update(cx, |svc, cx| svc.unpause_container("c1".to_string(), cx));
+    wait_until(cx, "unpaused", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerUnpaused(c) if c == "c1"))
+    });
+    assert_eq!(state(&daemon), "running");
+
+    service.update(cx, |svc, cx| svc.restart_container("c1".to_string(), 10, cx));
+    wait_until(cx, "restarted", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerRestarted(c) if c == "c1"))
+    });
+
+    service.update(cx, |svc, cx| svc.kill_container("c1".to_string(), "SIGHUP".to_string(), cx));
+    wait_until(cx, "signal sent", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerKilled { signal, .. } if signal == "SIGHUP"))
+    });
+    assert_eq!(state(&daemon), "running");
+
+    service.update(cx, |svc, cx| svc.kill_container("c1".to_string(), "SIGKILL".to_string(), cx));
+    wait_until(cx, "killed", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerKilled { signal, .. } if signal == "SIGKILL"))
+    });
+    assert_eq!(state(&daemon), "exited");
+
+    // Names must be unique
+    service.update(cx, |svc, cx| svc.rename_container("c1".to_string(), "db".to_string(), cx));
+    wait_until(cx, "rename conflict", |_| {
+        has_event(&events, |e| {
+            matches!(
+                e,
+                DaemonEvent::OperationFailed(DaemonError::Conflict {
+                    operation: DaemonOperation::RenameContainer,
+                    ..
+                })
+            )
+        })
+    });
+
+    service.update(cx, |svc, cx| svc.rename_container("c1".to_string(), "api".to_string(), cx));
+    wait_until(cx, "renamed", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerRenamed { name, .. } if name == "api"))
+    });
+    assert_eq!(daemon.state().containers[0].name, "/api");
+}
+
+#[gpui::test]
+fn inspects_containers(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    let (service, _events) = connect(cx, &daemon);
+
+    let inspect = |cx: &mut TestAppContext, id: &str| {
+        let task = service.update(cx, |svc, cx| svc.inspect_container(id.to_string(), cx));
+        let result = Rc::new(RefCell::new(None));
+        let slot = result.clone();
+        cx.update(|cx| {
+            cx.spawn(async move |_| *slot.borrow_mut() = Some(task.await)).detach();
+        });
+        wait_until(cx, "inspect finished", |_| result.borrow().is_some());
+        result.take().unwrap()
+    };
+
+    let found = inspect(cx, "c1").unwrap();
+    assert_eq!(found.state.status, "running");
+    assert_eq!(found.cmd, ["sh"]);
+    assert!(found.json.contains("\"Name\": \"/web\""));
+
+    let missing = inspect(cx, "nope").unwrap_err();
+    assert!(matches!(missing, DaemonError::NotFound { .. }), "{:?}", missing);
+}
+
+#[gpui::test]
+fn bulk_actions_report_each_container(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    for i in 1..=6 {
+        daemon.state().containers.push(container(&format!("c{}", i), &format!("web{}", i), "running"));
+    }
+    let (service, events) = connect(cx, &daemon);
+
+    let run = |cx: &mut TestAppContext, action: BulkAction, ids: &[&str]| {
+        let ids = ids.iter().map(|id| id.to_string()).collect();
+        let mut receiver = service.update(cx, |svc, cx| svc.bulk_container_action(action, ids, cx));
+        let results: Rc<RefCell<Vec<BulkResult>>> = Rc::default();
+        let done = Rc::new(RefCell::new(false));
+        let (slot, finished) = (results.clone(), done.clone());
+        cx.update(|cx| {
+            cx.spawn(async move |_| {
+                while let Some(result) = receiver.next().await {
+                    slot.borrow_mut().push(result);
+                }
+                *finished.borrow_mut() = true;
+            })
+            .detach();
+        });
+        wait_until(cx, "bulk action finished", |_| *done.borrow());
+        results.take()
+    };
+
+    let stopped = run(cx, BulkAction::Stop, &["c1", "c2", "c3", "c4", "c5", "c6"]);
+    assert_eq!(stopped.len(), 6);
+    assert!(stopped.iter().all(|r| r.result.is_ok()));
+    assert!(daemon.state().containers.iter().all(|c| c.state == "exited"));
+    // Summarised by the caller, not announced one by one
+    assert!(!has_event(&events, |e| matches!(e, DaemonEvent::ContainerStopped(_))));
+
+    let removed = run(cx, BulkAction::Remove, &["c1", "gone"]);
+    let failed: Vec<&BulkResult> = removed.iter().filter(|r| r.result.is_err()).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].id, "gone");
+    assert!(matches!(failed[0].result, Err(DaemonError::NotFound { .. })));
+    assert_eq!(daemon.state().containers.len(), 5);
+
+    // A running container is only removed when forced
+    daemon.state().containers.iter_mut().find(|c| c.id == "c2").unwrap().state = "running".to_string();
+    let refused = run(cx, BulkAction::Remove, &["c2"]);
+    assert!(matches!(refused[0].result, Err(DaemonError::Conflict { .. })), "{:?}", refused);
+    let forced = run(cx, BulkAction::ForceRemove, &["c2"]);
+    assert!(forced[0].result.is_ok(), "{:?}", forced);
+    assert_eq!(daemon.state().containers.len(), 4);
+}
+
+#[gpui::test]
+fn lists_one_directory_without_reading_the_archive(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    {
+        let mut state = daemon.state();
+        state.containers.push(container("c1", "web", "running"));
+        for (path, mode, size) in [
+            ("/bin", 0o040755, 0),
+            ("/etc", 0o040755, 0),
+            ("/etc/hosts", 0o100644, 20),
+            ("/etc/ssl", 0o040755, 0),
+            ("/etc/ssl/cert.pem", 0o100644, 3),
+        ] {
+            let stat = StatPathResponse {
+                name: path.rsplit('/').next().unwrap().to_string(),
+                size,
+                mode,
+                ..Default::default()
+            };
+            state.files.insert(path.to_string(), stat);
+        }
+    }
+    let (service, _events) = connect(cx, &daemon);
+
+    let task = service.update(cx, |svc, cx| svc.list_directory("c1".to_string(), "/".to_string(), cx));
+    let result = Rc::new(RefCell::new(None));
+    let slot = result.clone();
+    cx.update(|cx| {
+        cx.spawn(async move |_| *slot.borrow_mut() = Some(task.await)).detach();
+    });
+    wait_until(cx, "listing finished", |_| result.borrow().is_some());
+    let entries = result.take().unwrap().unwrap();
+
+    // Only "/" itself; nothing below it was read
+    let mut root: Vec<(String, bool)> = entries.iter().map(|e| (e.path.clone(), e.is_dir())).collect();
+    root.sort();
+    assert_eq!(root, [("/bin".to_string(), true), ("/etc".to_string(), true)]);
+    let calls = daemon.state().calls.clone();
+    assert!(!calls.iter().any(|call| call == "GetArchive"), "{:?}", calls);
+    assert_eq!(calls.iter().filter(|call| *call == "StatPath").count(), 2);
+}
+
+#[gpui::test]
+fn stopped_containers_are_not_browsed_through_the_archive(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "exited"));
+    let (service, _events) = connect(cx, &daemon);
+
+    let task = service.update(cx, |svc, cx| svc.list_directory("c1".to_string(), "/".to_string(), cx));
+    let result = Rc::new(RefCell::new(None));
+    let slot = result.clone();
+    cx.update(|cx| {
+        cx.spawn(async move |_| *slot.borrow_mut() = Some(task.await)).detach();
+    });
+    wait_until(cx, "listing finished", |_| result.borrow().is_some());
+
+    let error = result.take().unwrap().unwrap_err();
+    assert!(matches!(error, DaemonError::Unsupported { .. }), "{:?}", error);
+    let calls = daemon.state().calls.clone();
+    assert!(!calls.iter().any(|call| call == "GetArchive"), "{:?}", calls);
+}
+
+#[gpui::test]
+fn compose_up_and_down_follow_dependencies(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    {
+        let mut state = daemon.state();
+        state.images.push(ImageSummary {
+            id: "sha256:abc".to_string(),
+            repo_tags: vec!["nginx:latest".to_string(), "postgres:16".to_string()],
+            ..Default::default()
+        });
+        state.networks.push(NetworkSummary {
+            id: "n1".to_string(),
+            name: "shared".to_string(),
+            ..Default::default()
+        });
+    }
+    let (service, _events) = connect(cx, &daemon);
+    let project = ComposeProject::parse(
+        "services:\n  web:\n    image: nginx:latest\n    depends_on: [db]\n    networks: [default, shared]\n  \
+         db:\n    image: postgres:16\nnetworks:\n  shared:\n    external: true\n",
+        Path::new("/src/shop/compose.yaml"),
+        &HashMap::new(),
+    )
+    .unwrap();
+
+    // Final status of each step
+    let run = |cx: &mut TestAppContext, command: ComposeCommand| {
+        let services = project.start_order();
+        let project = project.clone();
+        let mut receiver = service.update(cx, |svc, cx| svc.run_compose(command, project, services, cx));
+        let statuses: Rc<RefCell<HashMap<ComposeStep, ComposeStatus>>> = Rc::default();
+        let done = Rc::new(RefCell::new(false));
+        let (slot, finished) = (statuses.clone(), done.clone());
+        cx.update(|cx| {
+            cx.spawn(async move |_| {
+                while let Some(update) = receiver.next().await {
+                    slot.borrow_mut().insert(update.step, update.status);
+                }
+                *finished.borrow_mut() = true;
+            })
+            .detach();
+        });
+        wait_until(cx, "compose command finished", |_| *done.borrow());
+        statuses.take()
+    };
+    let done = |message: &str| ComposeStatus::Done(message.to_string());
+    let service_step = |name: &str| ComposeStep::Service(name.to_string());
+    let network_step = |name: &str| ComposeStep::Network(name.to_string());
+
+    let up = run(cx, ComposeCommand::Up);
+    assert_eq!(up[&network_step("shop_default")], done("Created"));
+    assert_eq!(up[&network_step("shared")], done("Exists"));
+    assert_eq!(up[&service_step("db")], done("Started"));
+    assert_eq!(up[&service_step("web")], done("Started"));
+    {
+        let state = daemon.state();
+        let created: Vec<&str> = state.created.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(created, ["shop-db-1", "shop-web-1"]);
+        assert!(state.containers.iter().all(|c| c.state == "running"));
+        // The images were already there
+        assert!(!state.calls.iter().any(|call| call == "PullImage"));
+    }
+
+    let again = run(cx, ComposeCommand::Up);
+    assert_eq!(again[&service_step("web")], done("Running"));
+    assert_eq!(daemon.state().created.len(), 2);
+
+    let down = run(cx, ComposeCommand::Down);
+    assert_eq!(down[&service_step("web")], done("Removed"));
+    assert_eq!(down[&network_step("shop_default")], done("Removed"));
+    assert!(!down.contains_key(&network_step("shared")));
+    let state = daemon.state();
+    assert!(state.containers.is_empty());
+    let networks: Vec<&str> = state.networks.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(networks, ["shared"]);
+}
+
+#[gpui::test]
+fn rpc_errors_emit_operation_failed(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "exited"));
+    let (service, events) = connect(cx, &daemon);
+
+    daemon.fail_next("StartContainer", Status::permission_denied("not allowed"));
+    service.update(cx, |svc, cx| svc.start_container("c1".to_string(), cx));
+
+    wait_until(cx, "operation failure", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::OperationFailed(_)))
+    });
+    let error = events.borrow().iter().find_map(|e| match e {
+        DaemonEvent::OperationFailed(error) => Some(error.clone()),
+        _ => None,
+    });
+    assert_eq!(
+        error,
+        Some(DaemonError::PermissionDenied {
+            operation: DaemonOperation::StartContainer,
+            resource: Some("c1".to_string()),
+            message: "not allowed".to_string(),
+        })
+    );
+    assert!(!has_event(&events, |e| matches!(e, DaemonEvent::ContainerStarted(_))));
+    assert_eq!(daemon.state().containers[0].state, "exited");
+    // Ordinary RPC errors don't drop the connection
+    assert!(service.read_with(cx, |svc, _| svc.is_connected()));
+}
+
+#[gpui::test]
+fn status_codes_map_to_error_kinds(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    let (service, events) = connect(cx, &daemon);
+
+    let failure = |events: &Events, operation: DaemonOperation| {
+        events.borrow().iter().find_map(|e| match e {
+            DaemonEvent::OperationFailed(error) if error.operation() == operation => Some(error.clone()),
+            _ => None,
+        })
+    };
+
+    // Removing a running container without force is a conflict
+    service.update(cx, |svc, cx| svc.remove_container("c1".to_string(), false, cx));
+    wait_until(cx, "remove failure", |_| failure(&events, DaemonOperation::RemoveContainer).is_some());
+    let error = failure(&events, DaemonOperation::RemoveContainer).unwrap();
+    assert!(matches!(error, DaemonError::Conflict { .. }), "{:?}", error);
+    assert_eq!(error.resource(), Some("c1"));
+    assert!(!error.is_retryable());
+
+    service.update(cx, |svc, cx| svc.start_container("missing".to_string(), cx));
+    wait_until(cx, "start failure", |_| failure(&events, DaemonOperation::StartContainer).is_some());
+    let error = failure(&events, DaemonOperation::StartContainer).unwrap();
+    assert!(matches!(error, DaemonError::NotFound { .. }), "{:?}", error);
+    assert_eq!(error.resource(), Some("missing"));
+
+    daemon.fail_next("ListImages", Status::deadline_exceeded("too slow"));
+    service.update(cx, |svc, cx| svc.list_images(cx));
+    wait_until(cx, "list failure", |_| failure(&events, DaemonOperation::ListImages).is_some());
+    let error = failure(&events, DaemonOperation::ListImages).unwrap();
+    assert!(matches!(error, DaemonError::Timeout { .. }), "{:?}", error);
+    assert!(error.is_retryable());
+    assert_eq!(error.to_string(), "Failed to list images: too slow");
+}
+
+#[gpui::test]
+fn unavailable_error_reconnects_and_refreshes(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    let (service, events) = connect(cx, &daemon);
+
+    daemon.fail_next("StopContainer", Status::unavailable("daemon restarting"));
+    service.update(cx, |svc, cx| svc.stop_container("c1".to_string(), 10, cx));
+
+    wait_until(cx, "connection lost", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ConnectionLost(_)))
+    });
+    assert!(has_event(&events, |e| matches!(
+        e,
+        DaemonEvent::OperationFailed(DaemonError::Unavailable { resource: Some(id), .. }) if id == "c1"
+    )));
+    assert!(matches!(
+        service.read_with(cx, |svc, _| svc.state().clone()),
+        ConnectionState::Reconnecting { attempt: 1, .. }
+    ));
+
+    wait_until(cx, "reconnected", |_| has_event(&events, |e| matches!(e, DaemonEvent::Reconnected)));
+    wait_until(cx, "refresh after reconnect", |_| last_containers(&events).is_some());
+}
+
+#[gpui::test]
+fn daemon_restart_is_detected_and_recovered(cx: &mut TestAppContext) {
+    let mut daemon = FakeDaemon::start();
+    let (service, events) = connect(cx, &daemon);
+
+    daemon.stop();
+    wait_until(cx, "connection lost", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ConnectionLost(_)))
+    });
+    assert!(!service.read_with(cx, |svc, _| svc.is_connected()));
+
+    let _restarted = FakeDaemon::start_at(daemon.socket_path(), daemon.shared_state());
+    wait_until(cx, "reconnected", |_| has_event(&events, |e| matches!(e, DaemonEvent::Reconnected)));
+    assert!(service.read_with(cx, |svc, _| svc.is_connected()));
+}
+
+#[gpui::test]
+fn streams_and_follows_logs(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    daemon.push_log("c1", log_entry(1, "first"));
+    daemon.push_log("c1", log_entry(2, "second"));
+    let (service, events) = connect(cx, &daemon);
+
+    let log_lines = |events: &Events| -> Vec<String> {
+        events
+            .borrow()
+            .iter()
+            .filter_map(|e| match e {
+                DaemonEvent::LogsReceived { container_id, entry } if container_id == "c1" => {
+                    Some(String::from_utf8_lossy(&entry.data).trim().to_string())
+                }
+                _ => None,
+            })
+            .collect()
+    };
+
+    service.update(cx, |svc, cx| svc.subscribe_logs("c1".to_string(), true, None, cx));
+    wait_until(cx, "log history", |_| log_lines(&events).len() == 2);
+
+    daemon.push_log("c1", log_entry(3, "third"));
+    wait_until(cx, "followed log line", |_| log_lines(&events).len() == 3);
+    assert_eq!(log_lines(&events), ["first", "second", "third"]);
+
+    // Nothing is delivered once unsubscribed
+    service.update(cx, |svc, _| svc.unsubscribe_logs("c1"));
+    daemon.push_log("c1", log_entry(4, "fourth"));
+    for _ in 0..20 {
+        cx.executor().advance_clock(Duration::from_millis(10));
+        cx.run_until_parked();
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    assert_eq!(log_lines(&events).len(), 3);
+}
+
+#[gpui::test]
+fn resumed_logs_deliver_everything_missed_once(cx: &mut TestAppContext) {
+    let mut daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    daemon.push_log("c1", log_entry(1, "first"));
+    daemon.push_log("c1", log_entry(2, "second"));
+    let (service, events) = connect(cx, &daemon);
+
+    let log_count = |events: &Events| {
+        events.borrow().iter().filter(|e| matches!(e, DaemonEvent::LogsReceived { .. })).count()
+    };
+    service.update(cx, |svc, cx| svc.subscribe_logs("c1".to_string(), true, None, cx));
+    wait_until(cx, "log history", |_| log_count(&events) == 2);
+
+    // More lines than the initial tail while the daemon is away
+    daemon.stop();
+    wait_until(cx, "connection lost", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ConnectionLost(_)))
+    });
+    for second in 3..=152 {
+        daemon.push_log("c1", log_entry(second, &format!("line {}", second)));
+    }
+    let _restarted = FakeDaemon::start_at(daemon.socket_path(), daemon.shared_state());
+    wait_until(cx, "resumed logs", |_| log_count(&events) == 152);
+
+    let timestamps: Vec<i64> = events
+        .borrow()
+        .iter()
+        .filter_map(|e| match e {
+            DaemonEvent::LogsReceived { entry, .. } => Some(entry.timestamp),
+            _ => None,
+        })
+        .collect();
+    assert!(timestamps.windows(2).all(|pair| pair[0] < pair[1]), "duplicates or gaps: {:?}", timestamps);
+}
+
+#[gpui::test]
+fn streams_stats_for_watched_containers(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    daemon.state().stats.insert(
+        "c1".to_string(),
+        (0..3)
+            .map(|i| ContainerStatsResponse {
+                timestamp: i * 1_000_000_000,
+                cpu_total_usage: i as u64 * 500,
+                system_cpu_usage: i as u64 * 1_000,
+                online_cpus: 2,
+                memory_usage: 64 << 20,
+                memory_limit: 256 << 20,
+                network_tx_bytes: i as u64 * 1_024,
+                ..Default::default()
+            })
+            .collect(),
+    );
+    let (service, events) = connect(cx, &daemon);
+
+    let samples = |events: &Events| -> Vec<ContainerStats> {
+        events
+            .borrow()
+            .iter()
+            .filter_map(|e| match e {
+                DaemonEvent::StatsReceived { container_id, stats } if container_id == "c1" => Some(*stats),
+                _ => None,
+            })
+            .collect()
+    };
+
+    service.update(cx, |svc, cx| svc.watch_stats(["c1".to_string()], cx));
+    wait_until(cx, "stats samples", |_| samples(&events).len() == 2);
+
+    // The first report only primes the counters
+    let stats = samples(&events)[1];
+    assert_eq!(stats.cpu_percent, 100.0);
+    assert_eq!(stats.net_tx_per_sec, 1_024.0);
+    assert_eq!(stats.memory_percent(), 25.0);
+
+    // The stream ended, so watching again reopens it
+    wait_until(cx, "stats stream ended", |cx| {
+        service.read_with(cx, |svc, _| svc.stats_subscriptions.is_empty())
+    });
+    service.update(cx, |svc, cx| svc.watch_stats(["c1".to_string()], cx));
+    wait_until(cx, "reopened stats stream", |_| samples(&events).len() == 4);
+    assert_eq!(daemon.state().calls.iter().filter(|c| *c == "ContainerStats").count(), 2);
+}
+
+#[gpui::test]
+fn daemon_events_update_state(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.state().containers.push(container("c1", "web", "running"));
+    let (_service, events) = connect(cx, &daemon);
+
+    // The event stream is opened right after connecting
+    wait_until(cx, "events subscription", |_| {
+        daemon.state().calls.iter().any(|call| call == "Events")
+    });
+
+    daemon.emit_event(resource_event("container", "die", "c1"));
+    wait_until(cx, "state change", |_| {
+        has_event(&events, |e| matches!(
+            e,
+            DaemonEvent::ContainerStateChanged { id, state } if id == "c1" && state == "exited"
+        ))
+    });
+
+    daemon.emit_event(resource_event("container", "destroy", "c1"));
+    wait_until(cx, "destroyed", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::ContainerDestroyed(id) if id == "c1"))
+    });
+}
+
+#[gpui::test]
+fn handshake_refuses_incompatible_major(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    {
+        let mut state = daemon.state();
+        state.version.version = "9.0.0".to_string();
+        state.version.api_version = format!("{}.0", super::CLIENT_API_VERSION.major + 1);
+    }
+    let (service, events) = service_for(cx, &daemon);
+    service.update(cx, |svc, cx| svc.connect(cx));
+
+    wait_until(cx, "incompatible", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::Incompatible(_)))
+    });
+    service.read_with(cx, |svc, _| {
+        assert!(matches!(svc.state(), ConnectionState::Error(reason) if reason.contains("9.0.0")));
+        assert!(svc.daemon_info().is_none());
+    });
+    // Refused for good: no reconnect attempts and no event stream
+    assert!(!daemon.state().calls.iter().any(|call| call == "Events"));
+}
+
+#[gpui::test]
+fn handshake_gates_capabilities(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    {
+        let mut state = daemon.state();
+        state.version.version = "1.3.0".to_string();
+        state.version.api_version = format!("{}.3", super::CLIENT_API_VERSION.major);
+        state.capabilities = Some("logs.follow, events".to_string());
+    }
+    let (service, events) = connect(cx, &daemon);
+
+    service.read_with(cx, |svc, _| {
+        let info = svc.daemon_info().expect("handshake info");
+        assert_eq!(info.version, "1.3.0");
+        assert!(!svc.supports(Capability::Machines));
+        assert!(svc.supports(Capability::LogsFollow));
+    });
+    assert!(has_event(&events, |e| matches!(e, DaemonEvent::CapabilitiesChanged)));
+
+    // Machines are not listed against a daemon without them
+    service.update(cx, |svc, cx| svc.refresh_all(cx));
+    wait_until(cx, "lists", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::NetworksLoaded(_)))
+    });
+    assert!(!daemon.state().calls.iter().any(|call| call == "ListMachines"));
+}
+
+#[gpui::test]
+fn legacy_daemon_without_version_rpc_connects(cx: &mut TestAppContext) {
+    let daemon = FakeDaemon::start();
+    daemon.fail_next("Version", Status::unimplemented("unknown method Version"));
+    let (service, events) = connect(cx, &daemon);
+
+    service.read_with(cx, |svc, _| {
+        let info = svc.daemon_info().expect("handshake info");
+        assert!(info.api_version.is_none());
+        assert!(Capability::ALL.iter().all(|c| svc.supports(*c)));
+    });
+
+    // A feature that turns out to be missing is switched off
+    daemon.fail_next("ListMachines", Status::unimplemented("no machines here"));
+    service.update(cx, |svc, cx| svc.list_machines(cx));
+    wait_until(cx, "capability dropped", |_| {
+        has_event(&events, |e| matches!(e, DaemonEvent::CapabilitiesChanged))
+    });
+    service.read_with(cx, |svc, _| assert!(!svc.supports(Capability::Machines)));
+}
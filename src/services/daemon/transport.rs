@@ -0,0 +1,312 @@
+//! Channel construction for the different daemon context kinds.
+//!
+//! Local contexts dial a Unix socket directly, TCP contexts use mutual TLS,
+//! and SSH contexts forward the remote socket to a local one through an
+//! `ssh -L` child process that lives as long as the connection.
+
+use std::io::{BufRead, BufReader};
+use std::net::{TcpStream as StdTcpStream, ToSocketAddrs};
+use std::os::unix::net::UnixStream as StdUnixStream;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::net::UnixStream;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity, Uri};
+use tower::service_fn;
+
+use super::DaemonService;
+use crate::services::{DaemonContext, DaemonEndpoint};
+
+/// How long to wait for ssh to create the forwarded socket
+const SSH_TUNNEL_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Timeout for TCP health probes
+const TCP_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// ssh output kept for the error message when it exits
+const SSH_STDERR_LIMIT: usize = 4096;
+
+/// An `ssh -L` process forwarding a remote daemon socket to a local one
+///
+/// The process is killed and the local socket removed on drop.
+pub(super) struct SshTunnel {
+    child: Child,
+    local_socket: PathBuf,
+}
+
+impl SshTunnel {
+    /// Start ssh and wait until the forwarded socket accepts connections
+    fn open(
+        context_name: &str,
+        destination: &str,
+        port: Option<u16>,
+        identity_file: Option<&Path>,
+        remote_socket: &str,
+    ) -> Result<Self, String> {
+        let local_socket = std::env::temp_dir().join(format!(
+            "arcbox-ssh-{}-{}.sock",
+            std::process::id(),
+            context_name.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        ));
+        let _ = std::fs::remove_file(&local_socket);
+
+        let mut cmd = Command::new("ssh");
+        cmd.arg("-nNT")
+            .args(["-o", "ExitOnForwardFailure=yes"])
+            .args(["-o", "BatchMode=yes"])
+            .args(["-o", "StreamLocalBindUnlink=yes"])
+            .arg("-L")
+            .arg(format!("{}:{}", local_socket.display(), remote_socket));
+        if let Some(port) = port {
+            cmd.arg("-p").arg(port.to_string());
+        }
+        if let Some(identity) = identity_file {
+            cmd.arg("-i").arg(identity);
+        }
+        cmd.arg(destination)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to start ssh: {}", e))?;
+        let stderr = Self::drain_stderr(&mut child, destination);
+        let mut tunnel = Self {
+            child,
+            local_socket,
+        };
+
+        let deadline = Instant::now() + SSH_TUNNEL_TIMEOUT;
+        loop {
+            if StdUnixStream::connect(&tunnel.local_socket).is_ok() {
+                tracing::info!(
+                    "SSH tunnel to {} ready at {:?}",
+                    destination,
+                    tunnel.local_socket
+                );
+                return Ok(tunnel);
+            }
+
+            if let Ok(Some(status)) = tunnel.child.try_wait() {
+                // Give the reader a moment to pick up ssh's last words
+                std::thread::sleep(Duration::from_millis(100));
+                let stderr = stderr.lock().unwrap().clone();
+                return Err(format!(
+                    "ssh exited with {}: {}",
+                    status,
+                    stderr.trim()
+                ));
+            }
+
+            if Instant::now() >= deadline {
+                return Err(format!("Timed out waiting for SSH tunnel to {}", destination));
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    pub(super) fn local_socket(&self) -> &Path {
+        &self.local_socket
+    }
+
+    /// Read ssh's stderr on a thread for as long as it runs, so a chatty
+    /// ssh can't fill the pipe and block; the tail is kept for error messages
+    fn drain_stderr(child: &mut Child, destination: &str) -> Arc<Mutex<String>> {
+        let output = Arc::new(Mutex::new(String::new()));
+        let Some(stderr) = child.stderr.take() else {
+            return output;
+        };
+        let (collected, destination) = (output.clone(), destination.to_string());
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines() {
+                let Ok(line) = line else { break };
+                tracing::debug!("ssh {}: {}", destination, line);
+                let mut collected = collected.lock().unwrap();
+                collected.push_str(&line);
+                collected.push('\n');
+                if collected.len() > SSH_STDERR_LIMIT {
+                    let mut cut = collected.len() - SSH_STDERR_LIMIT;
+                    while !collected.is_char_boundary(cut) {
+                        cut += 1;
+                    }
+                    collected.drain(..cut);
+                }
+            }
+        });
+        output
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_file(&self.local_socket);
+    }
+}
+
+/// What the health check loop should probe for a connected context
+#[derive(Debug, Clone)]
+pub(super) enum HealthProbe {
+    Unix(PathBuf),
+    Tcp(String),
+}
+
+impl HealthProbe {
+    /// Whether the daemon endpoint still accepts connections (blocking)
+    pub(super) fn is_alive(&self) -> bool {
+        match self {
+            HealthProbe::Unix(path) => StdUnixStream::connect(path).is_ok(),
+            HealthProbe::Tcp(addr) => addr
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .is_some_and(|addr| StdTcpStream::connect_timeout(&addr, TCP_PROBE_TIMEOUT).is_ok()),
+        }
+    }
+}
+
+/// `host:port`, with IPv6 literals bracketed (`[::1]:2376`)
+fn host_port(host: &str, port: u16) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+impl DaemonService {
+    /// Create a gRPC channel for the context using the provided runtime
+    ///
+    /// Returns the tunnel for SSH contexts; it must be kept alive for as long
+    /// as the channel is in use.
+    pub(super) fn create_channel_with_runtime(
+        context: &DaemonContext,
+        runtime: &tokio::runtime::Runtime,
+    ) -> Result<(Channel, Option<SshTunnel>), String> {
+        match &context.endpoint {
+            DaemonEndpoint::Local { socket_path } => {
+                let channel = Self::connect_unix(socket_path, runtime)?;
+                Ok((channel, None))
+            }
+            DaemonEndpoint::Tcp {
+                host,
+                port,
+                ca_cert,
+                client_cert,
+                client_key,
+            } => {
+                let channel =
+                    Self::connect_tls(host, *port, ca_cert, client_cert, client_key, runtime)?;
+                Ok((channel, None))
+            }
+            DaemonEndpoint::Ssh {
+                destination,
+                port,
+                identity_file,
+                remote_socket,
+            } => {
+                let tunnel = SshTunnel::open(
+                    &context.name,
+                    destination,
+                    *port,
+                    identity_file.as_deref(),
+                    remote_socket,
+                )?;
+                let channel = Self::connect_unix(tunnel.local_socket(), runtime)?;
+                Ok((channel, Some(tunnel)))
+            }
+        }
+    }
+
+    /// Health probe for a connected context
+    pub(super) fn health_probe(
+        context: &DaemonContext,
+        tunnel: Option<&SshTunnel>,
+    ) -> HealthProbe {
+        match &context.endpoint {
+            DaemonEndpoint::Local { socket_path } => HealthProbe::Unix(socket_path.clone()),
+            DaemonEndpoint::Tcp { host, port, .. } => HealthProbe::Tcp(host_port(host, *port)),
+            // Without a tunnel nothing listens locally, so the probe reports it dead
+            DaemonEndpoint::Ssh { .. } => HealthProbe::Unix(
+                tunnel
+                    .map(|t| t.local_socket().to_path_buf())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Create gRPC channel over Unix socket
+    fn connect_unix(socket_path: &Path, runtime: &tokio::runtime::Runtime) -> Result<Channel, String> {
+        let socket_path_str = socket_path.to_string_lossy().to_string();
+
+        // First verify the socket exists and is connectable
+        if StdUnixStream::connect(&socket_path_str).is_err() {
+            return Err(format!("Cannot connect to socket: {}", socket_path_str));
+        }
+
+        runtime.block_on(async move {
+            let channel = Endpoint::try_from("http://[::]:50051")
+                .map_err(|e| format!("Invalid endpoint: {}", e))?
+                .connect_with_connector(service_fn(move |_: Uri| {
+                    let path = socket_path_str.clone();
+                    async move {
+                        UnixStream::connect(path).await.map(|s| {
+                            hyper_util::rt::TokioIo::new(s)
+                        })
+                    }
+                }))
+                .await
+                .map_err(|e| format!("Failed to connect: {}", e))?;
+
+            Ok(channel)
+        })
+    }
+
+    /// Create gRPC channel over TCP with mutual TLS
+    fn connect_tls(
+        host: &str,
+        port: u16,
+        ca_cert: &Path,
+        client_cert: &Path,
+        client_key: &Path,
+        runtime: &tokio::runtime::Runtime,
+    ) -> Result<Channel, String> {
+        let read = |path: &Path| {
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        };
+        let tls = ClientTlsConfig::new()
+            .domain_name(host.trim_start_matches('[').trim_end_matches(']'))
+            .ca_certificate(Certificate::from_pem(read(ca_cert)?))
+            .identity(Identity::from_pem(read(client_cert)?, read(client_key)?));
+
+        let uri = format!("https://{}", host_port(host, port));
+        runtime.block_on(async move {
+            let channel = Endpoint::from_shared(uri)
+                .map_err(|e| format!("Invalid endpoint: {}", e))?
+                .tls_config(tls)
+                .map_err(|e| format!("Invalid TLS configuration: {}", e))?
+                .connect()
+                .await
+                .map_err(|e| format!("Failed to connect: {}", e))?;
+
+            Ok(channel)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brackets_ipv6_hosts() {
+        assert_eq!(host_port("daemon.example.com", 2376), "daemon.example.com:2376");
+        assert_eq!(host_port("10.0.0.2", 2376), "10.0.0.2:2376");
+        assert_eq!(host_port("::1", 2376), "[::1]:2376");
+        assert_eq!(host_port("[fe80::1]", 2376), "[fe80::1]:2376");
+    }
+}
@@ -0,0 +1,504 @@
+//! Daemon log capture.
+//!
+//! The local daemon writes its stdout/stderr straight to files in the logs
+//! dir (`~/.arcbox/logs/daemon-stdout.log`, `daemon-stderr.log`) rather than
+//! to pipes, so a daemon left running after the app quits keeps its output
+//! instead of dying of SIGPIPE. Those files are the on-disk log, so startup
+//! failures can still be diagnosed after the app is restarted. Background
+//! threads follow them into an in-memory ring buffer for the Daemon Logs
+//! window.
+//!
+//! A file the daemon holds open can't be cut down without racing its writes,
+//! so the files are only rotated when a new daemon is about to be started.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use gpui::*;
+
+use super::daemon_manager::DATA_DIR_NAME;
+use super::strip_ansi;
+
+/// Lines kept in memory for the Daemon Logs window
+const RING_CAPACITY: usize = 5000;
+
+/// Size past which an output file is rotated before the next daemon start
+const MAX_LOG_FILE_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Rotated files kept next to the active one (<name>.1 .. <name>.N)
+const MAX_ROTATED_FILES: usize = 3;
+
+/// How often captured lines are moved from the reader threads into the buffer
+const PUMP_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the daemon's output files are checked for new lines
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Severity of a daemon log line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    /// Find the level in a `tracing`-style line
+    ///
+    /// Accepts `2026-01-01T00:00:00Z  INFO target: msg`, `[WARN] msg` and
+    /// `level=error msg="..."`; only the first few words are looked at, so a
+    /// message that merely mentions "error" isn't misclassified.
+    pub fn parse(line: &str) -> Option<Self> {
+        line.split_whitespace().take(4).find_map(|word| {
+            let word = word
+                .strip_prefix("level=")
+                .unwrap_or(word)
+                .trim_matches(|c: char| !c.is_ascii_alphabetic());
+            match word.to_ascii_uppercase().as_str() {
+                "TRACE" => Some(LogLevel::Trace),
+                "DEBUG" => Some(LogLevel::Debug),
+                "INFO" => Some(LogLevel::Info),
+                "WARN" | "WARNING" => Some(LogLevel::Warn),
+                "ERROR" | "FATAL" => Some(LogLevel::Error),
+                _ => None,
+            }
+        })
+    }
+}
+
+/// Which pipe a daemon line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+    /// Written by the app itself (spawn, exit and restart markers)
+    System,
+}
+
+impl LogStream {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogStream::Stdout => "stdout",
+            LogStream::Stderr => "stderr",
+            LogStream::System => "system",
+        }
+    }
+
+    /// Level for lines that don't name one; stderr is usually trouble
+    fn default_level(&self) -> LogLevel {
+        match self {
+            LogStream::Stderr => LogLevel::Warn,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
+/// A captured daemon log line
+#[derive(Debug, Clone)]
+pub struct DaemonLogLine {
+    /// Line content as written, color codes included (the daemon colors
+    /// its output even when it isn't writing to a terminal)
+    pub content: String,
+    pub stream: LogStream,
+    pub level: LogLevel,
+    /// Unix timestamp in nanoseconds
+    pub timestamp: i64,
+}
+
+/// Events emitted by DaemonLogs
+#[derive(Debug, Clone)]
+pub enum DaemonLogsEvent {
+    /// New lines were captured (oldest first)
+    Appended(Vec<DaemonLogLine>),
+}
+
+/// Rename an output file aside once it has grown past `MAX_LOG_FILE_SIZE`
+///
+/// <name> -> <name>.1 -> ... -> <name>.N (dropped). Only called while no
+/// daemon is writing to it.
+fn rotate_if_full(path: &Path) -> std::io::Result<()> {
+    match std::fs::metadata(path) {
+        Ok(metadata) if metadata.len() > MAX_LOG_FILE_SIZE => {}
+        Ok(_) => return Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    }
+    for index in (1..MAX_ROTATED_FILES).rev() {
+        let from = rotated_path(path, index);
+        if from.exists() {
+            std::fs::rename(&from, rotated_path(path, index + 1))?;
+        }
+    }
+    std::fs::rename(path, rotated_path(path, 1))
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// One of the files the daemon writes its output to, read as it grows
+struct FollowedFile {
+    path: PathBuf,
+    stream: LogStream,
+    /// Bytes read so far
+    offset: u64,
+    /// Start of a line whose newline hasn't been written yet
+    partial: Vec<u8>,
+    /// Continuation lines (backtraces, wrapped messages) keep the level of
+    /// the line they belong to
+    last_level: Option<LogLevel>,
+}
+
+impl FollowedFile {
+    /// Follow `path` from its current end; earlier output was already
+    /// captured, or belongs to a daemon from an earlier session
+    fn new(path: PathBuf, stream: LogStream) -> Self {
+        let offset = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        Self {
+            path,
+            stream,
+            offset,
+            partial: Vec::new(),
+            last_level: None,
+        }
+    }
+
+    /// Complete lines written since the last call
+    fn read_lines(&mut self) -> std::io::Result<Vec<DaemonLogLine>> {
+        let mut file = File::open(&self.path)?;
+        if file.metadata()?.len() < self.offset {
+            // Rotated before a restart, or truncated by hand
+            self.offset = 0;
+            self.partial.clear();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut data = std::mem::take(&mut self.partial);
+        let start = data.len();
+        file.read_to_end(&mut data)?;
+        self.offset += (data.len() - start) as u64;
+
+        let complete = data.iter().rposition(|b| *b == b'\n').map_or(0, |end| end + 1);
+        self.partial = data.split_off(complete);
+
+        let mut lines = Vec::new();
+        for line in data.split(|b| *b == b'\n') {
+            let content = String::from_utf8_lossy(line);
+            let content = content.trim_end();
+            let text = strip_ansi(content);
+            if text.trim_end().is_empty() {
+                continue;
+            }
+            let level = LogLevel::parse(&text)
+                .or(self.last_level)
+                .unwrap_or(self.stream.default_level());
+            self.last_level = Some(level);
+            lines.push(DaemonLogLine {
+                content: content.to_string(),
+                stream: self.stream,
+                level,
+                timestamp: now_nanos(),
+            });
+        }
+        Ok(lines)
+    }
+}
+
+/// Cloneable handle the daemon manager's background threads write through
+///
+/// Lines are queued for the in-memory buffer, which picks them up on the
+/// main thread.
+#[derive(Clone)]
+pub struct DaemonLogCapture {
+    tx: Sender<DaemonLogLine>,
+    /// Directory the daemon's output files are kept in
+    logs_dir: PathBuf,
+    /// Streams whose output file is already being followed
+    followed: Arc<Mutex<Vec<LogStream>>>,
+    /// Set when the buffer is dropped; the follower threads exit on it
+    stopped: Arc<AtomicBool>,
+}
+
+impl DaemonLogCapture {
+    /// Record a line written by the app itself
+    pub fn system(&self, level: LogLevel, message: impl Into<String>) {
+        self.record(DaemonLogLine {
+            content: message.into(),
+            stream: LogStream::System,
+            level,
+            timestamp: now_nanos(),
+        });
+    }
+
+    /// Open the files a new daemon's stdout and stderr should go to,
+    /// rotating ones that have grown too large, and follow them
+    pub fn output_files(&self) -> std::io::Result<(File, File)> {
+        let open = |stream| {
+            let path = self.output_path(stream);
+            if let Err(e) = rotate_if_full(&path) {
+                tracing::warn!("Failed to rotate {}: {}", path.display(), e);
+            }
+            self.follow(stream)?;
+            OpenOptions::new().create(true).append(true).open(&path)
+        };
+        Ok((open(LogStream::Stdout)?, open(LogStream::Stderr)?))
+    }
+
+    /// Follow the output files of a daemon that is already running
+    pub fn follow_output(&self) -> std::io::Result<()> {
+        self.follow(LogStream::Stdout)?;
+        self.follow(LogStream::Stderr)
+    }
+
+    fn output_path(&self, stream: LogStream) -> PathBuf {
+        self.logs_dir.join(format!("daemon-{}.log", stream.as_str()))
+    }
+
+    /// Poll a daemon output file for new lines on its own thread (once per
+    /// stream; the daemon may be restarted onto the same file)
+    fn follow(&self, stream: LogStream) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.logs_dir)?;
+        let mut followed = self.followed.lock().unwrap();
+        if followed.contains(&stream) {
+            return Ok(());
+        }
+        followed.push(stream);
+
+        let mut file = FollowedFile::new(self.output_path(stream), stream);
+        let capture = self.clone();
+        std::thread::spawn(move || {
+            while !capture.stopped.load(Ordering::Relaxed) {
+                match file.read_lines() {
+                    Ok(lines) => {
+                        for line in lines {
+                            let text = strip_ansi(&line.content);
+                            match line.stream {
+                                LogStream::Stderr => tracing::warn!("[daemon:err] {}", text),
+                                _ => tracing::debug!("[daemon] {}", text),
+                            }
+                            capture.record(line);
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => tracing::debug!("Failed to read {}: {}", file.path.display(), e),
+                }
+                std::thread::sleep(FOLLOW_INTERVAL);
+            }
+        });
+        Ok(())
+    }
+
+    fn record(&self, line: DaemonLogLine) {
+        let _ = self.tx.send(line);
+    }
+}
+
+struct GlobalDaemonLogs(Entity<DaemonLogs>);
+
+impl Global for GlobalDaemonLogs {}
+
+/// In-memory ring buffer of the local daemon's output
+pub struct DaemonLogs {
+    lines: VecDeque<DaemonLogLine>,
+    capture: DaemonLogCapture,
+    _pump_task: Task<()>,
+}
+
+impl DaemonLogs {
+    /// Register the buffer as a global (called in main)
+    pub fn init(cx: &mut App) {
+        let logs = cx.new(|cx| Self::new(default_logs_dir(), cx));
+        cx.set_global(GlobalDaemonLogs(logs));
+    }
+
+    /// Get the shared daemon log buffer
+    pub fn global(cx: &App) -> Entity<DaemonLogs> {
+        cx.global::<GlobalDaemonLogs>().0.clone()
+    }
+
+    pub fn new(logs_dir: PathBuf, cx: &mut Context<Self>) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let capture = DaemonLogCapture {
+            tx,
+            logs_dir,
+            followed: Arc::default(),
+            stopped: Arc::default(),
+        };
+
+        Self {
+            lines: VecDeque::new(),
+            capture,
+            _pump_task: Self::spawn_pump(rx, cx),
+        }
+    }
+
+    /// Move lines from the reader threads into the buffer in batches
+    fn spawn_pump(rx: Receiver<DaemonLogLine>, cx: &mut Context<Self>) -> Task<()> {
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            loop {
+                cx.background_executor().timer(PUMP_INTERVAL).await;
+
+                let mut batch = Vec::new();
+                loop {
+                    match rx.try_recv() {
+                        Ok(line) => batch.push(line),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => return,
+                    }
+                }
+                if batch.is_empty() {
+                    continue;
+                }
+
+                let alive = cx
+                    .update(|cx| this.update(cx, |this, cx| this.append(batch, cx)))
+                    .is_ok_and(|result| result.is_ok());
+                if !alive {
+                    return;
+                }
+            }
+        })
+    }
+
+    fn append(&mut self, batch: Vec<DaemonLogLine>, cx: &mut Context<Self>) {
+        self.lines.extend(batch.iter().cloned());
+        if self.lines.len() > RING_CAPACITY {
+            let excess = self.lines.len() - RING_CAPACITY;
+            self.lines.drain(..excess);
+        }
+        cx.emit(DaemonLogsEvent::Appended(batch));
+        cx.notify();
+    }
+
+    /// Handle for the daemon manager's reader threads
+    pub fn capture(&self) -> DaemonLogCapture {
+        self.capture.clone()
+    }
+
+    /// Buffered lines, oldest first
+    pub fn lines(&self) -> impl Iterator<Item = &DaemonLogLine> {
+        self.lines.iter()
+    }
+
+    /// Directory holding the daemon's output files
+    pub fn logs_dir(&self) -> &Path {
+        &self.capture.logs_dir
+    }
+}
+
+impl Drop for DaemonLogs {
+    fn drop(&mut self) {
+        self.capture.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl EventEmitter<DaemonLogsEvent> for DaemonLogs {}
+
+/// ~/.arcbox/logs (same data dir the daemon manager uses)
+fn default_logs_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join(DATA_DIR_NAME)
+        .join("logs")
+}
+
+fn now_nanos() -> i64 {
+    Utc::now().timestamp_nanos_opt().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn parses_levels_from_common_formats() {
+        assert_eq!(
+            LogLevel::parse("2026-01-01T00:00:00.123Z  INFO arcbox::daemon: listening"),
+            Some(LogLevel::Info)
+        );
+        assert_eq!(LogLevel::parse("[WARN] disk almost full"), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::parse("level=error msg=\"boom\""), Some(LogLevel::Error));
+        assert_eq!(LogLevel::parse("a b c d and then an error happened"), None);
+    }
+
+    #[test]
+    fn keeps_color_codes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon-stdout.log");
+        std::fs::write(&path, "").unwrap();
+        let mut followed = FollowedFile::new(path.clone(), LogStream::Stdout);
+
+        let colored = "\u{1b}[2m2026\u{1b}[0m \u{1b}[33m WARN\u{1b}[0m low on space";
+        std::fs::write(&path, format!("{}\n\u{1b}[2K\n", colored)).unwrap();
+        let lines = followed.read_lines().unwrap();
+        assert_eq!(lines.len(), 1, "a line that only erases is dropped");
+        assert_eq!(lines[0].content, colored);
+        assert_eq!(lines[0].level, LogLevel::Warn);
+    }
+
+    #[test]
+    fn follows_output_file_line_by_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon-stderr.log");
+        std::fs::write(&path, "from an earlier session\n").unwrap();
+        let mut followed = FollowedFile::new(path.clone(), LogStream::Stderr);
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"ERROR boom\n  at main.rs\nhalf a li").unwrap();
+        let lines = followed.read_lines().unwrap();
+        let contents: Vec<(&str, LogLevel)> = lines.iter().map(|l| (l.content.as_str(), l.level)).collect();
+        assert_eq!(contents, [("ERROR boom", LogLevel::Error), ("  at main.rs", LogLevel::Error)]);
+
+        file.write_all(b"ne\n").unwrap();
+        assert_eq!(followed.read_lines().unwrap()[0].content, "half a line");
+
+        // Truncated behind our back: start over
+        file.set_len(0).unwrap();
+        file.write_all(b"restarted\n").unwrap();
+        assert_eq!(followed.read_lines().unwrap()[0].content, "restarted");
+    }
+
+    #[test]
+    fn rotates_output_file_when_full() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon-stdout.log");
+
+        std::fs::write(&path, b"small").unwrap();
+        rotate_if_full(&path).unwrap();
+        assert!(!rotated_path(&path, 1).exists());
+
+        let full = vec![b'x'; MAX_LOG_FILE_SIZE as usize + 1];
+        for _ in 0..(MAX_ROTATED_FILES + 1) {
+            std::fs::write(&path, &full).unwrap();
+            rotate_if_full(&path).unwrap();
+        }
+
+        assert!(!path.exists());
+        for index in 1..=MAX_ROTATED_FILES {
+            assert!(rotated_path(&path, index).exists());
+        }
+        assert!(!rotated_path(&path, MAX_ROTATED_FILES + 1).exists());
+    }
+}
@@ -4,18 +4,24 @@
 //! - Locates daemon binary in app bundle
 //! - Spawns daemon process on startup
 //! - Monitors health via ping endpoint
+//! - Restarts the daemon when it crashes, up to a crash-loop limit
 //! - Gracefully shuts down on app exit
 
-use std::io::{BufRead, BufReader, Read, Write};
+use std::collections::VecDeque;
+use std::io::{Read, Write};
 use std::os::unix::net::UnixStream;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
-use std::time::Duration;
+use std::process::{Child, Command, ExitStatus};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use gpui::*;
 
+use super::{DaemonLogCapture, DaemonLogs, LogLevel};
+
 /// Data directory name (matches daemon default: ~/.arcbox)
-const DATA_DIR_NAME: &str = ".arcbox";
+pub(crate) const DATA_DIR_NAME: &str = ".arcbox";
 
 /// Daemon binary name
 const DAEMON_BINARY: &str = "arcbox";
@@ -26,6 +32,30 @@ const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
 /// Ping retry interval during startup
 const STARTUP_PING_INTERVAL: Duration = Duration::from_millis(200);
 
+/// Interval between supervisor health checks
+const SUPERVISOR_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Consecutive failed pings before a live process is considered hung
+const MAX_MISSED_PINGS: u32 = 3;
+
+/// Crashes are counted against the restart limit within this window
+const CRASH_LOOP_WINDOW: Duration = Duration::from_secs(120);
+
+/// Delay before the first automatic restart (doubles per recent crash)
+const RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the automatic restart delay
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Default number of automatic restarts allowed within the crash-loop window
+pub const DEFAULT_RESTART_LIMIT: u32 = 5;
+
+/// Default wait for a clean exit after SIGTERM before sending SIGKILL
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often to check whether the daemon has exited during shutdown
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Daemon manager state
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DaemonState {
@@ -35,6 +65,8 @@ pub enum DaemonState {
     Starting,
     /// Running and healthy
     Running,
+    /// Shutting down (SIGTERM sent, waiting for exit)
+    Stopping,
     /// Failed to start or crashed
     Failed(String),
 }
@@ -61,15 +93,38 @@ pub struct DaemonManager {
     socket_path: PathBuf,
     /// Path to data directory
     data_dir: PathBuf,
-    /// Child process handle (kept to prevent drop)
-    #[allow(dead_code)]
-    child_handle: Option<std::process::Child>,
+    /// Child process handle (None when attached to an already running daemon)
+    child_handle: Option<Arc<Mutex<Child>>>,
+    /// When the daemon last became healthy
+    started_at: Option<Instant>,
+    /// Automatic restarts performed since launch
+    restart_count: u32,
+    /// Crash times within the crash-loop window
+    recent_crashes: VecDeque<Instant>,
+    /// Automatic restarts allowed within the crash-loop window (0 disables them)
+    restart_limit: u32,
+    /// Watches the process and pings it while running
+    supervisor_task: Option<Task<()>>,
+    /// Pending automatic restart
+    restart_task: Option<Task<()>>,
+    /// When an in-progress shutdown escalates to SIGKILL
+    shutdown_deadline: Option<Instant>,
+    /// Where the daemon's stdout/stderr end up (Daemon Logs window and log file)
+    log_capture: DaemonLogCapture,
+}
+
+/// Result of a supervisor health check
+enum Health {
+    Alive,
+    Unresponsive,
+    Exited(ExitStatus),
 }
 
 impl DaemonManager {
-    pub fn new(_cx: &mut Context<Self>) -> Self {
+    pub fn new(cx: &mut Context<Self>) -> Self {
         let (socket_path, data_dir) = Self::get_paths();
         let daemon_path = Self::find_daemon_binary();
+        let log_capture = DaemonLogs::global(cx).read(cx).capture();
 
         Self {
             state: DaemonState::Stopped,
@@ -77,6 +132,14 @@ impl DaemonManager {
             socket_path,
             data_dir,
             child_handle: None,
+            started_at: None,
+            restart_count: 0,
+            recent_crashes: VecDeque::new(),
+            restart_limit: DEFAULT_RESTART_LIMIT,
+            supervisor_task: None,
+            restart_task: None,
+            shutdown_deadline: None,
+            log_capture,
         }
     }
 
@@ -198,15 +261,51 @@ impl DaemonManager {
         matches!(self.state, DaemonState::Running)
     }
 
+    /// Number of automatic restarts since the app launched
+    pub fn restart_count(&self) -> u32 {
+        self.restart_count
+    }
+
+    /// Time since the daemon last became healthy (None unless running)
+    pub fn uptime(&self) -> Option<Duration> {
+        self.started_at
+            .filter(|_| self.is_running())
+            .map(|started| started.elapsed())
+    }
+
+    /// Whether an automatic restart is scheduled
+    pub fn is_restart_pending(&self) -> bool {
+        self.restart_task.is_some()
+    }
+
+    /// When an in-progress shutdown will give up waiting and kill the daemon
+    pub fn shutdown_deadline(&self) -> Option<Instant> {
+        self.shutdown_deadline
+    }
+
+    /// Set how many automatic restarts are allowed within the crash-loop window
+    pub fn set_restart_limit(&mut self, limit: u32) {
+        self.restart_limit = limit;
+    }
+
     /// Start the daemon process
     pub fn start(&mut self, cx: &mut Context<Self>) {
-        if matches!(self.state, DaemonState::Starting | DaemonState::Running) {
-            tracing::debug!("Daemon already starting or running, skipping start");
+        if matches!(
+            self.state,
+            DaemonState::Starting | DaemonState::Running | DaemonState::Stopping
+        ) {
+            tracing::debug!("Daemon already starting, running or stopping, skipping start");
             return;
         }
+        // A manual start supersedes any scheduled restart
+        self.restart_task = None;
 
         let Some(daemon_path) = self.daemon_path.clone() else {
             tracing::error!("Daemon binary not found in bundle, alongside exe, or PATH");
+            self.log_capture.system(
+                LogLevel::Error,
+                "Daemon binary not found in bundle, alongside exe, or PATH",
+            );
             self.set_state(DaemonState::Failed("Daemon binary not found".into()), cx);
             return;
         };
@@ -216,6 +315,7 @@ impl DaemonManager {
 
         let socket_path = self.socket_path.clone();
         let data_dir = self.data_dir.clone();
+        let log_capture = self.log_capture.clone();
 
         // Spawn background thread for daemon startup
         // This avoids blocking the UI and works without tokio runtime
@@ -224,7 +324,7 @@ impl DaemonManager {
             let result = cx
                 .background_executor()
                 .spawn(async move {
-                    Self::start_daemon_sync(&daemon_path, &socket_path, &data_dir)
+                    Self::start_daemon_sync(&daemon_path, &socket_path, &data_dir, &log_capture)
                 })
                 .await;
 
@@ -232,15 +332,17 @@ impl DaemonManager {
             let _ = cx.update(|cx| {
                 this.update(cx, |this, cx| match result {
                     Ok(child) => {
-                        this.child_handle = Some(child);
-                        this.set_state(DaemonState::Running, cx);
+                        this.child_handle = Some(Arc::new(Mutex::new(child)));
+                        this.on_started(cx);
                     }
                     Err(e) if e == "ALREADY_RUNNING" => {
                         // Daemon is already running, treat as success
                         tracing::info!("Connected to existing daemon");
-                        this.set_state(DaemonState::Running, cx);
+                        this.child_handle = None;
+                        this.on_started(cx);
                     }
                     Err(e) => {
+                        this.log_capture.system(LogLevel::Error, format!("Daemon failed to start: {}", e));
                         this.set_state(DaemonState::Failed(e), cx);
                     }
                 })
@@ -249,12 +351,145 @@ impl DaemonManager {
         .detach();
     }
 
+    /// Daemon is healthy: record the start time and begin supervising it
+    fn on_started(&mut self, cx: &mut Context<Self>) {
+        self.started_at = Some(Instant::now());
+        self.set_state(DaemonState::Running, cx);
+        self.start_supervisor(cx);
+    }
+
+    /// Watch the child's exit status and ping the daemon periodically
+    fn start_supervisor(&mut self, cx: &mut Context<Self>) {
+        let child = self.child_handle.clone();
+        let socket_path = self.socket_path.clone();
+
+        self.supervisor_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let mut missed_pings = 0;
+            loop {
+                cx.background_executor().timer(SUPERVISOR_INTERVAL).await;
+
+                let child = child.clone();
+                let socket_path = socket_path.clone();
+                let health = cx
+                    .background_executor()
+                    .spawn(async move { Self::check_health_sync(child.as_deref(), &socket_path) })
+                    .await;
+
+                let reason = match health {
+                    Health::Alive => {
+                        missed_pings = 0;
+                        continue;
+                    }
+                    Health::Exited(status) => {
+                        format!("Daemon exited with {}", Self::describe_exit(status))
+                    }
+                    Health::Unresponsive => {
+                        missed_pings += 1;
+                        tracing::warn!("Daemon ping failed ({}/{})", missed_pings, MAX_MISSED_PINGS);
+                        if missed_pings < MAX_MISSED_PINGS {
+                            continue;
+                        }
+                        "Daemon stopped responding".to_string()
+                    }
+                };
+
+                let _ = cx.update(|cx| {
+                    this.update(cx, |this, cx| this.on_crashed(reason, cx))
+                });
+                break;
+            }
+        }));
+    }
+
+    /// One supervisor check: has the process exited, and does it answer pings?
+    fn check_health_sync(child: Option<&Mutex<Child>>, socket_path: &PathBuf) -> Health {
+        if let Some(child) = child {
+            if let Ok(Some(status)) = child.lock().unwrap().try_wait() {
+                return Health::Exited(status);
+            }
+        }
+        if Self::ping_daemon_sync(socket_path) {
+            Health::Alive
+        } else {
+            Health::Unresponsive
+        }
+    }
+
+    /// "exit code 1" or "signal 9"
+    fn describe_exit(status: ExitStatus) -> String {
+        match (status.code(), status.signal()) {
+            (Some(code), _) => format!("exit code {}", code),
+            (None, Some(signal)) => format!("signal {}", signal),
+            (None, None) => status.to_string(),
+        }
+    }
+
+    /// The daemon died or hung: report it and schedule a restart unless crash-looping
+    fn on_crashed(&mut self, reason: String, cx: &mut Context<Self>) {
+        tracing::error!("{}", reason);
+        self.log_capture.system(LogLevel::Error, reason.clone());
+        self.supervisor_task = None;
+        self.started_at = None;
+
+        // Reap the process (kill it first if it hung)
+        if let Some(child) = self.child_handle.take() {
+            let mut child = child.lock().unwrap();
+            if let Ok(None) = child.try_wait() {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+
+        let now = Instant::now();
+        self.recent_crashes
+            .retain(|crashed| now.duration_since(*crashed) < CRASH_LOOP_WINDOW);
+        self.recent_crashes.push_back(now);
+        let crashes = self.recent_crashes.len() as u32;
+
+        if crashes > self.restart_limit {
+            tracing::error!(
+                "Daemon crashed {} times in {}s, not restarting",
+                crashes,
+                CRASH_LOOP_WINDOW.as_secs()
+            );
+            let message = if self.restart_limit == 0 {
+                reason
+            } else {
+                format!("{} (crashed {} times, automatic restart stopped)", reason, crashes)
+            };
+            self.set_state(DaemonState::Failed(message), cx);
+            return;
+        }
+
+        let delay = RESTART_BASE_DELAY
+            .saturating_mul(1 << (crashes - 1).min(5))
+            .min(RESTART_MAX_DELAY);
+        tracing::info!("Restarting daemon in {:?} ({}/{})", delay, crashes, self.restart_limit);
+        self.log_capture.system(
+            LogLevel::Info,
+            format!("Restarting daemon in {}s ({}/{})", delay.as_secs(), crashes, self.restart_limit),
+        );
+
+        // Schedule before reporting so observers can tell a restart is coming
+        self.restart_task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            cx.background_executor().timer(delay).await;
+            let _ = cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.restart_count += 1;
+                    this.start(cx);
+                })
+            });
+        }));
+        self.set_state(DaemonState::Failed(reason), cx);
+    }
+
     /// Synchronous daemon startup logic (runs in background thread)
     fn start_daemon_sync(
         daemon_path: &PathBuf,
         socket_path: &PathBuf,
         data_dir: &PathBuf,
-    ) -> Result<std::process::Child, String> {
+        log_capture: &DaemonLogCapture,
+    ) -> Result<Child, String> {
         // Ensure data directory exists
         if let Err(e) = std::fs::create_dir_all(data_dir) {
             return Err(format!("Failed to create data dir: {}", e));
@@ -263,6 +498,10 @@ impl DaemonManager {
         // Check if daemon is already running (e.g., from previous session)
         if Self::ping_daemon_sync(socket_path) {
             tracing::info!("Daemon already running");
+            // Likely kept running by an earlier session; its output files are still written
+            if let Err(e) = log_capture.follow_output() {
+                tracing::warn!("Failed to follow daemon output: {}", e);
+            }
             // Return a placeholder - we don't have the actual child handle
             // This is fine since we just need to track that it's running
             return Err("ALREADY_RUNNING".to_string());
@@ -277,6 +516,16 @@ impl DaemonManager {
             daemon_path.display(),
             socket_path.display()
         );
+        log_capture.system(
+            LogLevel::Info,
+            format!("Starting daemon: {} daemon --socket {}", daemon_path.display(), socket_path.display()),
+        );
+
+        // Files rather than pipes: a daemon kept running after the app quits
+        // would otherwise lose its output, or die of SIGPIPE on the next write
+        let (stdout, stderr) = log_capture
+            .output_files()
+            .map_err(|e| format!("Failed to open daemon log files: {}", e))?;
 
         let mut child = Command::new(daemon_path)
             .arg("daemon")
@@ -285,35 +534,14 @@ impl DaemonManager {
             .arg("--data-dir")
             .arg(data_dir)
             .arg("--foreground")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            // Own process group, so terminal signals aimed at the app don't reach
+            // a daemon that is meant to outlive it
+            .process_group(0)
+            .stdout(stdout)
+            .stderr(stderr)
             .spawn()
             .map_err(|e| format!("Failed to spawn daemon: {}", e))?;
 
-        // Spawn thread to read daemon stdout
-        if let Some(stdout) = child.stdout.take() {
-            std::thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines() {
-                    if let Ok(line) = line {
-                        tracing::debug!("[daemon] {}", line);
-                    }
-                }
-            });
-        }
-
-        // Spawn thread to read daemon stderr
-        if let Some(stderr) = child.stderr.take() {
-            std::thread::spawn(move || {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines() {
-                    if let Ok(line) = line {
-                        tracing::warn!("[daemon:err] {}", line);
-                    }
-                }
-            });
-        }
-
         // Wait for daemon to become ready
         let start_time = std::time::Instant::now();
         loop {
@@ -369,13 +597,141 @@ impl DaemonManager {
         }
     }
 
-    /// Stop the daemon
+    /// Stop the daemon (SIGTERM, then SIGKILL after the default timeout)
     pub fn stop(&mut self, cx: &mut Context<Self>) {
-        // Send shutdown signal via socket or just let the process exit with app
-        self.set_state(DaemonState::Stopped, cx);
+        self.shutdown(DEFAULT_SHUTDOWN_TIMEOUT, cx).detach();
+    }
 
-        // The daemon will be terminated when the app exits since it's a child process
-        // For graceful shutdown, we could send a SIGTERM or call a shutdown endpoint
+    /// Shut the daemon down: SIGTERM, wait up to `timeout` for a clean exit, then SIGKILL
+    ///
+    /// The returned task resolves once the process is gone. A daemon we
+    /// attached to but did not spawn is left running.
+    pub fn shutdown(&mut self, timeout: Duration, cx: &mut Context<Self>) -> Task<()> {
+        // Stopping on purpose is not a crash
+        self.supervisor_task = None;
+        self.restart_task = None;
+        self.started_at = None;
+
+        let Some(child) = self.child_handle.clone() else {
+            self.set_state(DaemonState::Stopped, cx);
+            return Task::ready(());
+        };
+        if self.state == DaemonState::Stopping {
+            // Already shutting down; the first call finishes the job
+            return Task::ready(());
+        }
+
+        self.shutdown_deadline = Some(Instant::now() + timeout);
+        self.log_capture.system(LogLevel::Info, "Stopping daemon");
+        self.set_state(DaemonState::Stopping, cx);
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            cx.background_executor()
+                .spawn(async move { Self::terminate_sync(&child, timeout) })
+                .await;
+
+            let _ = cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.child_handle = None;
+                    this.shutdown_deadline = None;
+                    this.set_state(DaemonState::Stopped, cx);
+                })
+            });
+        })
+    }
+
+    /// SIGTERM the child and wait for it, escalating to SIGKILL after `timeout`
+    fn terminate_sync(child: &Mutex<Child>, timeout: Duration) {
+        let pid = {
+            let mut child = child.lock().unwrap();
+            if let Ok(Some(_)) = child.try_wait() {
+                return;
+            }
+            child.id()
+        };
+
+        tracing::info!("Sending SIGTERM to daemon (pid {})", pid);
+        // SAFETY: plain kill(2) on our own unreaped child, so the PID can't have been reused
+        unsafe {
+            libc::kill(pid as libc::pid_t, libc::SIGTERM);
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match child.lock().unwrap().try_wait() {
+                Ok(Some(status)) => {
+                    tracing::info!("Daemon exited with {}", Self::describe_exit(status));
+                    return;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to check daemon status: {}", e);
+                    break;
+                }
+            }
+            if Instant::now() >= deadline {
+                tracing::warn!("Daemon did not exit within {:?}, sending SIGKILL", timeout);
+                break;
+            }
+            std::thread::sleep(SHUTDOWN_POLL_INTERVAL);
+        }
+
+        let mut child = child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    /// The daemon is up but speaks an incompatible API: stop it and report why
+    ///
+    /// Restarting would only bring the same binary back, so no restart is
+    /// scheduled. A daemon we attached to but did not spawn is left running.
+    pub fn mark_incompatible(&mut self, reason: String, cx: &mut Context<Self>) {
+        self.supervisor_task = None;
+        self.restart_task = None;
+        self.started_at = None;
+        self.log_capture.system(LogLevel::Error, reason.clone());
+
+        if let Some(child) = self.child_handle.take() {
+            cx.background_executor()
+                .spawn(async move { Self::terminate_sync(&child, DEFAULT_SHUTDOWN_TIMEOUT) })
+                .detach();
+        }
+        self.set_state(DaemonState::Failed(reason), cx);
+    }
+
+    /// Skip the rest of the grace period and SIGKILL the daemon now
+    pub fn kill_now(&mut self) {
+        if let Some(child) = &self.child_handle {
+            tracing::warn!("Killing daemon without waiting for a clean exit");
+            let _ = child.lock().unwrap().kill();
+        }
+    }
+
+    /// Send SIGTERM without waiting (last resort when the app exits abruptly)
+    pub fn terminate(&mut self) {
+        self.supervisor_task = None;
+        self.restart_task = None;
+        if let Some(child) = self.child_handle.take() {
+            let mut child = child.lock().unwrap();
+            if let Ok(None) = child.try_wait() {
+                tracing::info!("Sending SIGTERM to daemon (pid {})", child.id());
+                // SAFETY: see terminate_sync
+                unsafe {
+                    libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+                }
+            }
+        }
+    }
+
+    /// Stop supervising and leave the daemon running after the app exits
+    ///
+    /// Its output goes to files it holds open itself, so it keeps logging.
+    pub fn detach(&mut self) {
+        self.supervisor_task = None;
+        self.restart_task = None;
+        if let Some(child) = self.child_handle.take() {
+            tracing::info!("Leaving daemon running (pid {})", child.lock().unwrap().id());
+        }
     }
 
     fn set_state(&mut self, state: DaemonState, cx: &mut Context<Self>) {
@@ -1,7 +1,19 @@
+mod ansi;
+mod compose_file;
 mod daemon;
+mod daemon_logs;
 mod daemon_manager;
 mod image_icon;
+mod notifications;
+mod settings;
+mod shell_words;
 
+pub use ansi::*;
+pub use compose_file::*;
 pub use daemon::*;
+pub use daemon_logs::*;
 pub use daemon_manager::*;
 pub use image_icon::*;
+pub use notifications::*;
+pub use settings::*;
+pub use shell_words::*;
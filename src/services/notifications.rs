@@ -0,0 +1,257 @@
+//! In-app notifications.
+//!
+//! Operation results and daemon failures are pushed to a global
+//! `NotificationCenter`. New notifications show up as stacked toasts in the
+//! main window and are kept in a history list until cleared.
+
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use gpui::*;
+
+/// Maximum number of toasts on screen; older ones stay in the history
+const MAX_VISIBLE_TOASTS: usize = 4;
+
+/// Maximum number of notifications kept in the history
+const MAX_HISTORY: usize = 200;
+
+/// How serious a notification is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// How long the toast stays up; errors stay until dismissed
+    fn auto_dismiss(&self) -> Option<Duration> {
+        match self {
+            Severity::Info | Severity::Success => Some(Duration::from_secs(4)),
+            Severity::Warning => Some(Duration::from_secs(8)),
+            Severity::Error => None,
+        }
+    }
+}
+
+/// Callback run when a notification action is clicked
+pub type NotificationHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+
+/// A button on a notification, e.g. "Retry"
+#[derive(Clone)]
+pub struct NotificationAction {
+    pub label: SharedString,
+    pub handler: NotificationHandler,
+}
+
+/// A single notification
+#[derive(Clone)]
+pub struct Notification {
+    /// Assigned by the center when pushed
+    pub id: usize,
+    pub severity: Severity,
+    pub title: SharedString,
+    pub message: Option<SharedString>,
+    pub actions: Vec<NotificationAction>,
+    pub created_at: DateTime<Local>,
+    /// Number of identical notifications folded into this one
+    pub count: usize,
+    pub read: bool,
+}
+
+impl Notification {
+    pub fn new(severity: Severity, title: impl Into<SharedString>) -> Self {
+        Self {
+            id: 0,
+            severity,
+            title: title.into(),
+            message: None,
+            actions: Vec::new(),
+            created_at: Local::now(),
+            count: 1,
+            read: false,
+        }
+    }
+
+    pub fn info(title: impl Into<SharedString>) -> Self {
+        Self::new(Severity::Info, title)
+    }
+
+    pub fn success(title: impl Into<SharedString>) -> Self {
+        Self::new(Severity::Success, title)
+    }
+
+    pub fn warning(title: impl Into<SharedString>) -> Self {
+        Self::new(Severity::Warning, title)
+    }
+
+    pub fn error(title: impl Into<SharedString>) -> Self {
+        Self::new(Severity::Error, title)
+    }
+
+    /// Set the body text
+    pub fn message(mut self, message: impl Into<SharedString>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Add an action button
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.actions.push(NotificationAction {
+            label: label.into(),
+            handler: Rc::new(handler),
+        });
+        self
+    }
+
+    /// Same title and body (used to fold repeats into one toast)
+    fn same_content(&self, other: &Notification) -> bool {
+        self.severity == other.severity && self.title == other.title && self.message == other.message
+    }
+}
+
+struct GlobalNotificationCenter(Entity<NotificationCenter>);
+
+impl Global for GlobalNotificationCenter {}
+
+/// Owns the notification history and the set of visible toasts
+pub struct NotificationCenter {
+    /// Newest first
+    history: VecDeque<Notification>,
+    /// IDs of notifications currently shown as toasts, oldest first
+    toasts: Vec<usize>,
+    next_id: usize,
+    /// Pending auto-dismiss timers by notification ID
+    dismiss_tasks: HashMap<usize, Task<()>>,
+}
+
+impl NotificationCenter {
+    /// Register the center as a global (called in main)
+    pub fn init(cx: &mut App) {
+        let center = cx.new(|_cx| Self {
+            history: VecDeque::new(),
+            toasts: Vec::new(),
+            next_id: 1,
+            dismiss_tasks: HashMap::new(),
+        });
+        cx.set_global(GlobalNotificationCenter(center));
+    }
+
+    /// Get the shared notification center
+    pub fn global(cx: &App) -> Entity<NotificationCenter> {
+        cx.global::<GlobalNotificationCenter>().0.clone()
+    }
+
+    /// Show a notification as a toast and record it in the history
+    ///
+    /// Returns the notification ID, which can be passed to `dismiss`.
+    pub fn push(&mut self, mut notification: Notification, cx: &mut Context<Self>) -> usize {
+        // Fold a repeat of a visible toast into it instead of stacking another
+        let repeat = self.toasts.iter().rev().copied().find(|id| {
+            self.get(*id).is_some_and(|existing| existing.same_content(&notification))
+        });
+        if let Some(id) = repeat {
+            if let Some(existing) = self.history.iter_mut().find(|n| n.id == id) {
+                existing.count += 1;
+                existing.created_at = notification.created_at;
+                existing.read = false;
+            }
+            self.schedule_dismiss(id, notification.severity, cx);
+            cx.notify();
+            return id;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        notification.id = id;
+        let severity = notification.severity;
+
+        self.history.push_front(notification);
+        self.history.truncate(MAX_HISTORY);
+
+        self.toasts.push(id);
+        if self.toasts.len() > MAX_VISIBLE_TOASTS {
+            let dropped = self.toasts.remove(0);
+            self.dismiss_tasks.remove(&dropped);
+        }
+        self.schedule_dismiss(id, severity, cx);
+
+        cx.notify();
+        id
+    }
+
+    /// (Re)start the auto-dismiss timer for a toast
+    fn schedule_dismiss(&mut self, id: usize, severity: Severity, cx: &mut Context<Self>) {
+        let Some(delay) = severity.auto_dismiss() else {
+            return;
+        };
+        let task = cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            cx.background_executor().timer(delay).await;
+            this.update(cx, |this, cx| this.dismiss(id, cx)).ok();
+        });
+        self.dismiss_tasks.insert(id, task);
+    }
+
+    /// Hide a toast (it stays in the history)
+    pub fn dismiss(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.dismiss_tasks.remove(&id);
+        let before = self.toasts.len();
+        self.toasts.retain(|t| *t != id);
+        if self.toasts.len() != before {
+            cx.notify();
+        }
+    }
+
+    /// Look up a notification by ID
+    pub fn get(&self, id: usize) -> Option<&Notification> {
+        self.history.iter().find(|n| n.id == id)
+    }
+
+    /// Notifications currently shown as toasts, oldest first
+    pub fn toasts(&self) -> Vec<Notification> {
+        self.toasts.iter().filter_map(|id| self.get(*id).cloned()).collect()
+    }
+
+    /// All recorded notifications, newest first
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    /// Number of notifications not yet seen in the history panel
+    pub fn unread_count(&self) -> usize {
+        self.history.iter().filter(|n| !n.read).count()
+    }
+
+    /// Mark everything as read (when the history panel is opened)
+    pub fn mark_all_read(&mut self, cx: &mut Context<Self>) {
+        if self.unread_count() == 0 {
+            return;
+        }
+        for notification in self.history.iter_mut() {
+            notification.read = true;
+        }
+        cx.notify();
+    }
+
+    /// Remove a single notification from the history
+    pub fn remove(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.dismiss(id, cx);
+        self.history.retain(|n| n.id != id);
+        cx.notify();
+    }
+
+    /// Drop the whole history, including visible toasts
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.history.clear();
+        self.toasts.clear();
+        self.dismiss_tasks.clear();
+        cx.notify();
+    }
+}
@@ -0,0 +1,303 @@
+//! Persistent application settings.
+//!
+//! Settings are stored as JSON under the user's config directory and shared
+//! between windows through a global `SettingsStore` entity.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+use crate::services::{DEFAULT_RESTART_LIMIT, DEFAULT_SHUTDOWN_TIMEOUT};
+
+/// Name of the built-in context backed by the bundled daemon
+pub const LOCAL_CONTEXT: &str = "local";
+
+/// How to reach a daemon
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DaemonEndpoint {
+    /// gRPC over a local Unix socket
+    Local { socket_path: PathBuf },
+    /// gRPC over TCP with mutual TLS
+    Tcp {
+        host: String,
+        port: u16,
+        /// CA certificate used to verify the daemon (PEM)
+        ca_cert: PathBuf,
+        /// Client certificate presented to the daemon (PEM)
+        client_cert: PathBuf,
+        /// Private key for the client certificate (PEM)
+        client_key: PathBuf,
+    },
+    /// Remote Unix socket forwarded over an SSH tunnel
+    Ssh {
+        /// `user@host` passed to ssh
+        destination: String,
+        #[serde(default)]
+        port: Option<u16>,
+        #[serde(default)]
+        identity_file: Option<PathBuf>,
+        /// Socket path on the remote host
+        remote_socket: String,
+    },
+}
+
+impl DaemonEndpoint {
+    /// Short kind label for display
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            DaemonEndpoint::Local { .. } => "Local",
+            DaemonEndpoint::Tcp { .. } => "TCP + TLS",
+            DaemonEndpoint::Ssh { .. } => "SSH",
+        }
+    }
+
+    /// Human readable address, e.g. "ssh://user@host:/var/run/arcbox.sock"
+    pub fn display(&self) -> String {
+        match self {
+            DaemonEndpoint::Local { socket_path } => {
+                format!("unix://{}", socket_path.display())
+            }
+            DaemonEndpoint::Tcp { host, port, .. } => format!("https://{}:{}", host, port),
+            DaemonEndpoint::Ssh {
+                destination,
+                port,
+                remote_socket,
+                ..
+            } => match port {
+                Some(port) => format!("ssh://{}:{}{}", destination, port, remote_socket),
+                None => format!("ssh://{}{}", destination, remote_socket),
+            },
+        }
+    }
+}
+
+/// A named daemon connection target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonContext {
+    pub name: String,
+    pub endpoint: DaemonEndpoint,
+}
+
+impl DaemonContext {
+    /// The built-in context for the bundled daemon (~/.arcbox/arcbox.sock)
+    pub fn local() -> Self {
+        Self {
+            name: LOCAL_CONTEXT.to_string(),
+            endpoint: DaemonEndpoint::Local {
+                socket_path: dirs::home_dir()
+                    .unwrap_or_else(|| PathBuf::from("/tmp"))
+                    .join(".arcbox")
+                    .join("arcbox.sock"),
+            },
+        }
+    }
+
+    /// Whether this context is the one whose daemon process we manage
+    pub fn is_managed(&self) -> bool {
+        self.name == LOCAL_CONTEXT
+    }
+}
+
+/// Filter and sort remembered for a list view
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListViewState {
+    pub filter: String,
+    /// Sort key understood by the view (e.g. "name"); empty for its default
+    pub sort: String,
+    pub descending: bool,
+}
+
+/// Serialized settings file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Known daemon contexts; always contains the local context
+    pub contexts: Vec<DaemonContext>,
+    /// Name of the context the UI is pointed at
+    pub active_context: String,
+    /// Automatic restarts of a crashing local daemon before giving up (0 = never restart)
+    pub daemon_restart_limit: u32,
+    /// Leave the local daemon running when the app quits
+    pub keep_running_when_quit: bool,
+    /// Seconds to wait for the daemon to exit after SIGTERM before killing it
+    pub shutdown_timeout_secs: u64,
+    /// Filter and sort per list view, by view name (e.g. "containers")
+    pub list_views: HashMap<String, ListViewState>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            contexts: vec![DaemonContext::local()],
+            active_context: LOCAL_CONTEXT.to_string(),
+            daemon_restart_limit: DEFAULT_RESTART_LIMIT,
+            keep_running_when_quit: false,
+            shutdown_timeout_secs: DEFAULT_SHUTDOWN_TIMEOUT.as_secs(),
+            list_views: HashMap::new(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Look up a context by name
+    pub fn context(&self, name: &str) -> Option<&DaemonContext> {
+        self.contexts.iter().find(|c| c.name == name)
+    }
+
+    /// The active context, falling back to local if it was removed
+    pub fn active(&self) -> DaemonContext {
+        self.context(&self.active_context)
+            .cloned()
+            .unwrap_or_else(DaemonContext::local)
+    }
+
+    /// How long to wait for a clean daemon exit on quit
+    pub fn shutdown_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.shutdown_timeout_secs)
+    }
+
+    /// Repair settings loaded from disk (missing local context, dangling active name)
+    fn normalize(&mut self) {
+        if !self.contexts.iter().any(|c| c.is_managed()) {
+            self.contexts.insert(0, DaemonContext::local());
+        }
+        if self.context(&self.active_context).is_none() {
+            self.active_context = LOCAL_CONTEXT.to_string();
+        }
+    }
+}
+
+struct GlobalSettingsStore(Entity<SettingsStore>);
+
+impl Global for GlobalSettingsStore {}
+
+/// Owns the settings and writes them back to disk on every change
+pub struct SettingsStore {
+    settings: AppSettings,
+    path: PathBuf,
+}
+
+impl SettingsStore {
+    /// Load settings and register the store as a global (called in main)
+    pub fn init(cx: &mut App) {
+        let store = cx.new(|_cx| {
+            let path = Self::get_settings_path();
+            let settings = Self::load(&path);
+            Self { settings, path }
+        });
+        cx.set_global(GlobalSettingsStore(store));
+    }
+
+    /// Get the shared settings store
+    pub fn global(cx: &App) -> Entity<SettingsStore> {
+        cx.global::<GlobalSettingsStore>().0.clone()
+    }
+
+    /// Get the settings file path
+    fn get_settings_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("arcbox")
+            .join("settings.json")
+    }
+
+    /// Load settings from file, falling back to defaults
+    fn load(path: &PathBuf) -> AppSettings {
+        let mut settings = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str::<AppSettings>(&content).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid settings file {:?}: {}", path, e);
+                AppSettings::default()
+            }),
+            Err(_) => AppSettings::default(),
+        };
+        settings.normalize();
+        settings
+    }
+
+    /// Save settings to file
+    fn save(&self) {
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+
+        if let Ok(content) = serde_json::to_string_pretty(&self.settings) {
+            if let Err(e) = fs::write(&self.path, content) {
+                tracing::warn!("Failed to save settings: {}", e);
+            }
+        }
+    }
+
+    /// Current settings
+    pub fn settings(&self) -> &AppSettings {
+        &self.settings
+    }
+
+    /// Modify settings, persist them and notify observers
+    pub fn update_settings(&mut self, f: impl FnOnce(&mut AppSettings), cx: &mut Context<Self>) {
+        f(&mut self.settings);
+        self.settings.normalize();
+        self.save();
+        cx.notify();
+    }
+
+    /// Add a context, replacing any existing one with the same name
+    pub fn add_context(&mut self, context: DaemonContext, cx: &mut Context<Self>) {
+        self.update_settings(
+            |settings| {
+                settings.contexts.retain(|c| c.name != context.name);
+                settings.contexts.push(context);
+            },
+            cx,
+        );
+    }
+
+    /// Remove a context by name (the local context cannot be removed)
+    pub fn remove_context(&mut self, name: &str, cx: &mut Context<Self>) {
+        if name == LOCAL_CONTEXT {
+            return;
+        }
+        self.update_settings(|settings| settings.contexts.retain(|c| c.name != name), cx);
+    }
+
+    /// Point the local context at the managed daemon's gRPC socket
+    pub fn set_local_socket_path(&mut self, socket_path: PathBuf, cx: &mut Context<Self>) {
+        let endpoint = DaemonEndpoint::Local { socket_path };
+        if self.settings.context(LOCAL_CONTEXT).map(|c| &c.endpoint) == Some(&endpoint) {
+            return;
+        }
+        self.update_settings(
+            |settings| {
+                for context in settings.contexts.iter_mut().filter(|c| c.is_managed()) {
+                    context.endpoint = endpoint.clone();
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Remember a list view's filter and sort
+    pub fn set_list_view(&mut self, view: &str, state: ListViewState, cx: &mut Context<Self>) {
+        if self.settings.list_views.get(view) == Some(&state) {
+            return;
+        }
+        self.update_settings(
+            |settings| {
+                settings.list_views.insert(view.to_string(), state);
+            },
+            cx,
+        );
+    }
+
+    /// Switch the active context
+    pub fn set_active_context(&mut self, name: &str, cx: &mut Context<Self>) {
+        if self.settings.active_context == name {
+            return;
+        }
+        self.update_settings(|settings| settings.active_context = name.to_string(), cx);
+    }
+}
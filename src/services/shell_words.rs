@@ -0,0 +1,159 @@
+//! POSIX shell word splitting.
+//!
+//! Commands typed into the New Container dialog (and pasted `docker run`
+//! lines) are split the way `sh` would split them, minus expansion: quotes
+//! group words, backslashes escape, a backslash-newline continues the line
+//! and `#` at the start of a word begins a comment. `join_shell_words` goes
+//! the other way and quotes only the words that need it.
+
+use std::fmt;
+
+/// Why a command line could not be split
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellWordsError {
+    /// A quote was opened at this byte offset and never closed
+    UnterminatedQuote { quote: char, position: usize },
+    /// The input ends with a lone backslash
+    TrailingBackslash,
+}
+
+impl fmt::Display for ShellWordsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShellWordsError::UnterminatedQuote { quote, position } => {
+                write!(f, "unterminated {} at position {}", quote, position + 1)
+            }
+            ShellWordsError::TrailingBackslash => write!(f, "trailing backslash"),
+        }
+    }
+}
+
+/// Split a command line into words
+pub fn split_shell_words(text: &str) -> Result<Vec<String>, ShellWordsError> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    // A word has started even if it is empty so far (e.g. after "")
+    let mut in_word = false;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '#' if !in_word => {
+                // Comment to end of line
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some((_, '\n')) => {}
+                Some((_, escaped)) => {
+                    word.push(escaped);
+                    in_word = true;
+                }
+                None => return Err(ShellWordsError::TrailingBackslash),
+            },
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some((_, '\'')) => break,
+                        Some((_, c)) => word.push(c),
+                        None => return Err(ShellWordsError::UnterminatedQuote { quote: '\'', position }),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        // Inside double quotes a backslash only escapes these
+                        Some((_, '\\')) => match chars.peek() {
+                            Some((_, '\n')) => {
+                                chars.next();
+                            }
+                            Some((_, next @ ('$' | '`' | '"' | '\\'))) => {
+                                word.push(*next);
+                                chars.next();
+                            }
+                            _ => word.push('\\'),
+                        },
+                        Some((_, c)) => word.push(c),
+                        None => return Err(ShellWordsError::UnterminatedQuote { quote: '"', position }),
+                    }
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Quote a word for a POSIX shell if it needs it
+pub fn quote_shell_word(word: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c);
+    if !word.is_empty() && word.chars().all(safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Join words into a command line that `split_shell_words` turns back into the same words
+pub fn join_shell_words(words: &[String]) -> String {
+    words.iter().map(|word| quote_shell_word(word)).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        split_shell_words(text).unwrap()
+    }
+
+    #[test]
+    fn splits_like_a_shell() {
+        assert_eq!(words(r#"sh -c "echo hi there""#), ["sh", "-c", "echo hi there"]);
+        assert_eq!(words("a 'b c'd \"\" e\\ f"), ["a", "b cd", "", "e f"]);
+        assert_eq!(words(r#""a \"q\" \$x \n""#), [r#"a "q" $x \n"#]);
+        assert_eq!(words("docker run \\\n  -p 80:80 nginx # web\n"), ["docker", "run", "-p", "80:80", "nginx"]);
+        assert_eq!(words("a#b"), ["a#b"]);
+        assert!(words("  ").is_empty());
+    }
+
+    #[test]
+    fn reports_unbalanced_input() {
+        assert_eq!(
+            split_shell_words("echo 'oops"),
+            Err(ShellWordsError::UnterminatedQuote { quote: '\'', position: 5 })
+        );
+        assert_eq!(
+            split_shell_words(r#"say "hi"#),
+            Err(ShellWordsError::UnterminatedQuote { quote: '"', position: 4 })
+        );
+        assert_eq!(split_shell_words("trailing \\"), Err(ShellWordsError::TrailingBackslash));
+    }
+
+    #[test]
+    fn join_round_trips() {
+        let original: Vec<String> = ["nginx", "-g", "daemon off;", "it's", "", "a=b"].map(String::from).to_vec();
+        let line = join_shell_words(&original);
+        assert_eq!(line, r#"nginx -g 'daemon off;' 'it'\''s' '' a=b"#);
+        assert_eq!(split_shell_words(&line).unwrap(), original);
+    }
+}
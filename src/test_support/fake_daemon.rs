@@ -0,0 +1,758 @@
+//! In-process fake of the arcbox-daemon gRPC API.
+//!
+//! Serves the container, image, network, machine and system services over a
+//! Unix socket in a temp directory, backed by in-memory state that tests can
+//! seed and inspect. Errors can be scripted per RPC with `fail_next`.
+//!
+//! Requests are routed by method name and decoded with the generated prost
+//! messages, so the fake only needs to know about the RPCs the desktop app
+//! actually calls; anything else is answered with `Unimplemented`.
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use arcbox_api::generated::{
+    ContainerLogsRequest, ContainerStatsRequest, ContainerStatsResponse, ContainerSummary,
+    CreateContainerRequest, CreateContainerResponse, CreateNetworkRequest, CreateNetworkResponse,
+    Event, EventsRequest, ExecCreateRequest, ExecCreateResponse, ExecStartRequest,
+    ExecStartResponse, ImageSummary, InspectContainerRequest, InspectContainerResponse,
+    KillContainerRequest, ListContainersRequest, ListContainersResponse, ListImagesRequest,
+    ListImagesResponse, ListMachinesRequest, ListMachinesResponse, ListNetworksRequest,
+    ListNetworksResponse, LogEntry, NetworkSummary, PauseContainerRequest, RemoveContainerRequest,
+    RemoveNetworkRequest, RenameContainerRequest, RestartContainerRequest, StartContainerRequest,
+    StatPathRequest, StatPathResponse, StopContainerRequest, UnpauseContainerRequest, VersionRequest, VersionResponse,
+};
+use futures::stream::{self, BoxStream, StreamExt};
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use tokio::net::UnixListener;
+use tokio::sync::{broadcast, oneshot};
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::server::{Grpc, ServerStreamingService, StreamingService, UnaryService};
+use tonic::{Request, Response, Status};
+
+/// In-memory daemon state
+#[derive(Default)]
+pub struct FakeState {
+    pub containers: Vec<ContainerSummary>,
+    pub images: Vec<ImageSummary>,
+    pub networks: Vec<NetworkSummary>,
+    pub machines: ListMachinesResponse,
+    /// Reply to `Version` (empty API version reads as "unknown", i.e. compatible)
+    pub version: VersionResponse,
+    /// Sent as `arcbox-capabilities` metadata on `Version` when set
+    pub capabilities: Option<String>,
+    /// Log entries by container ID
+    pub logs: HashMap<String, Vec<LogEntry>>,
+    /// Stats reports by container ID, streamed in order and then closed
+    pub stats: HashMap<String, Vec<ContainerStatsResponse>>,
+    /// Every `CreateContainer` request received, in order
+    pub created: Vec<CreateContainerRequest>,
+    /// Container files by absolute path, for `StatPath` and `find` execs
+    pub files: HashMap<String, StatPathResponse>,
+    /// Command of each created exec, by exec ID
+    execs: HashMap<String, Vec<String>>,
+    /// Scripted failures by RPC method name (e.g. "StartContainer")
+    failures: HashMap<String, VecDeque<Status>>,
+    /// Every RPC method name received, in order
+    pub calls: Vec<String>,
+    next_id: u64,
+}
+
+impl FakeState {
+    fn next_id(&mut self) -> String {
+        self.next_id += 1;
+        format!("{:064x}", self.next_id)
+    }
+
+    fn container_mut(&mut self, id: &str) -> Result<&mut ContainerSummary, Status> {
+        self.containers
+            .iter_mut()
+            .find(|c| c.id == id || c.name.trim_start_matches('/') == id)
+            .ok_or_else(|| Status::not_found(format!("No such container: {}", id)))
+    }
+
+    /// Output of an exec; only the `find` the file browser lists with is understood
+    fn exec_output(&self, cmd: &[String]) -> Result<Vec<u8>, Status> {
+        match cmd {
+            [find, dir, ..] if find == "find" => {
+                let prefix = if dir.ends_with('/') { dir.clone() } else { format!("{}/", dir) };
+                let mut paths: Vec<&String> = self
+                    .files
+                    .keys()
+                    .filter(|path| path.strip_prefix(&prefix).is_some_and(|name| !name.is_empty() && !name.contains('/')))
+                    .collect();
+                paths.sort();
+                Ok(paths.iter().map(|path| format!("{}\r\n", path)).collect::<String>().into_bytes())
+            }
+            _ => Err(Status::unimplemented(format!("fake daemon can't run {:?}", cmd))),
+        }
+    }
+}
+
+/// Handle to a running fake daemon; shuts the server down on drop
+pub struct FakeDaemon {
+    state: Arc<Mutex<FakeState>>,
+    events: broadcast::Sender<Event>,
+    logs: broadcast::Sender<(String, LogEntry)>,
+    socket_path: PathBuf,
+    shutdown: Option<oneshot::Sender<()>>,
+    runtime: Option<tokio::runtime::Runtime>,
+    _dir: Option<tempfile::TempDir>,
+}
+
+impl FakeDaemon {
+    /// Start a fake daemon on a fresh socket in a temp directory
+    pub fn start() -> Self {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let socket_path = dir.path().join("arcbox.sock");
+        let mut daemon = Self::start_at(&socket_path, Arc::default());
+        daemon._dir = Some(dir);
+        daemon
+    }
+
+    /// Start a fake daemon on the given socket, sharing existing state
+    ///
+    /// Used to simulate a daemon restart: stop the old one, then start a new
+    /// one on the same path with `daemon.shared_state()`.
+    pub fn start_at(socket_path: &Path, state: Arc<Mutex<FakeState>>) -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("Failed to create tokio runtime");
+
+        let _ = std::fs::remove_file(socket_path);
+        let listener = {
+            let _guard = runtime.enter();
+            UnixListener::bind(socket_path).expect("Failed to bind fake daemon socket")
+        };
+
+        let (events, _) = broadcast::channel(64);
+        let (logs, _) = broadcast::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+
+        let router = Router {
+            state: state.clone(),
+            events: events.clone(),
+            logs: logs.clone(),
+        };
+        runtime.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => break,
+                    accepted = listener.accept() => {
+                        let Ok((stream, _)) = accepted else { continue };
+                        let router = router.clone();
+                        tokio::spawn(async move {
+                            let _ = hyper::server::conn::http2::Builder::new(TokioExecutor::new())
+                                .serve_connection(TokioIo::new(stream), router)
+                                .await;
+                        });
+                    }
+                }
+            }
+        });
+
+        Self {
+            state,
+            events,
+            logs,
+            socket_path: socket_path.to_path_buf(),
+            shutdown: Some(shutdown_tx),
+            runtime: Some(runtime),
+            _dir: None,
+        }
+    }
+
+    /// Path of the Unix socket the fake is listening on
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Lock the daemon state for seeding or assertions
+    pub fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state.lock().unwrap()
+    }
+
+    /// State handle to carry over to a restarted fake
+    pub fn shared_state(&self) -> Arc<Mutex<FakeState>> {
+        self.state.clone()
+    }
+
+    /// Make the next call to `method` fail with `status`
+    pub fn fail_next(&self, method: &str, status: Status) {
+        self.state()
+            .failures
+            .entry(method.to_string())
+            .or_default()
+            .push_back(status);
+    }
+
+    /// Deliver an event to every open `Events` stream
+    pub fn emit_event(&self, event: Event) {
+        let _ = self.events.send(event);
+    }
+
+    /// Append a log line, delivering it to followers of the container
+    pub fn push_log(&self, container_id: &str, entry: LogEntry) {
+        self.state()
+            .logs
+            .entry(container_id.to_string())
+            .or_default()
+            .push(entry.clone());
+        let _ = self.logs.send((container_id.to_string(), entry));
+    }
+
+    /// Stop serving and remove the socket (connections are dropped)
+    pub fn stop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+            // Only on the first stop: a restarted fake may own the path by now
+            let _ = std::fs::remove_file(&self.socket_path);
+        }
+    }
+}
+
+impl Drop for FakeDaemon {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Build a daemon event for a resource, e.g. `resource_event("container", "die", id)`
+pub fn resource_event(kind: &str, action: &str, id: &str) -> Event {
+    let mut event = Event {
+        r#type: kind.to_string(),
+        action: action.to_string(),
+        ..Default::default()
+    };
+    let mut actor = event.actor.take().unwrap_or_default();
+    actor.id = id.to_string();
+    event.actor = Some(actor);
+    event
+}
+
+/// Dispatches gRPC requests to handlers by method name
+#[derive(Clone)]
+struct Router {
+    state: Arc<Mutex<FakeState>>,
+    events: broadcast::Sender<Event>,
+    logs: broadcast::Sender<(String, LogEntry)>,
+}
+
+impl hyper::service::Service<http::Request<Incoming>> for Router {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn call(&self, req: http::Request<Incoming>) -> Self::Future {
+        let router = self.clone();
+        Box::pin(async move { Ok(router.route(req).await) })
+    }
+}
+
+impl Router {
+    async fn route(self, req: http::Request<Incoming>) -> http::Response<BoxBody> {
+        let method = req.uri().path().rsplit('/').next().unwrap_or_default().to_string();
+
+        // Subscribe before the call is recorded so tests that wait for the
+        // call can't publish into the gap
+        let events = self.events.subscribe();
+        let logs = self.logs.subscribe();
+
+        let failure = {
+            let mut state = self.state.lock().unwrap();
+            state.calls.push(method.clone());
+            state.failures.get_mut(&method).and_then(|q| q.pop_front())
+        };
+        if let Some(status) = failure {
+            return unary(req, move |_: ()| Err::<(), _>(status)).await;
+        }
+
+        let state = self.state.clone();
+        match method.as_str() {
+            "ListContainers" => {
+                unary(req, move |r: ListContainersRequest| {
+                    let state = state.lock().unwrap();
+                    let id_filter = r.filters.get("id");
+                    let containers = state
+                        .containers
+                        .iter()
+                        .filter(|c| r.all || c.state == "running")
+                        .filter(|c| id_filter.is_none_or(|id| c.id.starts_with(id.as_str())))
+                        .cloned()
+                        .collect();
+                    Ok(ListContainersResponse { containers })
+                })
+                .await
+            }
+            "CreateContainer" => {
+                unary(req, move |r: CreateContainerRequest| {
+                    let mut state = state.lock().unwrap();
+                    if r.image.is_empty() {
+                        return Err(Status::invalid_argument("image is required"));
+                    }
+                    state.created.push(r.clone());
+                    let id = state.next_id();
+                    let name = if r.name.is_empty() {
+                        id.chars().take(12).collect()
+                    } else {
+                        r.name
+                    };
+                    state.containers.push(ContainerSummary {
+                        id: id.clone(),
+                        name: format!("/{}", name),
+                        image: r.image,
+                        labels: r.labels,
+                        state: "created".to_string(),
+                        ..Default::default()
+                    });
+                    Ok(CreateContainerResponse {
+                        id,
+                        ..Default::default()
+                    })
+                })
+                .await
+            }
+            "StartContainer" => {
+                unary(req, move |r: StartContainerRequest| {
+                    state.lock().unwrap().container_mut(&r.id)?.state = "running".to_string();
+                    Ok(())
+                })
+                .await
+            }
+            "StopContainer" => {
+                unary(req, move |r: StopContainerRequest| {
+                    state.lock().unwrap().container_mut(&r.id)?.state = "exited".to_string();
+                    Ok(())
+                })
+                .await
+            }
+            "InspectContainer" => {
+                unary(req, move |r: InspectContainerRequest| {
+                    let mut state = state.lock().unwrap();
+                    let container = state.container_mut(&r.id)?;
+                    let document = serde_json::json!({
+                        "Id": container.id,
+                        "Name": container.name,
+                        "Config": {"Image": container.image, "Cmd": ["sh"]},
+                        "State": {"Status": container.state},
+                    });
+                    Ok(InspectContainerResponse { json: document.to_string() })
+                })
+                .await
+            }
+            "PauseContainer" => {
+                unary(req, move |r: PauseContainerRequest| {
+                    let mut state = state.lock().unwrap();
+                    let container = state.container_mut(&r.id)?;
+                    if container.state != "running" {
+                        return Err(Status::failed_precondition(format!(
+                            "container {} is not running",
+                            r.id
+                        )));
+                    }
+                    container.state = "paused".to_string();
+                    Ok(())
+                })
+                .await
+            }
+            "UnpauseContainer" => {
+                unary(req, move |r: UnpauseContainerRequest| {
+                    let mut state = state.lock().unwrap();
+                    let container = state.container_mut(&r.id)?;
+                    if container.state != "paused" {
+                        return Err(Status::failed_precondition(format!(
+                            "container {} is not paused",
+                            r.id
+                        )));
+                    }
+                    container.state = "running".to_string();
+                    Ok(())
+                })
+                .await
+            }
+            "RestartContainer" => {
+                unary(req, move |r: RestartContainerRequest| {
+                    state.lock().unwrap().container_mut(&r.id)?.state = "running".to_string();
+                    Ok(())
+                })
+                .await
+            }
+            "KillContainer" => {
+                unary(req, move |r: KillContainerRequest| {
+                    let mut state = state.lock().unwrap();
+                    let container = state.container_mut(&r.id)?;
+                    if container.state != "running" && container.state != "paused" {
+                        return Err(Status::failed_precondition(format!(
+                            "container {} is not running",
+                            r.id
+                        )));
+                    }
+                    // Default signal stops the container; others are just delivered
+                    if r.signal.is_empty() || r.signal == "SIGKILL" {
+                        container.state = "exited".to_string();
+                    }
+                    Ok(())
+                })
+                .await
+            }
+            "RenameContainer" => {
+                unary(req, move |r: RenameContainerRequest| {
+                    let mut state = state.lock().unwrap();
+                    let taken = state
+                        .containers
+                        .iter()
+                        .any(|c| c.name.trim_start_matches('/') == r.name);
+                    if taken {
+                        return Err(Status::already_exists(format!(
+                            "name {} is already in use",
+                            r.name
+                        )));
+                    }
+                    state.container_mut(&r.id)?.name = format!("/{}", r.name);
+                    Ok(())
+                })
+                .await
+            }
+            "RemoveContainer" => {
+                unary(req, move |r: RemoveContainerRequest| {
+                    let mut state = state.lock().unwrap();
+                    let container = state.container_mut(&r.id)?;
+                    if container.state == "running" && !r.force {
+                        return Err(Status::failed_precondition(
+                            "cannot remove a running container, stop it or use force",
+                        ));
+                    }
+                    let id = container.id.clone();
+                    state.containers.retain(|c| c.id != id);
+                    state.logs.remove(&id);
+                    Ok(())
+                })
+                .await
+            }
+            "ContainerLogs" => {
+                server_streaming(req, move |r: ContainerLogsRequest| {
+                    Self::container_logs(&state, logs, r)
+                })
+                .await
+            }
+            "ContainerStats" => {
+                server_streaming(req, move |r: ContainerStatsRequest| {
+                    let mut state = state.lock().unwrap();
+                    let id = state.container_mut(&r.id)?.id.clone();
+                    let reports = state.stats.get(&id).cloned().unwrap_or_default();
+                    Ok(stream::iter(reports.into_iter().map(Ok)).boxed())
+                })
+                .await
+            }
+            "ListImages" => {
+                unary(req, move |r: ListImagesRequest| {
+                    let state = state.lock().unwrap();
+                    let reference = r.filters.get("reference");
+                    let images = state
+                        .images
+                        .iter()
+                        .filter(|i| {
+                            reference.is_none_or(|reference| {
+                                i.id == *reference || i.repo_tags.contains(reference)
+                            })
+                        })
+                        .cloned()
+                        .collect();
+                    Ok(ListImagesResponse { images })
+                })
+                .await
+            }
+            "ListNetworks" => {
+                unary(req, move |_: ListNetworksRequest| {
+                    let networks = state.lock().unwrap().networks.clone();
+                    Ok(ListNetworksResponse { networks })
+                })
+                .await
+            }
+            "CreateNetwork" => {
+                unary(req, move |r: CreateNetworkRequest| {
+                    let mut state = state.lock().unwrap();
+                    if state.networks.iter().any(|n| n.name == r.name) {
+                        return Err(Status::already_exists(format!("network {} already exists", r.name)));
+                    }
+                    let id = state.next_id();
+                    state.networks.push(NetworkSummary {
+                        id: id.clone(),
+                        name: r.name,
+                        driver: if r.driver.is_empty() { "bridge".to_string() } else { r.driver },
+                        scope: "local".to_string(),
+                        internal: r.internal,
+                        ..Default::default()
+                    });
+                    Ok(CreateNetworkResponse {
+                        id,
+                        ..Default::default()
+                    })
+                })
+                .await
+            }
+            "RemoveNetwork" => {
+                unary(req, move |r: RemoveNetworkRequest| {
+                    let mut state = state.lock().unwrap();
+                    let before = state.networks.len();
+                    state.networks.retain(|n| n.id != r.id && n.name != r.id);
+                    if state.networks.len() == before {
+                        return Err(Status::not_found(format!("network {} not found", r.id)));
+                    }
+                    Ok(())
+                })
+                .await
+            }
+            "Version" => {
+                unary_response(req, move |_: VersionRequest| {
+                    let state = state.lock().unwrap();
+                    let mut response = Response::new(state.version.clone());
+                    if let Some(capabilities) = &state.capabilities {
+                        let value = capabilities
+                            .parse()
+                            .map_err(|_| Status::internal("invalid capabilities metadata"))?;
+                        response.metadata_mut().insert("arcbox-capabilities", value);
+                    }
+                    Ok(response)
+                })
+                .await
+            }
+            "ListMachines" => {
+                unary(req, move |_: ListMachinesRequest| {
+                    Ok(state.lock().unwrap().machines.clone())
+                })
+                .await
+            }
+            "Events" => {
+                server_streaming(req, move |_: EventsRequest| {
+                    let stream = stream::unfold(events, |mut events| async move {
+                        loop {
+                            match events.recv().await {
+                                Ok(event) => return Some((Ok(event), events)),
+                                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                                Err(broadcast::error::RecvError::Closed) => return None,
+                            }
+                        }
+                    });
+                    Ok(stream.boxed())
+                })
+                .await
+            }
+            "StatPath" => {
+                unary(req, move |r: StatPathRequest| {
+                    let mut state = state.lock().unwrap();
+                    state.container_mut(&r.id)?;
+                    state
+                        .files
+                        .get(&r.path)
+                        .cloned()
+                        .ok_or_else(|| Status::not_found(format!("No such file or directory: {}", r.path)))
+                })
+                .await
+            }
+            "ExecCreate" => {
+                unary(req, move |r: ExecCreateRequest| {
+                    let mut state = state.lock().unwrap();
+                    if state.container_mut(&r.container_id)?.state != "running" {
+                        return Err(Status::failed_precondition(format!(
+                            "container {} is not running",
+                            r.container_id
+                        )));
+                    }
+                    let id = state.next_id();
+                    state.execs.insert(id.clone(), r.cmd);
+                    Ok(ExecCreateResponse {
+                        id,
+                        ..Default::default()
+                    })
+                })
+                .await
+            }
+            "ExecStart" => {
+                streaming(req, move |mut requests: tonic::Streaming<ExecStartRequest>| async move {
+                    let first = requests.message().await?.unwrap_or_default();
+                    let data = {
+                        let state = state.lock().unwrap();
+                        let cmd = state
+                            .execs
+                            .get(&first.exec_id)
+                            .ok_or_else(|| Status::not_found(format!("No such exec: {}", first.exec_id)))?;
+                        state.exec_output(cmd)?
+                    };
+                    let output = ExecStartResponse {
+                        data,
+                        ..Default::default()
+                    };
+                    Ok(stream::iter([Ok(output)]).boxed())
+                })
+                .await
+            }
+            _ => {
+                let status = Status::unimplemented(format!("fake daemon does not implement {}", method));
+                unary(req, move |_: ()| Err::<(), _>(status)).await
+            }
+        }
+    }
+
+    /// Stored entries after `since`, limited to `tail`, then live entries when following
+    fn container_logs(
+        state: &Mutex<FakeState>,
+        live: broadcast::Receiver<(String, LogEntry)>,
+        r: ContainerLogsRequest,
+    ) -> Result<BoxStream<'static, Result<LogEntry, Status>>, Status> {
+        let mut state = state.lock().unwrap();
+        let id = state.container_mut(&r.id)?.id.clone();
+
+        let since_ns = r.since.saturating_mul(1_000_000_000);
+        let mut entries: Vec<LogEntry> = state
+            .logs
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.timestamp >= since_ns)
+            .cloned()
+            .collect();
+        if r.tail > 0 && entries.len() > r.tail as usize {
+            entries.drain(..entries.len() - r.tail as usize);
+        }
+
+        let history = stream::iter(entries.into_iter().map(Ok));
+        if !r.follow {
+            return Ok(history.boxed());
+        }
+
+        let follow = stream::unfold(live, move |mut live| {
+            let id = id.clone();
+            async move {
+                loop {
+                    match live.recv().await {
+                        Ok((container_id, entry)) if container_id == id => {
+                            return Some((Ok(entry), live));
+                        }
+                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+        Ok(history.chain(follow).boxed())
+    }
+}
+
+/// Decode a unary request, run `handler` and encode its reply
+async fn unary<Req, Resp, F>(req: http::Request<Incoming>, handler: F) -> http::Response<BoxBody>
+where
+    Req: prost::Message + Default + Send + 'static,
+    Resp: prost::Message + Send + 'static,
+    F: FnOnce(Req) -> Result<Resp, Status> + Send + 'static,
+{
+    unary_response(req, move |r| handler(r).map(Response::new)).await
+}
+
+/// Like `unary`, for handlers that set response metadata
+async fn unary_response<Req, Resp, F>(
+    req: http::Request<Incoming>,
+    handler: F,
+) -> http::Response<BoxBody>
+where
+    Req: prost::Message + Default + Send + 'static,
+    Resp: prost::Message + Send + 'static,
+    F: FnOnce(Req) -> Result<Response<Resp>, Status> + Send + 'static,
+{
+    Grpc::new(ProstCodec::<Resp, Req>::default())
+        .unary(Unary(Some(handler)), req)
+        .await
+}
+
+/// Decode a server-streaming request and stream the handler's items back
+async fn server_streaming<Req, Resp, F>(
+    req: http::Request<Incoming>,
+    handler: F,
+) -> http::Response<BoxBody>
+where
+    Req: prost::Message + Default + Send + 'static,
+    Resp: prost::Message + Send + 'static,
+    F: FnOnce(Req) -> Result<BoxStream<'static, Result<Resp, Status>>, Status> + Send + 'static,
+{
+    Grpc::new(ProstCodec::<Resp, Req>::default())
+        .server_streaming(Streaming(Some(handler)), req)
+        .await
+}
+
+/// Decode a bidirectional-streaming request and stream the handler's items back
+async fn streaming<Req, Resp, F, Fut>(req: http::Request<Incoming>, handler: F) -> http::Response<BoxBody>
+where
+    Req: prost::Message + Default + Send + 'static,
+    Resp: prost::Message + Send + 'static,
+    F: FnOnce(tonic::Streaming<Req>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<BoxStream<'static, Result<Resp, Status>>, Status>> + Send + 'static,
+{
+    Grpc::new(ProstCodec::<Resp, Req>::default())
+        .streaming(Bidi(Some(handler)), req)
+        .await
+}
+
+/// Adapts a closure to tonic's `UnaryService`
+struct Unary<F>(Option<F>);
+
+impl<Req, Resp, F> UnaryService<Req> for Unary<F>
+where
+    F: FnOnce(Req) -> Result<Response<Resp>, Status>,
+{
+    type Response = Resp;
+    type Future = std::future::Ready<Result<Response<Resp>, Status>>;
+
+    fn call(&mut self, request: Request<Req>) -> Self::Future {
+        let handler = self.0.take().expect("unary handler called twice");
+        std::future::ready(handler(request.into_inner()))
+    }
+}
+
+/// Adapts a closure to tonic's `ServerStreamingService`
+struct Streaming<F>(Option<F>);
+
+impl<Req, Resp, F> ServerStreamingService<Req> for Streaming<F>
+where
+    Resp: Send + 'static,
+    F: FnOnce(Req) -> Result<BoxStream<'static, Result<Resp, Status>>, Status>,
+{
+    type Response = Resp;
+    type ResponseStream = BoxStream<'static, Result<Resp, Status>>;
+    type Future = std::future::Ready<Result<Response<Self::ResponseStream>, Status>>;
+
+    fn call(&mut self, request: Request<Req>) -> Self::Future {
+        let handler = self.0.take().expect("streaming handler called twice");
+        std::future::ready(handler(request.into_inner()).map(Response::new))
+    }
+}
+
+/// Adapts an async closure to tonic's `StreamingService`
+struct Bidi<F>(Option<F>);
+
+impl<Req, Resp, F, Fut> StreamingService<Req> for Bidi<F>
+where
+    Resp: Send + 'static,
+    F: FnOnce(tonic::Streaming<Req>) -> Fut,
+    Fut: Future<Output = Result<BoxStream<'static, Result<Resp, Status>>, Status>> + Send + 'static,
+{
+    type Response = Resp;
+    type ResponseStream = BoxStream<'static, Result<Resp, Status>>;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Self::ResponseStream>, Status>> + Send>>;
+
+    fn call(&mut self, request: Request<tonic::Streaming<Req>>) -> Self::Future {
+        let handler = self.0.take().expect("streaming handler called twice");
+        let reply = handler(request.into_inner());
+        Box::pin(async move { reply.await.map(Response::new) })
+    }
+}
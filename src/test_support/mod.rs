@@ -0,0 +1,5 @@
+//! Shared helpers for tests.
+
+mod fake_daemon;
+
+pub use fake_daemon::*;
@@ -0,0 +1,482 @@
+use std::collections::{BTreeSet, HashMap};
+
+use futures::StreamExt;
+use gpui::*;
+use gpui::prelude::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::Sizable;
+use gpui_component::Root;
+
+use crate::services::{
+    ComposeCommand, ComposeProgress, ComposeProject, ComposeStatus, ComposeStep, DaemonService, DependencyCondition,
+};
+use crate::theme::{colors, MONO_FONT};
+
+/// A compose project opened from disk, with up/down/pull and per-step progress,
+/// shown as a PopUp window
+pub struct ComposeWindow {
+    daemon_service: Entity<DaemonService>,
+    project: ComposeProject,
+    /// Profiles switched on
+    profiles: BTreeSet<String>,
+    /// The command in flight or last run
+    command: Option<ComposeCommand>,
+    running: bool,
+    /// Latest status of each step
+    progress: HashMap<ComposeStep, ComposeStatus>,
+    /// Failure to reload the file
+    error: Option<String>,
+    _task: Option<Task<()>>,
+}
+
+impl ComposeWindow {
+    pub fn new(daemon_service: Entity<DaemonService>, project: ComposeProject) -> Self {
+        Self {
+            daemon_service,
+            project,
+            profiles: BTreeSet::new(),
+            command: None,
+            running: false,
+            progress: HashMap::new(),
+            error: None,
+            _task: None,
+        }
+    }
+
+    fn services(&self) -> Vec<String> {
+        self.project.active_services(&self.profiles)
+    }
+
+    fn toggle_profile(&mut self, profile: String, cx: &mut Context<Self>) {
+        if !self.profiles.remove(&profile) {
+            self.profiles.insert(profile);
+        }
+        cx.notify();
+    }
+
+    /// Read the compose file again, e.g. after editing it
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        if self.running {
+            return;
+        }
+        match ComposeProject::load(&self.project.file) {
+            Ok(project) => {
+                tracing::info!("Reloaded compose project {}", project.name);
+                let profiles = project.profiles();
+                self.profiles.retain(|profile| profiles.contains(profile));
+                self.project = project;
+                self.progress.clear();
+                self.command = None;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+        cx.notify();
+    }
+
+    /// Confirm before taking the project down
+    fn confirm_down(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let message = format!("Take down {}?", self.project.name);
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &message,
+            Some("Its containers are stopped and removed, along with the networks it created. Volumes are kept."),
+            &["Down", "Cancel"],
+            cx,
+        );
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            if answer.await == Ok(0) {
+                this.update(cx, |this, cx| this.run(ComposeCommand::Down, cx)).ok();
+            }
+        })
+        .detach();
+    }
+
+    fn run(&mut self, command: ComposeCommand, cx: &mut Context<Self>) {
+        if self.running {
+            return;
+        }
+        // Down covers every service, whichever profiles started it
+        let services = match command {
+            ComposeCommand::Down => self.project.start_order(),
+            ComposeCommand::Up | ComposeCommand::Pull => self.services(),
+        };
+        let project = self.project.clone();
+        let mut updates = self.daemon_service.update(cx, |svc, cx| {
+            svc.run_compose(command, project, services, cx)
+        });
+
+        self.command = Some(command);
+        self.running = true;
+        self.progress.clear();
+        self._task = Some(cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            while let Some(update) = updates.next().await {
+                if this.update(cx, |this, cx| this.on_progress(update, cx)).is_err() {
+                    return;
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.running = false;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn on_progress(&mut self, update: ComposeProgress, cx: &mut Context<Self>) {
+        self.progress.insert(update.step, update.status);
+        cx.notify();
+    }
+
+    /// "Up finished: 3 done, 1 failed", once the command is over
+    fn summary(&self) -> Option<String> {
+        let command = self.command?;
+        if self.running {
+            return Some(format!("{}…", command.label()));
+        }
+        let count = |is: fn(&ComposeStatus) -> bool| self.progress.values().filter(|s| is(s)).count();
+        let done = count(|s| matches!(s, ComposeStatus::Done(_)));
+        let failed = count(|s| matches!(s, ComposeStatus::Failed(_)));
+        let skipped = count(|s| matches!(s, ComposeStatus::Skipped(_)));
+        let mut parts = vec![format!("{} done", done)];
+        if failed > 0 {
+            parts.push(format!("{} failed", failed));
+        }
+        if skipped > 0 {
+            parts.push(format!("{} skipped", skipped));
+        }
+        Some(format!("{} finished: {}", command.label(), parts.join(", ")))
+    }
+
+    fn close_window(&self, window: &mut Window, _cx: &mut Context<Self>) {
+        window.remove_window();
+    }
+}
+
+impl Render for ComposeWindow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Services a finished command touched stay listed after a profile is switched off
+        let active = self.services();
+        let services: Vec<String> = self
+            .project
+            .start_order()
+            .into_iter()
+            .filter(|name| active.contains(name) || self.progress.contains_key(&ComposeStep::Service(name.clone())))
+            .collect();
+        let networks: Vec<String> = self
+            .project
+            .networks_for(&services)
+            .into_iter()
+            .map(|network| network.name.clone())
+            .collect();
+
+        div()
+            .id("compose-window")
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(colors::background())
+            .text_color(colors::text())
+            .rounded_lg()
+            .border_1()
+            .border_color(colors::border())
+            .shadow_lg()
+            .child(self.render_title_bar(cx))
+            .child(
+                div()
+                    .id("compose-content")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .px_4()
+                    .py_2()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_family(MONO_FONT)
+                            .text_color(colors::text_secondary())
+                            .child(self.project.file.display().to_string()),
+                    )
+                    .when(!self.project.warnings.is_empty(), |el| {
+                        el.child(self.render_section_header("Warnings")).children(
+                            self.project.warnings.iter().map(|warning| {
+                                div()
+                                    .text_xs()
+                                    .text_color(colors::warning())
+                                    .child(warning.clone())
+                            }),
+                        )
+                    })
+                    .when(!self.project.profiles().is_empty(), |el| {
+                        el.child(self.render_section_header("Profiles"))
+                            .child(self.render_profiles(cx))
+                    })
+                    .child(self.render_section_header("Services"))
+                    .children(services.iter().map(|name| self.render_service_row(name)))
+                    .child(self.render_section_header("Networks"))
+                    .children(networks.iter().map(|name| {
+                        let status = self.progress.get(&ComposeStep::Network(name.clone()));
+                        self.render_step_row(name.clone(), None, status)
+                    })),
+            )
+            .child(self.render_footer(cx))
+    }
+}
+
+impl ComposeWindow {
+    fn render_title_bar(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .h(px(44.0))
+            .px_4()
+            .flex()
+            .items_center()
+            .justify_between()
+            .border_b_1()
+            .border_color(colors::border())
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors::text())
+                    .child(format!("Compose: {}", self.project.name)),
+            )
+            .child(
+                div()
+                    .id("close-button")
+                    .w(px(24.0))
+                    .h(px(24.0))
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded(px(4.0))
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.close_window(window, cx);
+                    }))
+                    .child(
+                        svg()
+                            .path("icons/close.svg")
+                            .size(px(16.0))
+                            .text_color(colors::text_secondary()),
+                    ),
+            )
+    }
+
+    fn render_section_header(&self, title: &'static str) -> impl IntoElement {
+        div()
+            .mt_4()
+            .mb_1()
+            .text_sm()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(colors::text())
+            .child(title)
+    }
+
+    /// A chip per profile; switched-on ones are highlighted
+    fn render_profiles(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_1()
+            .children(self.project.profiles().into_iter().map(|profile| {
+                let enabled = self.profiles.contains(&profile);
+                let label = profile.clone();
+                div()
+                    .id(SharedString::from(format!("profile-{}", profile)))
+                    .px_2()
+                    .py(px(2.0))
+                    .rounded(px(4.0))
+                    .text_xs()
+                    .border_1()
+                    .border_color(colors::border())
+                    .when(enabled, |el| el.bg(colors::selection()).text_color(colors::text()))
+                    .when(!enabled, |el| el.text_color(colors::text_secondary()))
+                    .when(!self.running, |el| {
+                        el.cursor_pointer()
+                            .hover(|el| el.bg(colors::hover()))
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.toggle_profile(profile.clone(), cx);
+                            }))
+                    })
+                    .child(label)
+            }))
+    }
+
+    fn render_service_row(&self, name: &str) -> impl IntoElement {
+        let service = &self.project.services[name];
+        let waits_for: Vec<String> = service
+            .depends_on
+            .iter()
+            .map(|dependency| match dependency.condition {
+                DependencyCondition::Started => dependency.service.clone(),
+                DependencyCondition::Healthy => format!("{} (healthy)", dependency.service),
+                DependencyCondition::CompletedSuccessfully => format!("{} (completed)", dependency.service),
+            })
+            .collect();
+        let mut detail = service.spec.image.clone();
+        if !waits_for.is_empty() {
+            detail.push_str(&format!(" · after {}", waits_for.join(", ")));
+        }
+        let status = self.progress.get(&ComposeStep::Service(name.to_string()));
+        self.render_step_row(name.to_string(), Some(detail), status)
+    }
+
+    /// Name (with detail underneath) on the left, status on the right
+    fn render_step_row(&self, name: String, detail: Option<String>, status: Option<&ComposeStatus>) -> impl IntoElement {
+        let (text, color) = match status {
+            None => (String::new(), colors::text_muted()),
+            Some(status @ ComposeStatus::Working(_)) => (status.message().to_string(), colors::accent()),
+            Some(status @ ComposeStatus::Done(_)) => (status.message().to_string(), colors::running()),
+            Some(status @ ComposeStatus::Failed(_)) => (status.message().to_string(), colors::error()),
+            Some(status @ ComposeStatus::Skipped(_)) => (status.message().to_string(), colors::text_secondary()),
+        };
+
+        div()
+            .py_2()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .border_b_1()
+            .border_color(colors::border())
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .min_w_0()
+                    .child(div().text_sm().text_color(colors::text()).child(name))
+                    .children(detail.map(|detail| {
+                        div()
+                            .text_xs()
+                            .text_color(colors::text_secondary())
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .whitespace_nowrap()
+                            .child(detail)
+                    })),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .max_w(px(220.0))
+                    .text_xs()
+                    .text_color(color)
+                    .child(text),
+            )
+    }
+
+    fn render_footer(&self, cx: &Context<Self>) -> impl IntoElement {
+        // gpui-component Button.on_click expects Fn(&ClickEvent, &mut Window, &mut App)
+        // so we need to use entity.update() pattern instead of cx.listener()
+        let entity = cx.entity();
+        let reload_entity = entity.clone();
+        let pull_entity = entity.clone();
+        let down_entity = entity.clone();
+        let up_entity = entity.clone();
+        let (message, color) = match &self.error {
+            Some(error) => (Some(error.clone()), colors::error()),
+            None => (self.summary(), colors::text_secondary()),
+        };
+
+        div()
+            .px_4()
+            .py_3()
+            .border_t_1()
+            .border_color(colors::border())
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_2()
+            .child(div().flex_1().text_xs().text_color(color).children(message))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        Button::new("reload-button")
+                            .ghost()
+                            .small()
+                            .disabled(self.running)
+                            .child("Reload")
+                            .on_click(move |_, _window, cx| {
+                                reload_entity.update(cx, |this, cx| this.reload(cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("pull-button")
+                            .ghost()
+                            .small()
+                            .disabled(self.running)
+                            .child("Pull")
+                            .on_click(move |_, _window, cx| {
+                                pull_entity.update(cx, |this, cx| this.run(ComposeCommand::Pull, cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("down-button")
+                            .ghost()
+                            .small()
+                            .disabled(self.running)
+                            .child("Down")
+                            .on_click(move |_, window, cx| {
+                                down_entity.update(cx, |this, cx| this.confirm_down(window, cx));
+                            }),
+                    )
+                    .child(
+                        Button::new("up-button")
+                            .primary()
+                            .small()
+                            .disabled(self.running)
+                            .child("Up")
+                            .on_click(move |_, _window, cx| {
+                                up_entity.update(cx, |this, cx| this.run(ComposeCommand::Up, cx));
+                            }),
+                    ),
+            )
+    }
+}
+
+/// Open a compose project's window as a PopUp window
+pub fn open_compose_window(
+    daemon_service: Entity<DaemonService>,
+    project: ComposeProject,
+    parent_bounds: Bounds<Pixels>,
+    cx: &mut App,
+) {
+    // Window size
+    let window_size = size(px(520.0), px(600.0));
+
+    // Calculate centered position relative to parent window
+    let x = parent_bounds.origin.x + (parent_bounds.size.width - window_size.width) / 2.0;
+    let y = parent_bounds.origin.y + (parent_bounds.size.height - window_size.height) / 2.0;
+
+    let bounds = Bounds {
+        origin: point(x, y),
+        size: window_size,
+    };
+
+    let window_options = WindowOptions {
+        kind: WindowKind::PopUp,
+        titlebar: None,
+        window_bounds: Some(WindowBounds::Windowed(bounds)),
+        focus: true,
+        show: true,
+        window_background: WindowBackgroundAppearance::Transparent,
+        ..Default::default()
+    };
+
+    let _ = cx.open_window(window_options, |window, cx| {
+        // Initialize gpui-component (required for Button)
+        gpui_component::init(cx);
+
+        let view = cx.new(|_| ComposeWindow::new(daemon_service, project));
+
+        // Wrap in Root - gpui-component requires Root to be the window's root view
+        cx.new(|cx| Root::new(view, window, cx))
+    });
+}
@@ -1,16 +1,27 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
 
-use arcbox_api::generated::ListContainersResponse;
+use arcbox_api::generated::{ContainerSummary, ListContainersResponse};
+use futures::StreamExt;
 use gpui::*;
 use gpui::prelude::*;
+use gpui_component::input::{Input, InputEvent, InputState};
 use gpui_component::tab::TabBar;
 use gpui_component::Sizable;
 
-use crate::components::LogViewer;
-use crate::models::ContainerViewModel;
-use crate::services::{DaemonService, ImageIconService, IconState};
+use crate::components::{
+    ContainerStatusBadge, FileBrowser, InspectView, LogViewer, Sparkline, TerminalStatus,
+    TerminalView,
+};
+use crate::models::{
+    format_bytes, ContainerAction, ContainerFilter, ContainerSort, ContainerState, ContainerViewModel, ProjectStatus,
+};
+use crate::services::{
+    BulkAction, BulkResult, Capability, ComposeProject, ContainerStats, DaemonError, DaemonOperation, DaemonService, ImageIconService,
+    IconState, ListViewState, Notification, NotificationCenter, SettingsStore, StatsHistory, STATS_HISTORY_LEN,
+};
 use crate::theme::{colors, Theme, MONO_FONT};
-use crate::views::open_new_container_dialog;
+use crate::views::{open_compose_window, open_new_container_dialog};
 
 /// Detail panel tab
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -20,15 +31,17 @@ pub enum DetailTab {
     Logs,
     Terminal,
     Files,
+    Stats,
 }
 
 impl DetailTab {
     /// All tabs in order
-    const ALL: [DetailTab; 4] = [
+    const ALL: [DetailTab; 5] = [
         DetailTab::Info,
         DetailTab::Logs,
         DetailTab::Terminal,
         DetailTab::Files,
+        DetailTab::Stats,
     ];
 
     /// Get label for the tab
@@ -38,6 +51,7 @@ impl DetailTab {
             DetailTab::Logs => "Logs",
             DetailTab::Terminal => "Terminal",
             DetailTab::Files => "Files",
+            DetailTab::Stats => "Stats",
         }
     }
 
@@ -65,6 +79,75 @@ impl Render for EmptyView {
     }
 }
 
+/// One shell in a container's Terminal tab
+struct TerminalTab {
+    /// Shown as "Shell N" until the shell sets a title
+    number: usize,
+    view: Entity<TerminalView>,
+}
+
+/// Shells open for one container, shown as sub-tabs
+#[derive(Default)]
+struct TerminalTabs {
+    tabs: Vec<TerminalTab>,
+    active: usize,
+    next_number: usize,
+}
+
+/// Signals offered by the Kill menu
+const KILL_SIGNALS: [&str; 7] = ["SIGKILL", "SIGTERM", "SIGINT", "SIGHUP", "SIGQUIT", "SIGUSR1", "SIGUSR2"];
+
+/// Open action menu for a container
+struct ActionMenu {
+    container_id: String,
+    /// Window position the menu is anchored at
+    position: Point<Pixels>,
+    /// Only list signals (the header's Kill button)
+    signals_only: bool,
+}
+
+/// Inline rename in the detail header
+struct RenameState {
+    container_id: String,
+    input: Entity<InputState>,
+    _subscription: Subscription,
+}
+
+/// The filter bar under the list header
+struct FilterBar {
+    input: Entity<InputState>,
+    _subscription: Subscription,
+}
+
+/// A bulk action in progress, or finished and not yet dismissed
+struct BulkProgress {
+    action: BulkAction,
+    total: usize,
+    succeeded: usize,
+    /// (container name, reason) for each failure
+    failures: Vec<(String, String)>,
+    /// Containers that refused a plain remove (still running), for Force Remove
+    conflicts: Vec<String>,
+    /// Names captured up front; removed containers leave the list
+    names: HashMap<String, String>,
+    _task: Task<()>,
+}
+
+impl BulkProgress {
+    fn finished(&self) -> usize {
+        self.succeeded + self.failures.len()
+    }
+
+    fn is_done(&self) -> bool {
+        self.finished() >= self.total
+    }
+}
+
+/// Settings key for this view's filter and sort
+const LIST_VIEW_KEY: &str = "containers";
+/// Wait for typing to pause before saving the filter
+const PERSIST_DELAY: Duration = Duration::from_millis(500);
+
 const LIST_MIN_WIDTH: f32 = 200.0;
 const LIST_MAX_WIDTH: f32 = 500.0;
 const LIST_DEFAULT_WIDTH: f32 = 340.0;
@@ -72,16 +155,50 @@ const LIST_DEFAULT_WIDTH: f32 = 340.0;
 /// Containers list view
 pub struct ContainersView {
     containers: Vec<ContainerViewModel>,
+    /// Container shown in the detail panel
     selected_id: Option<String>,
+    /// Every selected container, in the order picked (includes `selected_id`)
+    selection: Vec<String>,
+    /// Where a shift-click range starts
+    selection_anchor: Option<String>,
+    /// Compose project shown in the detail panel instead of a container
+    selected_project: Option<String>,
     expanded_groups: HashMap<String, bool>,
     active_tab: DetailTab,
     list_width: f32,
     daemon_service: Entity<DaemonService>,
+    /// Observation of the current daemon service (replaced on context switch)
+    _daemon_subscription: Subscription,
     icon_service: Entity<ImageIconService>,
     /// Loading state for container list
     is_loading: bool,
     /// Cached log viewers per container
     log_viewers: HashMap<String, Entity<LogViewer>>,
+    /// Merged log viewers per compose project
+    project_logs: HashMap<String, Entity<LogViewer>>,
+    /// Services being scaled, by `service_key`
+    scaling: HashMap<String, Task<()>>,
+    /// Open terminal sessions per container
+    terminals: HashMap<String, TerminalTabs>,
+    /// Cached file browsers per container
+    file_browsers: HashMap<String, Entity<FileBrowser>>,
+    /// Cached inspect views per container
+    inspect_views: HashMap<String, Entity<InspectView>>,
+    /// Recent resource usage per running container
+    stats: HashMap<String, StatsHistory>,
+    action_menu: Option<ActionMenu>,
+    rename: Option<RenameState>,
+    bulk: Option<BulkProgress>,
+    filter_bar: Option<FilterBar>,
+    /// Filter text as typed (kept while the bar is hidden at startup)
+    filter_text: String,
+    filter: ContainerFilter,
+    sort: ContainerSort,
+    sort_descending: bool,
+    /// Window position of the open sort menu
+    sort_menu: Option<Point<Pixels>>,
+    /// Pending save of the filter and sort
+    _persist_task: Option<Task<()>>,
 }
 
 impl ContainersView {
@@ -93,29 +210,110 @@ impl ContainersView {
         // Subscribe to icon service updates for re-rendering
         cx.observe(&icon_service, |_, _, cx| cx.notify()).detach();
 
-        // Subscribe to daemon service connection state changes
-        cx.observe(&daemon_service, |this, daemon, cx| {
-            if daemon.read(cx).is_connected() && this.is_loading {
-                // Request container list when connected
-                daemon.update(cx, |svc, cx| {
-                    svc.list_containers(true, cx);
-                });
-            }
-            cx.notify();
-        })
-        .detach();
+        let daemon_subscription = Self::observe_daemon(&daemon_service, cx);
+
+        let list_view = SettingsStore::global(cx)
+            .read(cx)
+            .settings()
+            .list_views
+            .get(LIST_VIEW_KEY)
+            .cloned()
+            .unwrap_or_default();
 
         Self {
             containers: Vec::new(),
             selected_id: None,
+            selection: Vec::new(),
+            selection_anchor: None,
+            selected_project: None,
             expanded_groups: HashMap::new(),
             active_tab: DetailTab::Info,
             list_width: LIST_DEFAULT_WIDTH,
             daemon_service,
+            _daemon_subscription: daemon_subscription,
             icon_service,
             is_loading: true,
             log_viewers: HashMap::new(),
+            project_logs: HashMap::new(),
+            scaling: HashMap::new(),
+            terminals: HashMap::new(),
+            file_browsers: HashMap::new(),
+            inspect_views: HashMap::new(),
+            stats: HashMap::new(),
+            action_menu: None,
+            rename: None,
+            bulk: None,
+            filter_bar: None,
+            filter: ContainerFilter::parse(&list_view.filter),
+            filter_text: list_view.filter,
+            sort: ContainerSort::from_key(&list_view.sort).unwrap_or_default(),
+            sort_descending: list_view.descending,
+            sort_menu: None,
+            _persist_task: None,
+        }
+    }
+
+    /// Subscribe to daemon service connection state changes
+    fn observe_daemon(daemon_service: &Entity<DaemonService>, cx: &mut Context<Self>) -> Subscription {
+        cx.observe(daemon_service, |this, daemon, cx| {
+            if daemon.read(cx).is_connected() && this.is_loading {
+                // Request container list when connected
+                daemon.update(cx, |svc, cx| {
+                    svc.list_containers(true, cx);
+                });
+            }
+            cx.notify();
+        })
+    }
+
+    /// Point the view at another daemon (context switch) and reload
+    pub fn set_daemon_service(&mut self, daemon_service: Entity<DaemonService>, cx: &mut Context<Self>) {
+        // Log streams belong to the old daemon
+        for id in self.log_viewers.keys() {
+            self.daemon_service.update(cx, |svc, _cx| {
+                svc.unsubscribe_logs(id);
+            });
+        }
+        self.log_viewers.clear();
+        for viewer in self.project_logs.values() {
+            let ids = viewer.read(cx).container_ids();
+            self.daemon_service.update(cx, |svc, _cx| {
+                for id in &ids {
+                    svc.unsubscribe_logs(id);
+                }
+            });
         }
+        self.project_logs.clear();
+        self.scaling.clear();
+        // Dropping the terminals closes their exec sessions
+        self.terminals.clear();
+        self.file_browsers.clear();
+        self.inspect_views.clear();
+        // Stats streams belong to the old daemon too
+        self.daemon_service.update(cx, |svc, cx| {
+            svc.watch_stats(Vec::new(), cx);
+        });
+        self.stats.clear();
+        self.action_menu = None;
+        self.rename = None;
+        self.bulk = None;
+
+        self._daemon_subscription = Self::observe_daemon(&daemon_service, cx);
+        self.daemon_service = daemon_service;
+        self.containers.clear();
+        self.expanded_groups.clear();
+        self.selected_id = None;
+        self.selection.clear();
+        self.selection_anchor = None;
+        self.selected_project = None;
+        self.is_loading = true;
+
+        if self.daemon_service.read(cx).is_connected() {
+            self.daemon_service.update(cx, |svc, cx| {
+                svc.list_containers(true, cx);
+            });
+        }
+        cx.notify();
     }
 
     /// Show the new container dialog as a PopUp window
@@ -134,6 +332,45 @@ impl ContainersView {
         .detach();
     }
 
+    /// Pick a compose file or project folder and open it in a compose window
+    fn open_compose_project(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: true,
+            multiple: false,
+            prompt: Some("Open".into()),
+        });
+        let daemon_service = self.daemon_service.clone();
+        let parent_bounds = window.bounds();
+
+        cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let Ok(Ok(Some(paths))) = paths.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let result = cx
+                .background_executor()
+                .spawn(async move { ComposeProject::load(&path) })
+                .await;
+            cx.update(move |cx| match result {
+                Ok(project) => {
+                    tracing::info!("Opened compose project {} from {}", project.name, project.file.display());
+                    open_compose_window(daemon_service, project, parent_bounds, cx);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to open compose project: {}", e);
+                    NotificationCenter::global(cx).update(cx, |center, cx| {
+                        center.push(Notification::error("Failed to open compose project").message(e), cx);
+                    });
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
     /// Handle containers loaded from daemon
     pub fn on_containers_loaded(&mut self, response: ListContainersResponse, cx: &mut Context<Self>) {
         self.is_loading = false;
@@ -142,6 +379,30 @@ impl ContainersView {
             .into_iter()
             .map(ContainerViewModel::from)
             .collect();
+        for container in &mut self.containers {
+            if let Some(stats) = self.stats.get(&container.id).and_then(|h| h.latest()) {
+                apply_stats(container, stats);
+            }
+        }
+
+        // Drop log viewers (and their streams) for containers that no longer exist
+        let containers = &self.containers;
+        let stale: Vec<String> = self
+            .log_viewers
+            .keys()
+            .filter(|id| !containers.iter().any(|c| &c.id == *id))
+            .cloned()
+            .collect();
+        for id in stale {
+            self.log_viewers.remove(&id);
+            self.daemon_service.update(cx, |svc, _cx| {
+                svc.unsubscribe_logs(&id);
+            });
+        }
+        self.terminals.retain(|id, _| containers.iter().any(|c| &c.id == id));
+        self.file_browsers.retain(|id, _| containers.iter().any(|c| &c.id == id));
+        self.inspect_views.retain(|id, _| containers.iter().any(|c| &c.id == id));
+        self.selection.retain(|id| containers.iter().any(|c| &c.id == id));
 
         // Update expanded groups
         self.expanded_groups.clear();
@@ -159,9 +420,179 @@ impl ContainersView {
             });
         }
 
+        self.sync_stats(cx);
+        self.sync_project_logs(cx);
+        cx.notify();
+    }
+
+    /// Insert or replace a single container (from the daemon event stream)
+    pub fn on_container_updated(&mut self, summary: ContainerSummary, cx: &mut Context<Self>) {
+        let mut container = ContainerViewModel::from(summary);
+        if let Some(stats) = self.stats.get(&container.id).and_then(|h| h.latest()) {
+            apply_stats(&mut container, stats);
+        }
+
+        if let Some(ref project) = container.compose_project {
+            self.expanded_groups.entry(project.clone()).or_insert(true);
+        }
+
+        let repo = Self::extract_repository(&container.image);
+        self.icon_service.update(cx, |svc, cx| {
+            svc.get_icon(&repo, cx);
+        });
+
+        let id = container.id.clone();
+        match self.containers.iter_mut().find(|c| c.id == id) {
+            Some(existing) => *existing = container,
+            None => self.containers.push(container),
+        }
+        self.refresh_inspect(&id, cx);
+        self.sync_stats(cx);
+        self.sync_project_logs(cx);
         cx.notify();
     }
 
+    /// Update a container's state in place (from the daemon event stream)
+    pub fn on_container_state_changed(&mut self, id: &str, state: &str, cx: &mut Context<Self>) {
+        if let Some(container) = self.containers.iter_mut().find(|c| c.id == id) {
+            container.state = ContainerState::from_docker_state(state);
+            self.refresh_inspect(id, cx);
+            self.sync_stats(cx);
+            cx.notify();
+        }
+    }
+
+    /// Drop a destroyed container (from the daemon event stream)
+    pub fn on_container_destroyed(&mut self, id: &str, cx: &mut Context<Self>) {
+        self.containers.retain(|c| c.id != id);
+        if self.selected_id.as_deref() == Some(id) {
+            self.selected_id = None;
+        }
+        self.selection.retain(|s| s != id);
+        if self.log_viewers.remove(id).is_some() {
+            self.daemon_service.update(cx, |svc, _cx| {
+                svc.unsubscribe_logs(id);
+            });
+        }
+        self.terminals.remove(id);
+        self.file_browsers.remove(id);
+        self.inspect_views.remove(id);
+        self.sync_stats(cx);
+        self.sync_project_logs(cx);
+        cx.notify();
+    }
+
+    /// Point merged project logs at the projects' current containers, and
+    /// drop those of projects that are gone
+    fn sync_project_logs(&mut self, cx: &mut Context<Self>) {
+        if self
+            .selected_project
+            .as_ref()
+            .is_some_and(|project| self.project_containers(project).is_empty())
+        {
+            self.selected_project = None;
+        }
+
+        let projects: Vec<String> = self.project_logs.keys().cloned().collect();
+        for project in projects {
+            let containers = self.log_origins(&project);
+            let viewer = self.project_logs[&project].clone();
+            let gone: Vec<String> = viewer
+                .read(cx)
+                .container_ids()
+                .into_iter()
+                .filter(|id| !containers.contains_key(id) && !self.log_viewers.contains_key(id))
+                .collect();
+            self.daemon_service.update(cx, |svc, _cx| {
+                for id in &gone {
+                    svc.unsubscribe_logs(id);
+                }
+            });
+            if containers.is_empty() {
+                self.project_logs.remove(&project);
+            } else {
+                viewer.update(cx, |viewer, cx| viewer.set_containers(containers, cx));
+            }
+        }
+    }
+
+    /// Re-fetch the inspect document if the container's Info tab has been opened
+    fn refresh_inspect(&mut self, id: &str, cx: &mut Context<Self>) {
+        if let Some(view) = self.inspect_views.get(id) {
+            view.update(cx, |view, cx| view.refresh(cx));
+        }
+    }
+
+    /// Record a stats sample (from the daemon stats stream)
+    pub fn on_stats_received(&mut self, id: &str, stats: ContainerStats, cx: &mut Context<Self>) {
+        let Some(container) = self.containers.iter_mut().find(|c| c.id == id) else {
+            return;
+        };
+        apply_stats(container, &stats);
+        self.stats.entry(id.to_string()).or_default().push(stats);
+        cx.notify();
+    }
+
+    /// Stream stats for the running containers and forget the rest
+    fn sync_stats(&mut self, cx: &mut Context<Self>) {
+        let running: Vec<String> = self
+            .containers
+            .iter()
+            .filter(|c| c.is_running())
+            .map(|c| c.id.clone())
+            .collect();
+        self.stats.retain(|id, _| running.contains(id));
+        self.daemon_service.update(cx, |svc, cx| {
+            svc.watch_stats(running, cx);
+        });
+    }
+
+    /// React to a failed container operation
+    pub fn on_operation_failed(&mut self, error: &DaemonError, cx: &mut Context<Self>) {
+        match (error, error.operation()) {
+            (DaemonError::Unavailable { .. }, _) => {
+                // Reconnecting; the list is refreshed once the daemon is back
+            }
+            (_, DaemonOperation::ListContainers) => {
+                self.is_loading = false;
+                cx.notify();
+            }
+            (
+                DaemonError::NotFound { .. },
+                DaemonOperation::StartContainer
+                | DaemonOperation::StopContainer
+                | DaemonOperation::RemoveContainer
+                | DaemonOperation::PauseContainer
+                | DaemonOperation::UnpauseContainer
+                | DaemonOperation::RestartContainer
+                | DaemonOperation::KillContainer
+                | DaemonOperation::RenameContainer
+                | DaemonOperation::ContainerLogs
+                | DaemonOperation::Exec,
+            ) => {
+                // Removed behind our back; drop the stale row
+                if let Some(id) = error.resource() {
+                    self.on_container_destroyed(id, cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Display name for a container, if it is in the list
+    pub fn container_name(&self, id: &str) -> Option<String> {
+        self.containers
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.name.clone())
+    }
+
+    /// Select a container and switch to its Logs tab
+    pub fn show_logs(&mut self, id: &str, cx: &mut Context<Self>) {
+        self.active_tab = DetailTab::Logs;
+        self.select_container(id.to_string(), cx);
+    }
+
     /// Refresh container list from daemon
     pub fn refresh(&mut self, cx: &mut Context<Self>) {
         self.is_loading = true;
@@ -188,11 +619,438 @@ impl ContainersView {
         cx.notify();
     }
 
+    /// Select just this container
     fn select_container(&mut self, id: String, cx: &mut Context<Self>) {
+        self.selected_project = None;
+        self.selection = vec![id.clone()];
+        self.selection_anchor = Some(id.clone());
         self.selected_id = Some(id);
         cx.notify();
     }
 
+    /// Plain click selects one row, cmd/ctrl-click toggles one, shift-click extends from the anchor
+    fn click_container(&mut self, id: String, modifiers: Modifiers, cx: &mut Context<Self>) {
+        self.selected_project = None;
+        if modifiers.shift {
+            let order = self.visible_order();
+            let anchor = self.selection_anchor.as_ref().unwrap_or(&id);
+            let position = |id: &String| order.iter().position(|o| o == id);
+            if let (Some(from), Some(to)) = (position(anchor), position(&id)) {
+                self.selection = order[from.min(to)..=from.max(to)].to_vec();
+                self.selected_id = Some(id);
+                cx.notify();
+                return;
+            }
+        } else if modifiers.secondary() {
+            if let Some(index) = self.selection.iter().position(|s| *s == id) {
+                self.selection.remove(index);
+                if self.selected_id.as_ref() == Some(&id) {
+                    self.selected_id = self.selection.last().cloned();
+                }
+            } else {
+                self.selection.push(id.clone());
+                self.selected_id = Some(id.clone());
+            }
+            self.selection_anchor = Some(id);
+            cx.notify();
+            return;
+        }
+        self.select_container(id, cx);
+    }
+
+    /// Select every container shown in a compose project
+    fn select_group(&mut self, project: &str, cx: &mut Context<Self>) {
+        let (compose_groups, _) = self.grouped();
+        self.selection = compose_groups
+            .get(project)
+            .into_iter()
+            .flatten()
+            .map(|c| c.id.clone())
+            .collect();
+        self.selection_anchor = self.selection.first().cloned();
+        self.selected_id = self.selection.first().cloned();
+        self.expanded_groups.insert(project.to_string(), true);
+        cx.notify();
+    }
+
+    /// Drop back to just the container in the detail panel
+    fn clear_selection(&mut self, cx: &mut Context<Self>) {
+        self.selection = self.selected_id.iter().cloned().collect();
+        self.selection_anchor = self.selected_id.clone();
+        cx.notify();
+    }
+
+    /// Show a compose project in the detail panel
+    fn select_project(&mut self, project: String, cx: &mut Context<Self>) {
+        self.expanded_groups.insert(project.clone(), true);
+        self.selected_project = Some(project);
+        cx.notify();
+    }
+
+    /// Every container in a compose project, filtered or not
+    fn project_containers(&self, project: &str) -> Vec<&ContainerViewModel> {
+        self.containers
+            .iter()
+            .filter(|c| c.compose_project.as_deref() == Some(project))
+            .collect()
+    }
+
+    /// A project's containers by compose service, replicas in number order
+    fn project_services(&self, project: &str) -> BTreeMap<String, Vec<&ContainerViewModel>> {
+        let mut services: BTreeMap<String, Vec<&ContainerViewModel>> = BTreeMap::new();
+        for container in self.project_containers(project) {
+            let service = container.compose_service().unwrap_or(&container.name);
+            services.entry(service.to_string()).or_default().push(container);
+        }
+        for replicas in services.values_mut() {
+            replicas.sort_by_key(|c| c.compose_number());
+        }
+        services
+    }
+
+    /// How each of a project's containers is tagged in its merged logs:
+    /// the service, with the replica number when there are several
+    fn log_origins(&self, project: &str) -> HashMap<String, String> {
+        self.project_services(project)
+            .into_iter()
+            .flat_map(|(service, replicas)| {
+                let numbered = replicas.len() > 1;
+                replicas.into_iter().map(move |c| {
+                    let origin = match c.compose_number() {
+                        Some(number) if numbered => format!("{}-{}", service, number),
+                        _ => service.clone(),
+                    };
+                    (c.id.clone(), origin)
+                })
+            })
+            .collect()
+    }
+
+    /// Open the compose files a project was started from
+    fn open_compose_file(&mut self, project: &str, cx: &mut Context<Self>) {
+        let files = self
+            .project_containers(project)
+            .iter()
+            .map(|c| c.compose_config_files())
+            .find(|files| !files.is_empty())
+            .unwrap_or_default();
+        let notification = if files.is_empty() {
+            Notification::warning(format!("No compose file recorded for {}", project))
+                .message("The project's containers don't say which file they came from.")
+        } else if let Some(missing) = files.iter().find(|file| !file.exists()) {
+            Notification::error("Compose file not found")
+                .message(format!("{} isn't on this machine; it may be on the daemon's host.", missing.display()))
+        } else {
+            for file in &files {
+                cx.open_with_system(file);
+            }
+            return;
+        };
+        NotificationCenter::global(cx).update(cx, |center, cx| {
+            center.push(notification, cx);
+        });
+    }
+
+    /// Run `count` replicas of a project's service
+    fn scale_service(&mut self, project: &str, service: &str, count: usize, cx: &mut Context<Self>) {
+        let key = service_key(project, service);
+        if self.scaling.contains_key(&key) {
+            return;
+        }
+        let replicas: Vec<ContainerViewModel> = self
+            .project_services(project)
+            .remove(service)
+            .unwrap_or_default()
+            .into_iter()
+            .cloned()
+            .collect();
+        if replicas.is_empty() || replicas.len() == count {
+            return;
+        }
+
+        let task = self.daemon_service.update(cx, |svc, cx| svc.scale_service(replicas, count, cx));
+        let service = service.to_string();
+        let task_key = key.clone();
+        let task = cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            cx.update(|cx| {
+                this.update(cx, |this, cx| {
+                    this.scaling.remove(&task_key);
+                    cx.notify();
+                })
+                .ok();
+                if let Err(error) = result {
+                    let notification = Notification::error(format!("Couldn't scale {}", service))
+                        .message(error.message().to_string());
+                    NotificationCenter::global(cx).update(cx, |center, cx| {
+                        center.push(notification, cx);
+                    });
+                }
+            })
+            .ok();
+        });
+        self.scaling.insert(key, task);
+        cx.notify();
+    }
+
+    /// Containers passing the filter by compose project (by name), then
+    /// standalone ones, each in the chosen sort order
+    fn grouped(&self) -> (BTreeMap<String, Vec<&ContainerViewModel>>, Vec<&ContainerViewModel>) {
+        let mut compose_groups: BTreeMap<String, Vec<&ContainerViewModel>> = BTreeMap::new();
+        let mut standalone: Vec<&ContainerViewModel> = Vec::new();
+
+        for container in self.containers.iter().filter(|c| self.filter.matches(c)) {
+            if let Some(ref project) = container.compose_project {
+                compose_groups
+                    .entry(project.clone())
+                    .or_default()
+                    .push(container);
+            } else {
+                standalone.push(container);
+            }
+        }
+
+        let compare = |a: &&ContainerViewModel, b: &&ContainerViewModel| {
+            let ordering = self.sort.compare(a, b);
+            if self.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        };
+        for containers in compose_groups.values_mut() {
+            containers.sort_by(compare);
+        }
+        standalone.sort_by(compare);
+        (compose_groups, standalone)
+    }
+
+    /// Show the filter bar and focus it, or hide it and clear the filter
+    fn toggle_filter_bar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.filter_bar.take().is_some() {
+            self.set_filter(String::new(), cx);
+            return;
+        }
+        self.open_filter_bar(window, cx);
+        if let Some(bar) = &self.filter_bar {
+            bar.input.read(cx).focus_handle(cx).focus(window);
+        }
+    }
+
+    fn open_filter_bar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Filter: name, status:running, label:k=v, port:8080")
+                .default_value(self.filter_text.clone())
+        });
+        let subscription = cx.subscribe(&input, |this, input, event: &InputEvent, cx| {
+            if let InputEvent::Change { .. } = event {
+                let text = input.read(cx).value().to_string();
+                this.set_filter(text, cx);
+            }
+        });
+        self.filter_bar = Some(FilterBar {
+            input,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    /// Empty the filter box, keeping the bar open
+    fn clear_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(bar) = &self.filter_bar {
+            bar.input.update(cx, |input, cx| input.set_value("", window, cx));
+        }
+        self.set_filter(String::new(), cx);
+    }
+
+    fn set_filter(&mut self, text: String, cx: &mut Context<Self>) {
+        if text == self.filter_text {
+            return;
+        }
+        self.filter = ContainerFilter::parse(&text);
+        self.filter_text = text;
+        self.persist_list_view(cx);
+        cx.notify();
+    }
+
+    fn set_sort(&mut self, sort: ContainerSort, descending: bool, cx: &mut Context<Self>) {
+        self.sort = sort;
+        self.sort_descending = descending;
+        self.sort_menu = None;
+        self.persist_list_view(cx);
+        cx.notify();
+    }
+
+    /// Save the filter and sort once typing pauses
+    fn persist_list_view(&mut self, cx: &mut Context<Self>) {
+        let state = ListViewState {
+            filter: self.filter_text.clone(),
+            sort: self.sort.key().to_string(),
+            descending: self.sort_descending,
+        };
+        // Replacing the task cancels the previous pending save
+        self._persist_task = Some(cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            cx.background_executor().timer(PERSIST_DELAY).await;
+            cx.update(|cx| {
+                SettingsStore::global(cx).update(cx, |store, cx| {
+                    store.set_list_view(LIST_VIEW_KEY, state, cx);
+                });
+            })
+            .ok();
+        }));
+    }
+
+    fn open_sort_menu(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        self.sort_menu = Some(position);
+        cx.notify();
+    }
+
+    fn close_sort_menu(&mut self, cx: &mut Context<Self>) {
+        if self.sort_menu.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Container IDs in the order rows are shown, skipping collapsed groups
+    fn visible_order(&self) -> Vec<String> {
+        let (compose_groups, standalone) = self.grouped();
+        compose_groups
+            .iter()
+            .filter(|(project, _)| self.expanded_groups.get(*project).copied().unwrap_or(true))
+            .flat_map(|(_, containers)| containers.iter())
+            .chain(standalone.iter())
+            .map(|c| c.id.clone())
+            .collect()
+    }
+
+    /// Selected containers that `action` applies to
+    fn bulk_targets(&self, action: BulkAction) -> Vec<&ContainerViewModel> {
+        action_targets(action, self.containers.iter().filter(|c| self.selection.contains(&c.id)))
+    }
+
+    /// Ask once, then run `action` on the selection
+    fn confirm_bulk_action(&mut self, action: BulkAction, window: &mut Window, cx: &mut Context<Self>) {
+        let targets = self.bulk_targets(action);
+        let message = format!("{} {}?", action.label(), count_containers(targets.len()));
+        self.confirm_action_on(action, message, targets, window, cx);
+    }
+
+    /// Ask once, then run `action` on every container of a compose project
+    fn confirm_project_action(&mut self, project: &str, action: BulkAction, window: &mut Window, cx: &mut Context<Self>) {
+        let targets = action_targets(action, self.project_containers(project).into_iter());
+        let message = format!("{} {} in \"{}\"?", action.label(), count_containers(targets.len()), project);
+        self.confirm_action_on(action, message, targets, window, cx);
+    }
+
+    /// Confirm `message`, listing the targets, then run `action` on them
+    fn confirm_action_on(
+        &self,
+        action: BulkAction,
+        message: String,
+        targets: Vec<&ContainerViewModel>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if targets.is_empty() || self.bulk.as_ref().is_some_and(|bulk| !bulk.is_done()) {
+            return;
+        }
+        let ids: Vec<String> = targets.iter().map(|c| c.id.clone()).collect();
+
+        const LISTED: usize = 10;
+        let mut detail = targets
+            .iter()
+            .take(LISTED)
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if targets.len() > LISTED {
+            detail.push_str(&format!("\nand {} more", targets.len() - LISTED));
+        }
+        if action == BulkAction::Remove {
+            detail.push_str("\n\nRunning containers are left alone and can be force-removed afterwards. This can't be undone.");
+        }
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &message,
+            Some(&detail),
+            &[action.label(), "Cancel"],
+            cx,
+        );
+
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            if answer.await == Ok(0) {
+                this.update(cx, |this, cx| this.run_bulk_action(action, ids, cx)).ok();
+            }
+        })
+        .detach();
+    }
+
+    fn run_bulk_action(&mut self, action: BulkAction, ids: Vec<String>, cx: &mut Context<Self>) {
+        tracing::info!("{} {}", action.progressive(), count_containers(ids.len()));
+        let names = ids
+            .iter()
+            .map(|id| (id.clone(), self.container_name(id).unwrap_or_else(|| id.clone())))
+            .collect();
+        let total = ids.len();
+        let mut results = self.daemon_service.update(cx, |svc, cx| {
+            svc.bulk_container_action(action, ids, cx)
+        });
+        let task = cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            while let Some(result) = results.next().await {
+                if this.update(cx, |this, cx| this.on_bulk_result(result, cx)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.bulk = Some(BulkProgress {
+            action,
+            total,
+            succeeded: 0,
+            failures: Vec::new(),
+            conflicts: Vec::new(),
+            names,
+            _task: task,
+        });
+        cx.notify();
+    }
+
+    fn on_bulk_result(&mut self, result: BulkResult, cx: &mut Context<Self>) {
+        let Some(bulk) = self.bulk.as_mut() else {
+            return;
+        };
+        match result.result {
+            Ok(()) => bulk.succeeded += 1,
+            Err(error) => {
+                if bulk.action == BulkAction::Remove && matches!(error, DaemonError::Conflict { .. }) {
+                    bulk.conflicts.push(result.id.clone());
+                }
+                let name = bulk.names.get(&result.id).cloned().unwrap_or(result.id);
+                bulk.failures.push((name, error.message().to_string()));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Force-remove the containers a finished Remove couldn't
+    fn force_remove_conflicts(&mut self, cx: &mut Context<Self>) {
+        let Some(bulk) = self.bulk.as_ref().filter(|bulk| bulk.is_done()) else {
+            return;
+        };
+        let ids = bulk.conflicts.clone();
+        if !ids.is_empty() {
+            self.run_bulk_action(BulkAction::ForceRemove, ids, cx);
+        }
+    }
+
+    fn dismiss_bulk(&mut self, cx: &mut Context<Self>) {
+        if self.bulk.as_ref().is_some_and(BulkProgress::is_done) {
+            self.bulk = None;
+            cx.notify();
+        }
+    }
+
     fn set_tab(&mut self, tab: DetailTab, cx: &mut Context<Self>) {
         self.active_tab = tab;
         cx.notify();
@@ -222,6 +1080,136 @@ impl ContainersView {
         });
     }
 
+    /// Run a lifecycle action from the row menu or detail header
+    fn run_action(&mut self, id: &str, action: ContainerAction, window: &mut Window, cx: &mut Context<Self>) {
+        self.action_menu = None;
+        let container_id = id.to_string();
+        match action {
+            ContainerAction::Start => self.start_container(id, cx),
+            ContainerAction::Stop => self.stop_container(id, cx),
+            ContainerAction::Pause => {
+                tracing::info!("Pausing container: {}", id);
+                self.daemon_service.update(cx, |svc, cx| svc.pause_container(container_id, cx));
+            }
+            ContainerAction::Unpause => {
+                tracing::info!("Unpausing container: {}", id);
+                self.daemon_service.update(cx, |svc, cx| svc.unpause_container(container_id, cx));
+            }
+            ContainerAction::Restart => {
+                tracing::info!("Restarting container: {}", id);
+                self.daemon_service.update(cx, |svc, cx| svc.restart_container(container_id, 10, cx));
+            }
+            // Without a chosen signal, like `docker kill`
+            ContainerAction::Kill => self.kill_container(id, "SIGKILL", cx),
+            ContainerAction::Rename => self.start_rename(id, window, cx),
+            ContainerAction::Remove => self.remove_container(id, cx),
+        }
+        cx.notify();
+    }
+
+    fn kill_container(&mut self, id: &str, signal: &str, cx: &mut Context<Self>) {
+        tracing::info!("Sending {} to container: {}", signal, id);
+        self.action_menu = None;
+        let id = id.to_string();
+        let signal = signal.to_string();
+        self.daemon_service.update(cx, |svc, cx| {
+            svc.kill_container(id, signal, cx);
+        });
+        cx.notify();
+    }
+
+    fn open_action_menu(&mut self, id: String, position: Point<Pixels>, signals_only: bool, cx: &mut Context<Self>) {
+        self.action_menu = Some(ActionMenu {
+            container_id: id,
+            position,
+            signals_only,
+        });
+        cx.notify();
+    }
+
+    fn close_action_menu(&mut self, cx: &mut Context<Self>) {
+        if self.action_menu.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Select a container and edit its name in the detail header
+    fn start_rename(&mut self, id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.container_name(id).unwrap_or_default();
+        let input = cx.new(|cx| InputState::new(window, cx).default_value(name));
+        let subscription = cx.subscribe(&input, |this, _, event: &InputEvent, cx| {
+            if let InputEvent::PressEnter { .. } = event {
+                this.commit_rename(cx);
+            }
+        });
+        input.read(cx).focus_handle(cx).focus(window);
+
+        self.select_container(id.to_string(), cx);
+        self.rename = Some(RenameState {
+            container_id: id.to_string(),
+            input,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    fn commit_rename(&mut self, cx: &mut Context<Self>) {
+        let Some(rename) = self.rename.take() else {
+            return;
+        };
+        let name = rename.input.read(cx).value().trim().to_string();
+        if !name.is_empty() && self.container_name(&rename.container_id).as_deref() != Some(name.as_str()) {
+            tracing::info!("Renaming container {} to {}", rename.container_id, name);
+            self.daemon_service.update(cx, |svc, cx| {
+                svc.rename_container(rename.container_id, name, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    fn cancel_rename(&mut self, cx: &mut Context<Self>) {
+        if self.rename.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Open another shell in a container and switch to it
+    fn open_terminal(&mut self, id: &str, cx: &mut Context<Self>) {
+        let container_id = id.to_string();
+        let daemon_service = self.daemon_service.clone();
+        let view = cx.new(|cx| TerminalView::new(container_id, daemon_service, cx));
+
+        let terminals = self.terminals.entry(id.to_string()).or_default();
+        terminals.next_number += 1;
+        terminals.tabs.push(TerminalTab {
+            number: terminals.next_number,
+            view,
+        });
+        terminals.active = terminals.tabs.len() - 1;
+        cx.notify();
+    }
+
+    /// Close a shell; its exec session ends with it
+    fn close_terminal(&mut self, id: &str, index: usize, cx: &mut Context<Self>) {
+        let Some(terminals) = self.terminals.get_mut(id) else {
+            return;
+        };
+        if index < terminals.tabs.len() {
+            terminals.tabs.remove(index);
+            if terminals.active >= index && terminals.active > 0 {
+                terminals.active -= 1;
+            }
+        }
+        cx.notify();
+    }
+
+    fn select_terminal(&mut self, id: &str, index: usize, cx: &mut Context<Self>) {
+        if let Some(terminals) = self.terminals.get_mut(id) {
+            terminals.active = index.min(terminals.tabs.len().saturating_sub(1));
+            cx.notify();
+        }
+    }
+
     fn get_selected_container(&self) -> Option<&ContainerViewModel> {
         self.selected_id
             .as_ref()
@@ -230,7 +1218,19 @@ impl ContainersView {
 }
 
 impl Render for ContainersView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Create inspect view for selected container if on Info tab
+        if self.active_tab == DetailTab::Info {
+            if let Some(ref id) = self.selected_id {
+                if !self.inspect_views.contains_key(id) {
+                    let container_id = id.clone();
+                    let daemon_service = self.daemon_service.clone();
+                    let view = cx.new(|cx| InspectView::new(container_id, daemon_service, window, cx));
+                    self.inspect_views.insert(id.clone(), view);
+                }
+            }
+        }
+
         // Ensure LogViewer exists for selected container when on Logs tab
         if self.active_tab == DetailTab::Logs {
             if let Some(ref id) = self.selected_id {
@@ -243,22 +1243,49 @@ impl Render for ContainersView {
             }
         }
 
+        // Merged logs for the selected compose project
+        if let Some(project) = self.selected_project.clone() {
+            if !self.project_logs.contains_key(&project) {
+                let containers = self.log_origins(&project);
+                let daemon_service = self.daemon_service.clone();
+                let viewer = cx.new(|cx| LogViewer::merged(containers, daemon_service, cx));
+                self.project_logs.insert(project, viewer);
+            }
+        }
+
+        // Create file browser for selected container if on Files tab (stopped ones say why they can't be browsed)
+        if self.active_tab == DetailTab::Files {
+            if let Some(ref id) = self.selected_id {
+                if !self.file_browsers.contains_key(id) {
+                    let container_id = id.clone();
+                    let daemon_service = self.daemon_service.clone();
+                    let browser = cx.new(|cx| FileBrowser::new(container_id, daemon_service, cx));
+                    self.file_browsers.insert(id.clone(), browser);
+                }
+            }
+        }
+
+        // Open the first shell when the Terminal tab is shown for a running container
+        if self.active_tab == DetailTab::Terminal {
+            let needs_shell = self
+                .get_selected_container()
+                .filter(|c| c.is_running() && !self.terminals.contains_key(&c.id))
+                .map(|c| c.id.clone());
+            if let Some(id) = needs_shell {
+                self.open_terminal(&id, cx);
+            }
+        }
+
+        // Bring back a remembered filter
+        if self.filter_bar.is_none() && !self.filter_text.is_empty() {
+            self.open_filter_bar(window, cx);
+        }
+
         let running_count = self.containers.iter().filter(|c| c.is_running()).count();
 
         // Group containers
-        let mut compose_groups: HashMap<String, Vec<&ContainerViewModel>> = HashMap::new();
-        let mut standalone: Vec<&ContainerViewModel> = Vec::new();
-
-        for container in &self.containers {
-            if let Some(ref project) = container.compose_project {
-                compose_groups
-                    .entry(project.clone())
-                    .or_default()
-                    .push(container);
-            } else {
-                standalone.push(container);
-            }
-        }
+        let (compose_groups, standalone) = self.grouped();
+        let no_matches = !self.containers.is_empty() && compose_groups.is_empty() && standalone.is_empty();
 
         let list_width = self.list_width;
         // Sidebar width for offset calculation
@@ -324,9 +1351,43 @@ impl Render for ContainersView {
                                                     .text_color(colors::text_secondary())
                                             ),
                                     )
+                                    .child(
+                                        Theme::button_icon()
+                                            .id("open-compose-project")
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.open_compose_project(window, cx);
+                                            }))
+                                            .child(
+                                                svg()
+                                                    .path("icons/layer.svg")
+                                                    .size(px(16.0))
+                                                    .text_color(colors::text_secondary())
+                                            ),
+                                    )
+                                    .child(
+                                        Theme::button_icon()
+                                            .id("sort-containers")
+                                            .on_mouse_down(
+                                                MouseButton::Left,
+                                                cx.listener(|this, event: &MouseDownEvent, _window, cx| {
+                                                    cx.stop_propagation();
+                                                    this.open_sort_menu(event.position, cx);
+                                                }),
+                                            )
+                                            .child(
+                                                svg()
+                                                    .path("icons/sort.svg")
+                                                    .size(px(16.0))
+                                                    .text_color(colors::text_secondary())
+                                            ),
+                                    )
                                     .child(
                                         Theme::button_icon()
                                             .id("search-containers")
+                                            .when(self.filter_bar.is_some(), |el| el.bg(colors::selection()))
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.toggle_filter_bar(window, cx);
+                                            }))
                                             .child(
                                                 svg()
                                                     .path("icons/search.svg")
@@ -336,6 +1397,17 @@ impl Render for ContainersView {
                                     ),
                             ),
                     )
+                    .when_some(self.filter_bar.as_ref(), |el, bar| {
+                        el.child(
+                            div()
+                                .px_3()
+                                .py_2()
+                                .border_b_1()
+                                .border_color(colors::border())
+                                .child(Input::new(&bar.input).small()),
+                        )
+                    })
+                    .when(self.selection.len() > 1, |el| el.child(self.render_selection_bar(cx)))
                     // Container list
                     .child(
                         div()
@@ -345,6 +1417,7 @@ impl Render for ContainersView {
                             .when(self.containers.is_empty(), |el| {
                                 el.child(self.render_empty_state())
                             })
+                            .when(no_matches, |el| el.child(self.render_no_matches(cx)))
                             .when(!self.containers.is_empty(), |el| {
                                 el.child(
                                     div()
@@ -372,7 +1445,8 @@ impl Render for ContainersView {
                                         }),
                                 )
                             }),
-                    ),
+                    )
+                    .when_some(self.bulk.as_ref(), |el, bulk| el.child(self.render_bulk_progress(bulk, cx))),
             )
             // Resize handle - subtle macOS style
             .child(
@@ -395,6 +1469,22 @@ impl Render for ContainersView {
             )
             // Right panel - detail
             .child(self.render_detail_panel(cx))
+            .when_some(self.action_menu.as_ref(), |el, menu| {
+                el.child(deferred(
+                    anchored()
+                        .position(menu.position)
+                        .snap_to_window()
+                        .child(self.render_action_menu(menu, cx)),
+                ))
+            })
+            .when_some(self.sort_menu, |el, position| {
+                el.child(deferred(
+                    anchored()
+                        .position(position)
+                        .snap_to_window()
+                        .child(self.render_sort_menu(cx)),
+                ))
+            })
     }
 }
 
@@ -406,7 +1496,14 @@ impl ContainersView {
         cx: &Context<Self>,
     ) -> impl IntoElement {
         let is_expanded = self.expanded_groups.get(&project).copied().unwrap_or(true);
+        let is_selected = self.selected_project.as_ref() == Some(&project);
         let project_for_click = project.clone();
+        let project_for_toggle = project.clone();
+        let project_for_select = project.clone();
+        // Roll up the whole project, not just the rows the filter lets through
+        let all = self.project_containers(&project);
+        let running = all.iter().filter(|c| c.is_running()).count();
+        let status = ProjectStatus::of(all.iter().copied());
 
         div()
             .flex()
@@ -423,19 +1520,28 @@ impl ContainersView {
                     .px_2()
                     .rounded(px(6.0))
                     .cursor_pointer()
-                    .hover(|el| el.bg(colors::hover()))
+                    .when(is_selected, |el| el.bg(colors::selection_inactive()))
+                    .when(!is_selected, |el| el.hover(|el| el.bg(colors::hover())))
                     .on_click(cx.listener(move |this, _, _window, cx| {
-                        this.toggle_group(project_for_click.clone(), cx);
+                        this.select_project(project_for_click.clone(), cx);
                     }))
                     .child(
-                        svg()
-                            .path(if is_expanded {
-                                "icons/chevron-down.svg"
-                            } else {
-                                "icons/chevron-right.svg"
-                            })
-                            .size(px(14.0))
-                            .text_color(colors::text_secondary()),
+                        div()
+                            .id(SharedString::from(format!("toggle-group-{}", &project)))
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                cx.stop_propagation();
+                                this.toggle_group(project_for_toggle.clone(), cx);
+                            }))
+                            .child(
+                                svg()
+                                    .path(if is_expanded {
+                                        "icons/chevron-down.svg"
+                                    } else {
+                                        "icons/chevron-right.svg"
+                                    })
+                                    .size(px(14.0))
+                                    .text_color(colors::text_secondary()),
+                            ),
                     )
                     .child(
                         div()
@@ -456,10 +1562,42 @@ impl ContainersView {
                     .child(
                         div()
                             .flex_1()
-                            .text_sm()
-                            .font_weight(FontWeight::MEDIUM)
-                            .text_color(colors::text())
-                            .child(project),
+                            .min_w_0()
+                            .flex()
+                            .flex_col()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .text_color(colors::text())
+                                    .overflow_hidden()
+                                    .text_ellipsis()
+                                    .whitespace_nowrap()
+                                    .child(project.clone()),
+                            )
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap_1()
+                                    .text_xs()
+                                    .text_color(colors::text_secondary())
+                                    .child(div().size(px(6.0)).rounded_full().bg(project_status_color(status)))
+                                    .child(format!("{}/{} running", running, all.len())),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id(SharedString::from(format!("select-group-{}", &project)))
+                            .px_1()
+                            .text_xs()
+                            .text_color(colors::text_muted())
+                            .hover(|el| el.text_color(colors::accent()))
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                cx.stop_propagation();
+                                this.select_group(&project_for_select, cx);
+                            }))
+                            .child("Select"),
                     ),
             )
             // Container rows (if expanded)
@@ -472,6 +1610,139 @@ impl ContainersView {
             })
     }
 
+    /// Count and bulk actions for a multi-selection
+    fn render_selection_bar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let busy = self.bulk.as_ref().is_some_and(|bulk| !bulk.is_done());
+        let action_button = |action: BulkAction, icon: &'static str| {
+            let enabled = !busy && !self.bulk_targets(action).is_empty();
+            Theme::button_icon()
+                .id(SharedString::from(format!("bulk-{}", action.label())))
+                .when(!enabled, |el| el.opacity(0.4).cursor_default())
+                .when(enabled, |el| {
+                    el.on_click(cx.listener(move |this, _, window, cx| {
+                        this.confirm_bulk_action(action, window, cx);
+                    }))
+                })
+                .child(svg().path(icon).size(px(16.0)).text_color(colors::text_secondary()))
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .h(px(36.0))
+            .px_4()
+            .border_b_1()
+            .border_color(colors::border())
+            .bg(colors::surface())
+            .child(
+                div()
+                    .text_xs()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(colors::text())
+                    .child(format!("{} selected", self.selection.len())),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .child(action_button(BulkAction::Start, "icons/play.svg"))
+                    .child(action_button(BulkAction::Stop, "icons/stop.svg"))
+                    .child(action_button(BulkAction::Restart, "icons/restart.svg"))
+                    .child(action_button(BulkAction::Remove, "icons/delete.svg"))
+                    .child(
+                        Theme::button_icon()
+                            .id("clear-selection")
+                            .on_click(cx.listener(|this, _, _window, cx| this.clear_selection(cx)))
+                            .child(
+                                svg()
+                                    .path("icons/close.svg")
+                                    .size(px(14.0))
+                                    .text_color(colors::text_secondary()),
+                            ),
+                    ),
+            )
+    }
+
+    /// Progress of the current bulk action, then its outcome until dismissed
+    fn render_bulk_progress(&self, bulk: &BulkProgress, cx: &Context<Self>) -> impl IntoElement {
+        let done = bulk.is_done();
+        let summary = if done {
+            format!("{} {} of {}", bulk.action.past(), bulk.succeeded, count_containers(bulk.total))
+        } else {
+            format!("{} {} of {}…", bulk.action.progressive(), bulk.finished() + 1, bulk.total)
+        };
+        let fraction = bulk.finished() as f32 / bulk.total.max(1) as f32;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .border_t_1()
+            .border_color(colors::border())
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_xs()
+                            .text_color(if done && !bulk.failures.is_empty() {
+                                colors::warning()
+                            } else {
+                                colors::text_secondary()
+                            })
+                            .child(summary),
+                    )
+                    .when(done && !bulk.conflicts.is_empty(), |el| {
+                        el.child(
+                            Theme::button_ghost()
+                                .id("force-remove-bulk")
+                                .text_xs()
+                                .on_click(cx.listener(|this, _, _window, cx| this.force_remove_conflicts(cx)))
+                                .child(format!("Force Remove {}", count_containers(bulk.conflicts.len()))),
+                        )
+                    })
+                    .when(done, |el| {
+                        el.child(
+                            Theme::button_icon()
+                                .id("dismiss-bulk")
+                                .on_click(cx.listener(|this, _, _window, cx| this.dismiss_bulk(cx)))
+                                .child(
+                                    svg()
+                                        .path("icons/close.svg")
+                                        .size(px(12.0))
+                                        .text_color(colors::text_secondary()),
+                                ),
+                        )
+                    }),
+            )
+            .when(!done, |el| {
+                el.child(
+                    div()
+                        .h(px(3.0))
+                        .rounded_full()
+                        .bg(colors::border())
+                        .child(div().h_full().rounded_full().w(relative(fraction)).bg(colors::accent())),
+                )
+            })
+            .children(bulk.failures.iter().map(|(name, reason)| {
+                div()
+                    .text_xs()
+                    .text_color(colors::error())
+                    .text_ellipsis()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .child(format!("{}: {}", name, reason))
+            }))
+    }
+
     fn render_container_row(
         &self,
         container: &ContainerViewModel,
@@ -482,7 +1753,8 @@ impl ContainersView {
         let id_for_select = container.id.clone();
         let id_for_action = container.id.clone();
         let id_for_delete = container.id.clone();
-        let is_selected = self.selected_id.as_ref() == Some(&id);
+        let id_for_menu = container.id.clone();
+        let is_selected = self.selection.contains(&id);
         let is_running = container.is_running();
 
         let base = div()
@@ -496,9 +1768,16 @@ impl ContainersView {
             .rounded(px(6.0)) // macOS rounded selection
             .cursor_pointer()
             .when(indented, |el| el.ml(px(36.0)))
-            .on_click(cx.listener(move |this, _, _window, cx| {
-                this.select_container(id_for_select.clone(), cx);
-            }));
+            .on_click(cx.listener(move |this, event: &ClickEvent, _window, cx| {
+                this.click_container(id_for_select.clone(), event.modifiers(), cx);
+            }))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                    this.select_container(id_for_menu.clone(), cx);
+                    this.open_action_menu(id_for_menu.clone(), event.position, false, cx);
+                }),
+            );
 
         let base = if is_selected {
             base.bg(colors::selection()).text_color(colors::on_accent())
@@ -584,6 +1863,31 @@ impl ContainersView {
                             .child(container.image.clone()),
                     ),
             )
+            // Live CPU usage
+            .when_some(self.stats.get(&id).filter(|h| is_running && !h.is_empty()), |el, history| {
+                let color = if is_selected { colors::on_accent() } else { colors::accent() };
+                el.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .items_end()
+                        .gap_0p5()
+                        .flex_shrink_0()
+                        .child(
+                            div().w(px(56.0)).h(px(16.0)).child(
+                                Sparkline::new(history.series(|s| s.cpu_percent), color)
+                                    .capacity(STATS_HISTORY_LEN),
+                            ),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .when(is_selected, |el| el.text_color(rgba(0xffffffaa)))
+                                .when(!is_selected, |el| el.text_color(colors::text_secondary()))
+                                .child(format!("{:.0}%", container.cpu_percent)),
+                        ),
+                )
+            })
             // Action buttons
             .child(
                 div()
@@ -687,7 +1991,10 @@ impl ContainersView {
         rgba(((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | 0xFF)
     }
 
-    fn render_detail_panel(&self, cx: &Context<Self>) -> impl IntoElement {
+    fn render_detail_panel(&self, cx: &Context<Self>) -> AnyElement {
+        if let Some(project) = &self.selected_project {
+            return self.render_project_panel(project, cx).into_any_element();
+        }
         let selected = self.get_selected_container();
         let selected_index = self.active_tab.to_index();
 
@@ -696,6 +2003,7 @@ impl ContainersView {
             .flex()
             .flex_col()
             .bg(colors::background())
+            .when_some(selected, |el, container| el.child(self.render_detail_header(container, cx)))
             // Tab bar using gpui-component - macOS style
             .child(
                 div()
@@ -723,11 +2031,240 @@ impl ContainersView {
                     .overflow_y_scroll()
                     .p_4()
                     .child(if let Some(container) = selected {
-                        self.render_detail_content(container).into_any_element()
+                        self.render_detail_content(container, cx).into_any_element()
                     } else {
                         self.render_no_selection().into_any_element()
                     }),
             )
+            .into_any_element()
+    }
+
+    /// Rolled-up status, project actions, services with their replica
+    /// counts, and the merged logs of a compose project
+    fn render_project_panel(&self, project: &str, cx: &Context<Self>) -> impl IntoElement {
+        let containers = self.project_containers(project);
+        let status = ProjectStatus::of(containers.iter().copied());
+        let running = containers.iter().filter(|c| c.is_running()).count();
+        let busy = self.bulk.as_ref().is_some_and(|bulk| !bulk.is_done());
+        let has_compose_file = containers.iter().any(|c| !c.compose_config_files().is_empty());
+
+        let action_button = |action: BulkAction, icon: &'static str| {
+            let enabled = !busy && !action_targets(action, containers.iter().copied()).is_empty();
+            let project = project.to_string();
+            Theme::button_icon()
+                .id(SharedString::from(format!("project-{}", action.label())))
+                .when(!enabled, |el| el.opacity(0.4).cursor_default())
+                .when(enabled, |el| {
+                    el.on_click(cx.listener(move |this, _, window, cx| {
+                        this.confirm_project_action(&project, action, window, cx);
+                    }))
+                })
+                .child(svg().path(icon).size(px(16.0)).text_color(colors::text_secondary()))
+        };
+        let project_for_open = project.to_string();
+
+        div()
+            .flex_1()
+            .min_w_0()
+            .flex()
+            .flex_col()
+            .bg(colors::background())
+            // Header
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_3()
+                    .h(px(52.0))
+                    .px_4()
+                    .border_b_1()
+                    .border_color(colors::border_subtle())
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w_0()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                svg()
+                                    .path("icons/layer.svg")
+                                    .size(px(18.0))
+                                    .flex_shrink_0()
+                                    .text_color(colors::accent()),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(colors::text())
+                                    .overflow_hidden()
+                                    .text_ellipsis()
+                                    .whitespace_nowrap()
+                                    .child(project.to_string()),
+                            )
+                            .child(
+                                Theme::badge()
+                                    .flex()
+                                    .items_center()
+                                    .gap_1()
+                                    .flex_shrink_0()
+                                    .child(div().size(px(6.0)).rounded_full().bg(project_status_color(status)))
+                                    .child(format!("{} · {}/{}", status.label(), running, containers.len())),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .flex_shrink_0()
+                            .child(action_button(BulkAction::Start, "icons/play.svg"))
+                            .child(action_button(BulkAction::Stop, "icons/stop.svg"))
+                            .child(action_button(BulkAction::Restart, "icons/restart.svg"))
+                            .child(
+                                Theme::button_ghost()
+                                    .id("project-open-compose-file")
+                                    .text_xs()
+                                    .when(!has_compose_file, |el| el.opacity(0.4).cursor_default())
+                                    .when(has_compose_file, |el| {
+                                        el.on_click(cx.listener(move |this, _, _window, cx| {
+                                            this.open_compose_file(&project_for_open, cx);
+                                        }))
+                                    })
+                                    .child("Open Compose File"),
+                            )
+                            .child(action_button(BulkAction::Remove, "icons/delete.svg")),
+                    ),
+            )
+            // Services
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .p_4()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(colors::border_subtle())
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(colors::text_muted())
+                            .child("Services"),
+                    )
+                    .children(
+                        self.project_services(project)
+                            .into_iter()
+                            .map(|(service, replicas)| self.render_service_row(project, service, replicas, cx)),
+                    ),
+            )
+            // Merged logs
+            .child(
+                div()
+                    .flex_1()
+                    .min_h_0()
+                    .min_w_0()
+                    .p_4()
+                    .overflow_hidden()
+                    .when_some(self.project_logs.get(project), |el, viewer| el.child(viewer.clone())),
+            )
+    }
+
+    /// A compose service: image, replicas running, and a stepper to scale it
+    fn render_service_row(
+        &self,
+        project: &str,
+        service: String,
+        replicas: Vec<&ContainerViewModel>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let count = replicas.len();
+        let running = replicas.iter().filter(|c| c.is_running()).count();
+        let image = replicas.first().map(|c| c.image.clone()).unwrap_or_default();
+        let scaling = self.scaling.contains_key(&service_key(project, &service));
+        // Only labelled compose containers can be copied as replicas
+        let scalable = replicas.iter().all(|c| c.compose_service().is_some());
+
+        let step = |delta: isize, label: &'static str| {
+            let target = count as isize + delta;
+            let enabled = scalable && !scaling && target >= 1;
+            let project = project.to_string();
+            let service = service.clone();
+            Theme::button_icon()
+                .id(SharedString::from(format!("scale-{}-{}", service, label)))
+                .text_sm()
+                .text_color(colors::text_secondary())
+                .when(!enabled, |el| el.opacity(0.4).cursor_default())
+                .when(enabled, |el| {
+                    el.on_click(cx.listener(move |this, _, _window, cx| {
+                        this.scale_service(&project, &service, target as usize, cx);
+                    }))
+                })
+                .child(label)
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .py_1()
+            .child(
+                div()
+                    .size(px(6.0))
+                    .flex_shrink_0()
+                    .rounded_full()
+                    .bg(project_status_color(ProjectStatus::of(replicas.iter().copied()))),
+            )
+            .child(
+                div()
+                    .w(px(120.0))
+                    .flex_shrink_0()
+                    .text_sm()
+                    .text_color(colors::text())
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .child(service.clone()),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .text_xs()
+                    .text_color(colors::text_secondary())
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .child(image),
+            )
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_xs()
+                    .text_color(colors::text_secondary())
+                    .child(if scaling {
+                        "Scaling…".to_string()
+                    } else {
+                        format!("{}/{} running", running, count)
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .flex_shrink_0()
+                    .child(step(-1, "−"))
+                    .child(
+                        div()
+                            .w(px(24.0))
+                            .text_center()
+                            .text_sm()
+                            .text_color(colors::text())
+                            .child(count.to_string()),
+                    )
+                    .child(step(1, "+")),
+            )
     }
 
     fn render_no_selection(&self) -> impl IntoElement {
@@ -784,6 +2321,32 @@ impl ContainersView {
             )
     }
 
+    /// Shown when the filter hides every container
+    fn render_no_matches(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .gap_2()
+            .p_6()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(colors::text_secondary())
+                    .child("No matching containers"),
+            )
+            .child(
+                div()
+                    .id("clear-filter")
+                    .text_xs()
+                    .text_color(colors::accent())
+                    .cursor_pointer()
+                    .hover(|el| el.text_color(colors::accent_hover()))
+                    .on_click(cx.listener(|this, _, window, cx| this.clear_filter(window, cx)))
+                    .child("Clear Filter"),
+            )
+    }
+
     fn render_command_hint(command: &'static str, desc: &'static str) -> impl IntoElement {
         div()
             .flex()
@@ -808,12 +2371,13 @@ impl ContainersView {
             )
     }
 
-    fn render_detail_content(&self, container: &ContainerViewModel) -> impl IntoElement {
+    fn render_detail_content(&self, container: &ContainerViewModel, cx: &Context<Self>) -> impl IntoElement {
         match self.active_tab {
             DetailTab::Info => self.render_info_tab(container).into_any_element(),
             DetailTab::Logs => self.render_logs_tab().into_any_element(),
-            DetailTab::Terminal => self.render_terminal_tab().into_any_element(),
+            DetailTab::Terminal => self.render_terminal_tab(container, cx).into_any_element(),
             DetailTab::Files => self.render_files_tab().into_any_element(),
+            DetailTab::Stats => self.render_stats_tab(container, cx).into_any_element(),
         }
     }
 
@@ -901,6 +2465,10 @@ impl ContainersView {
                         ),
                 )
             })
+            // Full configuration from inspect
+            .when_some(self.inspect_views.get(&container.id), |el, view| {
+                el.child(div().mt_4().child(view.clone()))
+            })
     }
 
     fn render_label_row(key: &str, value: &str) -> impl IntoElement {
@@ -948,23 +2516,615 @@ impl ContainersView {
             .into_any_element()
     }
 
-    fn render_terminal_tab(&self) -> impl IntoElement {
+    fn render_terminal_tab(&self, container: &ContainerViewModel, cx: &Context<Self>) -> impl IntoElement {
+        let terminals = self.terminals.get(&container.id);
+        let has_tabs = terminals.is_some_and(|t| !t.tabs.is_empty());
+
+        if !container.is_running() && !has_tabs {
+            return div()
+                .flex_1()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_color(colors::text_secondary())
+                .child("Start the container to open a terminal")
+                .into_any_element();
+        }
+
+        let id = container.id.clone();
+        let active = terminals.and_then(|t| t.tabs.get(t.active));
+
         div()
-            .flex_1()
+            .size_full()
+            .min_w_0()
+            .flex()
+            .flex_col()
+            .gap_2()
+            // Shell sub-tabs
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .children(terminals.into_iter().flat_map(|t| {
+                        t.tabs.iter().enumerate().map(|(index, tab)| {
+                            self.render_terminal_tab_button(&id, index, tab, index == t.active, cx)
+                        })
+                    }))
+                    .when(container.is_running(), |el| {
+                        let id = id.clone();
+                        el.child(
+                            Theme::button_icon()
+                                .id("new-terminal")
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    this.open_terminal(&id, cx);
+                                }))
+                                .child(
+                                    svg()
+                                        .path("icons/add.svg")
+                                        .size(px(14.0))
+                                        .text_color(colors::text_secondary()),
+                                ),
+                        )
+                    }),
+            )
+            .child(match active {
+                Some(tab) => div()
+                    .flex_1()
+                    .min_h_0()
+                    .child(tab.view.clone())
+                    .into_any_element(),
+                None => div()
+                    .flex_1()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .text_color(colors::text_secondary())
+                    .child("No open shells")
+                    .into_any_element(),
+            })
+            .into_any_element()
+    }
+
+    fn render_terminal_tab_button(
+        &self,
+        container_id: &str,
+        index: usize,
+        tab: &TerminalTab,
+        is_active: bool,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let view = tab.view.read(cx);
+        let label = view
+            .title()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Shell {}", tab.number));
+        let ended = matches!(view.status(), TerminalStatus::Exited | TerminalStatus::Failed(_));
+        let id_for_select = container_id.to_string();
+        let id_for_close = container_id.to_string();
+
+        div()
+            .id(SharedString::from(format!("terminal-tab-{}", tab.number)))
             .flex()
             .items_center()
-            .justify_center()
-            .text_color(colors::text_secondary())
-            .child("Terminal coming soon...")
+            .gap_1()
+            .max_w(px(180.0))
+            .pl_2()
+            .pr_1()
+            .py_1()
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .text_xs()
+            .when(is_active, |el| {
+                el.bg(colors::selection())
+                    .text_color(colors::on_accent())
+            })
+            .when(!is_active, |el| {
+                el.hover(|el| el.bg(colors::hover()))
+                    .text_color(if ended {
+                        colors::text_muted()
+                    } else {
+                        colors::text_secondary()
+                    })
+            })
+            .on_click(cx.listener(move |this, _, _window, cx| {
+                this.select_terminal(&id_for_select, index, cx);
+            }))
+            .child(
+                div()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .whitespace_nowrap()
+                    .child(label),
+            )
+            .child(
+                div()
+                    .id(SharedString::from(format!("close-terminal-{}", tab.number)))
+                    .flex_shrink_0()
+                    .p_0p5()
+                    .rounded(px(3.0))
+                    .hover(|el| el.bg(colors::hover()))
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        cx.stop_propagation();
+                        this.close_terminal(&id_for_close, index, cx);
+                    }))
+                    .child(
+                        svg()
+                            .path("icons/close.svg")
+                            .size(px(10.0))
+                            .text_color(if is_active {
+                                colors::on_accent()
+                            } else {
+                                colors::text_secondary()
+                            }),
+                    ),
+            )
     }
 
     fn render_files_tab(&self) -> impl IntoElement {
+        if let Some(ref id) = self.selected_id {
+            if let Some(browser) = self.file_browsers.get(id) {
+                return div()
+                    .size_full()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .child(browser.clone())
+                    .into_any_element();
+            }
+        }
+
         div()
             .flex_1()
             .flex()
             .items_center()
             .justify_center()
             .text_color(colors::text_secondary())
-            .child("File browser coming soon...")
+            .child("Select a container to browse files")
+            .into_any_element()
+    }
+
+    fn render_stats_tab(&self, container: &ContainerViewModel, cx: &Context<Self>) -> impl IntoElement {
+        let message = if !self.daemon_service.read(cx).supports(Capability::Stats) {
+            Some("This daemon does not report container stats")
+        } else if !container.is_running() {
+            Some("Start the container to see live stats")
+        } else {
+            None
+        };
+        let history = self.stats.get(&container.id);
+        let latest = history.and_then(|h| h.latest());
+        let (Some(history), Some(latest), None) = (history, latest, message) else {
+            return div()
+                .flex_1()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_color(colors::text_secondary())
+                .child(message.unwrap_or("Waiting for stats..."))
+                .into_any_element();
+        };
+
+        let cpu = history.series(|s| s.cpu_percent);
+        let memory = history.series(|s| s.memory_bytes as f64);
+        let net_rx = history.series(|s| s.net_rx_per_sec);
+        let net_tx = history.series(|s| s.net_tx_per_sec);
+        let block_read = history.series(|s| s.block_read_per_sec);
+        let block_write = history.series(|s| s.block_write_per_sec);
+        let net_max = net_rx.iter().chain(&net_tx).copied().fold(0.0, f64::max);
+        let block_max = block_read.iter().chain(&block_write).copied().fold(0.0, f64::max);
+
+        let memory_value = if latest.memory_limit_bytes > 0 {
+            format!(
+                "{} / {} ({:.0}%)",
+                format_bytes(latest.memory_bytes as f64),
+                format_bytes(latest.memory_limit_bytes as f64),
+                latest.memory_percent()
+            )
+        } else {
+            format_bytes(latest.memory_bytes as f64)
+        };
+        let mut memory_chart = Sparkline::new(memory, colors::running())
+            .capacity(STATS_HISTORY_LEN)
+            .filled();
+        if latest.memory_limit_bytes > 0 {
+            memory_chart = memory_chart.max(latest.memory_limit_bytes as f64);
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(Self::render_stats_card(
+                "CPU",
+                vec![(format!("{:.1}%", latest.cpu_percent), colors::accent())],
+                vec![Sparkline::new(cpu, colors::accent())
+                    .capacity(STATS_HISTORY_LEN)
+                    .max(100.0)
+                    .filled()],
+            ))
+            .child(Self::render_stats_card(
+                "Memory",
+                vec![(memory_value, colors::running())],
+                vec![memory_chart],
+            ))
+            .child(Self::render_stats_card(
+                "Network",
+                vec![
+                    (format!("↓ {}/s", format_bytes(latest.net_rx_per_sec)), colors::accent()),
+                    (format!("↑ {}/s", format_bytes(latest.net_tx_per_sec)), colors::warning()),
+                ],
+                vec![
+                    Sparkline::new(net_rx, colors::accent()).capacity(STATS_HISTORY_LEN).max(net_max),
+                    Sparkline::new(net_tx, colors::warning()).capacity(STATS_HISTORY_LEN).max(net_max),
+                ],
+            ))
+            .child(Self::render_stats_card(
+                "Block I/O",
+                vec![
+                    (format!("Read {}/s", format_bytes(latest.block_read_per_sec)), colors::accent()),
+                    (format!("Write {}/s", format_bytes(latest.block_write_per_sec)), colors::warning()),
+                ],
+                vec![
+                    Sparkline::new(block_read, colors::accent()).capacity(STATS_HISTORY_LEN).max(block_max),
+                    Sparkline::new(block_write, colors::warning()).capacity(STATS_HISTORY_LEN).max(block_max),
+                ],
+            ))
+            .into_any_element()
+    }
+
+    /// Chart card with a title, current values and overlaid series
+    fn render_stats_card(
+        title: &'static str,
+        values: Vec<(String, Rgba)>,
+        charts: Vec<Sparkline>,
+    ) -> impl IntoElement {
+        Theme::card()
+            .p_3()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(colors::text())
+                            .child(title),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_3()
+                            .text_xs()
+                            .font_family(MONO_FONT)
+                            .children(values.into_iter().map(|(value, color)| {
+                                div().text_color(color).child(value)
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .relative()
+                    .h(px(72.0))
+                    .children(charts.into_iter().map(|chart| div().absolute().top_0().left_0().size_full().child(chart))),
+            )
+    }
+
+    /// Name, state and lifecycle actions above the tabs
+    fn render_detail_header(&self, container: &ContainerViewModel, cx: &Context<Self>) -> impl IntoElement {
+        let state = container.state;
+        let renaming = self
+            .rename
+            .as_ref()
+            .filter(|rename| rename.container_id == container.id);
+        let primary = if ContainerAction::Start.is_available(state) {
+            (ContainerAction::Start, "icons/play.svg")
+        } else {
+            (ContainerAction::Stop, "icons/stop.svg")
+        };
+        let pause = if ContainerAction::Unpause.is_available(state) {
+            (ContainerAction::Unpause, "icons/play.svg")
+        } else {
+            (ContainerAction::Pause, "icons/pause.svg")
+        };
+        let id_for_kill = container.id.clone();
+        let can_kill = ContainerAction::Kill.is_available(state);
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_3()
+            .h(px(52.0))
+            .px_4()
+            .border_b_1()
+            .border_color(colors::border_subtle())
+            .child(match renaming {
+                Some(rename) => div()
+                    .flex_1()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                        if event.keystroke.key == "escape" {
+                            this.cancel_rename(cx);
+                        }
+                    }))
+                    .child(div().flex_1().child(Input::new(&rename.input).small()))
+                    .child(
+                        Theme::button_primary()
+                            .id("rename-save")
+                            .text_xs()
+                            .on_click(cx.listener(|this, _, _window, cx| this.commit_rename(cx)))
+                            .child("Rename"),
+                    )
+                    .child(
+                        Theme::button_ghost()
+                            .id("rename-cancel")
+                            .text_xs()
+                            .on_click(cx.listener(|this, _, _window, cx| this.cancel_rename(cx)))
+                            .child("Cancel"),
+                    )
+                    .into_any_element(),
+                None => div()
+                    .flex_1()
+                    .min_w_0()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(colors::text())
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .whitespace_nowrap()
+                            .child(container.name.clone()),
+                    )
+                    .child(ContainerStatusBadge::new(state))
+                    .into_any_element(),
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .flex_shrink_0()
+                    .child(self.render_header_action(container, primary.0, primary.1, cx))
+                    .child(self.render_header_action(container, pause.0, pause.1, cx))
+                    .child(self.render_header_action(container, ContainerAction::Restart, "icons/restart.svg", cx))
+                    .child(
+                        Theme::button_ghost()
+                            .id("header-kill")
+                            .text_xs()
+                            .when(!can_kill, |el| el.opacity(0.4).cursor_default())
+                            .when(can_kill, |el| {
+                                el.on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                                        cx.stop_propagation();
+                                        this.open_action_menu(id_for_kill.clone(), event.position, true, cx);
+                                    }),
+                                )
+                            })
+                            .child("Kill ▾"),
+                    )
+                    .child(self.render_header_action(container, ContainerAction::Rename, "", cx))
+                    .child(self.render_header_action(container, ContainerAction::Remove, "icons/delete.svg", cx)),
+            )
+    }
+
+    /// Icon (or text, without an icon) button for one action, disabled when the state doesn't allow it
+    fn render_header_action(
+        &self,
+        container: &ContainerViewModel,
+        action: ContainerAction,
+        icon: &'static str,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let enabled = action.is_available(container.state);
+        let id = container.id.clone();
+        let button = if icon.is_empty() {
+            Theme::button_ghost().text_xs().child(action.label())
+        } else {
+            Theme::button_icon().child(
+                svg()
+                    .path(icon)
+                    .size(px(16.0))
+                    .text_color(colors::text_secondary()),
+            )
+        };
+
+        button
+            .id(SharedString::from(format!("header-{}", action.label().to_lowercase())))
+            .when(!enabled, |el| el.opacity(0.4).cursor_default())
+            .when(enabled, |el| {
+                el.on_click(cx.listener(move |this, _, window, cx| {
+                    this.run_action(&id, action, window, cx);
+                }))
+            })
+    }
+
+    /// Row context menu, or the signal list for the header's Kill button
+    fn render_action_menu(&self, menu: &ActionMenu, cx: &Context<Self>) -> impl IntoElement {
+        let state = self
+            .containers
+            .iter()
+            .find(|c| c.id == menu.container_id)
+            .map(|c| c.state);
+        let can_kill = state.is_some_and(|state| ContainerAction::Kill.is_available(state));
+        let actions = if menu.signals_only {
+            Vec::new()
+        } else {
+            vec![
+                ContainerAction::Start,
+                ContainerAction::Stop,
+                ContainerAction::Pause,
+                ContainerAction::Unpause,
+                ContainerAction::Restart,
+                ContainerAction::Rename,
+            ]
+        };
+
+        div()
+            .occlude()
+            .min_w(px(180.0))
+            .py_1()
+            .bg(colors::background())
+            .border_1()
+            .border_color(colors::border())
+            .rounded_md()
+            .shadow_lg()
+            .on_mouse_down_out(cx.listener(|this, _, _window, cx| this.close_action_menu(cx)))
+            .children(actions.into_iter().map(|action| {
+                let enabled = state.is_some_and(|state| action.is_available(state));
+                let id = menu.container_id.clone();
+                let label = if action == ContainerAction::Rename {
+                    "Rename…".to_string()
+                } else {
+                    action.label().to_string()
+                };
+                Self::render_menu_item(format!("menu-{}", action.label()), label, enabled)
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        if enabled {
+                            this.run_action(&id, action, window, cx);
+                        }
+                    }))
+            }))
+            .when(!menu.signals_only, |el| {
+                el.child(div().my_1().h(px(1.0)).bg(colors::border_subtle()))
+            })
+            .child(
+                div()
+                    .px_3()
+                    .py_1()
+                    .text_xs()
+                    .text_color(colors::text_muted())
+                    .child("Send Signal"),
+            )
+            .children(KILL_SIGNALS.into_iter().map(|signal| {
+                let id = menu.container_id.clone();
+                Self::render_menu_item(format!("menu-{}", signal), signal.to_string(), can_kill)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        if can_kill {
+                            this.kill_container(&id, signal, cx);
+                        }
+                    }))
+            }))
+            .when(!menu.signals_only, |el| {
+                let id = menu.container_id.clone();
+                el.child(div().my_1().h(px(1.0)).bg(colors::border_subtle()))
+                    .child(
+                        Self::render_menu_item("menu-remove".to_string(), "Remove".to_string(), true)
+                            .text_color(colors::error())
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.run_action(&id, ContainerAction::Remove, window, cx);
+                            })),
+                    )
+            })
+    }
+
+    fn render_sort_menu(&self, cx: &Context<Self>) -> impl IntoElement {
+        let check = |on: bool| if on { "✓ " } else { "    " };
+        let descending = self.sort_descending;
+        let sort = self.sort;
+
+        div()
+            .occlude()
+            .min_w(px(160.0))
+            .py_1()
+            .bg(colors::background())
+            .border_1()
+            .border_color(colors::border())
+            .rounded_md()
+            .shadow_lg()
+            .on_mouse_down_out(cx.listener(|this, _, _window, cx| this.close_sort_menu(cx)))
+            .child(
+                div()
+                    .px_3()
+                    .py_1()
+                    .text_xs()
+                    .text_color(colors::text_muted())
+                    .child("Sort By"),
+            )
+            .children(ContainerSort::ALL.into_iter().map(|option| {
+                let label = format!("{}{}", check(option == sort), option.label());
+                Self::render_menu_item(format!("sort-{}", option.key()), label, true)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.set_sort(option, descending, cx);
+                    }))
+            }))
+            .child(div().my_1().h(px(1.0)).bg(colors::border_subtle()))
+            .children([("Ascending", false), ("Descending", true)].into_iter().map(|(label, reversed)| {
+                let label = format!("{}{}", check(reversed == descending), label);
+                Self::render_menu_item(format!("sort-order-{}", reversed), label, true)
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.set_sort(sort, reversed, cx);
+                    }))
+            }))
+    }
+
+    fn render_menu_item(id: String, label: String, enabled: bool) -> Stateful<Div> {
+        div()
+            .id(SharedString::from(id))
+            .px_3()
+            .py_1()
+            .text_sm()
+            .when(enabled, |el| {
+                el.text_color(colors::text())
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()))
+            })
+            .when(!enabled, |el| el.text_color(colors::text_muted()))
+            .child(label)
+    }
+}
+
+/// Which of `containers` a bulk action applies to
+fn action_targets<'a>(
+    action: BulkAction,
+    containers: impl Iterator<Item = &'a ContainerViewModel>,
+) -> Vec<&'a ContainerViewModel> {
+    containers
+        .filter(|c| match action {
+            BulkAction::Start => !c.is_running(),
+            BulkAction::Stop => c.is_running(),
+            BulkAction::Restart | BulkAction::Remove | BulkAction::ForceRemove => true,
+        })
+        .collect()
+}
+
+fn project_status_color(status: ProjectStatus) -> Rgba {
+    match status {
+        ProjectStatus::Running => colors::running(),
+        ProjectStatus::Partial => colors::warning(),
+        ProjectStatus::Stopped => colors::stopped(),
+    }
+}
+
+/// Key for a compose service in `ContainersView::scaling`
+fn service_key(project: &str, service: &str) -> String {
+    format!("{}/{}", project, service)
+}
+
+/// "1 container", "3 containers"
+fn count_containers(count: usize) -> String {
+    if count == 1 {
+        "1 container".to_string()
+    } else {
+        format!("{} containers", count)
     }
 }
+
+/// Copy a stats sample into the fields the list and Info tab show
+fn apply_stats(container: &mut ContainerViewModel, stats: &ContainerStats) {
+    container.cpu_percent = stats.cpu_percent;
+    container.memory_mb = stats.memory_bytes as f64 / (1024.0 * 1024.0);
+    container.memory_limit_mb = stats.memory_limit_bytes as f64 / (1024.0 * 1024.0);
+}
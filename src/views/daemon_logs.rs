@@ -0,0 +1,80 @@
+use gpui::*;
+use gpui::prelude::*;
+
+use crate::components::LogViewer;
+use crate::services::DaemonLogs;
+use crate::theme::colors;
+
+/// Daemon Logs window: the local daemon's captured stdout/stderr
+pub struct DaemonLogsView {
+    logs: Entity<DaemonLogs>,
+    viewer: Entity<LogViewer>,
+}
+
+impl DaemonLogsView {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let logs = DaemonLogs::global(cx);
+        let viewer = cx.new(|cx| LogViewer::daemon(logs.clone(), cx));
+        Self { logs, viewer }
+    }
+
+    fn render_header(&self, cx: &Context<Self>) -> impl IntoElement {
+        let logs_dir = self.logs.read(cx).logs_dir().to_path_buf();
+
+        div()
+            .h(px(52.0))
+            .flex_shrink_0()
+            .flex()
+            .items_center()
+            .gap_3()
+            // Leave room for the traffic lights on macOS
+            .pl(px(80.0))
+            .pr_4()
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors::text())
+                    .child("Daemon Logs"),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .overflow_hidden()
+                    .text_ellipsis()
+                    .text_xs()
+                    .text_color(colors::text_muted())
+                    .child(logs_dir.display().to_string()),
+            )
+            .child(
+                div()
+                    .id("reveal-daemon-logs")
+                    .px_2()
+                    .py_1()
+                    .rounded(px(4.0))
+                    .border_1()
+                    .border_color(colors::border())
+                    .text_xs()
+                    .text_color(colors::text_secondary())
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()).text_color(colors::text()))
+                    .on_click(move |_, _window, cx| {
+                        cx.reveal_path(&logs_dir);
+                    })
+                    .child("Reveal Log Files"),
+            )
+    }
+}
+
+impl Render for DaemonLogsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(colors::background())
+            .child(self.render_header(cx))
+            .child(div().flex_1().min_h_0().px_3().pb_3().child(self.viewer.clone()))
+    }
+}
@@ -1,11 +1,11 @@
-use arcbox_api::generated::ListImagesResponse;
+use arcbox_api::generated::{ImageSummary, ListImagesResponse};
 use gpui::*;
 use gpui::prelude::*;
 use gpui_component::tab::TabBar;
 use gpui_component::Sizable;
 
-use crate::models::{ImageViewModel, calculate_image_stats};
-use crate::services::{ImageIconService, IconState};
+use crate::models::{format_bytes, ImageViewModel, calculate_image_stats};
+use crate::services::{DaemonError, DaemonOperation, ImageIconService, IconState};
 use crate::theme::{colors, Theme, MONO_FONT};
 
 /// Detail tab for images
@@ -65,6 +65,8 @@ pub struct ImagesView {
     active_tab: ImageDetailTab,
     list_width: f32,
     daemon_service: Entity<crate::services::DaemonService>,
+    /// Observation of the current daemon service (replaced on context switch)
+    _daemon_subscription: Subscription,
     icon_service: Entity<ImageIconService>,
     is_loading: bool,
 }
@@ -78,17 +80,7 @@ impl ImagesView {
         // Subscribe to icon service updates for re-rendering
         cx.observe(&icon_service, |_, _, cx| cx.notify()).detach();
 
-        // Subscribe to daemon service connection state changes
-        cx.observe(&daemon_service, |this, daemon, cx| {
-            if daemon.read(cx).is_connected() && this.is_loading {
-                // Request image list when connected
-                daemon.update(cx, |svc, cx| {
-                    svc.list_images(cx);
-                });
-            }
-            cx.notify();
-        })
-        .detach();
+        let daemon_subscription = Self::observe_daemon(&daemon_service, cx);
 
         Self {
             images: Vec::new(),
@@ -96,11 +88,48 @@ impl ImagesView {
             active_tab: ImageDetailTab::Info,
             list_width: LIST_DEFAULT_WIDTH,
             daemon_service,
+            _daemon_subscription: daemon_subscription,
             icon_service,
             is_loading: true,
         }
     }
 
+    /// Subscribe to daemon service connection state changes
+    fn observe_daemon(
+        daemon_service: &Entity<crate::services::DaemonService>,
+        cx: &mut Context<Self>,
+    ) -> Subscription {
+        cx.observe(daemon_service, |this, daemon, cx| {
+            if daemon.read(cx).is_connected() && this.is_loading {
+                // Request image list when connected
+                daemon.update(cx, |svc, cx| {
+                    svc.list_images(cx);
+                });
+            }
+            cx.notify();
+        })
+    }
+
+    /// Point the view at another daemon (context switch) and reload
+    pub fn set_daemon_service(
+        &mut self,
+        daemon_service: Entity<crate::services::DaemonService>,
+        cx: &mut Context<Self>,
+    ) {
+        self._daemon_subscription = Self::observe_daemon(&daemon_service, cx);
+        self.daemon_service = daemon_service;
+        self.images.clear();
+        self.selected_id = None;
+        self.is_loading = true;
+
+        if self.daemon_service.read(cx).is_connected() {
+            self.daemon_service.update(cx, |svc, cx| {
+                svc.list_images(cx);
+            });
+        }
+        cx.notify();
+    }
+
     fn resize_list(&mut self, new_width: f32, cx: &mut Context<Self>) {
         self.list_width = new_width.clamp(LIST_MIN_WIDTH, LIST_MAX_WIDTH);
         cx.notify();
@@ -147,6 +176,46 @@ impl ImagesView {
 
         cx.notify();
     }
+
+    /// Insert or replace a single image (from the daemon event stream)
+    pub fn on_image_updated(&mut self, summary: ImageSummary, cx: &mut Context<Self>) {
+        let image = ImageViewModel::from(summary);
+
+        self.icon_service.update(cx, |svc, cx| {
+            let _ = svc.get_icon(&image.repository, cx);
+        });
+
+        match self.images.iter_mut().find(|i| i.id == image.id) {
+            Some(existing) => *existing = image,
+            None => self.images.push(image),
+        }
+        cx.notify();
+    }
+
+    /// React to a failed image operation
+    pub fn on_operation_failed(&mut self, error: &DaemonError, cx: &mut Context<Self>) {
+        if error.operation() == DaemonOperation::ListImages
+            && !matches!(error, DaemonError::Unavailable { .. })
+        {
+            self.is_loading = false;
+            cx.notify();
+        }
+    }
+
+    /// Drop a deleted image (from the daemon event stream)
+    pub fn on_image_deleted(&mut self, id: &str, cx: &mut Context<Self>) {
+        // Events may report the ID with or without the "sha256:" prefix
+        let short = id.trim_start_matches("sha256:");
+        self.images.retain(|i| i.id.trim_start_matches("sha256:") != short);
+        if self
+            .selected_id
+            .as_deref()
+            .is_some_and(|sel| sel.trim_start_matches("sha256:") == short)
+        {
+            self.selected_id = None;
+        }
+        cx.notify();
+    }
 }
 
 impl Render for ImagesView {
@@ -194,7 +263,7 @@ impl Render for ImagesView {
                                         div()
                                             .text_xs()
                                             .text_color(colors::text_secondary())
-                                            .child(format_size(total_size)),
+                                            .child(format!("{} total", format_bytes(total_size as f64))),
                                     ),
                             )
                             .child(
@@ -635,13 +704,3 @@ impl ImagesView {
             .child(format!("{} coming soon...", name))
     }
 }
-
-fn format_size(bytes: u64) -> String {
-    let gb = bytes as f64 / 1_000_000_000.0;
-    if gb >= 1.0 {
-        format!("{:.2} GB total", gb)
-    } else {
-        let mb = bytes as f64 / 1_000_000.0;
-        format!("{:.0} MB total", mb)
-    }
-}
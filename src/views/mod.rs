@@ -1,19 +1,27 @@
+mod compose_window;
 mod containers;
 mod container_detail;
+mod daemon_logs;
 mod images;
 mod machines;
 mod machine_detail;
 mod networks;
 mod new_container_dialog;
+mod new_context_dialog;
+mod notifications;
 mod settings;
 mod volumes;
 
+pub use compose_window::*;
 pub use containers::*;
 pub use container_detail::*;
+pub use daemon_logs::*;
 pub use images::*;
 pub use machines::*;
 pub use machine_detail::*;
 pub use networks::*;
 pub use new_container_dialog::*;
+pub use new_context_dialog::*;
+pub use notifications::*;
 pub use settings::*;
 pub use volumes::*;
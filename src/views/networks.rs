@@ -1,9 +1,12 @@
+use arcbox_api::generated::ListNetworksResponse;
+use chrono::{TimeZone, Utc};
 use gpui::*;
 use gpui::prelude::*;
 use gpui_component::tab::TabBar;
 use gpui_component::Sizable;
 
 use crate::models::NetworkViewModel;
+use crate::services::{DaemonError, DaemonOperation, DaemonService, ResourceEvent};
 use crate::theme::{colors, Theme, MONO_FONT};
 
 /// Detail tab for networks
@@ -56,18 +59,129 @@ pub struct NetworksView {
     selected_id: Option<String>,
     active_tab: NetworkDetailTab,
     list_width: f32,
+    daemon_service: Entity<DaemonService>,
+    /// Observation of the current daemon service (replaced on context switch)
+    _daemon_subscription: Subscription,
+    is_loading: bool,
 }
 
 impl NetworksView {
-    pub fn new(_cx: &mut Context<Self>) -> Self {
+    pub fn new(daemon_service: Entity<DaemonService>, cx: &mut Context<Self>) -> Self {
+        let daemon_subscription = Self::observe_daemon(&daemon_service, cx);
+
         Self {
             networks: Vec::new(),
             selected_id: None,
             active_tab: NetworkDetailTab::Info,
             list_width: LIST_DEFAULT_WIDTH,
+            daemon_service,
+            _daemon_subscription: daemon_subscription,
+            is_loading: true,
+        }
+    }
+
+    /// Subscribe to daemon service connection state changes
+    fn observe_daemon(daemon_service: &Entity<DaemonService>, cx: &mut Context<Self>) -> Subscription {
+        cx.observe(daemon_service, |this, daemon, cx| {
+            if daemon.read(cx).is_connected() && this.is_loading {
+                // Request network list when connected
+                daemon.update(cx, |svc, cx| {
+                    svc.list_networks(cx);
+                });
+            }
+            cx.notify();
+        })
+    }
+
+    /// Point the view at another daemon (context switch) and reload
+    pub fn set_daemon_service(&mut self, daemon_service: Entity<DaemonService>, cx: &mut Context<Self>) {
+        self._daemon_subscription = Self::observe_daemon(&daemon_service, cx);
+        self.daemon_service = daemon_service;
+        self.networks.clear();
+        self.selected_id = None;
+        self.is_loading = true;
+
+        if self.daemon_service.read(cx).is_connected() {
+            self.daemon_service.update(cx, |svc, cx| {
+                svc.list_networks(cx);
+            });
+        }
+        cx.notify();
+    }
+
+    /// Handle networks loaded from daemon
+    pub fn on_networks_loaded(&mut self, response: ListNetworksResponse, cx: &mut Context<Self>) {
+        self.is_loading = false;
+        self.networks = response
+            .networks
+            .into_iter()
+            .map(NetworkViewModel::from)
+            .collect();
+        cx.notify();
+    }
+
+    /// React to a failed network operation
+    pub fn on_operation_failed(&mut self, error: &DaemonError, cx: &mut Context<Self>) {
+        match (error, error.operation()) {
+            (DaemonError::Unavailable { .. }, _) => {}
+            (_, DaemonOperation::ListNetworks) => {
+                self.is_loading = false;
+                cx.notify();
+            }
+            (DaemonError::NotFound { .. }, DaemonOperation::RemoveNetwork) => {
+                // Already gone; drop the stale row
+                if let Some(id) = error.resource() {
+                    self.networks.retain(|n| n.id != id);
+                    if self.selected_id.as_deref() == Some(id) {
+                        self.selected_id = None;
+                    }
+                    cx.notify();
+                }
+            }
+            _ => {}
         }
     }
 
+    /// Apply a network event from the daemon event stream
+    pub fn on_network_event(&mut self, event: &ResourceEvent, cx: &mut Context<Self>) {
+        match event.action.as_str() {
+            "create" => {
+                if self.networks.iter().any(|n| n.id == event.id) {
+                    return;
+                }
+                let attr = |key: &str| event.attributes.get(key).cloned().unwrap_or_default();
+                self.networks.push(NetworkViewModel {
+                    id: event.id.clone(),
+                    name: attr("name"),
+                    driver: attr("type"),
+                    scope: "local".to_string(),
+                    created_at: Utc.timestamp_opt(event.time, 0).single().unwrap_or_else(Utc::now),
+                    internal: false,
+                    attachable: false,
+                    container_count: 0,
+                });
+            }
+            "destroy" => {
+                self.networks.retain(|n| n.id != event.id);
+                if self.selected_id.as_ref() == Some(&event.id) {
+                    self.selected_id = None;
+                }
+            }
+            "connect" | "disconnect" => {
+                let Some(network) = self.networks.iter_mut().find(|n| n.id == event.id) else {
+                    return;
+                };
+                if event.action == "connect" {
+                    network.container_count += 1;
+                } else {
+                    network.container_count = network.container_count.saturating_sub(1);
+                }
+            }
+            _ => return,
+        }
+        cx.notify();
+    }
+
     fn resize_list(&mut self, new_width: f32, cx: &mut Context<Self>) {
         self.list_width = new_width.clamp(LIST_MIN_WIDTH, LIST_MAX_WIDTH);
         cx.notify();
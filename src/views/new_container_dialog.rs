@@ -1,14 +1,21 @@
+use std::collections::HashMap;
+
 use gpui::*;
 use gpui::prelude::*;
 use gpui_component::button::{Button, ButtonVariants};
-use gpui_component::input::{Input, InputState};
+use gpui_component::input::{Input, InputEvent, InputState};
 use gpui_component::select::{Select, SelectItem, SelectState};
 use gpui_component::switch::Switch;
-use gpui_component::Sizable;
+use gpui_component::tab::TabBar;
+use gpui_component::{Disableable, Sizable};
 use gpui_component::Root;
 
-use crate::theme::colors;
-use crate::services::DaemonService;
+use crate::theme::{colors, Theme};
+use crate::services::{
+    format_byte_size, join_shell_words, parse_byte_size, parse_env_file, split_shell_words,
+    validate_container_name, validate_image_reference, ContainerSpec, DaemonEvent, DaemonService,
+    DockerRunCommand, MountKind, MountSpec, PortMapping,
+};
 
 /// Restart policy options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -87,6 +94,69 @@ impl SelectItem for Platform {
     }
 }
 
+/// Editable list in the dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ListKind {
+    Ports,
+    Env,
+    Mounts,
+    Networks,
+    Labels,
+}
+
+impl ListKind {
+    /// One input per column
+    fn placeholders(&self) -> &'static [&'static str] {
+        match self {
+            ListKind::Ports => &["Host port", "Container port (80/tcp)"],
+            ListKind::Env => &["NAME", "value"],
+            ListKind::Mounts => &["Volume or /host/path", "/container/path"],
+            ListKind::Networks => &["Network name"],
+            ListKind::Labels => &["key", "value"],
+        }
+    }
+
+    fn add_label(&self) -> &'static str {
+        match self {
+            ListKind::Ports => "Add port",
+            ListKind::Env => "Add variable",
+            ListKind::Mounts => "Add mount",
+            ListKind::Networks => "Add network",
+            ListKind::Labels => "Add label",
+        }
+    }
+
+    /// Mounts have a read-only toggle
+    fn has_flag(&self) -> bool {
+        matches!(self, ListKind::Mounts)
+    }
+}
+
+/// One row of an editable list
+struct ListRow {
+    id: usize,
+    inputs: Vec<Entity<InputState>>,
+    /// Read-only for mounts
+    flag: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// A form field that can fail validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Field {
+    Image,
+    Name,
+    Command,
+    Entrypoint,
+    Cpus,
+    Memory,
+    /// A list row, by row ID
+    Row(usize),
+}
+
+/// Validation messages by field
+type FormErrors = HashMap<Field, String>;
+
 /// New container dialog as a PopUp window
 pub struct NewContainerDialog {
     // Basic settings - text inputs using gpui-component
@@ -99,12 +169,32 @@ pub struct NewContainerDialog {
     command_input: Entity<InputState>,
     entrypoint_input: Entity<InputState>,
     workdir_input: Entity<InputState>,
+    // Ports, environment, mounts, networks and labels
+    lists: HashMap<ListKind, Vec<ListRow>>,
+    next_row_id: usize,
+    /// Networks on the daemon, offered as shortcuts
+    available_networks: Vec<String>,
+    /// Names already taken on the daemon
+    existing_names: Vec<String>,
+    // Resource limits
+    cpus_input: Entity<InputState>,
+    memory_input: Entity<InputState>,
     // Advanced settings
     privileged: bool,
     read_only: bool,
     use_docker_init: bool,
+    // `docker run` import/export
+    /// Showing the command line instead of the form
+    docker_run_mode: bool,
+    docker_run_input: Entity<InputState>,
+    docker_run_error: Option<String>,
+    /// Options the last import couldn't carry over
+    import_notice: Option<String>,
     // Services
     daemon_service: Entity<DaemonService>,
+    _daemon_subscription: Subscription,
+    /// Re-validate whenever a field changes
+    _input_subscriptions: Vec<Subscription>,
 }
 
 impl NewContainerDialog {
@@ -150,6 +240,59 @@ impl NewContainerDialog {
             )
         });
 
+        let cpus_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("unlimited")
+        });
+
+        let memory_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("unlimited")
+        });
+
+        let docker_run_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(8)
+                .placeholder("docker run -p 8080:80 --name web nginx")
+        });
+
+        let input_subscriptions = [
+            &image_input,
+            &name_input,
+            &command_input,
+            &entrypoint_input,
+            &cpus_input,
+            &memory_input,
+        ]
+        .into_iter()
+        .map(|input| cx.subscribe(input, |_, _, _: &InputEvent, cx| cx.notify()))
+        .collect();
+
+        // Offer the daemon's networks when attaching, and catch name clashes early
+        let daemon_subscription = cx.subscribe(&daemon_service, |this, _, event: &DaemonEvent, cx| {
+            match event {
+                DaemonEvent::NetworksLoaded(response) => {
+                    this.available_networks = response.networks.iter().map(|n| n.name.clone()).collect();
+                    this.available_networks.sort();
+                    cx.notify();
+                }
+                DaemonEvent::ContainersLoaded(response) => {
+                    this.existing_names = response
+                        .containers
+                        .iter()
+                        .map(|c| c.name.trim_start_matches('/').to_string())
+                        .collect();
+                    cx.notify();
+                }
+                _ => {}
+            }
+        });
+        daemon_service.update(cx, |svc, cx| {
+            if svc.is_connected() {
+                svc.list_networks(cx);
+                svc.list_containers(true, cx);
+            }
+        });
+
         Self {
             image_input,
             platform_select,
@@ -159,61 +302,405 @@ impl NewContainerDialog {
             command_input,
             entrypoint_input,
             workdir_input,
+            lists: HashMap::new(),
+            next_row_id: 0,
+            available_networks: Vec::new(),
+            existing_names: Vec::new(),
+            cpus_input,
+            memory_input,
             privileged: false,
             read_only: false,
             use_docker_init: false,
+            docker_run_mode: false,
+            docker_run_input,
+            docker_run_error: None,
+            import_notice: None,
             daemon_service,
+            _daemon_subscription: daemon_subscription,
+            _input_subscriptions: input_subscriptions,
         }
     }
 
-    fn create_container(&mut self, start: bool, window: &mut Window, cx: &mut Context<Self>) {
-        // Read values from InputState entities
-        let image = self.image_input.read(cx).value().to_string();
-        let name_value = self.name_input.read(cx).value().to_string();
-        let command_value = self.command_input.read(cx).value().to_string();
-        let entrypoint_value = self.entrypoint_input.read(cx).value().to_string();
-        let workdir_value = self.workdir_input.read(cx).value().to_string();
-
-        // Read selected values from SelectState entities
-        let _platform = self.platform_select.read(cx).selected_value().copied().unwrap_or_default();
-        let _restart_policy = self.restart_policy_select.read(cx).selected_value().copied().unwrap_or_default();
-
-        if image.is_empty() {
-            // TODO: Show validation error
-            return;
+    /// Append a row to a list, pre-filled with `values`
+    fn add_row(&mut self, kind: ListKind, values: &[&str], window: &mut Window, cx: &mut Context<Self>) {
+        let inputs: Vec<Entity<InputState>> = kind
+            .placeholders()
+            .iter()
+            .enumerate()
+            .map(|(column, placeholder)| {
+                let value = values.get(column).copied().unwrap_or_default().to_string();
+                cx.new(|cx| InputState::new(window, cx).placeholder(*placeholder).default_value(value))
+            })
+            .collect();
+        let subscriptions = inputs
+            .iter()
+            .map(|input| cx.subscribe(input, |_, _, _: &InputEvent, cx| cx.notify()))
+            .collect();
+        self.next_row_id += 1;
+        self.lists.entry(kind).or_default().push(ListRow {
+            id: self.next_row_id,
+            inputs,
+            flag: false,
+            _subscriptions: subscriptions,
+        });
+        cx.notify();
+    }
+
+    fn remove_row(&mut self, kind: ListKind, id: usize, cx: &mut Context<Self>) {
+        if let Some(rows) = self.lists.get_mut(&kind) {
+            rows.retain(|row| row.id != id);
+        }
+        cx.notify();
+    }
+
+    fn toggle_row_flag(&mut self, kind: ListKind, id: usize, cx: &mut Context<Self>) {
+        if let Some(row) = self.lists.get_mut(&kind).and_then(|rows| rows.iter_mut().find(|r| r.id == id)) {
+            row.flag = !row.flag;
+        }
+        cx.notify();
+    }
+
+    /// Row IDs with trimmed values, skipping rows left blank
+    fn row_values(&self, kind: ListKind, cx: &App) -> Vec<(usize, Vec<String>, bool)> {
+        self.lists
+            .get(&kind)
+            .into_iter()
+            .flatten()
+            .map(|row| {
+                let values: Vec<String> = row
+                    .inputs
+                    .iter()
+                    .map(|input| input.read(cx).value().trim().to_string())
+                    .collect();
+                (row.id, values, row.flag)
+            })
+            .filter(|(_, values, _)| values.iter().any(|v| !v.is_empty()))
+            .collect()
+    }
+
+    /// Pick a .env file and append its variables
+    fn load_env_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Load".into()),
+        });
+        cx.spawn_in(window, async move |this: WeakEntity<Self>, cx: &mut AsyncWindowContext| {
+            let Ok(Ok(Some(paths))) = paths.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let contents = match std::fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    tracing::warn!("Failed to read env file {}: {}", path.display(), e);
+                    return;
+                }
+            };
+            let variables = parse_env_file(&contents);
+            tracing::info!("Loaded {} variables from {}", variables.len(), path.display());
+            this.update_in(cx, |this, window, cx| {
+                // Fill blank rows first so an empty starter row doesn't linger
+                if let Some(rows) = this.lists.get_mut(&ListKind::Env) {
+                    rows.retain(|row| row.inputs.iter().any(|input| !input.read(cx).value().trim().is_empty()));
+                }
+                for (name, value) in &variables {
+                    this.add_row(ListKind::Env, &[name.as_str(), value.as_str()], window, cx);
+                }
+            }).ok();
+        }).detach();
+    }
+
+    /// Switch between the form and the `docker run` command line
+    fn set_docker_run_mode(&mut self, docker_run: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if docker_run && !self.docker_run_mode {
+            // Start from the form as it stands, if it's complete
+            if let Ok(spec) = self.build_spec(cx) {
+                let line = spec.to_docker_run();
+                self.docker_run_input.update(cx, |input, cx| input.set_value(line, window, cx));
+            }
+            self.docker_run_error = None;
         }
+        self.docker_run_mode = docker_run;
+        cx.notify();
+    }
+
+    fn copy_docker_run(&self, cx: &mut Context<Self>) {
+        let line = self.docker_run_input.read(cx).value().trim().to_string();
+        if !line.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(line));
+        }
+    }
 
-        let name = if name_value.is_empty() {
-            None
-        } else {
-            Some(name_value)
+    /// Parse the pasted command into the form
+    fn apply_docker_run(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let line = self.docker_run_input.read(cx).value().to_string();
+        let command = match DockerRunCommand::parse(&line) {
+            Ok(command) => command,
+            Err(e) => {
+                self.docker_run_error = Some(e);
+                cx.notify();
+                return;
+            }
         };
-        let cmd = if command_value.is_empty() {
-            None
-        } else {
-            Some(command_value.split_whitespace().map(String::from).collect())
+
+        let mut ignored = command.ignored;
+        ignored.extend(self.apply_spec(command.spec, window, cx));
+        self.import_notice = (!ignored.is_empty()).then(|| format!("Not imported: {}", ignored.join(", ")));
+        self.docker_run_error = None;
+        self.docker_run_mode = false;
+        cx.notify();
+    }
+
+    /// Replace the form with `spec`, returning what the form can't show
+    fn apply_spec(&mut self, spec: ContainerSpec, window: &mut Window, cx: &mut Context<Self>) -> Vec<String> {
+        let mut ignored = Vec::new();
+        let mut set = |input: &Entity<InputState>, value: String, cx: &mut Context<Self>| {
+            input.update(cx, |input, cx| input.set_value(value, window, cx));
         };
-        let entrypoint = if entrypoint_value.is_empty() {
-            None
-        } else {
-            Some(entrypoint_value.split_whitespace().map(String::from).collect())
+        set(&self.image_input, spec.image, cx);
+        set(&self.name_input, spec.name.unwrap_or_default(), cx);
+        set(&self.command_input, join_shell_words(&spec.cmd), cx);
+        set(&self.entrypoint_input, join_shell_words(&spec.entrypoint), cx);
+        set(&self.workdir_input, spec.working_dir.unwrap_or_default(), cx);
+        set(&self.cpus_input, spec.cpus.map(|cpus| cpus.to_string()).unwrap_or_default(), cx);
+        set(&self.memory_input, spec.memory_bytes.map(format_byte_size).unwrap_or_default(), cx);
+
+        let platform = match spec.platform.as_deref() {
+            None => Some(0),
+            Some(label) => Platform::all().iter().position(|p| p.label() == label),
         };
-        let working_dir = if workdir_value.is_empty() {
-            None
-        } else {
-            Some(workdir_value)
+        if platform.is_none() {
+            ignored.push(format!("--platform {}", spec.platform.unwrap_or_default()));
+        }
+        self.platform_select.update(cx, |select, cx| {
+            select.set_selected_index(Some(gpui_component::IndexPath::default().row(platform.unwrap_or(0))), window, cx);
+        });
+        let restart_policy = match spec.restart_policy.as_deref() {
+            None => Some(0),
+            Some(label) => RestartPolicy::all().iter().position(|p| p.label() == label),
         };
+        if restart_policy.is_none() {
+            ignored.push(format!("--restart {}", spec.restart_policy.unwrap_or_default()));
+        }
+        self.restart_policy_select.update(cx, |select, cx| {
+            select.set_selected_index(
+                Some(gpui_component::IndexPath::default().row(restart_policy.unwrap_or(0))),
+                window,
+                cx,
+            );
+        });
 
-        tracing::info!(
-            "Creating container: image={}, name={:?}, start={}",
+        self.remove_after_stop = spec.auto_remove;
+        self.privileged = spec.privileged;
+        self.read_only = spec.read_only;
+        self.use_docker_init = spec.init;
+
+        self.lists.clear();
+        for port in &spec.ports {
+            if !port.host_ip.is_empty() {
+                ignored.push(format!("host address {} for port {}", port.host_ip, port.host_port));
+            }
+            let container = if port.protocol == "tcp" {
+                port.container_port.to_string()
+            } else {
+                format!("{}/{}", port.container_port, port.protocol)
+            };
+            let host_port = port.host_port.to_string();
+            self.add_row(ListKind::Ports, &[host_port.as_str(), container.as_str()], window, cx);
+        }
+        for (name, value) in &spec.env {
+            self.add_row(ListKind::Env, &[name.as_str(), value.as_str()], window, cx);
+        }
+        for mount in &spec.mounts {
+            self.add_row(ListKind::Mounts, &[mount.source.as_str(), mount.target.as_str()], window, cx);
+            if let Some(row) = self.lists.get_mut(&ListKind::Mounts).and_then(|rows| rows.last_mut()) {
+                row.flag = mount.read_only;
+            }
+        }
+        for network in &spec.networks {
+            self.add_row(ListKind::Networks, &[network.as_str()], window, cx);
+        }
+        for (key, value) in &spec.labels {
+            self.add_row(ListKind::Labels, &[key.as_str(), value.as_str()], window, cx);
+        }
+        ignored
+    }
+
+    /// Collect the form into a spec, or every problem with it
+    fn build_spec(&self, cx: &App) -> Result<ContainerSpec, FormErrors> {
+        let value = |input: &Entity<InputState>| input.read(cx).value().trim().to_string();
+        let optional = |input: &Entity<InputState>| Some(value(input)).filter(|v| !v.is_empty());
+        let mut errors = FormErrors::new();
+
+        let image = value(&self.image_input);
+        if let Err(e) = validate_image_reference(&image) {
+            errors.insert(Field::Image, e);
+        }
+
+        let name = optional(&self.name_input);
+        if let Some(name) = &name {
+            if let Err(e) = validate_container_name(name) {
+                errors.insert(Field::Name, e);
+            } else if self.existing_names.contains(name) {
+                errors.insert(Field::Name, format!("A container named \"{}\" already exists", name));
+            }
+        }
+
+        let cmd = split_shell_words(&value(&self.command_input)).unwrap_or_else(|e| {
+            errors.insert(Field::Command, format!("Invalid command: {}", e));
+            Vec::new()
+        });
+        let entrypoint = split_shell_words(&value(&self.entrypoint_input)).unwrap_or_else(|e| {
+            errors.insert(Field::Entrypoint, format!("Invalid entrypoint: {}", e));
+            Vec::new()
+        });
+
+        let platform = self.platform_select.read(cx).selected_value().copied().unwrap_or_default();
+        let restart_policy = self.restart_policy_select.read(cx).selected_value().copied().unwrap_or_default();
+
+        let mut ports: Vec<PortMapping> = Vec::new();
+        for (id, values, _) in self.row_values(ListKind::Ports, cx) {
+            match PortMapping::parse(&values[0], &values[1]) {
+                Ok(port) if ports.iter().any(|p| p.host_port == port.host_port && p.protocol == port.protocol) => {
+                    errors.insert(Field::Row(id), format!("Host port {} is already published", port.host_port));
+                }
+                Ok(port) => ports.push(port),
+                Err(e) => {
+                    errors.insert(Field::Row(id), e);
+                }
+            }
+        }
+
+        // Environment and labels: a name is required and may appear once
+        let mut pairs = |kind: ListKind, what: &str| -> Vec<(String, String)> {
+            let mut pairs: Vec<(String, String)> = Vec::new();
+            for (id, mut values, _) in self.row_values(kind, cx) {
+                let key = values.remove(0);
+                let error = if key.is_empty() {
+                    Some(format!("{} is required", what))
+                } else if key.contains(|c: char| c == '=' || c.is_whitespace()) {
+                    Some(format!("{} cannot contain spaces or \"=\"", what))
+                } else if pairs.iter().any(|(existing, _)| *existing == key) {
+                    Some(format!("{} is set more than once", key))
+                } else {
+                    None
+                };
+                match error {
+                    Some(error) => {
+                        errors.insert(Field::Row(id), error);
+                    }
+                    None => pairs.push((key, values.remove(0))),
+                }
+            }
+            pairs
+        };
+        let env = pairs(ListKind::Env, "Variable name");
+        let labels = pairs(ListKind::Labels, "Label key");
+
+        let mut mounts: Vec<MountSpec> = Vec::new();
+        for (id, mut values, read_only) in self.row_values(ListKind::Mounts, cx) {
+            let kind = MountKind::for_source(&values[0]);
+            if kind == MountKind::Bind {
+                match MountKind::bind_source(&values[0]) {
+                    Ok(source) => values[0] = source,
+                    Err(e) => {
+                        errors.insert(Field::Row(id), e);
+                        continue;
+                    }
+                }
+            }
+            let error = if values[0].is_empty() {
+                Some("Source is required".to_string())
+            } else if !values[1].starts_with('/') {
+                Some("Container path must be absolute".to_string())
+            } else if mounts.iter().any(|m| m.target == values[1]) {
+                Some(format!("{} is already mounted", values[1]))
+            } else {
+                None
+            };
+            match error {
+                Some(error) => {
+                    errors.insert(Field::Row(id), error);
+                }
+                None => mounts.push(MountSpec {
+                    kind,
+                    source: values[0].clone(),
+                    target: values[1].clone(),
+                    read_only,
+                }),
+            }
+        }
+
+        let mut networks: Vec<String> = Vec::new();
+        for (id, mut values, _) in self.row_values(ListKind::Networks, cx) {
+            let network = values.remove(0);
+            if networks.contains(&network) {
+                errors.insert(Field::Row(id), format!("{} is already attached", network));
+            } else {
+                networks.push(network);
+            }
+        }
+
+        let cpus = optional(&self.cpus_input).and_then(|text| {
+            let cpus = text.parse::<f64>().ok().filter(|cpus| cpus.is_finite() && *cpus > 0.0);
+            if cpus.is_none() {
+                errors.insert(Field::Cpus, format!("\"{}\" is not a positive number", text));
+            }
+            cpus
+        });
+        let memory_bytes = optional(&self.memory_input).and_then(|text| {
+            let bytes = parse_byte_size(&text);
+            if bytes.is_none() {
+                errors.insert(Field::Memory, format!("\"{}\" is not a size like 512m or 2g", text));
+            }
+            bytes
+        });
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(ContainerSpec {
             image,
             name,
+            cmd,
+            entrypoint,
+            working_dir: optional(&self.workdir_input),
+            platform: (platform != Platform::Auto).then(|| platform.label().to_string()),
+            restart_policy: (restart_policy != RestartPolicy::No).then(|| restart_policy.label().to_string()),
+            auto_remove: self.remove_after_stop,
+            privileged: self.privileged,
+            read_only: self.read_only,
+            init: self.use_docker_init,
+            ports,
+            env,
+            mounts,
+            networks,
+            labels,
+            cpus,
+            memory_bytes,
+        })
+    }
+
+    fn create_container(&mut self, start: bool, window: &mut Window, cx: &mut Context<Self>) {
+        // The buttons are disabled while the form is invalid
+        let Ok(spec) = self.build_spec(cx) else {
+            return;
+        };
+
+        tracing::info!(
+            "Creating container: image={}, name={:?}, start={}",
+            spec.image,
+            spec.name,
             start
         );
 
         // Call daemon service to create container
         self.daemon_service.update(cx, |svc, cx| {
-            svc.create_container(image, name, cmd, entrypoint, working_dir, start, cx);
+            svc.create_container(spec, start, cx);
         });
 
         // Close the popup window
@@ -227,6 +714,12 @@ impl NewContainerDialog {
 
 impl Render for NewContainerDialog {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let errors = self.build_spec(cx).err().unwrap_or_default();
+        let can_create = errors.is_empty();
+        // Don't greet an untouched form with "Image is required"
+        let image_blank = self.image_input.read(cx).value().trim().is_empty();
+        let error = |field: Field| errors.get(&field).map(String::as_str);
+
         div()
             .id("new-container-dialog")
             .size_full()
@@ -240,72 +733,124 @@ impl Render for NewContainerDialog {
             .shadow_lg()
             // Title bar
             .child(self.render_title_bar(cx))
+            .when(self.docker_run_mode, |el| el.child(self.render_docker_run(cx)))
             // Scrollable form content
-            .child(
-                div()
-                    .id("form-content")
-                    .flex_1()
-                    .overflow_y_scroll()
-                    .px_4()
-                    .py_2()
-                    .flex()
-                    .flex_col()
-                    .gap_1()
-                    // Basic settings
-                    .child(self.render_input_field("Image", self.image_input.clone()))
-                    .child(self.render_select_field("Platform", self.platform_select.clone()))
-                    .child(self.render_input_field("Name", self.name_input.clone()))
-                    .child(self.render_switch_field(
-                        "remove-after-stop",
-                        "Remove after stop",
-                        Some("Automatically delete the container after it stops. (--rm)"),
-                        self.remove_after_stop,
-                        cx,
-                    ))
-                    .child(self.render_select_field("Restart policy", self.restart_policy_select.clone()))
-                    // Payload section
-                    .child(self.render_section_header("Payload"))
-                    .child(self.render_input_field_with_desc(
-                        "Command",
-                        self.command_input.clone(),
-                        "Command to run in the container",
-                    ))
-                    .child(self.render_input_field_with_desc(
-                        "Entrypoint",
-                        self.entrypoint_input.clone(),
-                        "If set, command will be passed to the entrypoint instead of shell. (--entrypoint)",
-                    ))
-                    .child(self.render_input_field_with_desc(
-                        "Working directory",
-                        self.workdir_input.clone(),
-                        "Working directory for the command. (--workdir)",
-                    ))
-                    // Advanced section
-                    .child(self.render_section_header("Advanced"))
-                    .child(self.render_switch_field(
-                        "privileged",
-                        "Privileged",
-                        Some("Allow access to privileged APIs and resources. (--privileged)"),
-                        self.privileged,
-                        cx,
-                    ))
-                    .child(self.render_switch_field(
-                        "read-only",
-                        "Read-only",
-                        Some("Mount the container's root filesystem as read-only. (--read-only)"),
-                        self.read_only,
-                        cx,
-                    ))
-                    .child(self.render_switch_field(
-                        "docker-init",
-                        "Use docker-init",
-                        Some("Run the container payload under a docker-init process. (--init)"),
-                        self.use_docker_init,
-                        cx,
-                    ))
-            )
+            .when(!self.docker_run_mode, |el| {
+                el.child(
+                    div()
+                        .id("form-content")
+                        .flex_1()
+                        .overflow_y_scroll()
+                        .px_4()
+                        .py_2()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .when_some(self.import_notice.clone(), |el, notice| {
+                            el.child(
+                                div()
+                                    .py_2()
+                                    .text_xs()
+                                    .text_color(colors::warning())
+                                    .child(notice),
+                            )
+                        })
+                        // Basic settings
+                        .child(self.render_input_field(
+                            "Image",
+                            self.image_input.clone(),
+                            error(Field::Image).filter(|_| !image_blank),
+                        ))
+                        .child(self.render_select_field("Platform", self.platform_select.clone()))
+                        .child(self.render_input_field("Name", self.name_input.clone(), error(Field::Name)))
+                        .child(self.render_switch_field(
+                            "remove-after-stop",
+                            "Remove after stop",
+                            Some("Automatically delete the container after it stops. (--rm)"),
+                            self.remove_after_stop,
+                            cx,
+                        ))
+                        .child(self.render_select_field("Restart policy", self.restart_policy_select.clone()))
+                        // Payload section
+                        .child(self.render_section_header("Payload"))
+                        .child(self.render_input_field_with_desc(
+                            "Command",
+                            self.command_input.clone(),
+                            "Command to run in the container",
+                            error(Field::Command),
+                        ))
+                        .child(self.render_input_field_with_desc(
+                            "Entrypoint",
+                            self.entrypoint_input.clone(),
+                            "If set, command will be passed to the entrypoint instead of shell. (--entrypoint)",
+                            error(Field::Entrypoint),
+                        ))
+                        .child(self.render_input_field_with_desc(
+                            "Working directory",
+                            self.workdir_input.clone(),
+                            "Working directory for the command. (--workdir)",
+                            None,
+                        ))
+                        // Ports, environment, mounts, networks and labels
+                        .child(self.render_list_section("Ports", ListKind::Ports, None, &errors, cx))
+                        .child(self.render_list_section(
+                            "Environment",
+                            ListKind::Env,
+                            Some(
+                                list_action("load-env-file", "Load .env...")
+                                    .on_click(cx.listener(|this, _, window, cx| this.load_env_file(window, cx)))
+                                    .into_any_element(),
+                            ),
+                            &errors,
+                            cx,
+                        ))
+                        .child(self.render_list_section("Volumes", ListKind::Mounts, None, &errors, cx))
+                        .child(self.render_list_section("Networks", ListKind::Networks, None, &errors, cx))
+                        .when(!self.available_networks.is_empty(), |el| {
+                            el.child(self.render_network_suggestions(cx))
+                        })
+                        .child(self.render_list_section("Labels", ListKind::Labels, None, &errors, cx))
+                        // Resources section
+                        .child(self.render_section_header("Resources"))
+                        .child(self.render_input_field_with_desc(
+                            "CPUs",
+                            self.cpus_input.clone(),
+                            "Number of CPUs, e.g. 1.5. (--cpus)",
+                            error(Field::Cpus),
+                        ))
+                        .child(self.render_input_field_with_desc(
+                            "Memory",
+                            self.memory_input.clone(),
+                            "Memory limit, e.g. 512m or 2g. (--memory)",
+                            error(Field::Memory),
+                        ))
+                        // Advanced section
+                        .child(self.render_section_header("Advanced"))
+                        .child(self.render_switch_field(
+                            "privileged",
+                            "Privileged",
+                            Some("Allow access to privileged APIs and resources. (--privileged)"),
+                            self.privileged,
+                            cx,
+                        ))
+                        .child(self.render_switch_field(
+                            "read-only",
+                            "Read-only",
+                            Some("Mount the container's root filesystem as read-only. (--read-only)"),
+                            self.read_only,
+                            cx,
+                        ))
+                        .child(self.render_switch_field(
+                            "docker-init",
+                            "Use docker-init",
+                            Some("Run the container payload under a docker-init process. (--init)"),
+                            self.use_docker_init,
+                            cx,
+                        ))
+                )
+            })
             // Fixed footer (outside scroll area)
-            .child(self.render_footer(cx))
+            .child(self.render_footer(can_create, cx))
     }
 }
 
@@ -328,23 +873,73 @@ impl NewContainerDialog {
             )
             .child(
                 div()
-                    .id("close-button")
-                    .w(px(24.0))
-                    .h(px(24.0))
                     .flex()
                     .items_center()
-                    .justify_center()
-                    .rounded(px(4.0))
-                    .cursor_pointer()
-                    .hover(|el| el.bg(colors::hover()))
-                    .on_click(cx.listener(|this, _, window, cx| {
-                        this.close_dialog(window, cx);
-                    }))
+                    .gap_2()
                     .child(
-                        svg()
-                            .path("icons/close.svg")
-                            .size(px(16.0))
-                            .text_color(colors::text_secondary()),
+                        TabBar::new("dialog-mode")
+                            .segmented()
+                            .small()
+                            .children(["Form", "docker run"])
+                            .selected_index(usize::from(self.docker_run_mode))
+                            .on_click(cx.listener(|this, index: &usize, window, cx| {
+                                this.set_docker_run_mode(*index == 1, window, cx);
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("close-button")
+                            .w(px(24.0))
+                            .h(px(24.0))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded(px(4.0))
+                            .cursor_pointer()
+                            .hover(|el| el.bg(colors::hover()))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.close_dialog(window, cx);
+                            }))
+                            .child(
+                                svg()
+                                    .path("icons/close.svg")
+                                    .size(px(16.0))
+                                    .text_color(colors::text_secondary()),
+                            ),
+                    ),
+            )
+    }
+
+    /// Paste a `docker run` command, or copy the form as one
+    fn render_docker_run(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .flex_1()
+            .px_4()
+            .py_3()
+            .flex()
+            .flex_col()
+            .gap_2()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(colors::text_secondary())
+                    .child("Paste a docker run command to fill in the form, or copy the current settings as one."),
+            )
+            .child(Input::new(&self.docker_run_input).small())
+            .children(self.docker_run_error.as_deref().map(field_error))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_end()
+                    .gap_3()
+                    .child(
+                        list_action("copy-docker-run", "Copy")
+                            .on_click(cx.listener(|this, _, _, cx| this.copy_docker_run(cx))),
+                    )
+                    .child(
+                        list_action("apply-docker-run", "Apply to Form")
+                            .on_click(cx.listener(|this, _, window, cx| this.apply_docker_run(window, cx))),
                     ),
             )
     }
@@ -359,21 +954,186 @@ impl NewContainerDialog {
             .child(title)
     }
 
+    /// Render an editable list with a header, its rows and an add link
+    fn render_list_section(
+        &self,
+        title: &'static str,
+        kind: ListKind,
+        extra_action: Option<AnyElement>,
+        errors: &FormErrors,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let rows = self.lists.get(&kind).map(Vec::as_slice).unwrap_or_default();
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .mt_4()
+                    .mb_1()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(colors::text())
+                            .child(title),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_1()
+                            .children(extra_action)
+                            .child(
+                                list_action(SharedString::from(format!("add-{:?}", kind)), kind.add_label())
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.add_row(kind, &[], window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+            .when(rows.is_empty(), |el| {
+                el.child(
+                    div()
+                        .pb_2()
+                        .border_b_1()
+                        .border_color(colors::border())
+                        .text_xs()
+                        .text_color(colors::text_muted())
+                        .child("None"),
+                )
+            })
+            .children(rows.iter().map(|row| {
+                let error = errors.get(&Field::Row(row.id)).map(String::as_str);
+                self.render_list_row(kind, row, error, cx)
+            }))
+    }
+
+    fn render_list_row(
+        &self,
+        kind: ListKind,
+        row: &ListRow,
+        error: Option<&str>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let id = row.id;
+
+        let fields = div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .children(row.inputs.iter().map(|input| div().flex_1().min_w_0().child(Input::new(input).small())))
+            .when(kind.has_flag(), |el| {
+                el.child(
+                    div()
+                        .id(SharedString::from(format!("row-flag-{}", id)))
+                        .px_1p5()
+                        .py_0p5()
+                        .rounded(px(4.0))
+                        .border_1()
+                        .text_xs()
+                        .cursor_pointer()
+                        .map(|el| {
+                            if row.flag {
+                                el.border_color(colors::accent()).text_color(colors::accent())
+                            } else {
+                                el.border_color(colors::border()).text_color(colors::text_muted())
+                            }
+                        })
+                        .on_click(cx.listener(move |this, _, _, cx| this.toggle_row_flag(kind, id, cx)))
+                        .child("RO"),
+                )
+            })
+            .child(
+                div()
+                    .id(SharedString::from(format!("row-remove-{}", id)))
+                    .w(px(20.0))
+                    .h(px(20.0))
+                    .flex()
+                    .flex_shrink_0()
+                    .items_center()
+                    .justify_center()
+                    .rounded(px(4.0))
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()))
+                    .on_click(cx.listener(move |this, _, _, cx| this.remove_row(kind, id, cx)))
+                    .child(
+                        svg()
+                            .path("icons/close.svg")
+                            .size(px(12.0))
+                            .text_color(colors::text_secondary()),
+                    ),
+            );
+
+        div()
+            .py_1()
+            .flex()
+            .flex_col()
+            .gap_0p5()
+            .child(fields)
+            .children(error.map(field_error))
+    }
+
+    /// Networks on the daemon that aren't attached yet, as one-click chips
+    fn render_network_suggestions(&self, cx: &Context<Self>) -> impl IntoElement {
+        let attached: Vec<String> = self
+            .row_values(ListKind::Networks, cx)
+            .into_iter()
+            .map(|(_, mut values, _)| values.remove(0))
+            .collect();
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .pb_2()
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(colors::text_secondary())
+                    .child("Available:"),
+            )
+            .children(
+                self.available_networks
+                    .iter()
+                    .filter(|name| !attached.contains(name))
+                    .map(|name| {
+                        let network = name.clone();
+                        Theme::badge()
+                            .id(SharedString::from(format!("network-{}", name)))
+                            .cursor_pointer()
+                            .hover(|el| el.bg(colors::hover()))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.add_row(ListKind::Networks, &[network.as_str()], window, cx);
+                            }))
+                            .child(name.clone())
+                    }),
+            )
+    }
+
     /// Render a text input field with a label using gpui-component's Input
     fn render_input_field(
         &self,
         label: &'static str,
         input: Entity<InputState>,
+        error: Option<&str>,
     ) -> impl IntoElement {
         div()
             .py_2()
             .flex()
-            .items_center()
+            .items_start()
             .justify_between()
             .border_b_1()
             .border_color(colors::border())
             .child(
                 div()
+                    .pt_1()
                     .text_sm()
                     .text_color(colors::text())
                     .child(label),
@@ -381,7 +1141,11 @@ impl NewContainerDialog {
             .child(
                 div()
                     .w(px(200.0))
-                    .child(Input::new(&input).small()),
+                    .flex()
+                    .flex_col()
+                    .gap_0p5()
+                    .child(Input::new(&input).small())
+                    .children(error.map(field_error)),
             )
     }
 
@@ -391,6 +1155,7 @@ impl NewContainerDialog {
         label: &'static str,
         input: Entity<InputState>,
         description: &'static str,
+        error: Option<&str>,
     ) -> impl IntoElement {
         div()
             .py_2()
@@ -421,7 +1186,11 @@ impl NewContainerDialog {
             .child(
                 div()
                     .w(px(200.0))
-                    .child(Input::new(&input).small()),
+                    .flex()
+                    .flex_col()
+                    .gap_0p5()
+                    .child(Input::new(&input).small())
+                    .children(error.map(field_error)),
             )
     }
 
@@ -508,7 +1277,7 @@ impl NewContainerDialog {
             )
     }
 
-    fn render_footer(&self, cx: &Context<Self>) -> impl IntoElement {
+    fn render_footer(&self, can_create: bool, cx: &Context<Self>) -> impl IntoElement {
         // Get entity handle for use in button callbacks
         // gpui-component Button.on_click expects Fn(&ClickEvent, &mut Window, &mut App)
         // so we need to use entity.update() pattern instead of cx.listener()
@@ -558,6 +1327,7 @@ impl NewContainerDialog {
                             .ghost()
                             .small()
                             .child("Create")
+                            .disabled(!can_create)
                             .on_click(move |_, window, cx| {
                                 create_entity.update(cx, |this, cx| {
                                     this.create_container(false, window, cx);
@@ -570,6 +1340,7 @@ impl NewContainerDialog {
                             .primary()
                             .small()
                             .child("Create & Start")
+                            .disabled(!can_create)
                             .on_click(move |_, window, cx| {
                                 create_start_entity.update(cx, |this, cx| {
                                     this.create_container(true, window, cx);
@@ -590,7 +1361,7 @@ pub fn open_new_container_dialog(
     cx: &mut App,
 ) {
     // Dialog size
-    let dialog_size = size(px(480.0), px(560.0));
+    let dialog_size = size(px(480.0), px(640.0));
 
     // Calculate centered position relative to parent window
     let x = parent_bounds.origin.x + (parent_bounds.size.width - dialog_size.width) / 2.0;
@@ -623,3 +1394,22 @@ pub fn open_new_container_dialog(
         cx.new(|cx| Root::new(dialog_view, window, cx))
     });
 }
+
+/// Small link-style button in a list section header
+fn list_action(id: impl Into<ElementId>, label: &'static str) -> Stateful<Div> {
+    div()
+        .id(id)
+        .text_xs()
+        .text_color(colors::accent())
+        .cursor_pointer()
+        .hover(|el| el.text_color(colors::accent_hover()))
+        .child(label)
+}
+
+/// Validation message shown under a field
+fn field_error(message: &str) -> Div {
+    div()
+        .text_xs()
+        .text_color(colors::error())
+        .child(message.to_string())
+}
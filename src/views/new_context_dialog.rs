@@ -0,0 +1,454 @@
+use std::path::PathBuf;
+
+use gpui::*;
+use gpui::prelude::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::{Input, InputState};
+use gpui_component::select::{Select, SelectItem, SelectState};
+use gpui_component::Sizable;
+use gpui_component::Root;
+
+use crate::services::{DaemonContext, DaemonEndpoint, SettingsStore, LOCAL_CONTEXT};
+use crate::theme::colors;
+
+/// Context kind options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContextKind {
+    #[default]
+    Ssh,
+    Tcp,
+    Local,
+}
+
+impl ContextKind {
+    fn label(&self) -> &'static str {
+        match self {
+            ContextKind::Ssh => "SSH tunnel",
+            ContextKind::Tcp => "TCP + TLS",
+            ContextKind::Local => "Unix socket",
+        }
+    }
+
+    fn all() -> Vec<ContextKind> {
+        vec![ContextKind::Ssh, ContextKind::Tcp, ContextKind::Local]
+    }
+}
+
+impl SelectItem for ContextKind {
+    type Value = Self;
+
+    fn title(&self) -> SharedString {
+        SharedString::from(self.label())
+    }
+
+    fn value(&self) -> &Self::Value {
+        self
+    }
+}
+
+/// Dialog for adding a daemon context, shown as a PopUp window
+pub struct NewContextDialog {
+    name_input: Entity<InputState>,
+    kind_select: Entity<SelectState<Vec<ContextKind>>>,
+    // Local
+    socket_path_input: Entity<InputState>,
+    // TCP + TLS
+    host_input: Entity<InputState>,
+    port_input: Entity<InputState>,
+    ca_cert_input: Entity<InputState>,
+    client_cert_input: Entity<InputState>,
+    client_key_input: Entity<InputState>,
+    // SSH
+    destination_input: Entity<InputState>,
+    ssh_port_input: Entity<InputState>,
+    identity_input: Entity<InputState>,
+    remote_socket_input: Entity<InputState>,
+    /// Validation error shown in the footer
+    error: Option<String>,
+}
+
+impl NewContextDialog {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut input = |placeholder: &'static str| {
+            cx.new(|cx| InputState::new(window, cx).placeholder(placeholder))
+        };
+
+        let name_input = input("e.g. staging");
+        let socket_path_input = input("/var/run/arcbox.sock");
+        let host_input = input("daemon.example.com");
+        let port_input = input("2376");
+        let ca_cert_input = input("~/.arcbox/certs/ca.pem");
+        let client_cert_input = input("~/.arcbox/certs/cert.pem");
+        let client_key_input = input("~/.arcbox/certs/key.pem");
+        let destination_input = input("user@host");
+        let ssh_port_input = input("22");
+        let identity_input = input("default");
+        let remote_socket_input = input("/home/user/.arcbox/arcbox.sock");
+
+        let kind_select = cx.new(|cx| {
+            SelectState::new(
+                ContextKind::all(),
+                Some(gpui_component::IndexPath::default().row(0)),
+                window,
+                cx,
+            )
+        });
+
+        // Re-render when the kind changes to show the matching fields
+        cx.observe(&kind_select, |_, _, cx| cx.notify()).detach();
+
+        Self {
+            name_input,
+            kind_select,
+            socket_path_input,
+            host_input,
+            port_input,
+            ca_cert_input,
+            client_cert_input,
+            client_key_input,
+            destination_input,
+            ssh_port_input,
+            identity_input,
+            remote_socket_input,
+            error: None,
+        }
+    }
+
+    fn selected_kind(&self, cx: &App) -> ContextKind {
+        self.kind_select
+            .read(cx)
+            .selected_value()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Build a context from the form, or describe what is missing
+    fn build_context(&self, cx: &App) -> Result<DaemonContext, String> {
+        let value = |input: &Entity<InputState>| input.read(cx).value().trim().to_string();
+        let required = |input: &Entity<InputState>, label: &str| {
+            let v = value(input);
+            if v.is_empty() {
+                Err(format!("{} is required", label))
+            } else {
+                Ok(v)
+            }
+        };
+        let path = |input: &Entity<InputState>, label: &str| required(input, label).map(expand_tilde);
+
+        let name = required(&self.name_input, "Name")?;
+        if name == LOCAL_CONTEXT {
+            return Err(format!("\"{}\" is reserved for the built-in context", LOCAL_CONTEXT));
+        }
+
+        let endpoint = match self.selected_kind(cx) {
+            ContextKind::Local => DaemonEndpoint::Local {
+                socket_path: path(&self.socket_path_input, "Socket path")?,
+            },
+            ContextKind::Tcp => DaemonEndpoint::Tcp {
+                host: required(&self.host_input, "Host")?,
+                port: required(&self.port_input, "Port")?
+                    .parse()
+                    .map_err(|_| "Port must be a number between 1 and 65535".to_string())?,
+                ca_cert: path(&self.ca_cert_input, "CA certificate")?,
+                client_cert: path(&self.client_cert_input, "Client certificate")?,
+                client_key: path(&self.client_key_input, "Client key")?,
+            },
+            ContextKind::Ssh => {
+                let port = value(&self.ssh_port_input);
+                let identity = value(&self.identity_input);
+                DaemonEndpoint::Ssh {
+                    destination: required(&self.destination_input, "Destination")?,
+                    port: if port.is_empty() {
+                        None
+                    } else {
+                        Some(port.parse().map_err(|_| {
+                            "SSH port must be a number between 1 and 65535".to_string()
+                        })?)
+                    },
+                    identity_file: (!identity.is_empty()).then(|| expand_tilde(identity)),
+                    remote_socket: required(&self.remote_socket_input, "Remote socket")?,
+                }
+            }
+        };
+
+        Ok(DaemonContext { name, endpoint })
+    }
+
+    fn add_context(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        match self.build_context(cx) {
+            Ok(context) => {
+                tracing::info!("Adding context {} ({})", context.name, context.endpoint.display());
+                SettingsStore::global(cx).update(cx, |store, cx| {
+                    store.add_context(context, cx);
+                });
+                window.remove_window();
+            }
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+            }
+        }
+    }
+
+    fn close_dialog(&self, window: &mut Window, _cx: &mut Context<Self>) {
+        window.remove_window();
+    }
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_tilde(path: String) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+impl Render for NewContextDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let kind = self.selected_kind(cx);
+
+        div()
+            .id("new-context-dialog")
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(colors::background())
+            .text_color(colors::text())
+            .rounded_lg()
+            .border_1()
+            .border_color(colors::border())
+            .shadow_lg()
+            // Title bar
+            .child(self.render_title_bar(cx))
+            // Scrollable form content
+            .child(
+                div()
+                    .id("form-content")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .px_4()
+                    .py_2()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(self.render_input_field("Name", self.name_input.clone()))
+                    .child(self.render_select_field("Type", self.kind_select.clone()))
+                    .when(kind == ContextKind::Local, |el| {
+                        el.child(self.render_input_field("Socket path", self.socket_path_input.clone()))
+                    })
+                    .when(kind == ContextKind::Tcp, |el| {
+                        el.child(self.render_input_field("Host", self.host_input.clone()))
+                            .child(self.render_input_field("Port", self.port_input.clone()))
+                            .child(self.render_section_header("Mutual TLS"))
+                            .child(self.render_input_field("CA certificate", self.ca_cert_input.clone()))
+                            .child(self.render_input_field("Client certificate", self.client_cert_input.clone()))
+                            .child(self.render_input_field("Client key", self.client_key_input.clone()))
+                    })
+                    .when(kind == ContextKind::Ssh, |el| {
+                        el.child(self.render_input_field("Destination", self.destination_input.clone()))
+                            .child(self.render_input_field("SSH port", self.ssh_port_input.clone()))
+                            .child(self.render_input_field("Identity file", self.identity_input.clone()))
+                            .child(self.render_input_field("Remote socket", self.remote_socket_input.clone()))
+                    }),
+            )
+            // Fixed footer (outside scroll area)
+            .child(self.render_footer(cx))
+    }
+}
+
+impl NewContextDialog {
+    fn render_title_bar(&self, cx: &Context<Self>) -> impl IntoElement {
+        div()
+            .h(px(44.0))
+            .px_4()
+            .flex()
+            .items_center()
+            .justify_between()
+            .border_b_1()
+            .border_color(colors::border())
+            .child(
+                div()
+                    .text_sm()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .text_color(colors::text())
+                    .child("New Context"),
+            )
+            .child(
+                div()
+                    .id("close-button")
+                    .w(px(24.0))
+                    .h(px(24.0))
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded(px(4.0))
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.close_dialog(window, cx);
+                    }))
+                    .child(
+                        svg()
+                            .path("icons/close.svg")
+                            .size(px(16.0))
+                            .text_color(colors::text_secondary()),
+                    ),
+            )
+    }
+
+    fn render_section_header(&self, title: &'static str) -> impl IntoElement {
+        div()
+            .mt_4()
+            .mb_1()
+            .text_sm()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(colors::text())
+            .child(title)
+    }
+
+    /// Render a text input field with a label using gpui-component's Input
+    fn render_input_field(
+        &self,
+        label: &'static str,
+        input: Entity<InputState>,
+    ) -> impl IntoElement {
+        div()
+            .py_2()
+            .flex()
+            .items_center()
+            .justify_between()
+            .border_b_1()
+            .border_color(colors::border())
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(colors::text())
+                    .child(label),
+            )
+            .child(
+                div()
+                    .w(px(240.0))
+                    .child(Input::new(&input).small()),
+            )
+    }
+
+    /// Render a select/dropdown field using gpui-component's Select
+    fn render_select_field<D>(
+        &self,
+        label: &'static str,
+        select_state: Entity<SelectState<D>>,
+    ) -> impl IntoElement
+    where
+        D: gpui_component::select::SelectDelegate + 'static,
+    {
+        div()
+            .py_2()
+            .flex()
+            .items_center()
+            .justify_between()
+            .border_b_1()
+            .border_color(colors::border())
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(colors::text())
+                    .child(label),
+            )
+            .child(
+                div()
+                    .w(px(240.0))
+                    .child(Select::new(&select_state).small()),
+            )
+    }
+
+    fn render_footer(&self, cx: &Context<Self>) -> impl IntoElement {
+        // gpui-component Button.on_click expects Fn(&ClickEvent, &mut Window, &mut App)
+        // so we need to use entity.update() pattern instead of cx.listener()
+        let entity = cx.entity();
+
+        let cancel_entity = entity.clone();
+        let add_entity = entity.clone();
+
+        div()
+            .px_4()
+            .py_3()
+            .border_t_1()
+            .border_color(colors::border())
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_2()
+            // Validation error
+            .child(
+                div()
+                    .flex_1()
+                    .text_xs()
+                    .text_color(colors::error())
+                    .children(self.error.clone()),
+            )
+            // Action buttons
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        Button::new("cancel-button")
+                            .ghost()
+                            .small()
+                            .child("Cancel")
+                            .on_click(move |_, window, cx| {
+                                cancel_entity.update(cx, |this, cx| {
+                                    this.close_dialog(window, cx);
+                                });
+                            })
+                    )
+                    .child(
+                        Button::new("add-button")
+                            .primary()
+                            .small()
+                            .child("Add Context")
+                            .on_click(move |_, window, cx| {
+                                add_entity.update(cx, |this, cx| {
+                                    this.add_context(window, cx);
+                                });
+                            })
+                    ),
+            )
+    }
+}
+
+/// Open the new context dialog as a PopUp window
+pub fn open_new_context_dialog(parent_bounds: Bounds<Pixels>, cx: &mut App) {
+    // Dialog size
+    let dialog_size = size(px(480.0), px(460.0));
+
+    // Calculate centered position relative to parent window
+    let x = parent_bounds.origin.x + (parent_bounds.size.width - dialog_size.width) / 2.0;
+    let y = parent_bounds.origin.y + (parent_bounds.size.height - dialog_size.height) / 2.0;
+
+    let bounds = Bounds {
+        origin: point(x, y),
+        size: dialog_size,
+    };
+
+    let window_options = WindowOptions {
+        kind: WindowKind::PopUp,
+        titlebar: None,
+        window_bounds: Some(WindowBounds::Windowed(bounds)),
+        focus: true,
+        show: true,
+        window_background: WindowBackgroundAppearance::Transparent,
+        ..Default::default()
+    };
+
+    let _ = cx.open_window(window_options, |window, cx| {
+        // Initialize gpui-component (required for Input, Select, etc.)
+        gpui_component::init(cx);
+
+        let dialog_view = cx.new(|cx| NewContextDialog::new(window, cx));
+
+        // Wrap in Root - gpui-component requires Root to be the window's root view
+        cx.new(|cx| Root::new(dialog_view, window, cx))
+    });
+}
@@ -0,0 +1,364 @@
+use gpui::*;
+use gpui::prelude::*;
+
+use crate::services::{Notification, NotificationCenter, Severity};
+use crate::theme::colors;
+
+const TOAST_WIDTH: f32 = 340.0;
+const PANEL_WIDTH: f32 = 380.0;
+const PANEL_MAX_HEIGHT: f32 = 480.0;
+
+/// Overlay that renders toasts and the notification history panel
+///
+/// Drawn on top of the main window content; it has no hitbox of its own, so
+/// clicks only land on the toasts and the panel.
+pub struct NotificationLayer {
+    center: Entity<NotificationCenter>,
+    history_open: bool,
+}
+
+impl NotificationLayer {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let center = NotificationCenter::global(cx);
+        cx.observe(&center, |this: &mut Self, center, cx| {
+            // Anything arriving while the panel is open has been seen
+            if this.history_open {
+                center.update(cx, |center, cx| center.mark_all_read(cx));
+            }
+            cx.notify();
+        })
+        .detach();
+        Self {
+            center,
+            history_open: false,
+        }
+    }
+
+    pub fn is_history_open(&self) -> bool {
+        self.history_open
+    }
+
+    /// Show or hide the history panel; opening it marks everything as read
+    pub fn toggle_history(&mut self, cx: &mut Context<Self>) {
+        self.history_open = !self.history_open;
+        if self.history_open {
+            self.center.update(cx, |center, cx| center.mark_all_read(cx));
+        }
+        cx.notify();
+    }
+
+    fn severity_color(severity: Severity) -> Rgba {
+        match severity {
+            Severity::Info => colors::accent(),
+            Severity::Success => colors::running(),
+            Severity::Warning => colors::warning(),
+            Severity::Error => colors::error(),
+        }
+    }
+
+    /// Title row shared by toasts and history entries
+    fn render_title(notification: &Notification) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .child(
+                div()
+                    .w(px(8.0))
+                    .h(px(8.0))
+                    .rounded_full()
+                    .flex_shrink_0()
+                    .bg(Self::severity_color(notification.severity)),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_sm()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(colors::text())
+                    .child(notification.title.clone()),
+            )
+            .when(notification.count > 1, |el| {
+                el.child(
+                    div()
+                        .px_1p5()
+                        .rounded(px(8.0))
+                        .bg(colors::surface_elevated())
+                        .text_xs()
+                        .text_color(colors::text_secondary())
+                        .child(format!("×{}", notification.count)),
+                )
+            })
+    }
+
+    /// Action buttons; clicking one runs it and hides the toast
+    fn render_actions(&self, notification: &Notification, prefix: &str) -> impl IntoElement {
+        let center = self.center.clone();
+        let id = notification.id;
+
+        div()
+            .flex()
+            .gap_2()
+            .children(notification.actions.iter().enumerate().map(|(index, action)| {
+                let handler = action.handler.clone();
+                let center = center.clone();
+                div()
+                    .id(SharedString::from(format!("{}-{}-action-{}", prefix, id, index)))
+                    .px_2()
+                    .py_0p5()
+                    .rounded(px(4.0))
+                    .border_1()
+                    .border_color(colors::border())
+                    .text_xs()
+                    .text_color(colors::accent())
+                    .cursor_pointer()
+                    .hover(|el| el.bg(colors::hover()))
+                    .on_click(move |_, window, cx| {
+                        center.update(cx, |center, cx| center.dismiss(id, cx));
+                        handler(window, cx);
+                    })
+                    .child(action.label.clone())
+            }))
+    }
+
+    fn render_toast(&self, notification: &Notification) -> impl IntoElement {
+        let center = self.center.clone();
+        let id = notification.id;
+
+        div()
+            .id(SharedString::from(format!("toast-{}", id)))
+            .occlude()
+            .w(px(TOAST_WIDTH))
+            .p_3()
+            .flex()
+            .flex_col()
+            .gap_1p5()
+            .bg(colors::background())
+            .border_1()
+            .border_color(colors::border())
+            .rounded(px(8.0))
+            .shadow_lg()
+            .child(
+                div()
+                    .flex()
+                    .items_start()
+                    .gap_2()
+                    .child(div().flex_1().child(Self::render_title(notification)))
+                    .child(
+                        div()
+                            .id(SharedString::from(format!("toast-{}-close", id)))
+                            .w(px(18.0))
+                            .h(px(18.0))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded(px(4.0))
+                            .cursor_pointer()
+                            .hover(|el| el.bg(colors::hover()))
+                            .on_click(move |_, _window, cx| {
+                                center.update(cx, |center, cx| center.dismiss(id, cx));
+                            })
+                            .child(
+                                svg()
+                                    .path("icons/close.svg")
+                                    .size(px(10.0))
+                                    .text_color(colors::text_secondary()),
+                            ),
+                    ),
+            )
+            .when_some(notification.message.clone(), |el, message| {
+                el.child(
+                    div()
+                        .pl(px(16.0))
+                        .text_xs()
+                        .text_color(colors::text_secondary())
+                        .child(message),
+                )
+            })
+            .when(!notification.actions.is_empty(), |el| {
+                el.child(div().pl(px(16.0)).child(self.render_actions(notification, "toast")))
+            })
+    }
+
+    fn render_history_entry(&self, notification: &Notification) -> impl IntoElement {
+        let center = self.center.clone();
+        let id = notification.id;
+
+        div()
+            .id(SharedString::from(format!("history-{}", id)))
+            .px_3()
+            .py_2()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .border_b_1()
+            .border_color(colors::border())
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(Self::render_title(notification)))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(colors::text_muted())
+                            .child(notification.created_at.format("%H:%M:%S").to_string()),
+                    )
+                    .child(
+                        div()
+                            .id(SharedString::from(format!("history-{}-remove", id)))
+                            .w(px(16.0))
+                            .h(px(16.0))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded(px(4.0))
+                            .cursor_pointer()
+                            .hover(|el| el.bg(colors::hover()))
+                            .on_click(move |_, _window, cx| {
+                                center.update(cx, |center, cx| center.remove(id, cx));
+                            })
+                            .child(
+                                svg()
+                                    .path("icons/close.svg")
+                                    .size(px(9.0))
+                                    .text_color(colors::text_secondary()),
+                            ),
+                    ),
+            )
+            .when_some(notification.message.clone(), |el, message| {
+                el.child(
+                    div()
+                        .pl(px(16.0))
+                        .text_xs()
+                        .text_color(colors::text_secondary())
+                        .child(message),
+                )
+            })
+            .when(!notification.actions.is_empty(), |el| {
+                el.child(div().pl(px(16.0)).child(self.render_actions(notification, "history")))
+            })
+    }
+
+    fn render_history_panel(&self, cx: &Context<Self>) -> impl IntoElement {
+        let center = self.center.read(cx);
+        let entries: Vec<Notification> = center.history().cloned().collect();
+
+        div()
+            .id("notification-history")
+            .occlude()
+            .absolute()
+            .top(px(12.0))
+            .right(px(12.0))
+            .w(px(PANEL_WIDTH))
+            .max_h(px(PANEL_MAX_HEIGHT))
+            .flex()
+            .flex_col()
+            .bg(colors::background())
+            .border_1()
+            .border_color(colors::border())
+            .rounded(px(10.0))
+            .shadow_lg()
+            .overflow_hidden()
+            // Header
+            .child(
+                div()
+                    .px_3()
+                    .py_2()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(colors::border())
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .child("Notifications"),
+                    )
+                    .when(!entries.is_empty(), |el| {
+                        el.child(
+                            div()
+                                .id("notification-clear")
+                                .px_2()
+                                .py_0p5()
+                                .rounded(px(4.0))
+                                .text_xs()
+                                .text_color(colors::text_secondary())
+                                .cursor_pointer()
+                                .hover(|el| el.bg(colors::hover()).text_color(colors::text()))
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.center.update(cx, |center, cx| center.clear(cx));
+                                }))
+                                .child("Clear All"),
+                        )
+                    })
+                    .child(
+                        div()
+                            .id("notification-close")
+                            .w(px(20.0))
+                            .h(px(20.0))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded(px(4.0))
+                            .cursor_pointer()
+                            .hover(|el| el.bg(colors::hover()))
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.toggle_history(cx);
+                            }))
+                            .child(
+                                svg()
+                                    .path("icons/close.svg")
+                                    .size(px(10.0))
+                                    .text_color(colors::text_secondary()),
+                            ),
+                    ),
+            )
+            // Entries
+            .child(
+                div()
+                    .id("notification-history-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .when(entries.is_empty(), |el| {
+                        el.child(
+                            div()
+                                .py_8()
+                                .flex()
+                                .justify_center()
+                                .text_sm()
+                                .text_color(colors::text_muted())
+                                .child("No notifications"),
+                        )
+                    })
+                    .children(entries.iter().map(|n| self.render_history_entry(n))),
+            )
+    }
+}
+
+impl Render for NotificationLayer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let toasts = self.center.read(cx).toasts();
+
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            // Toast stack, newest at the bottom
+            .child(
+                div()
+                    .absolute()
+                    .bottom(px(16.0))
+                    .right(px(16.0))
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .children(toasts.iter().map(|n| self.render_toast(n))),
+            )
+            .when(self.history_open, |el| el.child(self.render_history_panel(cx)))
+    }
+}
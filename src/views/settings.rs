@@ -1,7 +1,9 @@
 use gpui::*;
 use gpui::prelude::*;
 
+use crate::services::{DaemonContext, SettingsStore};
 use crate::theme::colors;
+use crate::views::open_new_context_dialog;
 
 /// Settings section in sidebar
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -145,12 +147,39 @@ impl ExternalTerminal {
     }
 }
 
+/// Automatic restart limit options for a crashing daemon
+const RESTART_LIMITS: [(u32, &str); 4] = [(0, "Never"), (3, "3 times"), (5, "5 times"), (10, "10 times")];
+
+/// Shutdown timeout options (seconds) for stopping the daemon on quit
+const SHUTDOWN_TIMEOUTS: [(u64, &str); 4] =
+    [(5, "5 seconds"), (10, "10 seconds"), (30, "30 seconds"), (60, "1 minute")];
+
+/// Label for a shutdown timeout
+fn shutdown_timeout_label(secs: u64) -> SharedString {
+    SHUTDOWN_TIMEOUTS
+        .iter()
+        .find(|(value, _)| *value == secs)
+        .map(|(_, label)| SharedString::from(*label))
+        .unwrap_or_else(|| format!("{} seconds", secs).into())
+}
+
+/// Label for a restart limit (limits set outside the UI show their number)
+fn restart_limit_label(limit: u32) -> SharedString {
+    RESTART_LIMITS
+        .iter()
+        .find(|(value, _)| *value == limit)
+        .map(|(_, label)| SharedString::from(*label))
+        .unwrap_or_else(|| format!("{} times", limit).into())
+}
+
 /// Dropdown identifiers
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DropdownId {
     UpdateChannel,
     TerminalTheme,
     ExternalTerminal,
+    RestartLimit,
+    ShutdownTimeout,
 }
 
 /// Settings view
@@ -159,7 +188,6 @@ pub struct SettingsView {
     // General settings
     start_at_login: bool,
     show_in_menu_bar: bool,
-    keep_running_when_quit: bool,
     // Updates
     auto_download_updates: bool,
     update_channel: UpdateChannel,
@@ -168,24 +196,29 @@ pub struct SettingsView {
     external_terminal: ExternalTerminal,
     // Dropdown state
     open_dropdown: Option<DropdownId>,
+    // Persisted settings (daemon contexts)
+    settings_store: Entity<SettingsStore>,
 }
 
 impl SettingsView {
-    pub fn new(_cx: &mut Context<Self>) -> Self {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let settings_store = SettingsStore::global(cx);
+        cx.observe(&settings_store, |_, _, cx| cx.notify()).detach();
+
         Self {
             active_section: SettingsSection::General,
             start_at_login: true,
             show_in_menu_bar: false,
-            keep_running_when_quit: false,
             auto_download_updates: true,
             update_channel: UpdateChannel::Stable,
             terminal_theme: TerminalTheme::System,
             external_terminal: ExternalTerminal::LastUsed,
             open_dropdown: None,
+            settings_store,
         }
     }
 
-    fn set_section(&mut self, section: SettingsSection, cx: &mut Context<Self>) {
+    pub fn set_section(&mut self, section: SettingsSection, cx: &mut Context<Self>) {
         self.active_section = section;
         cx.notify();
     }
@@ -210,6 +243,22 @@ impl SettingsView {
             DropdownId::ExternalTerminal => {
                 self.external_terminal = ExternalTerminal::from_index(idx);
             }
+            DropdownId::RestartLimit => {
+                if let Some((limit, _)) = RESTART_LIMITS.get(idx) {
+                    let limit = *limit;
+                    self.settings_store.update(cx, |store, cx| {
+                        store.update_settings(|settings| settings.daemon_restart_limit = limit, cx);
+                    });
+                }
+            }
+            DropdownId::ShutdownTimeout => {
+                if let Some((secs, _)) = SHUTDOWN_TIMEOUTS.get(idx) {
+                    let secs = *secs;
+                    self.settings_store.update(cx, |store, cx| {
+                        store.update_settings(|settings| settings.shutdown_timeout_secs = secs, cx);
+                    });
+                }
+            }
         }
         self.open_dropdown = None;
         cx.notify();
@@ -330,7 +379,7 @@ impl SettingsView {
                         SettingsSection::Network => self.render_placeholder("Network").into_any_element(),
                         SettingsSection::Storage => self.render_placeholder("Storage").into_any_element(),
                         SettingsSection::Machines => self.render_placeholder("Machines").into_any_element(),
-                        SettingsSection::Docker => self.render_placeholder("Docker").into_any_element(),
+                        SettingsSection::Docker => self.render_docker(cx).into_any_element(),
                         SettingsSection::Kubernetes => self.render_placeholder("Kubernetes").into_any_element(),
                     }),
             )
@@ -339,7 +388,7 @@ impl SettingsView {
     fn render_general(&self, cx: &Context<Self>) -> impl IntoElement {
         let start_at_login = self.start_at_login;
         let show_in_menu_bar = self.show_in_menu_bar;
-        let keep_running_when_quit = self.keep_running_when_quit;
+        let keep_running_when_quit = self.settings_store.read(cx).settings().keep_running_when_quit;
         let auto_download_updates = self.auto_download_updates;
         let update_channel = self.update_channel;
         let terminal_theme = self.terminal_theme;
@@ -378,11 +427,15 @@ impl SettingsView {
                     .child(Self::toggle_row(
                         "keep-running",
                         "Keep running when app is quit",
-                        None,
+                        Some("Leave the daemon and its containers running after ArcBox exits."),
                         keep_running_when_quit,
                         cx.listener(|this, _, _window, cx| {
-                            this.keep_running_when_quit = !this.keep_running_when_quit;
-                            cx.notify();
+                            this.settings_store.update(cx, |store, cx| {
+                                store.update_settings(
+                                    |settings| settings.keep_running_when_quit = !settings.keep_running_when_quit,
+                                    cx,
+                                );
+                            });
                         }),
                     )),
             )
@@ -435,6 +488,163 @@ impl SettingsView {
             )
     }
 
+    fn render_docker(&self, cx: &Context<Self>) -> impl IntoElement {
+        let settings = self.settings_store.read(cx).settings();
+        let active = settings.active_context.clone();
+        let contexts = settings.contexts.clone();
+        let count = contexts.len();
+        let restart_limit = settings.daemon_restart_limit;
+        let shutdown_timeout = settings.shutdown_timeout_secs;
+        let open_dropdown = self.open_dropdown;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_6()
+            .child(
+                Self::settings_group(Some("Contexts"))
+                    .children(contexts.into_iter().enumerate().map(|(idx, context)| {
+                        let is_active = context.name == active;
+                        div()
+                            .child(self.render_context_row(context, is_active, cx))
+                            .when(idx + 1 < count, |el| el.child(Self::divider()))
+                    }))
+                    .child(Self::divider())
+                    .child(
+                        div()
+                            .px_4()
+                            .py_3()
+                            .flex()
+                            .items_center()
+                            .justify_between()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(colors::text_secondary())
+                                    .child("Connect to remote daemons over TCP with mutual TLS or an SSH tunnel."),
+                            )
+                            .child(
+                                Self::small_button("add-context", "Add Context…").on_click(
+                                    cx.listener(|_, _, window, cx| {
+                                        let parent_bounds = window.bounds();
+                                        cx.defer(move |cx| open_new_context_dialog(parent_bounds, cx));
+                                    }),
+                                ),
+                            ),
+                    ),
+            )
+            .child(
+                Self::settings_group(Some("Local Daemon"))
+                    .child(self.render_dropdown(
+                        DropdownId::RestartLimit,
+                        "Restart after a crash",
+                        Some("Automatic restarts allowed within two minutes before giving up."),
+                        restart_limit_label(restart_limit),
+                        RESTART_LIMITS.iter().map(|(_, label)| *label).collect(),
+                        open_dropdown == Some(DropdownId::RestartLimit),
+                        cx,
+                    ))
+                    .child(Self::divider())
+                    .child(self.render_dropdown(
+                        DropdownId::ShutdownTimeout,
+                        "Shutdown timeout",
+                        Some("How long to wait for a clean exit on quit before killing the daemon."),
+                        shutdown_timeout_label(shutdown_timeout),
+                        SHUTDOWN_TIMEOUTS.iter().map(|(_, label)| *label).collect(),
+                        open_dropdown == Some(DropdownId::ShutdownTimeout),
+                        cx,
+                    )),
+            )
+    }
+
+    /// Single context row with its endpoint and actions
+    fn render_context_row(
+        &self,
+        context: DaemonContext,
+        is_active: bool,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let use_name = context.name.clone();
+        let remove_name = context.name.clone();
+
+        div()
+            .px_4()
+            .py_3()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap_4()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_0p5()
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(colors::text())
+                                    .child(context.name.clone()),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(colors::text_secondary())
+                                    .child(context.endpoint.kind_label()),
+                            )
+                            .when(is_active, |el| {
+                                el.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(colors::accent())
+                                        .child("Active"),
+                                )
+                            }),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(colors::text_secondary())
+                            .text_ellipsis()
+                            .child(context.endpoint.display()),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .flex_shrink_0()
+                    .when(!is_active, |el| {
+                        el.child(
+                            Self::small_button(format!("use-context-{}", context.name), "Use")
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    let name = use_name.clone();
+                                    this.settings_store.update(cx, |store, cx| {
+                                        store.set_active_context(&name, cx);
+                                    });
+                                })),
+                        )
+                    })
+                    .when(!context.is_managed(), |el| {
+                        el.child(
+                            Self::small_button(format!("remove-context-{}", context.name), "Remove")
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    let name = remove_name.clone();
+                                    this.settings_store.update(cx, |store, cx| {
+                                        store.remove_context(&name, cx);
+                                    });
+                                })),
+                        )
+                    }),
+            )
+    }
+
     fn render_placeholder(&self, name: &'static str) -> impl IntoElement {
         div()
             .flex()
@@ -480,6 +690,23 @@ impl SettingsView {
         }
     }
 
+    /// Small bordered button
+    fn small_button(id: impl Into<SharedString>, label: &'static str) -> Stateful<Div> {
+        div()
+            .id(id.into())
+            .px_3()
+            .py_1()
+            .rounded_md()
+            .border_1()
+            .border_color(colors::border())
+            .bg(colors::background())
+            .text_sm()
+            .text_color(colors::text())
+            .cursor_pointer()
+            .hover(|el| el.bg(colors::surface()))
+            .child(label)
+    }
+
     /// Horizontal divider line
     fn divider() -> Div {
         div()
@@ -565,7 +792,7 @@ impl SettingsView {
         dropdown_id: DropdownId,
         label: &'static str,
         description: Option<&'static str>,
-        current_value: &'static str,
+        current_value: impl Into<SharedString>,
         options: Vec<&'static str>,
         is_open: bool,
         cx: &Context<Self>,
@@ -600,7 +827,7 @@ impl SettingsView {
             .child(
                 div()
                     .relative()
-                    .child(self.render_dropdown_button(dropdown_id, current_value, cx))
+                    .child(self.render_dropdown_button(dropdown_id, current_value.into(), cx))
                     .when(is_open, |el| {
                         el.child(self.render_dropdown_menu(dropdown_id, options, cx))
                     }),
@@ -611,7 +838,7 @@ impl SettingsView {
     fn render_dropdown_button(
         &self,
         dropdown_id: DropdownId,
-        current_value: &'static str,
+        current_value: SharedString,
         cx: &Context<Self>,
     ) -> impl IntoElement {
         div()
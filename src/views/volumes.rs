@@ -1,9 +1,11 @@
+use chrono::{TimeZone, Utc};
 use gpui::*;
 use gpui::prelude::*;
 use gpui_component::tab::TabBar;
 use gpui_component::Sizable;
 
-use crate::models::VolumeViewModel;
+use crate::models::{format_bytes, VolumeViewModel};
+use crate::services::ResourceEvent;
 use crate::theme::{colors, Theme, MONO_FONT};
 
 /// Detail tab for volumes
@@ -68,6 +70,65 @@ impl VolumesView {
         }
     }
 
+    /// Forget volumes of the previous daemon (context switch)
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        self.volumes.clear();
+        self.selected_id = None;
+        cx.notify();
+    }
+
+    /// Apply a volume event from the daemon event stream
+    pub fn on_volume_event(&mut self, event: &ResourceEvent, cx: &mut Context<Self>) {
+        match event.action.as_str() {
+            "create" => {
+                if self.volumes.iter().any(|v| v.name == event.id) {
+                    return;
+                }
+                self.volumes.push(VolumeViewModel {
+                    name: event.id.clone(),
+                    driver: event
+                        .attributes
+                        .get("driver")
+                        .cloned()
+                        .unwrap_or_else(|| "local".to_string()),
+                    mount_point: String::new(),
+                    size_bytes: None,
+                    created_at: Utc.timestamp_opt(event.time, 0).single().unwrap_or_else(Utc::now),
+                    in_use: false,
+                    container_names: Vec::new(),
+                });
+            }
+            "destroy" => {
+                self.volumes.retain(|v| v.name != event.id);
+                if self.selected_id.as_ref() == Some(&event.id) {
+                    self.selected_id = None;
+                }
+            }
+            // Usage is refetched by the daemon service (`on_volume_usage`)
+            _ => return,
+        }
+        cx.notify();
+    }
+
+    /// Set the containers that use a volume
+    pub fn on_volume_usage(&mut self, name: &str, containers: Vec<String>, cx: &mut Context<Self>) {
+        let Some(volume) = self.volumes.iter_mut().find(|v| v.name == name) else {
+            return;
+        };
+        volume.in_use = !containers.is_empty();
+        volume.container_names = containers;
+        cx.notify();
+    }
+
+    /// Names of the volumes a container (by name) uses
+    pub fn volumes_used_by(&self, container: &str) -> Vec<String> {
+        self.volumes
+            .iter()
+            .filter(|v| v.container_names.iter().any(|c| c == container))
+            .map(|v| v.name.clone())
+            .collect()
+    }
+
     fn resize_list(&mut self, new_width: f32, cx: &mut Context<Self>) {
         self.list_width = new_width.clamp(LIST_MIN_WIDTH, LIST_MAX_WIDTH);
         cx.notify();
@@ -134,7 +195,7 @@ impl Render for VolumesView {
                                         div()
                                             .text_xs()
                                             .text_color(colors::text_secondary())
-                                            .child(format_size(total_size)),
+                                            .child(format!("{} total", format_bytes(total_size as f64))),
                                     ),
                             )
                             .child(
@@ -443,13 +504,3 @@ impl VolumesView {
             .child(format!("{} coming soon...", name))
     }
 }
-
-fn format_size(bytes: u64) -> String {
-    let gb = bytes as f64 / 1_000_000_000.0;
-    if gb >= 1.0 {
-        format!("{:.2} GB total", gb)
-    } else {
-        let mb = bytes as f64 / 1_000_000.0;
-        format!("{:.0} MB total", mb)
-    }
-}